@@ -23,7 +23,8 @@ fn main() -> Result<()> {
     .shader_source_type(
       WgslShaderSourceType::UseComposerWithPath
         | WgslShaderSourceType::UseComposerEmbed
-        | WgslShaderSourceType::UseEmbed,
+        | WgslShaderSourceType::UseEmbed
+        | WgslShaderSourceType::UseNagaIr,
     )
     .derive_serde(false)
     .output("src/shader_bindings.rs")
@@ -96,7 +96,7 @@ impl State {
       ),
       fragment: Some(wgpu::FragmentState {
         module: &shader,
-        entry_point: shader_bindings::triangle::ENTRY_FS_MAIN,
+        entry_point: Some(shader_bindings::triangle::ENTRY_FS_MAIN),
         targets: &[Some(surface_format.into())],
         compilation_options: Default::default(),
       }),
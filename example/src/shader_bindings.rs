@@ -1,8 +1,8 @@
 // File automatically generated by wgsl_bindgen^
 //
-// ^ wgsl_bindgen version 0.15.1
+// ^ wgsl_bindgen version 0.15.2
 // Changes made to this file will not be saved.
-// SourceHash: 1a4070f93a855f4af6fe42f4585ec16cd8592b2028a0b6c83365eef914476ec6
+// SourceHash: 4f5b4cc30d3fc7d157885e8f22683ca9185b79b2043258071736ee430fb7fb77
 
 #![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -11,17 +11,76 @@ pub enum ShaderEntry {
     Triangle,
 }
 impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Testbed, Self::Triangle];
     pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-        match self {
+        match *self {
             Self::Testbed => testbed::create_pipeline_layout(device),
             Self::Triangle => triangle::create_pipeline_layout(device),
         }
     }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Testbed => testbed::is_supported(adapter),
+            Self::Triangle => triangle::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Testbed => {
+                &[
+                    (wgpu::ShaderStages::VERTEX, testbed::ENTRY_VERTEX_MAIN),
+                    (wgpu::ShaderStages::FRAGMENT, testbed::ENTRY_FRAGMENT_MAIN),
+                ]
+            }
+            Self::Triangle => {
+                &[
+                    (wgpu::ShaderStages::VERTEX, triangle::ENTRY_VS_MAIN),
+                    (wgpu::ShaderStages::FRAGMENT, triangle::ENTRY_FS_MAIN),
+                ]
+            }
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Testbed => 3,
+            Self::Triangle => 2,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Testbed => {
+                vec![utils::types::VertexIn::vertex_buffer_layout(step_mode)]
+            }
+            Self::Triangle => {
+                vec![triangle::VertexInput::vertex_buffer_layout(step_mode)]
+            }
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Testbed => 0,
+            Self::Triangle => 64,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Testbed => testbed::RESOURCES,
+            Self::Triangle => triangle::RESOURCES,
+        }
+    }
     pub fn create_shader_module_embed_source(
         &self,
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
-        match self {
+        match *self {
             Self::Testbed => testbed::create_shader_module_embed_source(device),
             Self::Triangle => triangle::create_shader_module_embed_source(device),
         }
@@ -31,7 +90,7 @@ impl ShaderEntry {
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
     ) -> wgpu::ShaderModule {
-        match self {
+        match *self {
             Self::Testbed => testbed::create_shader_module_embedded(device, shader_defs),
             Self::Triangle => {
                 triangle::create_shader_module_embedded(device, shader_defs)
@@ -43,25 +102,316 @@ impl ShaderEntry {
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
     ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
-        match self {
+        match *self {
             Self::Testbed => testbed::create_shader_module_from_path(device, shader_defs),
             Self::Triangle => {
                 triangle::create_shader_module_from_path(device, shader_defs)
             }
         }
     }
+    pub fn create_shader_module_naga_ir(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match *self {
+            Self::Testbed => testbed::create_shader_module_naga_ir(device),
+            Self::Triangle => triangle::create_shader_module_naga_ir(device),
+        }
+    }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_embed_source<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Option<wgpu::RenderPipeline> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return None;
+        }
+        let module = self.create_shader_module_embed_source(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Some(pipeline)
+    }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_embedded<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Option<wgpu::RenderPipeline> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return None;
+        }
+        let module = self.create_shader_module_embedded(device, shader_defs);
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Some(pipeline)
+    }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_from_path<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Result<Option<wgpu::RenderPipeline>, naga_oil::compose::ComposerError> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return Ok(None);
+        }
+        let module = self.create_shader_module_from_path(device, shader_defs)?;
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Ok(Some(pipeline))
+    }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_naga_ir<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Option<wgpu::RenderPipeline> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return None;
+        }
+        let module = self.create_shader_module_naga_ir(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Some(pipeline)
+    }
     pub fn shader_entry_filename(&self) -> &'static str {
-        match self {
+        match *self {
             Self::Testbed => "testbed.wgsl",
             Self::Triangle => "triangle.wgsl",
         }
     }
     pub fn shader_paths(&self) -> &[&str] {
-        match self {
+        match *self {
             Self::Testbed => testbed::SHADER_PATHS,
             Self::Triangle => triangle::SHADER_PATHS,
         }
     }
+    pub fn load_naga_module(
+        &self,
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        match *self {
+            Self::Testbed => testbed::load_naga_module_from_dir(base_dir, shader_defs),
+            Self::Triangle => triangle::load_naga_module_from_dir(base_dir, shader_defs),
+        }
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "testbed.wgsl" => Some(Self::Testbed),
+            "triangle.wgsl" => Some(Self::Triangle),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Testbed => "testbed.wgsl",
+            Self::Triangle => "triangle.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Testbed => testbed::DEPENDENCY_PATHS,
+            Self::Triangle => triangle::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Testbed => "testbed",
+            Self::Triangle => "triangle",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "testbed" => Ok(Self::Testbed),
+            "triangle" => Ok(Self::Triangle),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
 }
 mod _root {
     pub use super::*;
@@ -134,6 +484,221 @@ pub mod layout_asserts {
         assert!(std::mem::size_of:: < triangle::PushConstants > () == 64);
     };
 }
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    /// The entry point and buffers of a [`VertexEntry`] without its pipeline constants, so it
+    /// can be created in a const fn.
+    #[derive(Debug)]
+    pub struct ConstVertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    impl<const N: usize> ConstVertexEntry<N> {
+        /// A [`VertexEntry`] with these buffers and the pipeline-overridable `constants`.
+        pub fn to_entry(
+            &self,
+            constants: std::collections::HashMap<String, f64>,
+        ) -> VertexEntry<N> {
+            VertexEntry {
+                entry_point: self.entry_point,
+                buffers: self.buffers.clone(),
+                constants,
+            }
+        }
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: Some(entry.entry_point),
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub targets: [Option<wgpu::ColorTargetState>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    pub fn fragment_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntry<N>,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: Some(entry.entry_point),
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    /// A uniform buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct UniformBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> UniformBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// A storage buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct StorageBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> StorageBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                            | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// Reads back the contents of `buffer`, which needs the `COPY_SRC` usage, by copying it to a
+    /// staging buffer mapped for reading. Blocks while polling the device until the mapping
+    /// finishes, so it isn't available on the web, where buffers are mapped asynchronously.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_buffer(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<std::vec::Vec<u8>, wgpu::BufferAsyncError> {
+        let staging = device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("read_buffer"),
+                    size: buffer.size(),
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            );
+        let mut encoder = device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+        queue.submit([encoder.finish()]);
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice
+            .map_async(
+                wgpu::MapMode::Read,
+                move |result| {
+                    let _ = sender.send(result);
+                },
+            );
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+        let bytes = slice.get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(bytes)
+    }
+}
 pub mod utils {
     use super::{_root, _root::*};
     pub mod types {
@@ -163,6 +728,56 @@ pub mod utils {
                 }
             }
         }
+        impl VectorsU32 {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl VectorsU32 {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<crate::MyTwoU32>(),
+                        0,
+                        8,
+                        16,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<[u32; 4]>(),
+                        16,
+                        12,
+                        16,
+                    ),
+                    (
+                        "c",
+                        std::mem::offset_of!(Self, c),
+                        std::mem::size_of::<[u32; 4]>(),
+                        32,
+                        16,
+                        32,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`utils::types::VectorsU32` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct VectorsU32Init {
@@ -208,6 +823,56 @@ pub mod utils {
                 }
             }
         }
+        impl VectorsI32 {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl VectorsI32 {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<[i32; 2]>(),
+                        0,
+                        8,
+                        16,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<[i32; 4]>(),
+                        16,
+                        12,
+                        16,
+                    ),
+                    (
+                        "c",
+                        std::mem::offset_of!(Self, c),
+                        std::mem::size_of::<[i32; 4]>(),
+                        32,
+                        16,
+                        16,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`utils::types::VectorsI32` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct VectorsI32Init {
@@ -251,6 +916,56 @@ pub mod utils {
                 }
             }
         }
+        impl VectorsF32 {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl VectorsF32 {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<[f32; 2]>(),
+                        0,
+                        8,
+                        16,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<glam::Vec3A>(),
+                        16,
+                        12,
+                        16,
+                    ),
+                    (
+                        "c",
+                        std::mem::offset_of!(Self, c),
+                        std::mem::size_of::<glam::Vec4>(),
+                        32,
+                        16,
+                        16,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`utils::types::VectorsF32` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct VectorsF32Init {
@@ -322,6 +1037,104 @@ pub mod utils {
                 }
             }
         }
+        impl MatricesF32 {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl MatricesF32 {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<glam::Mat4>(),
+                        0,
+                        64,
+                        64,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<[[f32; 4]; 4]>(),
+                        64,
+                        64,
+                        64,
+                    ),
+                    (
+                        "c",
+                        std::mem::offset_of!(Self, c),
+                        std::mem::size_of::<[[f32; 2]; 4]>(),
+                        128,
+                        32,
+                        32,
+                    ),
+                    (
+                        "d",
+                        std::mem::offset_of!(Self, d),
+                        std::mem::size_of::<[[f32; 4]; 3]>(),
+                        160,
+                        48,
+                        48,
+                    ),
+                    (
+                        "e",
+                        std::mem::offset_of!(Self, e),
+                        std::mem::size_of::<glam::Mat3A>(),
+                        208,
+                        48,
+                        48,
+                    ),
+                    (
+                        "f",
+                        std::mem::offset_of!(Self, f),
+                        std::mem::size_of::<[[f32; 2]; 3]>(),
+                        256,
+                        24,
+                        32,
+                    ),
+                    (
+                        "g",
+                        std::mem::offset_of!(Self, g),
+                        std::mem::size_of::<[[f32; 4]; 2]>(),
+                        288,
+                        32,
+                        32,
+                    ),
+                    (
+                        "h",
+                        std::mem::offset_of!(Self, h),
+                        std::mem::size_of::<[[f32; 4]; 2]>(),
+                        320,
+                        32,
+                        32,
+                    ),
+                    (
+                        "i",
+                        std::mem::offset_of!(Self, i),
+                        std::mem::size_of::<[[f32; 2]; 2]>(),
+                        352,
+                        16,
+                        16,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`utils::types::MatricesF32` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct MatricesF32Init {
@@ -391,6 +1204,64 @@ pub mod utils {
                 }
             }
         }
+        impl StaticArrays {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl StaticArrays {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<[u32; 5]>(),
+                        0,
+                        20,
+                        20,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<[f32; 3]>(),
+                        20,
+                        12,
+                        12,
+                    ),
+                    (
+                        "c",
+                        std::mem::offset_of!(Self, c),
+                        std::mem::size_of::<[glam::Mat4; 512]>(),
+                        32,
+                        32768,
+                        32768,
+                    ),
+                    (
+                        "d",
+                        std::mem::offset_of!(Self, d),
+                        std::mem::size_of::<[glam::Vec3A; 4]>(),
+                        32800,
+                        64,
+                        64,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`utils::types::StaticArrays` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct StaticArraysInit {
@@ -432,6 +1303,48 @@ pub mod utils {
         ) -> Nested {
             Nested { a, b }
         }
+        impl Nested {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl Nested {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<_root::utils::types::MatricesF32>(),
+                        0,
+                        368,
+                        368,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<_root::utils::types::VectorsF32>(),
+                        368,
+                        48,
+                        48,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`utils::types::Nested` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct VertexIn {
@@ -441,7 +1354,19 @@ pub mod utils {
             VertexIn { position }
         }
         impl VertexIn {
-            pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 1] = [
+            pub fn from_parts(position: impl Into<glam::Vec4>) -> Self {
+                Self { position: position.into() }
+            }
+        }
+        impl VertexIn {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl VertexIn {
+            pub const ATTRIBUTE_COUNT: usize = 1;
+            pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+            pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
                 wgpu::VertexAttribute {
                     format: wgpu::VertexFormat::Float32x4,
                     offset: std::mem::offset_of!(Self, position) as u64,
@@ -452,12 +1377,31 @@ pub mod utils {
                 step_mode: wgpu::VertexStepMode,
             ) -> wgpu::VertexBufferLayout<'static> {
                 wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<Self>() as u64,
+                    array_stride: Self::STRIDE,
                     step_mode,
                     attributes: &Self::VERTEX_ATTRIBUTES,
                 }
             }
         }
+        const VERTEX_IN_LAYOUT_ASSERTS: () = {
+            assert!(
+                std::mem::size_of:: < VertexIn > () % 4 == 0,
+                "the array stride of `utils::types::VertexIn` must be a multiple of 4 bytes"
+            );
+            assert!(
+                std::mem::size_of:: < VertexIn > () <= 2048,
+                "the array stride of `utils::types::VertexIn` must be at most 2048 bytes"
+            );
+            assert!(
+                std::mem::offset_of!(VertexIn, position) % 4 == 0,
+                "`utils::types::VertexIn::position` must be aligned to 4 bytes in the vertex buffer"
+            );
+            assert!(
+                std::mem::offset_of!(VertexIn, position) + 16 <= std::mem::size_of:: <
+                VertexIn > (),
+                "`utils::types::VertexIn::position` must be read within the array stride"
+            );
+        };
     }
 }
 pub mod bytemuck_impls {
@@ -498,6 +1442,48 @@ pub mod testbed {
     pub const fn Uniforms(color_rgb: glam::Vec4, scalars: crate::MyScalars) -> Uniforms {
         Uniforms { color_rgb, scalars }
     }
+    impl Uniforms {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Uniforms {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color_rgb",
+                    std::mem::offset_of!(Self, color_rgb),
+                    std::mem::size_of::<glam::Vec4>(),
+                    0,
+                    16,
+                    16,
+                ),
+                (
+                    "scalars",
+                    std::mem::offset_of!(Self, scalars),
+                    std::mem::size_of::<crate::MyScalars>(),
+                    16,
+                    16,
+                    16,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`testbed::Uniforms` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
     #[derive(Debug)]
     pub struct WgpuBindGroup0EntriesParams<'a> {
         pub color_texture: &'a wgpu::TextureView,
@@ -563,17 +1549,14 @@ pub mod testbed {
             device: &wgpu::Device,
             bindings: WgpuBindGroup0Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Testbed::BindGroup0"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Testbed::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
         }
         pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
@@ -631,17 +1614,14 @@ pub mod testbed {
             device: &wgpu::Device,
             bindings: WgpuBindGroup1Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Testbed::BindGroup1"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Testbed::BindGroup1",
+                    &bindings.as_array(),
+                ),
+            )
         }
         pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
@@ -827,17 +1807,14 @@ pub mod testbed {
             device: &wgpu::Device,
             bindings: WgpuBindGroup2Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Testbed::BindGroup2"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Testbed::BindGroup2",
+                    &bindings.as_array(),
+                ),
+            )
         }
         pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(2, &self.0, &[]);
@@ -866,28 +1843,377 @@ pub mod testbed {
         bind_group1.set(pass);
         bind_group2.set(pass);
     }
-    pub const ENTRY_VERTEX_MAIN: &str = "vertex_main";
-    pub const ENTRY_FRAGMENT_MAIN: &str = "fragment_main";
-    #[derive(Debug)]
-    pub struct VertexEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-        pub constants: std::collections::HashMap<String, f64>,
+    pub fn create_uniforms_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::testbed::Uniforms,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::uniforms"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
     }
-    pub fn vertex_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a VertexEntry<N>,
-    ) -> wgpu::VertexState<'a> {
-        wgpu::VertexState {
-            module,
-            entry_point: Some(entry.entry_point),
-            buffers: &entry.buffers,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
-        }
+    pub fn write_uniforms(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::testbed::Uniforms,
+    ) {
+        write_uniforms_at(queue, buffer, 0, value)
+    }
+    pub fn write_uniforms_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::testbed::Uniforms,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_uniforms_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::testbed::Uniforms],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::uniforms"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn create_b_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::utils::types::VectorsU32,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::b"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_b(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::utils::types::VectorsU32,
+    ) {
+        write_b_at(queue, buffer, 0, value)
+    }
+    pub fn write_b_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::utils::types::VectorsU32,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    pub fn create_c_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::utils::types::VectorsI32,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::c"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_c(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::utils::types::VectorsI32,
+    ) {
+        write_c_at(queue, buffer, 0, value)
+    }
+    pub fn write_c_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::utils::types::VectorsI32,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    pub fn create_d_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::utils::types::VectorsF32,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::d"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_d(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::utils::types::VectorsF32,
+    ) {
+        write_d_at(queue, buffer, 0, value)
+    }
+    pub fn write_d_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::utils::types::VectorsF32,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
     }
+    pub fn create_f_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::utils::types::MatricesF32,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::f"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_f(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::utils::types::MatricesF32,
+    ) {
+        write_f_at(queue, buffer, 0, value)
+    }
+    pub fn write_f_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::utils::types::MatricesF32,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    pub fn create_h_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::utils::types::StaticArrays,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::h"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_h(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::utils::types::StaticArrays,
+    ) {
+        write_h_at(queue, buffer, 0, value)
+    }
+    pub fn write_h_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::utils::types::StaticArrays,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    pub fn create_i_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::utils::types::Nested,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Testbed::i"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_i(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::utils::types::Nested,
+    ) {
+        write_i_at(queue, buffer, 0, value)
+    }
+    pub fn write_i_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::utils::types::Nested,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_b(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<_root::utils::types::VectorsU32, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(
+            bytemuck::pod_read_unaligned(
+                &bytes[..std::mem::size_of::<_root::utils::types::VectorsU32>()],
+            ),
+        )
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_c(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<_root::utils::types::VectorsI32, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(
+            bytemuck::pod_read_unaligned(
+                &bytes[..std::mem::size_of::<_root::utils::types::VectorsI32>()],
+            ),
+        )
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_d(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<_root::utils::types::VectorsF32, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(
+            bytemuck::pod_read_unaligned(
+                &bytes[..std::mem::size_of::<_root::utils::types::VectorsF32>()],
+            ),
+        )
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_f(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<_root::utils::types::MatricesF32, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(
+            bytemuck::pod_read_unaligned(
+                &bytes[..std::mem::size_of::<_root::utils::types::MatricesF32>()],
+            ),
+        )
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_h(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<_root::utils::types::StaticArrays, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(
+            bytemuck::pod_read_unaligned(
+                &bytes[..std::mem::size_of::<_root::utils::types::StaticArrays>()],
+            ),
+        )
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_i(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<_root::utils::types::Nested, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(
+            bytemuck::pod_read_unaligned(
+                &bytes[..std::mem::size_of::<_root::utils::types::Nested>()],
+            ),
+        )
+    }
+    pub fn color_texture_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Testbed::color_texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_color_texture(texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Testbed::color_texture"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    aspect: wgpu::TextureAspect::All,
+                    ..Default::default()
+                },
+            )
+    }
+    pub const ENTRY_VERTEX_MAIN: &str = "vertex_main";
+    pub const ENTRY_FRAGMENT_MAIN: &str = "fragment_main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        max_storage_buffer_binding_size: 32864,
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 1,
+        uniform_bytes: 32,
+        storage_buffers: 7,
+        textures_1d: 0,
+        textures_2d: 1,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 1,
+        push_constant_size: 0,
+    };
+    pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
     pub fn vertex_main_entry(vertex_in: wgpu::VertexStepMode) -> VertexEntry<1> {
         VertexEntry {
             entry_point: ENTRY_VERTEX_MAIN,
@@ -895,26 +2221,30 @@ pub mod testbed {
             constants: Default::default(),
         }
     }
-    #[derive(Debug)]
-    pub struct FragmentEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub targets: [Option<wgpu::ColorTargetState>; N],
-        pub constants: std::collections::HashMap<String, f64>,
+    /// `vertex_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes.
+    pub fn vertex_main_entry_default() -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VERTEX_MAIN,
+            buffers: [
+                utils::types::VertexIn::vertex_buffer_layout(
+                    wgpu::VertexStepMode::Vertex,
+                ),
+            ],
+            constants: Default::default(),
+        }
     }
-    pub fn fragment_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a FragmentEntry<N>,
-    ) -> wgpu::FragmentState<'a> {
-        wgpu::FragmentState {
-            module,
-            entry_point: Some(entry.entry_point),
-            targets: &entry.targets,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
+    /// The buffers of `vertex_main_entry_default` in a const fn, eg: to keep them in a static.
+    pub const fn vertex_main_const_entry() -> ConstVertexEntry<1> {
+        ConstVertexEntry {
+            entry_point: ENTRY_VERTEX_MAIN,
+            buffers: [
+                utils::types::VertexIn::vertex_buffer_layout(
+                    wgpu::VertexStepMode::Vertex,
+                ),
+            ],
         }
     }
+    pub use _root::_support::{FragmentEntry, fragment_state};
     pub fn fragment_main_entry(
         targets: [Option<wgpu::ColorTargetState>; 1],
     ) -> FragmentEntry<1> {
@@ -947,6 +2277,9 @@ pub mod testbed {
                 },
             )
     }
+    pub const SOURCE_HASH: &str = "ac9131231fb58413b97b31978f497f32b2ee8315fd5c0169290fad25bb7b2a01";
+    pub const SOURCE_PATH: &str = "shaders/testbed.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &["testbed.wgsl", "utils/types.wgsl"];
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -1056,6 +2389,16 @@ fn fragment_main(input_1: VertexOutput) -> @location(0) vec4<f32> {
     return vec4<f32>(1f, 1f, 1f, 1f);
 }
 "#;
+    pub fn create_shader_module_naga_ir(device: &wgpu::Device) -> wgpu::ShaderModule {
+        let module: wgpu::naga::Module = bincode::deserialize(NAGA_MODULE_BYTES)
+            .expect("failed to deserialize naga module");
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("testbed.wgsl"),
+                source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(module)),
+            })
+    }
+    pub const NAGA_MODULE_BYTES: &[u8] = b"%\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\x04\0\0\0\0\0\0\0\0\0\x04\0\0\0\0\0\x02\0\0\0\x04\x01,\0\0\0\0\0\0\0ScalarsX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x04\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\0\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\x01\0\0\0\0\x04\0\0\0\x01\x01\0\0\0\0\0\0\0c\x02\0\0\0\0\x08\0\0\0\x01\x01\0\0\0\0\0\0\0d\0\0\0\0\x01\0\0\0\0\x08\0\0\0\x0C\0\0\0\x10\0\0\0\0\x01\0\0\0\0\0\0\0\x01\0\0\0\x04\0\x01\0\0\0\x01\0\0\0\x01\0\0\0\x04\0\x01\0\0\0\x02\0\0\0\x01\0\0\0\x04\x01/\0\0\0\0\0\0\0VectorsU32X_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x04\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x04\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\x05\0\0\0\0\x10\0\0\0\x01\x01\0\0\0\0\0\0\0c\x06\0\0\0\0 \0\0\0\x01\x08\0\0\0\0\0\0\0_padding\x02\0\0\0\x000\0\0\0@\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\x04\0\x01\0\0\0\x01\0\0\0\0\0\0\0\x04\0\x01\0\0\0\x02\0\0\0\0\0\0\0\x04\x01/\0\0\0\0\0\0\0VectorsI32X_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x03\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x08\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\t\0\0\0\0\x10\0\0\0\x01\x01\0\0\0\0\0\0\0c\n\0\0\0\0 \0\0\x000\0\0\0\0\x01\0\0\0\0\0\0\0\x02\0\0\0\x04\0\x01\0\0\0\x01\0\0\0\x02\0\0\0\x04\0\x01\0\0\0\x02\0\0\0\x02\0\0\0\x04\x01/\0\0\0\0\0\0\0VectorsF32X_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x03\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x0C\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\r\0\0\0\0\x10\0\0\0\x01\x01\0\0\0\0\0\0\0c\x0E\0\0\0\0 \0\0\x000\0\0\0\0\x02\0\0\0\x02\0\0\0\x02\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\x02\0\0\0\x01\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\x02\0\0\0\0\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\x01\0\0\0\x02\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\x01\0\0\0\x01\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\x01\0\0\0\0\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\0\0\0\0\x02\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\x04\0\x02\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\x04\x010\0\0\0\0\0\0\0MatricesF32X_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\t\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x10\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\x11\0\0\0\0@\0\0\0\x01\x01\0\0\0\0\0\0\0c\x12\0\0\0\0\x80\0\0\0\x01\x01\0\0\0\0\0\0\0d\x13\0\0\0\0\xA0\0\0\0\x01\x01\0\0\0\0\0\0\0e\x14\0\0\0\0\xD0\0\0\0\x01\x01\0\0\0\0\0\0\0f\x15\0\0\0\0\0\x01\0\0\x01\x01\0\0\0\0\0\0\0g\x16\0\0\0\0 \x01\0\0\x01\x01\0\0\0\0\0\0\0h\x17\0\0\0\0@\x01\0\0\x01\x01\0\0\0\0\0\0\0i\x18\0\0\0\0`\x01\0\0p\x01\0\0\0\x06\0\0\0\0\0\0\0\0\0\0\0\x05\0\0\0\x04\0\0\0\0\x06\0\0\0\x02\0\0\0\0\0\0\0\x03\0\0\0\x04\0\0\0\0\x06\0\0\0\x10\0\0\0\0\0\0\0\0\x02\0\0@\0\0\0\0\x06\0\0\0\r\0\0\0\0\0\0\0\x04\0\0\0\x10\0\0\0\x011\0\0\0\0\0\0\0StaticArraysX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x04\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x1A\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\x1B\0\0\0\0\x14\0\0\0\x01\x01\0\0\0\0\0\0\0c\x1C\0\0\0\0 \0\0\0\x01\x01\0\0\0\0\0\0\0d\x1D\0\0\0\0 \x80\0\0`\x80\0\0\x01+\0\0\0\0\0\0\0NestedX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x02\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x19\0\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\x0F\0\0\0\0p\x01\0\0\xA0\x01\0\0\x01-\0\0\0\0\0\0\0VertexInX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX\x07\0\0\0\x01\0\0\0\0\0\0\0\x01\x08\0\0\0\0\0\0\0position\x0E\0\0\0\x01\x01\0\0\0\0\0\0\0\0\x01\0\0\0\0\x01\0\0\0\0\0\0\0\0\x10\0\0\0\x01\x08\0\0\0\0\0\0\0Uniforms\x07\0\0\0\x02\0\0\0\0\0\0\0\x01\t\0\0\0\0\0\0\0color_rgb\x0E\0\0\0\0\0\0\0\0\x01\x07\0\0\0\0\0\0\0scalars\x03\0\0\0\0\x10\0\0\0 \0\0\0\x01\x0C\0\0\0\0\0\0\0VertexOutput\x07\0\0\0\x01\0\0\0\0\0\0\0\x01\x08\0\0\0\0\0\0\0position\x0E\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\x10\0\0\0\0\x08\0\0\0\x01\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\t\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\n\0\0\0\0\0\0\0\x01\x01\0\0\0\0\0\0\0a\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\x02\0\0\0\x03\0\0\0\0\x01\x01\0\0\0\0\0\0\0b\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\x03\0\0\0\x07\0\0\0\0\x01\x01\0\0\0\0\0\0\0c\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\x04\0\0\0\x0B\0\0\0\0\x01\x01\0\0\0\0\0\0\0d\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\x05\0\0\0\x0F\0\0\0\0\x01\x01\0\0\0\0\0\0\0f\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\x06\0\0\0\x19\0\0\0\0\x01\x01\0\0\0\0\0\0\0h\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\x08\0\0\0\x1E\0\0\0\0\x01\x01\0\0\0\0\0\0\0i\x04\0\0\0\x01\0\0\0\x01\x02\0\0\0\t\0\0\0\x1F\0\0\0\0\x01\r\0\0\0\0\0\0\0color_texture\x05\0\0\0\x01\0\0\0\0\0\0\0\0#\0\0\0\0\x01\r\0\0\0\0\0\0\0color_sampler\x05\0\0\0\x01\0\0\0\0\x01\0\0\0$\0\0\0\0\x01\x08\0\0\0\0\0\0\0uniforms\x03\0\0\0\x01\x01\0\0\0\0\0\0\0!\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\x0B\0\0\0\0\0\0\0vertex_main\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\x0B\0\0\0\0\0\0\0vertex_main\x01\0\0\0\0\0\0\0\x01\x05\0\0\0\0\0\0\0input \0\0\0\0\x01\"\0\0\0\0\x01\0\0\0\0\0\0\0\x01\x06\0\0\0\0\0\0\0output\"\0\0\0\0\x05\0\0\0\0\0\0\0\x0B\0\0\0\0\0\0\0\t\0\0\0\0\0\0\0\x06\0\0\0\0\0\0\0\0\0\0\0\x06\0\0\0\x01\0\0\0\0\0\0\0\x0C\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\x01\0\0\0\x05\0\0\0\0\0\0\0input\x04\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\x04\0\0\0\n\0\0\0\x02\0\0\0\x03\0\0\0\0\0\0\0\x04\0\0\0\x05\0\0\0\x07\0\0\0\x01\x04\0\0\0\r\0\0\0\0\0\0\0fragment_main\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\r\0\0\0\0\0\0\0fragment_main\x01\0\0\0\0\0\0\0\x01\x05\0\0\0\0\0\0\0input\"\0\0\0\0\x01\x0E\0\0\0\x01\x01\0\0\0\0\0\0\0\0\x01\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\x05\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\0\0\x80?\0\0\0\0\x01\0\0\0\0\0\x80?\0\0\0\0\x01\0\0\0\0\0\x80?\0\0\0\0\x01\0\0\0\0\0\x80?\x04\0\0\0\x0E\0\0\0\x04\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\x03\0\0\0\0\0\0\0\0\0\0\0\x03\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x04\0\0\0\x05\0\0\0\x07\0\0\0\x01\x04\0\0\0";
     pub fn load_shader_modules_embedded(
         composer: &mut naga_oil::compose::Composer,
         shader_defs: &std::collections::HashMap<
@@ -1152,6 +2495,65 @@ fn fragment_main(input_1: VertexOutput) -> @location(0) vec4<f32> {
                 ..Default::default()
             })
     }
+    pub fn load_naga_module_from_reader(
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::from_bits_retain(1));
+        composer
+            .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
+                source: &read_source("utils/types.wgsl"),
+                file_path: "utils/types.wgsl",
+                language: naga_oil::compose::ShaderLanguage::Wgsl,
+                shader_defs: shader_defs.clone(),
+                as_name: Some("utils::types".into()),
+                ..Default::default()
+            })?;
+        composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: &read_source("testbed.wgsl"),
+                file_path: "testbed.wgsl",
+                shader_defs,
+                ..Default::default()
+            })
+    }
+    pub fn load_naga_module_from_dir(
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        load_naga_module_from_reader(
+            |path| std::fs::read_to_string(base_dir.join(path)).unwrap(),
+            shader_defs,
+        )
+    }
+    pub fn create_shader_module_from_reader(
+        device: &wgpu::Device,
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+        let module = load_naga_module_from_reader(read_source, shader_defs)?;
+        let info = wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::empty(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .unwrap();
+        let shader_string = wgpu::naga::back::wgsl::write_string(
+                &module,
+                &info,
+                wgpu::naga::back::wgsl::WriterFlags::empty(),
+            )
+            .expect("failed to convert naga module to source");
+        let source = std::borrow::Cow::Owned(shader_string);
+        Ok(
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("testbed.wgsl"),
+                    source: wgpu::ShaderSource::Wgsl(source),
+                }),
+        )
+    }
     pub fn create_shader_module_from_path(
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
@@ -1193,6 +2595,40 @@ pub mod triangle {
     pub const fn Uniforms(color_rgb: glam::Vec4) -> Uniforms {
         Uniforms { color_rgb }
     }
+    impl Uniforms {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Uniforms {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color_rgb",
+                    std::mem::offset_of!(Self, color_rgb),
+                    std::mem::size_of::<glam::Vec4>(),
+                    0,
+                    16,
+                    16,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`triangle::Uniforms` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
     #[repr(C)]
     #[derive(Debug, PartialEq, Clone, Copy)]
     pub struct VertexInput {
@@ -1202,7 +2638,19 @@ pub mod triangle {
         VertexInput { position }
     }
     impl VertexInput {
-        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 1] = [
+        pub fn from_parts(position: impl Into<glam::Vec3A>) -> Self {
+            Self { position: position.into() }
+        }
+    }
+    impl VertexInput {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl VertexInput {
+        pub const ATTRIBUTE_COUNT: usize = 1;
+        pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
             wgpu::VertexAttribute {
                 format: wgpu::VertexFormat::Float32x3,
                 offset: std::mem::offset_of!(Self, position) as u64,
@@ -1213,12 +2661,31 @@ pub mod triangle {
             step_mode: wgpu::VertexStepMode,
         ) -> wgpu::VertexBufferLayout<'static> {
             wgpu::VertexBufferLayout {
-                array_stride: std::mem::size_of::<Self>() as u64,
+                array_stride: Self::STRIDE,
                 step_mode,
                 attributes: &Self::VERTEX_ATTRIBUTES,
             }
         }
     }
+    const VERTEX_INPUT_LAYOUT_ASSERTS: () = {
+        assert!(
+            std::mem::size_of:: < VertexInput > () % 4 == 0,
+            "the array stride of `triangle::VertexInput` must be a multiple of 4 bytes"
+        );
+        assert!(
+            std::mem::size_of:: < VertexInput > () <= 2048,
+            "the array stride of `triangle::VertexInput` must be at most 2048 bytes"
+        );
+        assert!(
+            std::mem::offset_of!(VertexInput, position) % 4 == 0,
+            "`triangle::VertexInput::position` must be aligned to 4 bytes in the vertex buffer"
+        );
+        assert!(
+            std::mem::offset_of!(VertexInput, position) + 12 <= std::mem::size_of:: <
+            VertexInput > (),
+            "`triangle::VertexInput::position` must be read within the array stride"
+        );
+    };
     #[repr(C, align(16))]
     #[derive(Debug, PartialEq, Clone, Copy)]
     pub struct PushConstants {
@@ -1228,6 +2695,40 @@ pub mod triangle {
     pub const fn PushConstants(color_matrix: glam::Mat4) -> PushConstants {
         PushConstants { color_matrix }
     }
+    impl PushConstants {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl PushConstants {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color_matrix",
+                    std::mem::offset_of!(Self, color_matrix),
+                    std::mem::size_of::<glam::Mat4>(),
+                    0,
+                    64,
+                    64,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`triangle::PushConstants` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
     #[derive(Debug)]
     pub struct WgpuBindGroup0EntriesParams<'a> {
         pub color_texture: &'a wgpu::TextureView,
@@ -1293,17 +2794,14 @@ pub mod triangle {
             device: &wgpu::Device,
             bindings: WgpuBindGroup0Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Triangle::BindGroup0"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Triangle::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
         }
         pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
@@ -1361,17 +2859,14 @@ pub mod triangle {
             device: &wgpu::Device,
             bindings: WgpuBindGroup1Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Triangle::BindGroup1"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Triangle::BindGroup1",
+                    &bindings.as_array(),
+                ),
+            )
         }
         pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
@@ -1396,28 +2891,119 @@ pub mod triangle {
         bind_group0.set(pass);
         bind_group1.set(pass);
     }
-    pub const ENTRY_VS_MAIN: &str = "vs_main";
-    pub const ENTRY_FS_MAIN: &str = "fs_main";
-    #[derive(Debug)]
-    pub struct VertexEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-        pub constants: std::collections::HashMap<String, f64>,
+    pub fn create_uniforms_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::triangle::Uniforms,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Triangle::uniforms"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
     }
-    pub fn vertex_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a VertexEntry<N>,
-    ) -> wgpu::VertexState<'a> {
-        wgpu::VertexState {
-            module,
-            entry_point: Some(entry.entry_point),
-            buffers: &entry.buffers,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
-        }
+    pub fn write_uniforms(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::triangle::Uniforms,
+    ) {
+        write_uniforms_at(queue, buffer, 0, value)
+    }
+    pub fn write_uniforms_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::triangle::Uniforms,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
     }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_uniforms_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::triangle::Uniforms],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Triangle::uniforms"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn color_texture_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Triangle::color_texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_color_texture(texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Triangle::color_texture"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    aspect: wgpu::TextureAspect::All,
+                    ..Default::default()
+                },
+            )
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS;
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        max_push_constant_size: 64,
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 1,
+        uniform_bytes: 16,
+        storage_buffers: 0,
+        textures_1d: 0,
+        textures_2d: 1,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 1,
+        push_constant_size: 64,
+    };
+    pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
     pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
         VertexEntry {
             entry_point: ENTRY_VS_MAIN,
@@ -1425,26 +3011,22 @@ pub mod triangle {
             constants: Default::default(),
         }
     }
-    #[derive(Debug)]
-    pub struct FragmentEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub targets: [Option<wgpu::ColorTargetState>; N],
-        pub constants: std::collections::HashMap<String, f64>,
+    /// `vs_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes.
+    pub fn vs_main_entry_default() -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+            constants: Default::default(),
+        }
     }
-    pub fn fragment_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a FragmentEntry<N>,
-    ) -> wgpu::FragmentState<'a> {
-        wgpu::FragmentState {
-            module,
-            entry_point: Some(entry.entry_point),
-            targets: &entry.targets,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
+    /// The buffers of `vs_main_entry_default` in a const fn, eg: to keep them in a static.
+    pub const fn vs_main_const_entry() -> ConstVertexEntry<1> {
+        ConstVertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
         }
     }
+    pub use _root::_support::{FragmentEntry, fragment_state};
     pub fn fs_main_entry(
         targets: [Option<wgpu::ColorTargetState>; 1],
     ) -> FragmentEntry<1> {
@@ -1481,6 +3063,9 @@ pub mod triangle {
                 },
             )
     }
+    pub const SOURCE_HASH: &str = "b3b475217936cd705992360bd37e84226e86408c5001c28ab2f8fe6d819edd3a";
+    pub const SOURCE_PATH: &str = "shaders/triangle.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &["triangle.wgsl"];
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -1536,6 +3121,16 @@ fn fs_main(in_1: VertexOutput) -> @location(0) vec4<f32> {
     return (_e8 * vec4<f32>((color * _e11.xyz), 1f));
 }
 "#;
+    pub fn create_shader_module_naga_ir(device: &wgpu::Device) -> wgpu::ShaderModule {
+        let module: wgpu::naga::Module = bincode::deserialize(NAGA_MODULE_BYTES)
+            .expect("failed to deserialize naga module");
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("triangle.wgsl"),
+                source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(module)),
+            })
+    }
+    pub const NAGA_MODULE_BYTES: &[u8] = b"\n\0\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\x02\0\0\0\x04\x01\x08\0\0\0\0\0\0\0Uniforms\x07\0\0\0\x01\0\0\0\0\0\0\0\x01\t\0\0\0\0\0\0\0color_rgb\0\0\0\0\0\0\0\0\0\x10\0\0\0\0\x01\0\0\0\x01\0\0\0\x02\0\0\0\x04\x01\x0B\0\0\0\0\0\0\0VertexInput\x07\0\0\0\x01\0\0\0\0\0\0\0\x01\x08\0\0\0\0\0\0\0position\x02\0\0\0\x01\x01\0\0\0\0\0\0\0\0\x01\0\0\0\0\x01\0\0\0\0\0\0\0\0\x10\0\0\0\0\x01\0\0\0\0\0\0\0\x02\0\0\0\x04\x01\x0C\0\0\0\0\0\0\0VertexOutput\x07\0\0\0\x02\0\0\0\0\0\0\0\x01\r\0\0\0\0\0\0\0clip_position\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\n\0\0\0\0\0\0\0tex_coords\x04\0\0\0\x01\x01\0\0\0\0\0\0\0\0\x01\0\0\0\0\x01\0\0\0\0\x10\0\0\0 \0\0\0\0\x02\0\0\0\x02\0\0\0\x02\0\0\0\x02\0\0\0\x04\x01\r\0\0\0\0\0\0\0PushConstants\x07\0\0\0\x01\0\0\0\0\0\0\0\x01\x0C\0\0\0\0\0\0\0color_matrix\x06\0\0\0\0\0\0\0\0@\0\0\0\0\x08\0\0\0\x01\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\t\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x04\0\0\0\0\0\0\0\x01\r\0\0\0\0\0\0\0color_texture\x05\0\0\0\x01\0\0\0\0\0\0\0\0\x08\0\0\0\0\x01\r\0\0\0\0\0\0\0color_sampler\x05\0\0\0\x01\0\0\0\0\x01\0\0\0\t\0\0\0\0\x01\x08\0\0\0\0\0\0\0uniforms\x03\0\0\0\x01\x01\0\0\0\0\0\0\0\x01\0\0\0\0\x01\t\0\0\0\0\0\0\0constants\x06\0\0\0\0\x07\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\x07\0\0\0\0\0\0\0vs_main\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\x07\0\0\0\0\0\0\0vs_main\x01\0\0\0\0\0\0\0\x01\x02\0\0\0\0\0\0\0in\x03\0\0\0\0\x01\x05\0\0\0\0\x01\0\0\0\0\0\0\0\x01\x03\0\0\0\0\0\0\0out\x05\0\0\0\0\x10\0\0\0\0\0\0\0\x0B\0\0\0\0\0\0\0\t\0\0\0\0\0\0\0\x06\0\0\0\0\0\0\0\0\0\0\0\x06\0\0\0\x01\0\0\0\0\0\0\0\x08\0\0\0\x01\0\0\0\x03\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\0\0\x80?\x04\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\x04\0\0\0\x05\0\0\0\x06\0\0\0\0\0\0\0\x01\0\0\0\x06\0\0\0\x01\0\0\0\0\0\0\0\x08\0\0\0\0\0\0\0\x08\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0\0\0\0?\x11\0\0\0\x02\0\0\0\t\0\0\0\n\0\0\0\0\0\0\0\x01\0\0\0\0\0\0?\x07\0\0\0\0\0\0\0\x0C\0\0\0\x11\0\0\0\0\0\0\0\x0B\0\0\0\r\0\0\0\x0C\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\0\0\0\0in\n\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\x05\0\0\0\0\0\0\0\x06\0\0\0\x07\0\0\0\n\0\0\0\x02\0\0\0\x06\0\0\0\0\0\0\0\x07\0\0\0\n\0\0\0\0\0\0\0\x0B\0\0\0\x0C\0\0\0\0\0\0\0\x0C\0\0\0\x0C\0\0\0\0\0\0\0\r\0\0\0\x0F\0\0\0\n\0\0\0\x07\0\0\0\x0E\0\0\0\0\0\0\0\x0F\0\0\0\x10\0\0\0\x07\0\0\0\x01\x0F\0\0\0\x07\0\0\0\0\0\0\0fs_main\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\x07\0\0\0\0\0\0\0fs_main\x01\0\0\0\0\0\0\0\x01\x02\0\0\0\0\0\0\0in\x05\0\0\0\0\x01\0\0\0\0\x01\x01\0\0\0\0\0\0\0\0\x01\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\x11\0\0\0\0\0\0\0\t\0\0\0\0\0\0\0\n\0\0\0\0\0\0\0\n\0\0\0\x01\0\0\0\x06\0\0\0\0\0\0\0\x01\0\0\0\r\0\0\0\x01\0\0\0\x02\0\0\0\0\x03\0\0\0\0\0\0\0\0\0\0\x08\0\0\0\x01\0\0\0\x04\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\0\0\0\0\n\0\0\0\x03\0\0\0\x06\0\0\0\x06\0\0\0\0\0\0\0\x0C\0\0\0\x07\0\0\0\n\0\0\0\x02\0\0\0\x06\0\0\0\t\0\0\0\0\0\0\0\x0C\0\0\0\n\0\0\0\x08\0\0\0\x01\0\0\0\x0B\0\0\0\0\0\0\0\x01\0\0\0\x02\0\0\0\0\0\0\0\x11\0\0\0\x02\0\0\0\x05\0\0\0\x0C\0\0\0\0\0\0\0\x01\0\0\0\0\0\x80?\x04\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\r\0\0\0\x0E\0\0\0\x11\0\0\0\x02\0\0\0\x08\0\0\0\x0F\0\0\0\x02\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0in\x05\0\0\0\x05\0\0\0\0\0\0\0color\x05\0\0\0\0\0\0\0\0\0\0\0\x03\0\0\0\x06\0\0\0\0\0\0\0\x07\0\0\0\t\0\0\0\0\0\0\0\n\0\0\0\x0E\0\0\0\0\0\0\0\x0F\0\0\0\x11\0\0\0\x07\0\0\0\x01\x10\0\0\0";
     pub fn load_shader_modules_embedded(
         composer: &mut naga_oil::compose::Composer,
         shader_defs: &std::collections::HashMap<
@@ -1610,6 +3205,56 @@ fn fs_main(in_1: VertexOutput) -> @location(0) vec4<f32> {
                 ..Default::default()
             })
     }
+    pub fn load_naga_module_from_reader(
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::from_bits_retain(1));
+        composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: &read_source("triangle.wgsl"),
+                file_path: "triangle.wgsl",
+                shader_defs,
+                ..Default::default()
+            })
+    }
+    pub fn load_naga_module_from_dir(
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        load_naga_module_from_reader(
+            |path| std::fs::read_to_string(base_dir.join(path)).unwrap(),
+            shader_defs,
+        )
+    }
+    pub fn create_shader_module_from_reader(
+        device: &wgpu::Device,
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+        let module = load_naga_module_from_reader(read_source, shader_defs)?;
+        let info = wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::empty(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .unwrap();
+        let shader_string = wgpu::naga::back::wgsl::write_string(
+                &module,
+                &info,
+                wgpu::naga::back::wgsl::WriterFlags::empty(),
+            )
+            .expect("failed to convert naga module to source");
+        let source = std::borrow::Cow::Owned(shader_string);
+        Ok(
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("triangle.wgsl"),
+                    source: wgpu::ShaderSource::Wgsl(source),
+                }),
+        )
+    }
     pub fn create_shader_module_from_path(
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
@@ -1640,3 +3285,29 @@ fn fs_main(in_1: VertexOutput) -> @location(0) vec4<f32> {
         )
     }
 }
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 4;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        testbed::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+        testbed::WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+        testbed::WgpuBindGroup2::LAYOUT_DESCRIPTOR,
+        triangle::WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Testbed, 0) => Some(0),
+            (ShaderEntry::Testbed, 1) => Some(1),
+            (ShaderEntry::Testbed, 2) => Some(2),
+            (ShaderEntry::Triangle, 0) => Some(0),
+            (ShaderEntry::Triangle, 1) => Some(3),
+            _ => None,
+        }
+    }
+}
@@ -7,6 +7,7 @@ use smallvec::SmallVec;
 use thiserror::Error;
 use DependencyTreeError::*;
 
+use super::parse_directives::WgslExtension;
 use super::parse_imports::ImportStatement;
 use super::source_file::SourceFile;
 use super::ModulePathResolver;
@@ -18,9 +19,16 @@ use crate::{
 #[derive(Debug, Error, Diagnostic)]
 pub enum DependencyTreeError {
   #[error("Source file not found: {path}")]
+  #[diagnostic(
+    code(wgsl_bindgen::source_not_found),
+    help("Check the entry point paths passed to `add_entry_point`.")
+  )]
   SourceNotFound { path: SourceFilePath },
   #[error("Cannot find import `{path}` in this scope")]
-  #[diagnostic(help("Maybe a typo or a missing file."))]
+  #[diagnostic(
+    code(wgsl_bindgen::import_path_not_found),
+    help("Maybe a typo or a missing file.")
+  )]
   ImportPathNotFound {
     path: String,
     stmt: ImportStatement,
@@ -88,11 +96,64 @@ pub struct SourceWithFullDependenciesResult<'a> {
   pub full_dependencies: SmallVec<[&'a SourceFile; 16]>,
 }
 
+impl<'a> SourceWithFullDependenciesResult<'a> {
+  /// The extensions enabled by the source file and all of its dependencies.
+  pub fn extensions(&self) -> FxIndexSet<WgslExtension> {
+    std::iter::once(self.source_file)
+      .chain(self.full_dependencies.iter().copied())
+      .flat_map(|source| source.extensions.iter().copied())
+      .collect()
+  }
+
+  /// The `diagnostic(...)` directives declared by the source file and all of its dependencies.
+  pub fn diagnostics(&self) -> FxIndexSet<&'a str> {
+    std::iter::once(self.source_file)
+      .chain(self.full_dependencies.iter().copied())
+      .flat_map(|source| source.diagnostics.iter().map(String::as_str))
+      .collect()
+  }
+
+  /// The device features implied by the enabled extensions.
+  pub fn extension_features(&self) -> wgpu::Features {
+    self
+      .extensions()
+      .iter()
+      .fold(wgpu::Features::empty(), |acc, ext| acc | ext.features())
+  }
+
+  /// Combines the given naga capabilities with the ones implied by the enabled extensions and
+  /// the ones the sources use, leaving out the `denied` ones. Returns `None` when nothing is
+  /// added or denied, leaving the composer defaults in place.
+  pub fn capabilities_with(
+    &self,
+    capabilities: Option<naga::valid::Capabilities>,
+    denied: naga::valid::Capabilities,
+  ) -> Option<naga::valid::Capabilities> {
+    let sources =
+      std::iter::once(self.source_file).chain(self.full_dependencies.iter().copied());
+    let used_capabilities = self
+      .extensions()
+      .iter()
+      .map(WgslExtension::capabilities)
+      .chain(sources.map(|source| source.capabilities))
+      .fold(naga::valid::Capabilities::empty(), |acc, capabilities| acc | capabilities);
+
+    match capabilities {
+      Some(capabilities) => Some((capabilities | used_capabilities) - denied),
+      None if !used_capabilities.is_empty() || !denied.is_empty() => {
+        Some((naga::valid::Capabilities::default() | used_capabilities) - denied)
+      }
+      None => None,
+    }
+  }
+}
+
 #[derive(Debug)]
 pub struct DependencyTree {
   resolver: ModulePathResolver,
   parsed_sources: FxIndexMap<SourceFilePath, SourceFile>,
   entry_points: FxIndexSet<SourceFilePath>,
+  inline_sources: FxIndexMap<SourceFilePath, SourceFile>,
 }
 
 /// Represents a dependency tree for tracking the dependencies between source files.
@@ -113,6 +174,8 @@ impl DependencyTree {
   /// * `module_prefix` - An optional module prefix to be used when generating import paths.
   /// * `entry_points` - A vector of source file paths representing the entry points of the
   ///   dependency tree.
+  /// * `inline_sources` - Sources given as strings, which are used instead of reading the files
+  ///   at their paths. The ones with a module name can be imported by that name.
   ///
   /// # Returns
   ///
@@ -123,6 +186,7 @@ impl DependencyTree {
     entry_module_prefix: Option<String>,
     entry_points: Vec<SourceFilePath>, // path to entry points
     additional_scan_dirs: Vec<AdditionalScanDirectory>,
+    inline_sources: Vec<SourceFile>,
   ) -> Result<Self, DependencyTreeError> {
     let resolver =
       ModulePathResolver::new(workspace_root, entry_module_prefix, additional_scan_dirs);
@@ -131,6 +195,10 @@ impl DependencyTree {
       resolver,
       parsed_sources: Default::default(),
       entry_points: Default::default(),
+      inline_sources: inline_sources
+        .into_iter()
+        .map(|source| (source.file_path.clone(), source))
+        .collect(),
     };
 
     for entry_point in entry_points {
@@ -149,11 +217,13 @@ impl DependencyTree {
     import_path_part: &ImportPathPart,
     limiter: &mut MaxRecursionLimiter,
   ) -> Result<(), DependencyTreeError> {
-    let possible_source_path = self
-      .resolver
-      .generate_best_possible_paths(&import_path_part, parent_source_path)
-      .into_iter()
-      .find(|(_, path)| path.is_file()); // make sure this is not reimporting itself
+    let possible_source_path = self.find_inline_module(import_path_part).or_else(|| {
+      self
+        .resolver
+        .generate_best_possible_paths(&import_path_part, parent_source_path)
+        .into_iter()
+        .find(|(_, path)| path.is_file()) // make sure this is not reimporting itself
+    });
 
     let Some(parent_source) = self.parsed_sources.get_mut(parent_source_path) else {
       unreachable!("{:?} source code as not parsed", parent_source_path)
@@ -188,6 +258,30 @@ impl DependencyTree {
     Ok(())
   }
 
+  /// Finds the inline source with the longest module name the import path starts with,
+  /// eg: `procedural::noise` for `procedural::noise::fbm`.
+  fn find_inline_module(
+    &self,
+    import_path_part: &ImportPathPart,
+  ) -> Option<(SourceModuleName, SourceFilePath)> {
+    self
+      .inline_sources
+      .values()
+      .filter_map(|source| Some((source.module_name.as_ref()?, &source.file_path)))
+      .filter(|(name, _)| {
+        import_path_part
+          .strip_prefix(name.as_str())
+          .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+      })
+      .max_by_key(|(name, _)| name.len())
+      .map(|(name, path)| (name.clone(), path.clone()))
+  }
+
+  /// Returns whether the source at the path was given as a string rather than read from a file.
+  pub fn is_inline(&self, source_path: &SourceFilePath) -> bool {
+    self.inline_sources.contains_key(source_path)
+  }
+
   /// Crawls a source file and its dependencies.
   fn crawl_source(
     &mut self,
@@ -198,12 +292,15 @@ impl DependencyTree {
     match self.parsed_sources.entry(source_path.clone()) {
       Entry::Occupied(_) => {} // do nothing
       Entry::Vacant(entry) => {
-        let content = entry.key().read_contents().or(Err(SourceNotFound {
-          path: entry.key().clone(),
-        }))?;
-
-        let source_file =
-          SourceFile::create(entry.key().clone(), module_name.clone(), content);
+        let source_file = match self.inline_sources.get(entry.key()) {
+          Some(source_file) => source_file.clone(),
+          None => {
+            let content = entry.key().read_contents().or(Err(SourceNotFound {
+              path: entry.key().clone(),
+            }))?;
+            SourceFile::create(entry.key().clone(), module_name.clone(), content)
+          }
+        };
         entry.insert(source_file);
       }
     };
@@ -233,6 +330,49 @@ impl DependencyTree {
     self.parsed_sources.values().collect()
   }
 
+  /// Returns the paths of the entry points, in the order they were given.
+  pub fn entry_points(&self) -> &FxIndexSet<SourceFilePath> {
+    &self.entry_points
+  }
+
+  /// Returns the parsed source at the path, with its module name and direct dependencies.
+  pub fn source_file(&self, source_path: &SourceFilePath) -> Option<&SourceFile> {
+    self.parsed_sources.get(source_path)
+  }
+
+  /// Returns the import edges of the tree, as `(importing file, imported file)` pairs.
+  pub fn import_edges(&self) -> Vec<(&SourceFilePath, &SourceFilePath)> {
+    self
+      .parsed_sources
+      .values()
+      .flat_map(|source| {
+        let importer = &source.file_path;
+        source
+          .direct_dependencies
+          .iter()
+          .map(move |dep| (importer, dep))
+      })
+      .collect()
+  }
+
+  /// Returns the entry points that are, or transitively import, the source at the path.
+  pub fn entry_points_depending_on(
+    &self,
+    source_path: &SourceFilePath,
+  ) -> FxIndexSet<SourceFilePath> {
+    self
+      .entry_points
+      .iter()
+      .filter(|entry_point| {
+        *entry_point == source_path
+          || self
+            .get_full_dependency_for(entry_point)
+            .contains(source_path)
+      })
+      .cloned()
+      .collect()
+  }
+
   /// Returns the full set of dependencies for a given source file.
   pub fn get_full_dependency_for(
     &self,
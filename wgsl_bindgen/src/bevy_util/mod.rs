@@ -1,9 +1,12 @@
 pub mod deptree;
 mod module_path_resolver;
 mod name_demangle;
+pub mod parse_capabilities;
+pub mod parse_directives;
 pub mod parse_imports;
 pub mod source_file;
 
 pub use deptree::*;
 use module_path_resolver::*;
 pub use name_demangle::*;
+pub use parse_directives::WgslExtension;
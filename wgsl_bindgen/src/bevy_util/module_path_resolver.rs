@@ -107,10 +107,22 @@ impl ModulePathResolver {
     .collect::<FxIndexSet<_>>();
 
     for scan_dir in &self.additional_scan_dirs {
+      // Imports under the prefix of a directory resolve relative to it, eg: `game::materials`
+      // to `materials.wgsl` when the directory is registered with the `game` prefix.
+      let prefix = scan_dir
+        .module_import_root
+        .iter()
+        .flat_map(|prefix| prefix.split("::"))
+        .collect::<SmallVec<[&str; 4]>>();
+      let scan_parts = match import_parts.strip_prefix(prefix.as_slice()) {
+        Some(rest) if !rest.is_empty() => rest.into(),
+        _ => import_parts.clone(),
+      };
+
       let scan_path = Path::new(&scan_dir.directory);
       paths.extend(Self::generate_paths_for_dir(
         &scan_dir.module_import_root,
-        import_parts.clone(),
+        scan_parts,
         scan_path,
         source_path,
       ))
@@ -237,6 +249,21 @@ mod tests {
     assert_eq!(result, expected);
   }
 
+  #[test]
+  fn should_strip_prefix_of_additional_scan_dir() {
+    let source_path = SourceFilePath::new("mydir/source.wgsl");
+    let import_path_part = ImportPathPart::new("game::materials::Pbr");
+    let scan_dirs = vec![(Some("game::materials"), "game_lib").into()];
+
+    let result = ModulePathResolver::new("mydir".into(), None, scan_dirs)
+      .generate_best_possible_paths(&import_path_part, &source_path);
+
+    assert!(result.contains(&(
+      SourceModuleName::new("game::materials::Pbr"),
+      SourceFilePath::new("game_lib/Pbr.wgsl")
+    )));
+  }
+
   #[test]
   fn should_return_valid_pbr_paths_from_repeated_part() {
     let module_prefix = Some("bevy_pbr".to_string());
@@ -6,7 +6,7 @@ use smallvec::SmallVec;
 
 use crate::quote_gen::RustItemPath;
 
-const DECORATION_PRE: &str = "X_naga_oil_mod_X";
+pub(crate) const DECORATION_PRE: &str = "X_naga_oil_mod_X";
 const DECORATION_POST: &str = "X";
 
 impl RustItemPath {
@@ -0,0 +1,105 @@
+use std::sync::OnceLock;
+
+use naga::valid::Capabilities;
+use regex::Regex;
+
+/// The naga capabilities needed by each WGSL construct, matched as whole words of the source.
+const CAPABILITY_PATTERNS: &[(&str, Capabilities)] = &[
+  (r"var\s*<\s*push_constant\s*>", Capabilities::PUSH_CONSTANT),
+  (r"\bf64\b", Capabilities::FLOAT64),
+  (r"\b[iu]64\b", Capabilities::SHADER_INT64),
+  (r"\bprimitive_index\b", Capabilities::PRIMITIVE_INDEX),
+  (r"\bview_index\b", Capabilities::MULTIVIEW),
+  (r"\bclip_distances\b", Capabilities::CLIP_DISTANCE),
+  (r"\bearly_depth_test\b", Capabilities::EARLY_DEPTH_TEST),
+  (r"\bsample_index\b", Capabilities::MULTISAMPLED_SHADING),
+  (r"@interpolate\s*\([^)]*\bsample\b", Capabilities::MULTISAMPLED_SHADING),
+  (r"\btexture(_depth)?_cube_array\b", Capabilities::CUBE_ARRAY_TEXTURES),
+  (r"\b(rgba|rg|r)16[us]norm\b", Capabilities::STORAGE_TEXTURE_16BIT_NORM_FORMATS),
+  (r"\b(ray_query|acceleration_structure)\b", Capabilities::RAY_QUERY),
+  (r"@second_blend_source\b", Capabilities::DUAL_SOURCE_BLENDING),
+  (r"\bsubgroup_(invocation_id|size)\b", Capabilities::SUBGROUP),
+  (r"\bsubgroup[A-Z][A-Za-z]*\b", Capabilities::SUBGROUP),
+  (r"\bsubgroupBarrier\b", Capabilities::SUBGROUP_BARRIER),
+  // Whether the binding arrays are indexed uniformly isn't known from the source alone.
+  (
+    r"\bbinding_array\b",
+    Capabilities::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+      .union(Capabilities::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING)
+      .union(Capabilities::SAMPLER_NON_UNIFORM_INDEXING),
+  ),
+];
+
+fn capability_regexes() -> &'static [(Regex, Capabilities)] {
+  static MEM: OnceLock<Vec<(Regex, Capabilities)>> = OnceLock::new();
+  MEM.get_or_init(|| {
+    CAPABILITY_PATTERNS
+      .iter()
+      .map(|(pattern, capabilities)| {
+        (Regex::new(pattern).expect("Failed to compile regex"), *capabilities)
+      })
+      .collect()
+  })
+}
+
+fn comment_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"(?s)//[^\n]*|/\*.*?\*/").expect("Failed to compile regex")
+  })
+}
+
+/// Scans the content for the WGSL constructs naga only validates with a capability enabled,
+/// eg: `var<push_constant>` or `f64`, ignoring the ones in comments.
+pub fn used_capabilities(content: &str) -> Capabilities {
+  let code = comment_regex().replace_all(content, " ");
+  capability_regexes()
+    .iter()
+    .filter(|(regex, _)| regex.is_match(&code))
+    .fold(Capabilities::empty(), |acc, (_, capabilities)| acc | *capabilities)
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn capabilities_of_used_constructs() {
+    let content = indoc! {r#"
+      // var<push_constant> is only mentioned here.
+      @group(0) @binding(0) var textures: binding_array<texture_2d<f32>>;
+      var<push_constant> constants: vec4<f32>;
+
+      /* A multi line comment
+         mentioning f64 */
+      @fragment
+      fn main(@builtin(primitive_index) index: u32) -> @location(0) vec4<f32> {
+          return constants;
+      }
+    "#};
+
+    assert_eq!(
+      Capabilities::PUSH_CONSTANT
+        | Capabilities::PRIMITIVE_INDEX
+        | Capabilities::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+        | Capabilities::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING
+        | Capabilities::SAMPLER_NON_UNIFORM_INDEXING,
+      used_capabilities(content)
+    );
+  }
+
+  #[test]
+  fn no_capabilities_for_plain_shader() {
+    let content = indoc! {r#"
+      @fragment
+      fn main(@location(0) @interpolate(perspective, centroid) uv: vec2<f32>) -> @location(0) vec4<f32> {
+          return vec4(uv, 0.0, 1.0);
+      }
+    "#};
+
+    assert_eq!(Capabilities::empty(), used_capabilities(content));
+  }
+}
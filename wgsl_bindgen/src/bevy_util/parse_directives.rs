@@ -0,0 +1,451 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// A WGSL `enable` or `requires` extension understood by wgsl_bindgen.
+///
+/// naga does not accept these directives yet, so they are stripped from the source
+/// and mapped onto the naga capabilities and the device features they imply instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WgslExtension {
+  /// `enable f16;`
+  F16,
+  /// `enable clip_distances;`
+  ClipDistances,
+  /// `enable dual_source_blending;`
+  DualSourceBlending,
+  /// `enable subgroups;`
+  Subgroups,
+  /// `requires readonly_and_readwrite_storage_textures;`
+  ReadonlyAndReadwriteStorageTextures,
+  /// `requires packed_4x8_integer_dot_product;`
+  Packed4x8IntegerDotProduct,
+  /// `requires unrestricted_pointer_parameters;`
+  UnrestrictedPointerParameters,
+  /// `requires pointer_composite_access;`
+  PointerCompositeAccess,
+}
+
+impl WgslExtension {
+  fn from_directive(directive: &str, name: &str) -> Option<Self> {
+    use WgslExtension::*;
+    let extension = match (directive, name) {
+      ("enable", "f16") => F16,
+      ("enable", "clip_distances") => ClipDistances,
+      ("enable", "dual_source_blending") => DualSourceBlending,
+      ("enable", "subgroups") => Subgroups,
+      ("requires", "readonly_and_readwrite_storage_textures") => {
+        ReadonlyAndReadwriteStorageTextures
+      }
+      ("requires", "packed_4x8_integer_dot_product") => Packed4x8IntegerDotProduct,
+      ("requires", "unrestricted_pointer_parameters") => UnrestrictedPointerParameters,
+      ("requires", "pointer_composite_access") => PointerCompositeAccess,
+      _ => return None,
+    };
+    Some(extension)
+  }
+
+  /// The device features a shader using this extension requires.
+  pub fn features(&self) -> wgpu::Features {
+    use wgpu::Features;
+    match self {
+      Self::F16 => Features::SHADER_F16,
+      Self::DualSourceBlending => Features::DUAL_SOURCE_BLENDING,
+      Self::Subgroups => Features::SUBGROUP,
+      // wgpu doesn't gate clip distances on a device feature yet, and language extensions
+      // are part of the language itself.
+      _ => Features::empty(),
+    }
+  }
+
+  /// The naga capabilities needed to validate a shader using this extension.
+  pub fn capabilities(&self) -> naga::valid::Capabilities {
+    use naga::valid::Capabilities;
+    match self {
+      Self::ClipDistances => Capabilities::CLIP_DISTANCE,
+      Self::DualSourceBlending => Capabilities::DUAL_SOURCE_BLENDING,
+      Self::Subgroups => Capabilities::SUBGROUP,
+      _ => Capabilities::empty(),
+    }
+  }
+}
+
+fn directive_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"(?m)^[ \t]*(enable|requires)\s+([A-Za-z0-9_,\s]+?)\s*;")
+      .expect("Failed to compile regex")
+  })
+}
+
+/// Strips the `enable` and `requires` directives naming known extensions from the content,
+/// replacing them with whitespace so the offsets of the remaining source are unchanged.
+/// Directives naming any unknown extension are left in place for naga to report.
+pub fn strip_directives(content: &str) -> (String, Vec<WgslExtension>) {
+  let mut stripped = content.to_string();
+  let mut extensions = Vec::new();
+
+  for captures in directive_regex().captures_iter(content) {
+    let directive = captures.get(1).unwrap().as_str();
+    let names = captures.get(2).unwrap().as_str();
+
+    let known = names
+      .split(',')
+      .map(str::trim)
+      .filter(|name| !name.is_empty())
+      .map(|name| WgslExtension::from_directive(directive, name))
+      .collect::<Option<Vec<_>>>();
+
+    let Some(known) = known else {
+      continue;
+    };
+
+    let range = captures.get(0).unwrap().range();
+    let blank = content[range.clone()]
+      .chars()
+      .map(|c| if c == '\n' { '\n' } else { ' ' })
+      .collect::<String>();
+    stripped.replace_range(range, &blank);
+
+    for extension in known {
+      if !extensions.contains(&extension) {
+        extensions.push(extension);
+      }
+    }
+  }
+
+  (stripped, extensions)
+}
+
+fn diagnostic_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"(?m)^[ \t]*(diagnostic\s*\([^)]*\)\s*;)")
+      .expect("Failed to compile regex")
+  })
+}
+
+/// Comments out the global `diagnostic(...)` directives in the content, returning them as well.
+///
+/// naga does not parse diagnostic directives yet, so they are kept as comments
+/// in the composed and embedded sources rather than being dropped. They have no effect
+/// on the warnings of the shader, which is reported while generating.
+pub fn comment_out_diagnostics(content: &str) -> (String, Vec<String>) {
+  let mut diagnostics = Vec::new();
+  let commented =
+    diagnostic_regex().replace_all(content, |captures: &regex::Captures| {
+      let directive = captures.get(1).unwrap().as_str();
+      diagnostics.push(directive.to_string());
+      captures[0].replacen(directive, &format!("// {directive}"), 1)
+    });
+
+  (commented.into_owned(), diagnostics)
+}
+
+/// A `// @vertex_format(Unorm16x2)` comment on a field of a struct, reading the field's vertex
+/// attribute from data of another format, eg: normalized integers the shader sees as floats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexFormatDirective {
+  pub struct_name: String,
+  pub field_name: String,
+  /// The format as written in the comment, eg: `Unorm16x2`.
+  pub format: String,
+  /// The line of the comment, starting at 1.
+  pub line: usize,
+}
+
+fn struct_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"\bstruct\s+([A-Za-z_][A-Za-z0-9_]*)\s*\{([^}]*)\}")
+      .expect("Failed to compile regex")
+  })
+}
+
+fn vertex_format_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"//\s*@vertex_format\(\s*([A-Za-z0-9_-]+)\s*\)")
+      .expect("Failed to compile regex")
+  })
+}
+
+fn field_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"([A-Za-z_][A-Za-z0-9_]*)\s*:").expect("Failed to compile regex")
+  })
+}
+
+/// Collects the `// @vertex_format(...)` comments of the struct fields in the content. A comment
+/// applies to the field on its own line, or to the next field when it stands on a line of its own.
+pub fn vertex_format_directives(content: &str) -> Vec<VertexFormatDirective> {
+  let mut directives = Vec::new();
+
+  for captures in struct_regex().captures_iter(content) {
+    let struct_name = captures.get(1).unwrap().as_str();
+    let body = captures.get(2).unwrap();
+    let first_line = content[..body.start()].matches('\n').count() + 1;
+
+    let mut pending = None;
+    for (index, line) in body.as_str().lines().enumerate() {
+      let (code, comment) = match line.find("//") {
+        Some(start) => line.split_at(start),
+        None => (line, ""),
+      };
+      let format = vertex_format_regex()
+        .captures(comment)
+        .map(|captures| (captures[1].to_string(), first_line + index));
+
+      match field_regex().captures(code) {
+        Some(field) => {
+          if let Some((format, line)) = format.or(pending.take()) {
+            directives.push(VertexFormatDirective {
+              struct_name: struct_name.to_string(),
+              field_name: field[1].to_string(),
+              format,
+              line,
+            });
+          }
+        }
+        None if format.is_some() => pending = format,
+        None => {}
+      }
+    }
+  }
+
+  directives
+}
+
+fn instance_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(
+      r"(?m)^[ \t]*//[ \t]*@instance[ \t]*\n(?:[ \t]*//[^\n]*\n)*[ \t]*struct\s+([A-Za-z_][A-Za-z0-9_]*)",
+    )
+    .expect("Failed to compile regex")
+  })
+}
+
+/// Collects the names of the structs marked as per-instance vertex inputs by a `// @instance`
+/// comment among the comments right above them.
+pub fn instance_struct_directives(content: &str) -> Vec<String> {
+  instance_regex()
+    .captures_iter(content)
+    .map(|captures| captures[1].to_string())
+    .collect()
+}
+
+/// A `// @sampler(linear, repeat)` comment on a sampler binding, describing the sampler it's
+/// bound with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamplerDirective {
+  pub binding_name: String,
+  /// The arguments as written in the comment, eg: `linear, repeat`.
+  pub arguments: String,
+  /// The line of the comment, starting at 1.
+  pub line: usize,
+}
+
+fn sampler_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"//[ \t]*@sampler\(([^)]*)\)").expect("Failed to compile regex")
+  })
+}
+
+fn sampler_binding_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"\bvar\s+([A-Za-z_][A-Za-z0-9_]*)\s*:\s*sampler(?:_comparison)?\b")
+      .expect("Failed to compile regex")
+  })
+}
+
+/// Collects the `// @sampler(...)` comments of the sampler bindings in the content. A comment
+/// applies to the binding on its own line, or to the next binding when it stands on a line of
+/// its own, above the attributes of the binding.
+pub fn sampler_directives(content: &str) -> Vec<SamplerDirective> {
+  let mut directives = Vec::new();
+
+  let mut pending = None;
+  for (index, line) in content.lines().enumerate() {
+    let (code, comment) = match line.find("//") {
+      Some(start) => line.split_at(start),
+      None => (line, ""),
+    };
+    let arguments = sampler_regex()
+      .captures(comment)
+      .map(|captures| (captures[1].trim().to_string(), index + 1));
+
+    match sampler_binding_regex().captures(code) {
+      Some(binding) => {
+        if let Some((arguments, line)) = arguments.or(pending.take()) {
+          directives.push(SamplerDirective {
+            binding_name: binding[1].to_string(),
+            arguments,
+            line,
+          });
+        }
+      }
+      None if arguments.is_some() => pending = arguments,
+      // Only attributes and comments may stand between a comment and its binding.
+      None if code.trim().is_empty() || code.trim_start().starts_with('@') => {}
+      None => pending = None,
+    }
+  }
+
+  directives
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn strips_known_directives() {
+    let content = indoc! {r#"
+      enable f16, dual_source_blending;
+      requires pointer_composite_access;
+
+      @fragment
+      fn main() {}
+    "#};
+
+    let (stripped, extensions) = strip_directives(content);
+
+    assert_eq!(content.len(), stripped.len());
+    assert_eq!("@fragment\nfn main() {}\n", stripped.trim_start());
+    assert_eq!(
+      vec![
+        WgslExtension::F16,
+        WgslExtension::DualSourceBlending,
+        WgslExtension::PointerCompositeAccess
+      ],
+      extensions
+    );
+  }
+
+  #[test]
+  fn comments_out_diagnostic_directives() {
+    let content = indoc! {r#"
+      diagnostic(off, derivative_uniformity);
+      enable f16;
+
+      @fragment
+      fn main() {}
+    "#};
+
+    let (commented, diagnostics) = comment_out_diagnostics(content);
+
+    assert_eq!(
+      indoc! {r#"
+        // diagnostic(off, derivative_uniformity);
+        enable f16;
+
+        @fragment
+        fn main() {}
+      "#},
+      commented
+    );
+    assert_eq!(vec!["diagnostic(off, derivative_uniformity);"], diagnostics);
+  }
+
+  #[test]
+  fn keeps_directives_with_unknown_extensions() {
+    let content = "enable f16, chromium_internal_graphite;\nfn main() {}\n";
+
+    let (stripped, extensions) = strip_directives(content);
+
+    assert_eq!(content, stripped);
+    assert_eq!(Vec::<WgslExtension>::new(), extensions);
+  }
+
+  #[test]
+  fn collects_vertex_format_directives() {
+    let content = indoc! {r#"
+      struct VertexInput {
+          @location(0) position: vec3<f32>,
+          // @vertex_format(Unorm16x2)
+          @location(1) uv: vec2<f32>,
+          @location(2) color: vec4<f32>, // @vertex_format(Unorm8x4)
+      };
+
+      // @vertex_format(Unorm8x4)
+      struct Other {
+          a: f32,
+      };
+    "#};
+
+    let directives = vertex_format_directives(content);
+
+    assert_eq!(
+      vec![
+        VertexFormatDirective {
+          struct_name: "VertexInput".to_string(),
+          field_name: "uv".to_string(),
+          format: "Unorm16x2".to_string(),
+          line: 3,
+        },
+        VertexFormatDirective {
+          struct_name: "VertexInput".to_string(),
+          field_name: "color".to_string(),
+          format: "Unorm8x4".to_string(),
+          line: 5,
+        },
+      ],
+      directives
+    );
+  }
+
+  #[test]
+  fn collects_instance_struct_directives() {
+    let content = indoc! {r#"
+      // @instance
+      // The transform of each sprite.
+      struct SpriteInstance {
+          @location(1) offset: vec2<f32>,
+      };
+
+      // @instance is not a directive here.
+      struct VertexInput {
+          @location(0) position: vec2<f32>,
+      };
+    "#};
+
+    assert_eq!(vec!["SpriteInstance".to_string()], instance_struct_directives(content));
+  }
+
+  #[test]
+  fn collects_sampler_directives() {
+    let content = indoc! {r#"
+      // @sampler(linear, repeat)
+      @group(0) @binding(1)
+      var color_sampler: sampler;
+      @group(0) @binding(2) var shadow_sampler: sampler_comparison; // @sampler(linear, greater-equal)
+
+      // @sampler(nearest)
+      fn main() {}
+      @group(0) @binding(3) var other_sampler: sampler;
+    "#};
+
+    assert_eq!(
+      vec![
+        SamplerDirective {
+          binding_name: "color_sampler".to_string(),
+          arguments: "linear, repeat".to_string(),
+          line: 1,
+        },
+        SamplerDirective {
+          binding_name: "shadow_sampler".to_string(),
+          arguments: "linear, greater-equal".to_string(),
+          line: 4,
+        },
+      ],
+      sampler_directives(content)
+    );
+  }
+}
@@ -1,7 +1,10 @@
 use smallvec::SmallVec;
 
-use super::parse_imports;
+use super::parse_directives::{
+  self, SamplerDirective, VertexFormatDirective, WgslExtension,
+};
 use super::parse_imports::ImportStatement;
+use super::{parse_capabilities, parse_imports};
 use crate::types::{FxIndexSet, SourceFilePath};
 use crate::{ImportPathPart, SourceModuleName};
 
@@ -11,6 +14,13 @@ pub struct SourceFile {
   pub module_name: Option<SourceModuleName>,
   pub content: String,
   pub imports: SmallVec<[ImportStatement; 4]>,
+  pub extensions: Vec<WgslExtension>,
+  /// The naga capabilities needed by the constructs the content uses, eg: `var<push_constant>`.
+  pub capabilities: naga::valid::Capabilities,
+  pub diagnostics: Vec<String>,
+  pub vertex_formats: Vec<VertexFormatDirective>,
+  pub instance_structs: Vec<String>,
+  pub samplers: Vec<SamplerDirective>,
   pub direct_dependencies: FxIndexSet<SourceFilePath>,
 }
 
@@ -21,11 +31,23 @@ impl SourceFile {
     content: String,
   ) -> Self {
     let normalized_content = content.replace("\r\n", "\n").replace("\r", "\n");
+    let (content, extensions) = parse_directives::strip_directives(&normalized_content);
+    let (content, diagnostics) = parse_directives::comment_out_diagnostics(&content);
+    let vertex_formats = parse_directives::vertex_format_directives(&content);
+    let instance_structs = parse_directives::instance_struct_directives(&content);
+    let samplers = parse_directives::sampler_directives(&content);
+    let capabilities = parse_capabilities::used_capabilities(&content);
     let mut source = Self {
       file_path,
       module_name,
-      content: normalized_content,
+      content,
       imports: SmallVec::default(),
+      extensions,
+      capabilities,
+      diagnostics,
+      vertex_formats,
+      instance_structs,
+      samplers,
       direct_dependencies: FxIndexSet::default(),
     };
 
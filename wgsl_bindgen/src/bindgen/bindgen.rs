@@ -1,68 +1,311 @@
 use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 use naga_oil::compose::{
   ComposableModuleDescriptor, Composer, ComposerError, NagaModuleDescriptor,
   ShaderLanguage,
 };
+use rayon::prelude::*;
+use regex::Regex;
 
+use super::diagnostics::entry_diagnostics;
+use super::options::{sampler_preset, vertex_format};
+use super::report::entry_report;
+use super::{
+  binding_reference, c_header, dependency_graph, entry_glob, module_cache, module_names,
+  typescript, user_regions,
+};
 use crate::bevy_util::source_file::SourceFile;
 use crate::bevy_util::DependencyTree;
+use crate::generate::entry::{float64_vertex_attributes, vertex_layout_errors};
+use crate::generate::pipeline::push_constant_errors;
+use crate::generate::requirements::{
+  downlevel_webgl2_incompatibilities, oversized_uniform_bindings,
+  workgroup_storage_errors,
+};
+use crate::naga_util::{
+  module_info, pull_vertices, remap_vertex_locations, rename_structs, validation_warnings,
+};
 use crate::{
-  create_rust_bindings, SourceFilePath, SourceWithFullDependenciesResult,
-  WgslBindgenError, WgslBindgenOption, WgslEntryResult, WgslShaderIrCapabilities,
+  create_rust_bindings, create_rust_bindings_files, FastIndexMap, FxIndexSet,
+  OutputSelection, OverrideVertexFormat, RemapVertexLocation, SourceFilePath,
+  SourceModuleName, SourceWithFullDependenciesResult, VertexPulling, WgslBindgenError,
+  WgslBindgenOption, WgslBindgenReport, WgslDiagnosticKind, WgslDownlevelCheck,
+  WgslEntryResult, WgslHashStorage, WgslOutputMode, WgslShaderIrCapabilities,
+  WgslShaderSourceType,
 };
 
 const PKG_VER: &str = env!("CARGO_PKG_VERSION");
 const PKG_NAME: &str = env!("CARGO_PKG_NAME");
 
+/// The entries ordered by output, starting with the entries of the main output.
+struct PartitionedEntries<'a> {
+  entries: Vec<WgslEntryResult<'a>>,
+  main_entries: usize,
+  /// The output and range in `entries` of each partition.
+  partitions: Vec<(PathBuf, Range<usize>)>,
+}
+
+/// The composers of a worker, one per set of capabilities the entries are composed with.
+type EntryComposers = Vec<(Option<WgslShaderIrCapabilities>, Composer)>;
+
 pub struct WGSLBindgen {
   dependency_tree: DependencyTree,
   options: WgslBindgenOption,
+  /// The lengths of `override_vertex_format`, `instance_vertex_inputs` and `sampler_presets` as
+  /// given, before the ones of the comments in the shaders are appended to them. Only read when
+  /// reloading the shaders.
+  #[cfg_attr(not(feature = "watch"), allow(dead_code))]
+  user_directive_options: (usize, usize, usize),
   content_hash: String,
 }
 
 impl WGSLBindgen {
-  pub(crate) fn new(options: WgslBindgenOption) -> Result<Self, WgslBindgenError> {
-    let entry_points = options
-      .entry_points
-      .iter()
-      .cloned()
-      .map(SourceFilePath::new)
-      .collect();
+  pub(crate) fn new(mut options: WgslBindgenOption) -> Result<Self, WgslBindgenError> {
+    if options.no_std {
+      let unsupported = options.shader_source_type & !WgslShaderSourceType::UseEmbed;
+      if let Some(source_type) = unsupported.iter().next() {
+        return Err(WgslBindgenError::NoStdIncompatibleSourceType { source_type });
+      }
+    }
 
-    let dependency_tree = DependencyTree::try_build(
-      options.workspace_root.clone(),
-      options.module_import_root.clone(),
-      entry_points,
-      options.additional_scan_dirs.clone(),
-    )?;
+    // The other source types compile the shader sources as written, without the remaps.
+    if !options.remap_vertex_location.is_empty() {
+      let unsupported = options.shader_source_type
+        & !(WgslShaderSourceType::UseEmbed | WgslShaderSourceType::UseNagaIr);
+      if let Some(source_type) = unsupported.iter().next() {
+        return Err(WgslBindgenError::RemapVertexLocationIncompatibleSourceType {
+          source_type,
+        });
+      }
+    }
+
+    if !options.vertex_pulling.is_empty() {
+      let unsupported = options.shader_source_type
+        & !(WgslShaderSourceType::UseEmbed | WgslShaderSourceType::UseNagaIr);
+      if let Some(source_type) = unsupported.iter().next() {
+        return Err(WgslBindgenError::VertexPullingIncompatibleSourceType {
+          source_type,
+        });
+      }
+    }
 
+    let dependency_tree = Self::build_dependency_tree(&options)?;
     let content_hash = Self::get_contents_hash(&options, &dependency_tree);
 
+    // The vertex formats given in the options take precedence over the ones of the comments.
+    let user_directive_options = (
+      options.override_vertex_format.len(),
+      options.instance_vertex_inputs.len(),
+      options.sampler_presets.len(),
+    );
+    Self::add_source_directives(&mut options, &dependency_tree)?;
+
     if options.emit_rerun_if_change {
       for file in Self::iter_files_to_watch(&dependency_tree) {
         println!("cargo:rerun-if-changed={}", file);
       }
+
+      // Rerun when shaders are added to the globbed directories as well.
+      for pattern in &options.entry_point_globs {
+        println!("cargo:rerun-if-changed={}", entry_glob::base_dir(pattern).display());
+      }
     }
 
     Ok(Self {
       dependency_tree,
       options,
+      user_directive_options,
       content_hash,
     })
   }
 
-  fn iter_files_to_watch(dep_tree: &DependencyTree) -> impl Iterator<Item = String> {
+  fn build_dependency_tree(
+    options: &WgslBindgenOption,
+  ) -> Result<DependencyTree, WgslBindgenError> {
+    let mut entry_points = options
+      .entry_points
+      .iter()
+      .cloned()
+      .map(SourceFilePath::new)
+      .collect::<FxIndexSet<_>>();
+
+    for pattern in &options.entry_point_globs {
+      entry_points.extend(
+        entry_glob::expand(pattern)?
+          .into_iter()
+          .map(SourceFilePath::new),
+      );
+    }
+
+    // Inline sources stand in for files in the workspace root, named after the entry or module.
+    let inline_path = |name: &str| {
+      SourceFilePath::new(options.workspace_root.join(format!("{name}.wgsl")))
+    };
+
+    let inline_entries = options.entry_point_sources.iter().map(|entry| {
+      SourceFile::create(inline_path(&entry.name), None, entry.source.clone())
+    });
+
+    let inline_modules = options.composable_sources.iter().map(|module| {
+      SourceFile::create(
+        inline_path(&module.name.replace("::", "/")),
+        Some(SourceModuleName::new(module.name.as_str())),
+        module.source.clone(),
+      )
+    });
+
+    let inline_sources = inline_entries.chain(inline_modules).collect::<Vec<_>>();
+    entry_points.extend(
+      inline_sources
+        .iter()
+        .filter(|source| source.module_name.is_none())
+        .map(|source| source.file_path.clone()),
+    );
+
+    let dependency_tree = DependencyTree::try_build(
+      options.workspace_root.clone(),
+      options.module_import_root.clone(),
+      entry_points.into_iter().collect(),
+      options.additional_scan_dirs.clone(),
+      inline_sources,
+    )?;
+
+    Ok(dependency_tree)
+  }
+
+  /// Rescans the shader sources, picking up changed contents and imports.
+  #[cfg(feature = "watch")]
+  pub(crate) fn reload(&mut self) -> Result<(), WgslBindgenError> {
+    self.dependency_tree = Self::build_dependency_tree(&self.options)?;
+    let (vertex_formats, instance_vertex_inputs, sampler_presets) =
+      self.user_directive_options;
+    self.options.override_vertex_format.truncate(vertex_formats);
+    self
+      .options
+      .instance_vertex_inputs
+      .truncate(instance_vertex_inputs);
+    self.options.sampler_presets.truncate(sampler_presets);
+    self.content_hash = Self::get_contents_hash(&self.options, &self.dependency_tree);
+
+    Self::add_source_directives(&mut self.options, &self.dependency_tree)
+  }
+
+  /// Adds the `// @vertex_format(...)` comments on the struct fields of the shaders to
+  /// `override_vertex_format`, the `// @instance` comments on their structs to
+  /// `instance_vertex_inputs`, and the `// @sampler(...)` comments on their sampler bindings to
+  /// `sampler_presets`, matching the items of the module declaring them.
+  fn add_source_directives(
+    options: &mut WgslBindgenOption,
+    dep_tree: &DependencyTree,
+  ) -> Result<(), WgslBindgenError> {
+    let entries = dep_tree.get_source_files_with_full_dependencies();
+    // Colliding module names fail the generation itself, so they aren't reported here.
+    let module_names = module_names::resolve(
+      entries.iter().map(|entry| &entry.source_file.file_path),
+      options,
+    )
+    .unwrap_or_default();
+
+    // The structs of an entry are in the module of the entry, and the ones of its imports in
+    // the modules of their import paths.
+    let sources = entries.iter().flat_map(|entry| {
+      let own = module_names
+        .get(&entry.source_file.file_path)
+        .map(|name| (entry.source_file, name.clone()));
+      let dependencies = entry.full_dependencies.iter().filter_map(|dependency| {
+        let name = dependency.module_name.as_ref()?;
+        Some((*dependency, name.to_string()))
+      });
+      own.into_iter().chain(dependencies)
+    });
+
+    let mut visited = FxIndexSet::default();
+    let mut vertex_formats = Vec::new();
+    let mut instance_vertex_inputs = Vec::new();
+    let mut sampler_presets = Vec::new();
+    for (source, module) in sources {
+      if !visited.insert((source.file_path.clone(), module.clone())) {
+        continue;
+      }
+
+      for directive in &source.vertex_formats {
+        // Both the Rust and the WebGPU names are accepted, eg: `Unorm8x4` or `unorm8x4`.
+        let name = directive.format.to_lowercase().replace('_', "-");
+        let format = vertex_format(&name).map_err(|_| {
+          WgslBindgenError::InvalidVertexFormatDirective {
+            path: source.file_path.to_string(),
+            line: directive.line,
+            format: directive.format.clone(),
+          }
+        })?;
+
+        let struct_name = format!("{module}::{}", directive.struct_name);
+        vertex_formats.push(OverrideVertexFormat {
+          struct_regex: exact_regex(&struct_name),
+          field_regex: exact_regex(&directive.field_name),
+          format,
+        });
+      }
+
+      instance_vertex_inputs.extend(
+        source
+          .instance_structs
+          .iter()
+          .map(|name| exact_regex(&format!("{module}::{name}"))),
+      );
+
+      for directive in &source.samplers {
+        let binding_regex = exact_regex(&format!("{module}::{}", directive.binding_name));
+        let preset =
+          sampler_preset(binding_regex, &directive.arguments).map_err(|reason| {
+            WgslBindgenError::InvalidSamplerDirective {
+              path: source.file_path.to_string(),
+              line: directive.line,
+              reason,
+            }
+          })?;
+        sampler_presets.push(preset);
+      }
+    }
+
+    options.override_vertex_format.extend(vertex_formats);
+    options
+      .instance_vertex_inputs
+      .extend(instance_vertex_inputs);
+    options.sampler_presets.extend(sampler_presets);
+    Ok(())
+  }
+
+  /// Returns the resolved dependency tree of the shaders, ie: their files, module names and
+  /// imports, as used to generate the bindings.
+  pub fn dependency_tree(&self) -> &DependencyTree {
+    &self.dependency_tree
+  }
+
+  #[cfg(feature = "watch")]
+  pub(crate) fn options(&self) -> &WgslBindgenOption {
+    &self.options
+  }
+
+  /// Returns the paths of the entry point files and all of their imports, skipping inline sources.
+  pub fn source_files(&self) -> Vec<String> {
+    Self::iter_files_to_watch(&self.dependency_tree).collect()
+  }
+
+  fn iter_files_to_watch(dep_tree: &DependencyTree) -> impl Iterator<Item = String> + '_ {
     dep_tree
       .all_files_including_dependencies()
       .into_iter()
+      .filter(|path| !dep_tree.is_inline(path))
       .map(|path| path.to_string())
   }
 
   fn get_contents_hash(options: &WgslBindgenOption, dep_tree: &DependencyTree) -> String {
     let mut hasher = blake3::Hasher::new();
 
-    hasher.update(format!("{:?}", options).as_bytes());
+    options.hash_output_options(&mut hasher);
     hasher.update(PKG_VER.as_bytes());
 
     for SourceFile { content, .. } in dep_tree.parsed_files() {
@@ -72,26 +315,87 @@ impl WGSLBindgen {
     hasher.finalize().to_string()
   }
 
-  fn generate_naga_module_for_entry(
-    ir_capabilities: Option<WgslShaderIrCapabilities>,
-    entry: SourceWithFullDependenciesResult<'_>,
-  ) -> Result<WgslEntryResult, WgslBindgenError> {
-    let map_err = |composer: &Composer, err: ComposerError| {
-      let msg = err.emit_to_string(composer);
-      WgslBindgenError::NagaModuleComposeError {
-        entry: entry.source_file.file_path.to_string(),
-        inner: err.inner,
-        msg,
+  fn generate_naga_module_for_entry<'a>(
+    composers: &mut EntryComposers,
+    capabilities: Option<WgslShaderIrCapabilities>,
+    cache_dir: Option<&Path>,
+    remaps: &[RemapVertexLocation],
+    pullings: &[VertexPulling],
+    module_names: &FastIndexMap<SourceFilePath, String>,
+    entry: SourceWithFullDependenciesResult<'a>,
+  ) -> Result<WgslEntryResult<'a>, WgslBindgenError> {
+    let mut compose = || Self::compose_entry(composers, capabilities, &entry);
+    let mut module = match cache_dir {
+      Some(cache_dir) => {
+        module_cache::get_or_compose(cache_dir, &entry, capabilities, compose)?
       }
+      None => compose()?,
+    };
+
+    let mod_name = module_names[&entry.source_file.file_path].clone();
+    remap_vertex_locations(&mut module, |location| {
+      RemapVertexLocation::find(remaps, &mod_name, location)
+    })
+    .map_err(|reasons| WgslBindgenError::InvalidVertexLayoutError {
+      entry: entry.source_file.file_path.to_string(),
+      reasons,
+    })?;
+
+    if let Some(group) = VertexPulling::find(pullings, &mod_name) {
+      pull_vertices(&mut module, &mod_name, group).map_err(|reasons| {
+        WgslBindgenError::VertexPullingError {
+          entry: entry.source_file.file_path.to_string(),
+          reasons,
+        }
+      })?;
+    }
+
+    Ok(WgslEntryResult {
+      mod_name,
+      module_info: module_info(&module),
+      naga_module: module,
+      source_including_deps: entry,
+    })
+  }
+
+  fn compose_entry(
+    composers: &mut EntryComposers,
+    capabilities: Option<WgslShaderIrCapabilities>,
+    entry: &SourceWithFullDependenciesResult<'_>,
+  ) -> Result<naga::Module, WgslBindgenError> {
+    let map_err = |composer: &Composer, err: ComposerError| {
+      WgslBindgenError::from_compose_error(
+        entry.source_file.file_path.to_string(),
+        composer,
+        err,
+      )
     };
 
-    let mut composer = match ir_capabilities {
-      Some(capabilities) => Composer::default().with_capabilities(capabilities),
-      _ => Composer::default(),
+    let index = match composers.iter().position(|(it, _)| *it == capabilities) {
+      Some(index) => index,
+      None => {
+        let composer = match capabilities {
+          Some(capabilities) => Composer::default().with_capabilities(capabilities),
+          _ => Composer::default(),
+        };
+        composers.push((capabilities, composer));
+        composers.len() - 1
+      }
     };
+    let composer = &mut composers[index].1;
     let source = entry.source_file;
 
     for dependency in entry.full_dependencies.iter() {
+      // Import paths resolve to the same file for every entry, so a dependency added by a
+      // previous entry is reused along with its composed module.
+      let is_added = dependency
+        .module_name
+        .as_ref()
+        .is_some_and(|name| composer.contains_module(&name.to_string()));
+      if is_added {
+        continue;
+      }
+
       composer
         .add_composable_module(ComposableModuleDescriptor {
           source: &dependency.content,
@@ -101,22 +405,16 @@ impl WGSLBindgen {
           ..Default::default()
         })
         .map(|_| ())
-        .map_err(|err| map_err(&composer, err))?;
+        .map_err(|err| map_err(composer, err))?;
     }
 
-    let module = composer
+    composer
       .make_naga_module(NagaModuleDescriptor {
         source: &source.content,
         file_path: &source.file_path.to_string(),
         ..Default::default()
       })
-      .map_err(|err| map_err(&composer, err))?;
-
-    Ok(WgslEntryResult {
-      mod_name: source.file_path.file_prefix(),
-      naga_module: module,
-      source_including_deps: entry,
-    })
+      .map_err(|err| map_err(composer, err))
   }
 
   pub fn header_texts(&self) -> String {
@@ -127,52 +425,683 @@ impl WGSLBindgen {
       writeln!(text, "//").unwrap();
       writeln!(text, "// ^ {PKG_NAME} version {PKG_VER}",).unwrap();
       writeln!(text, "// Changes made to this file will not be saved.").unwrap();
-      writeln!(text, "// SourceHash: {}", self.content_hash).unwrap();
+      if self.options.hash_storage != WgslHashStorage::SidecarFile {
+        writeln!(text, "// SourceHash: {}", self.content_hash).unwrap();
+      }
       writeln!(text).unwrap();
     }
     text
   }
 
-  fn generate_output(&self) -> Result<String, WgslBindgenError> {
-    let ir_capabilities = self.options.ir_capabilities;
-    let entry_results = self
+  fn check_downlevel_compatibility(
+    &self,
+    entry_results: &[WgslEntryResult],
+  ) -> Result<(), WgslBindgenError> {
+    if self.options.downlevel_check == WgslDownlevelCheck::Skip {
+      return Ok(());
+    }
+
+    for entry in entry_results {
+      let reasons = downlevel_webgl2_incompatibilities(
+        &entry.naga_module,
+        entry.module_info.as_ref(),
+        entry.source_including_deps.extension_features(),
+      );
+      if reasons.is_empty() {
+        continue;
+      }
+
+      let entry = entry
+        .source_including_deps
+        .source_file
+        .file_path
+        .to_string();
+      match self.options.downlevel_check {
+        WgslDownlevelCheck::Deny => {
+          return Err(WgslBindgenError::DownlevelIncompatibleError { entry, reasons })
+        }
+        WgslDownlevelCheck::Warn => println!(
+          "cargo:warning=Shader `{entry}` will not run on WebGL2: {}",
+          reasons.join(", ")
+        ),
+        WgslDownlevelCheck::Skip => {}
+      }
+    }
+
+    Ok(())
+  }
+
+  fn check_vertex_layouts(
+    &self,
+    entry_results: &[WgslEntryResult],
+  ) -> Result<(), WgslBindgenError> {
+    for entry in entry_results {
+      let reasons =
+        vertex_layout_errors(&entry.mod_name, &entry.naga_module, &self.options);
+      let entry_path = &entry.source_including_deps.source_file.file_path;
+      if !reasons.is_empty() {
+        let entry = entry_path.to_string();
+        return Err(WgslBindgenError::InvalidVertexLayoutError { entry, reasons });
+      }
+
+      // The pipelines reading these attributes panic on most backends, so they're always
+      // reported rather than only along the other diagnostics.
+      for attribute in
+        float64_vertex_attributes(&entry.mod_name, &entry.naga_module, &self.options)
+      {
+        println!("cargo:warning={entry_path}: {attribute}");
+      }
+    }
+
+    Ok(())
+  }
+
+  fn check_push_constants(
+    &self,
+    entry_results: &[WgslEntryResult],
+  ) -> Result<(), WgslBindgenError> {
+    for entry in entry_results {
+      let reasons = push_constant_errors(
+        &entry.naga_module,
+        entry.module_info.as_ref(),
+        &self.options,
+      );
+      if !reasons.is_empty() {
+        let entry = entry
+          .source_including_deps
+          .source_file
+          .file_path
+          .to_string();
+        return Err(WgslBindgenError::InvalidPushConstantsError { entry, reasons });
+      }
+    }
+
+    Ok(())
+  }
+
+  fn check_workgroup_storage(
+    &self,
+    entry_results: &[WgslEntryResult],
+  ) -> Result<(), WgslBindgenError> {
+    let Some(max_size) = self.options.max_compute_workgroup_storage_size else {
+      return Ok(());
+    };
+
+    for entry in entry_results {
+      let reasons = workgroup_storage_errors(
+        &entry.naga_module,
+        entry.module_info.as_ref(),
+        max_size,
+      );
+      if !reasons.is_empty() {
+        let entry = entry
+          .source_including_deps
+          .source_file
+          .file_path
+          .to_string();
+        return Err(WgslBindgenError::WorkgroupStorageExceededError { entry, reasons });
+      }
+    }
+
+    Ok(())
+  }
+
+  fn emit_oversized_uniform_warnings(&self, entry_results: &[WgslEntryResult]) {
+    let Some(max_size) = self.options.max_uniform_buffer_binding_size else {
+      return;
+    };
+    // Denied diagnostics are reported along the error instead.
+    if self
+      .options
+      .deny_diagnostics
+      .contains(&WgslDiagnosticKind::UniformBindingTooLarge)
+    {
+      return;
+    }
+
+    for entry in entry_results {
+      let entry_path = &entry.source_including_deps.source_file.file_path;
+      for binding in oversized_uniform_bindings(&entry.naga_module, max_size) {
+        println!("cargo:warning={entry_path}: {binding}");
+      }
+    }
+  }
+
+  /// naga 23 doesn't parse `diagnostic(...)` directives, so they are commented out and have no
+  /// effect on the warnings of the shader.
+  fn emit_ignored_diagnostic_directive_warnings(
+    &self,
+    entry_results: &[WgslEntryResult],
+  ) {
+    for entry in entry_results {
+      let entry_path = &entry.source_including_deps.source_file.file_path;
+      for directive in entry.source_including_deps.diagnostics() {
+        println!(
+          "cargo:warning={entry_path}: `{directive}` is ignored, since naga doesn't support diagnostic directives yet"
+        );
+      }
+    }
+  }
+
+  fn emit_validation_warnings(&self, entry_results: &[WgslEntryResult]) {
+    if !self.options.emit_validation_warnings {
+      return;
+    }
+
+    for entry in entry_results {
+      let entry_path = &entry.source_including_deps.source_file.file_path;
+      for (_, warning) in validation_warnings(
+        &entry.naga_module,
+        entry.module_info.as_ref(),
+        self.options.strip_unused_bindings,
+      ) {
+        println!("cargo:warning={entry_path}: {warning}");
+      }
+    }
+  }
+
+  fn check_denied_diagnostics(
+    &self,
+    entry_results: &[WgslEntryResult],
+  ) -> Result<(), WgslBindgenError> {
+    if self.options.deny_diagnostics.is_empty() {
+      return Ok(());
+    }
+
+    let diagnostics = entry_results
+      .iter()
+      .flat_map(|entry| entry_diagnostics(entry, &self.options))
+      .filter(|diagnostic| self.options.deny_diagnostics.contains(&diagnostic.kind))
+      .collect::<Vec<_>>();
+
+    match diagnostics.is_empty() {
+      true => Ok(()),
+      false => Err(WgslBindgenError::DeniedDiagnosticsError { diagnostics }),
+    }
+  }
+
+  fn generate_entry_results(&self) -> Result<Vec<WgslEntryResult<'_>>, WgslBindgenError> {
+    let cache_dir = self.options.module_cache_dir.as_deref();
+    let remaps = self.options.remap_vertex_location.as_slice();
+    let pullings = self.options.vertex_pulling.as_slice();
+    let entries = self
       .dependency_tree
-      .get_source_files_with_full_dependencies()
+      .get_source_files_with_full_dependencies();
+    let module_names = module_names::resolve(
+      entries.iter().map(|entry| &entry.source_file.file_path),
+      &self.options,
+    )?;
+
+    // The capabilities are resolved up front, since the options can't be shared by the workers.
+    let entries = entries
       .into_iter()
-      .map(|it| Self::generate_naga_module_for_entry(ir_capabilities, it))
+      .map(|entry| {
+        let capabilities = entry.capabilities_with(
+          self.options.ir_capabilities,
+          self.options.deny_ir_capabilities,
+        );
+        (capabilities, entry)
+      })
+      .collect::<Vec<_>>();
+
+    // The entries are processed in parallel, each worker reusing its composers across the
+    // entries it composes, so shared dependencies aren't added again for every entry.
+    let mut entry_results = entries
+      .into_par_iter()
+      .map_init(EntryComposers::default, |composers, (capabilities, it)| {
+        Self::generate_naga_module_for_entry(
+          composers,
+          capabilities,
+          cache_dir,
+          remaps,
+          pullings,
+          &module_names,
+          it,
+        )
+      })
       .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(create_rust_bindings(entry_results, &self.options)?)
+    for entry_result in &mut entry_results {
+      rename_structs(&mut entry_result.naga_module, self.options.struct_name_case);
+    }
+
+    self.check_downlevel_compatibility(&entry_results)?;
+    self.check_vertex_layouts(&entry_results)?;
+    self.check_push_constants(&entry_results)?;
+    self.check_workgroup_storage(&entry_results)?;
+    self.emit_validation_warnings(&entry_results);
+    self.emit_oversized_uniform_warnings(&entry_results);
+    self.emit_ignored_diagnostic_directive_warnings(&entry_results);
+    self.check_denied_diagnostics(&entry_results)?;
+
+    Ok(entry_results)
   }
 
   pub fn generate_string(&self) -> Result<String, WgslBindgenError> {
+    self.create_string(self.generate_entry_results()?)
+  }
+
+  /// Generates the bindings like `generate_string`, along with a report of what was generated for each entry.
+  pub fn generate_string_with_report(
+    &self,
+  ) -> Result<(String, WgslBindgenReport), WgslBindgenError> {
+    let entry_results = self.generate_entry_results()?;
+    let report = self.create_report(&entry_results)?;
+    Ok((self.create_string(entry_results)?, report))
+  }
+
+  fn create_string(
+    &self,
+    entry_results: Vec<WgslEntryResult<'_>>,
+  ) -> Result<String, WgslBindgenError> {
+    let partitioned = self.partition_entries(entry_results);
+    let selection = OutputSelection::Main {
+      own_entries: partitioned.main_entries,
+    };
+    self.create_output_string(&partitioned.entries, &selection)
+  }
+
+  fn create_output_string(
+    &self,
+    entries: &[WgslEntryResult<'_>],
+    selection: &OutputSelection,
+  ) -> Result<String, WgslBindgenError> {
     let mut text = self.header_texts();
-    text += &self.generate_output()?;
+    text += &create_rust_bindings(entries, selection, &self.options)?;
     Ok(text)
   }
 
+  /// Generates the bindings as separate files, as written by [`WgslOutputMode::Directory`].
+  /// Returns the name and contents of each file, starting with `mod.rs`.
+  pub fn generate_files(&self) -> Result<Vec<(String, String)>, WgslBindgenError> {
+    self.create_files(self.generate_entry_results()?)
+  }
+
+  fn create_files(
+    &self,
+    entry_results: Vec<WgslEntryResult<'_>>,
+  ) -> Result<Vec<(String, String)>, WgslBindgenError> {
+    let partitioned = self.partition_entries(entry_results);
+    let selection = OutputSelection::Main {
+      own_entries: partitioned.main_entries,
+    };
+    self.create_dir_files(&partitioned.entries, &selection)
+  }
+
+  fn create_dir_files(
+    &self,
+    entries: &[WgslEntryResult<'_>],
+    selection: &OutputSelection,
+  ) -> Result<Vec<(String, String)>, WgslBindgenError> {
+    let header = self.header_texts();
+
+    let files = create_rust_bindings_files(entries, selection, &self.options)?
+      .into_iter()
+      .map(|(name, contents)| (name, format!("{header}{contents}")))
+      .collect();
+
+    Ok(files)
+  }
+
+  /// Orders the entries by the output generating them, each going to the first partition
+  /// matching its path, or to the main output otherwise.
+  fn partition_entries<'a>(
+    &self,
+    entry_results: Vec<WgslEntryResult<'a>>,
+  ) -> PartitionedEntries<'a> {
+    let partitions = &self.options.output_partitions;
+    let output_index = |entry: &WgslEntryResult| {
+      let path = entry
+        .source_including_deps
+        .source_file
+        .file_path
+        .to_string();
+      partitions
+        .iter()
+        .position(|partition| partition.entry_regex.is_match(&path))
+    };
+
+    let mut entries = entry_results;
+    // The sort is stable, so the entries of each output keep their order.
+    entries.sort_by_key(|entry| output_index(entry).map_or(0, |index| index + 1));
+
+    let main_entries = entries
+      .iter()
+      .take_while(|entry| output_index(entry).is_none())
+      .count();
+    let mut start = main_entries;
+    let partitions = partitions
+      .iter()
+      .enumerate()
+      .map(|(index, partition)| {
+        let len = entries[start..]
+          .iter()
+          .take_while(|entry| output_index(entry) == Some(index))
+          .count();
+        start += len;
+        (partition.output.clone(), start - len..start)
+      })
+      .collect();
+
+    PartitionedEntries {
+      entries,
+      main_entries,
+      partitions,
+    }
+  }
+
+  /// The path of the main output as parsed from `output_module_path`, which partitions
+  /// import the modules shared with the other entries from.
+  fn output_module_path(&self) -> Result<syn::Path, WgslBindgenError> {
+    self
+      .options
+      .output_module_path
+      .as_deref()
+      .and_then(|path| syn::parse_str(path).ok())
+      .ok_or_else(|| WgslBindgenError::InvalidOutputModulePath {
+        path: self.options.output_module_path.clone(),
+      })
+  }
+
+  fn create_report(
+    &self,
+    entry_results: &[WgslEntryResult<'_>],
+  ) -> Result<WgslBindgenReport, WgslBindgenError> {
+    let entries = entry_results
+      .iter()
+      .map(|entry| entry_report(entry, &self.options))
+      .collect::<Result<_, _>>()?;
+
+    Ok(WgslBindgenReport { entries })
+  }
+
   pub fn generate(&self) -> Result<(), WgslBindgenError> {
-    let out = self
+    if self.is_output_outdated()? {
+      self.write_output(self.generate_entry_results()?)?;
+    }
+
+    Ok(())
+  }
+
+  /// Writes the bindings like `generate`, and returns a report of what was generated for each entry.
+  /// The report is created even when the output is up to date and left untouched.
+  pub fn generate_with_report(&self) -> Result<WgslBindgenReport, WgslBindgenError> {
+    let entry_results = self.generate_entry_results()?;
+    let report = self.create_report(&entry_results)?;
+
+    if self.is_output_outdated()? {
+      self.write_output(entry_results)?;
+    }
+
+    Ok(report)
+  }
+
+  fn output_path(&self) -> Result<&PathBuf, WgslBindgenError> {
+    self
       .options
       .output
       .as_ref()
-      .ok_or(WgslBindgenError::OutputFileNotSpecified)?;
+      .ok_or(WgslBindgenError::OutputFileNotSpecified)
+  }
 
-    let old_content = std::fs::read_to_string(out).unwrap_or_else(|_| String::new());
+  /// The output files recording the hash of the shaders, one for each output.
+  fn hashed_files(&self) -> Result<Vec<PathBuf>, WgslBindgenError> {
+    let out = self.output_path()?;
 
-    let old_hashstr_comment = old_content
-      .lines()
-      .find(|line| line.starts_with("// SourceHash:"))
-      .unwrap_or("");
+    // The hash is recorded for `mod.rs` when writing a directory.
+    let hashed_file = match self.options.output_mode {
+      WgslOutputMode::SingleFile => out.clone(),
+      WgslOutputMode::Directory => out.join("mod.rs"),
+    };
+    let partition_files = self
+      .options
+      .output_partitions
+      .iter()
+      .map(|partition| partition.output.clone());
+
+    Ok(
+      std::iter::once(hashed_file)
+        .chain(partition_files)
+        .collect(),
+    )
+  }
+
+  /// Compares the hash of the shaders with the one recorded for each of the existing outputs.
+  fn is_output_outdated(&self) -> Result<bool, WgslBindgenError> {
+    let recorded_hash = |file: &Path| match self.options.hash_storage {
+      WgslHashStorage::HeaderComment => {
+        let old_content = std::fs::read_to_string(file).unwrap_or_default();
+        old_content
+          .lines()
+          .find_map(|line| line.strip_prefix("// SourceHash: "))
+          .map(str::to_string)
+      }
+      WgslHashStorage::SidecarFile | WgslHashStorage::Both => {
+        let hash = std::fs::read_to_string(sidecar_path(file)).ok()?;
+        Some(hash.trim().to_string())
+      }
+    };
 
     let is_hash_changed =
-      || old_hashstr_comment != format!("// SourceHash: {}", &self.content_hash);
+      |file: &PathBuf| recorded_hash(file).as_deref() != Some(self.content_hash.as_str());
 
-    if self.options.skip_hash_check || is_hash_changed() {
-      let content = self.generate_string()?;
-      std::fs::File::create(out)?.write_all(content.as_bytes())?
+    Ok(
+      self.options.skip_hash_check
+        || self.options.has_unhashed_callbacks()
+        || self.hashed_files()?.iter().any(is_hash_changed),
+    )
+  }
+
+  fn write_output(
+    &self,
+    entry_results: Vec<WgslEntryResult<'_>>,
+  ) -> Result<(), WgslBindgenError> {
+    if self.options.output_mode == WgslOutputMode::Directory {
+      std::fs::create_dir_all(self.output_path()?)?;
+    }
+
+    for (path, content) in self.create_output_files(entry_results)? {
+      write_atomically(&path, &content)?
     }
 
     Ok(())
   }
+
+  /// Returns the path and contents of each file `generate` would write.
+  fn output_files(&self) -> Result<Vec<(PathBuf, String)>, WgslBindgenError> {
+    self.create_output_files(self.generate_entry_results()?)
+  }
+
+  fn create_output_files(
+    &self,
+    entry_results: Vec<WgslEntryResult<'_>>,
+  ) -> Result<Vec<(PathBuf, String)>, WgslBindgenError> {
+    let out = self.output_path()?;
+    let binding_reference = match &self.options.binding_reference_output {
+      Some(path) => {
+        let reference = binding_reference::render(&entry_results, &self.options)?;
+        Some((path.clone(), reference))
+      }
+      None => None,
+    };
+    let reflection = match &self.options.reflection_output {
+      Some(path) => {
+        let report = self.create_report(&entry_results)?;
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        Some((path.clone(), json + "\n"))
+      }
+      None => None,
+    };
+    let c_header = self
+      .options
+      .c_header_output
+      .as_ref()
+      .map(|path| (path.clone(), c_header::render(&entry_results)));
+    let typescript = match &self.options.typescript_output {
+      Some(path) => {
+        Some((path.clone(), typescript::render(&entry_results, &self.options)?))
+      }
+      None => None,
+    };
+    let partitioned = self.partition_entries(entry_results);
+    let selection = OutputSelection::Main {
+      own_entries: partitioned.main_entries,
+    };
+
+    let mut files = match self.options.output_mode {
+      WgslOutputMode::SingleFile => {
+        vec![(out.clone(), self.create_output_string(&partitioned.entries, &selection)?)]
+      }
+      WgslOutputMode::Directory => self
+        .create_dir_files(&partitioned.entries, &selection)?
+        .into_iter()
+        .map(|(name, content)| (out.join(name), content))
+        .collect(),
+    };
+
+    // Partitions are always written as single files.
+    if !partitioned.partitions.is_empty() {
+      let shared_path = self.output_module_path()?;
+      let selection = OutputSelection::Partition {
+        shared_path: &shared_path,
+      };
+      for (path, range) in &partitioned.partitions {
+        let content =
+          self.create_output_string(&partitioned.entries[range.clone()], &selection)?;
+        files.push((path.clone(), content));
+      }
+    }
+
+    // Hand-written regions of the existing files are carried over.
+    let mut files = files
+      .into_iter()
+      .map(|(path, content)| {
+        let old_content = std::fs::read_to_string(&path).unwrap_or_default();
+        let content = user_regions::append(&old_content, content).map_err(|line| {
+          WgslBindgenError::UnterminatedUserRegion {
+            path: path.display().to_string(),
+            line,
+          }
+        })?;
+        Ok((path, content))
+      })
+      .collect::<Result<Vec<_>, WgslBindgenError>>()?;
+
+    if self.options.hash_storage != WgslHashStorage::HeaderComment {
+      for hashed_file in self.hashed_files()? {
+        files.push((sidecar_path(&hashed_file), format!("{}\n", self.content_hash)));
+      }
+    }
+
+    if let Some(path) = &self.options.dependency_graph_output {
+      let graph = dependency_graph::render(&self.dependency_tree, path);
+      files.push((path.clone(), graph));
+    }
+    files.extend(binding_reference);
+    files.extend(reflection);
+    files.extend(c_header);
+    files.extend(typescript);
+
+    Ok(files)
+  }
+
+  /// Lists the output files whose contents differ from what `generate` would write, along with a
+  /// summary of the differences, or `missing` when the file doesn't exist.
+  fn stale_output_summaries(&self) -> Result<Vec<(PathBuf, String)>, WgslBindgenError> {
+    let stale = self
+      .output_files()?
+      .into_iter()
+      .filter_map(|(path, content)| {
+        let summary = match std::fs::read_to_string(&path) {
+          Ok(old_content) if old_content == content => return None,
+          Ok(old_content) => diff_summary(&old_content, &content),
+          Err(_) => "missing".to_string(),
+        };
+        Some((path, summary))
+      })
+      .collect();
+
+    Ok(stale)
+  }
+
+  /// Returns the output files whose contents differ from what `generate` would write,
+  /// without writing anything. This is useful to check that committed bindings are up to date.
+  /// Unlike `check`, being out of date isn't an error, and the differences aren't summarized.
+  pub fn stale_outputs(&self) -> Result<Vec<PathBuf>, WgslBindgenError> {
+    let stale = self.stale_output_summaries()?;
+    Ok(stale.into_iter().map(|(path, _)| path).collect())
+  }
+
+  /// Regenerates the bindings in memory and fails with a summary of the differences if the
+  /// output on disk is out of date, like `cargo fmt --check`. Nothing is written. This is
+  /// `stale_outputs` as an error, for CI and the command line.
+  pub fn check(&self) -> Result<(), WgslBindgenError> {
+    let stale = self
+      .stale_output_summaries()?
+      .into_iter()
+      .map(|(path, summary)| format!("{}: {summary}", path.display()))
+      .collect::<Vec<_>>();
+
+    match stale.is_empty() {
+      true => Ok(()),
+      false => Err(WgslBindgenError::StaleOutputError { files: stale }),
+    }
+  }
+}
+
+/// The sidecar file recording the hash of the shaders for `path`, eg: `shader.rs.hash`.
+fn sidecar_path(path: &Path) -> PathBuf {
+  let mut sidecar = path.as_os_str().to_owned();
+  sidecar.push(".hash");
+  PathBuf::from(sidecar)
+}
+
+/// A regex matching `name` and nothing else.
+fn exact_regex(name: &str) -> Regex {
+  Regex::new(&format!("^{}$", regex::escape(name))).unwrap()
+}
+
+/// Writes to a temporary sibling file renamed over `path`, so readers like rust-analyzer or
+/// concurrent builds never observe a partially written file.
+pub(crate) fn write_atomically(
+  path: &Path,
+  content: impl AsRef<[u8]>,
+) -> std::io::Result<()> {
+  let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+  let temp_path = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+
+  let written = std::fs::File::create(&temp_path)
+    .and_then(|mut file| file.write_all(content.as_ref()))
+    .and_then(|_| std::fs::rename(&temp_path, path));
+
+  if written.is_err() {
+    let _ = std::fs::remove_file(&temp_path);
+  }
+  written
+}
+
+/// Describes where the contents on disk first diverge from the regenerated ones.
+fn diff_summary(old_content: &str, new_content: &str) -> String {
+  let old_lines = old_content.lines().collect::<Vec<_>>();
+  let new_lines = new_content.lines().collect::<Vec<_>>();
+
+  let first_diff = old_lines
+    .iter()
+    .zip(&new_lines)
+    .position(|(old, new)| old != new)
+    .unwrap_or(old_lines.len().min(new_lines.len()));
+
+  let line_at = |lines: &[&str]| match lines.get(first_diff) {
+    Some(line) => format!("`{}`", line.trim()),
+    None => "end of file".to_string(),
+  };
+
+  format!(
+    "{} lines on disk, {} generated, first difference at line {}: {} on disk, {} generated",
+    old_lines.len(),
+    new_lines.len(),
+    first_diff + 1,
+    line_at(&old_lines),
+    line_at(&new_lines),
+  )
 }
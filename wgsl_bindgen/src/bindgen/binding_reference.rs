@@ -0,0 +1,98 @@
+//! A Markdown reference of the bindings of each shader entry, for readers who don't want to
+//! read the generated code, eg: tech artists and reviewers.
+
+use std::fmt::Write;
+
+use super::diagnostics::stage_names;
+use crate::generate::bind_group::generated_bind_group_data;
+use crate::generate::pipeline::push_constant_size;
+use crate::quote_gen::RustItemPath;
+use crate::{wgsl, CreateModuleError, WgslBindgenOption, WgslEntryResult};
+
+pub(crate) fn render(
+  entries: &[WgslEntryResult<'_>],
+  options: &WgslBindgenOption,
+) -> Result<String, CreateModuleError> {
+  let mut doc = String::from("# Shader bindings\n");
+  for entry in entries {
+    write_entry(&mut doc, entry, options)?;
+  }
+  Ok(doc)
+}
+
+fn write_entry(
+  doc: &mut String,
+  entry: &WgslEntryResult<'_>,
+  options: &WgslBindgenOption,
+) -> Result<(), CreateModuleError> {
+  let WgslEntryResult {
+    mod_name,
+    naga_module: module,
+    module_info,
+    source_including_deps,
+  } = entry;
+  let source_path = &source_including_deps.source_file.file_path;
+
+  // Every binding of the generated layouts is visible to all the stages of the shader.
+  let visibility = stage_names(wgsl::shader_stages(module));
+  let type_name = |ty| wgsl::type_name(module, ty);
+
+  writeln!(doc, "\n## `{mod_name}`\n").unwrap();
+  writeln!(doc, "Source: `{source_path}`").unwrap();
+
+  let bind_group_prefix = &options.wgpu_binding_generator.bind_group_layout.name_prefix;
+  for (group, data) in generated_bind_group_data(module, module_info.as_ref(), options)? {
+    writeln!(doc, "\n### Bind group {group} (`{bind_group_prefix}{group}`)\n").unwrap();
+    writeln!(doc, "| Binding | Name | Type | Address space | Visibility |").unwrap();
+    writeln!(doc, "| --- | --- | --- | --- | --- |").unwrap();
+    for binding in &data.bindings {
+      let name = binding.name.as_deref().unwrap_or("");
+      let name = RustItemPath::from_mangled(name, mod_name).name;
+      let address_space = match binding.address_space {
+        naga::AddressSpace::Uniform => "uniform",
+        naga::AddressSpace::Storage { access }
+          if access.contains(naga::StorageAccess::STORE) =>
+        {
+          "storage, read_write"
+        }
+        naga::AddressSpace::Storage { .. } => "storage, read",
+        _ => "handle",
+      };
+      writeln!(
+        doc,
+        "| {} | `{name}` | `{}` | {address_space} | {visibility} |",
+        binding.binding_index,
+        type_name(binding.binding_type),
+      )
+      .unwrap();
+    }
+  }
+
+  let push_constant = module
+    .global_variables
+    .iter()
+    .find(|(_, var)| var.space == naga::AddressSpace::PushConstant);
+  if let (Some((_, var)), Some(size)) = (push_constant, push_constant_size(module)) {
+    writeln!(doc, "\n### Push constants\n").unwrap();
+    writeln!(
+      doc,
+      "`{}`, {size} bytes, visible to {visibility}.",
+      type_name(&module.types[var.ty]),
+    )
+    .unwrap();
+  }
+
+  for input in wgsl::get_vertex_input_structs(mod_name, module) {
+    let name = input.item_path.get_fully_qualified_name();
+    writeln!(doc, "\n### Vertex layout `{name}`\n").unwrap();
+    writeln!(doc, "| Location | Field | Format |").unwrap();
+    writeln!(doc, "| --- | --- | --- |").unwrap();
+    for (location, member) in &input.fields {
+      let field = member.name.as_deref().unwrap_or("");
+      let format = wgsl::vertex_attribute_format(&name, member, module, options);
+      writeln!(doc, "| {location} | `{field}` | `{format:?}` |").unwrap();
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,183 @@
+//! A C header declaring the structs of the uniform, storage and push constant buffers, so native
+//! code filling these buffers stays layout compatible with the shaders.
+
+use std::fmt::Write;
+
+use naga::proc::Layouter;
+use naga::{ArraySize, Handle, Scalar, ScalarKind, Type, TypeInner};
+
+use crate::quote_gen::RustItemPath;
+use crate::{wgsl, FxIndexSet, WgslEntryResult};
+
+pub(crate) fn render(entries: &[WgslEntryResult<'_>]) -> String {
+  let mut header = String::new();
+  writeln!(header, "// File automatically generated by wgsl_bindgen.").unwrap();
+  writeln!(header, "// Changes made to this file will not be saved.").unwrap();
+  writeln!(header, "\n#pragma once\n").unwrap();
+  writeln!(header, "#include <assert.h>").unwrap();
+  writeln!(header, "#include <stddef.h>").unwrap();
+  writeln!(header, "#include <stdint.h>").unwrap();
+
+  // Structs of shared imports are used by several entries, but declared once.
+  let mut declared = FxIndexSet::default();
+  for entry in entries {
+    let module = &entry.naga_module;
+    let mut layouter = Layouter::default();
+    layouter.update(module.to_ctx()).unwrap();
+
+    for handle in wgsl::buffer_structs(module) {
+      let name = c_name(&entry.mod_name, &module.types[handle]);
+      if declared.insert(name.clone()) {
+        write_struct(&mut header, &entry.mod_name, module, &layouter, handle, &name);
+      }
+    }
+  }
+  header
+}
+
+/// The name of the struct in C, which has no namespaces, eg: `triangle_Uniforms`.
+fn c_name(mod_name: &str, ty: &Type) -> String {
+  let name = ty.name.as_deref().unwrap_or_default();
+  let path = RustItemPath::from_mangled(name, mod_name);
+  path.get_fully_qualified_name().replace("::", "_")
+}
+
+fn write_struct(
+  header: &mut String,
+  mod_name: &str,
+  module: &naga::Module,
+  layouter: &Layouter,
+  handle: Handle<Type>,
+  name: &str,
+) {
+  let TypeInner::Struct { members, span } = &module.types[handle].inner else {
+    unreachable!("only structs are collected");
+  };
+
+  writeln!(header, "\ntypedef struct {name} {{").unwrap();
+  let mut end = 0;
+  let mut padding_count = 0;
+  let mut is_runtime_sized = false;
+  for member in members {
+    if member.offset > end {
+      let size = member.offset - end;
+      writeln!(header, "  uint8_t _pad{padding_count}[{size}];").unwrap();
+      padding_count += 1;
+    }
+    let field = member.name.as_deref().unwrap_or_default();
+    let declaration = c_declaration(mod_name, module, layouter, member.ty, field);
+    writeln!(header, "  {declaration};").unwrap();
+
+    is_runtime_sized = matches!(
+      module.types[member.ty].inner,
+      TypeInner::Array {
+        size: ArraySize::Dynamic,
+        ..
+      }
+    );
+    end = member.offset + layouter[member.ty].size;
+  }
+  if !is_runtime_sized && *span > end {
+    writeln!(header, "  uint8_t _pad{padding_count}[{}];", span - end).unwrap();
+  }
+  writeln!(header, "}} {name};").unwrap();
+
+  // The size of a struct ending with a runtime-sized array depends on the buffer.
+  if !is_runtime_sized {
+    writeln!(header, "static_assert(sizeof({name}) == {span}, \"size of {name}\");")
+      .unwrap();
+  }
+  for member in members {
+    let field = member.name.as_deref().unwrap_or_default();
+    writeln!(
+      header,
+      "static_assert(offsetof({name}, {field}) == {}, \"offset of {name}.{field}\");",
+      member.offset
+    )
+    .unwrap();
+  }
+}
+
+/// Declares `field` with the C type matching the layout of `ty`, eg: `float color[4]`.
+fn c_declaration(
+  mod_name: &str,
+  module: &naga::Module,
+  layouter: &Layouter,
+  ty: Handle<Type>,
+  field: &str,
+) -> String {
+  let (c_type, dimensions) = c_type(mod_name, module, layouter, ty);
+  let dimensions = dimensions
+    .iter()
+    .map(|dimension| match dimension {
+      Some(length) => format!("[{length}]"),
+      None => "[]".to_string(),
+    })
+    .collect::<String>();
+  format!("{c_type} {field}{dimensions}")
+}
+
+/// The element type and array dimensions matching the layout of `ty`. Vectors and matrix
+/// columns are arrays of scalars, padded to their stride. Types without a C equivalent are
+/// declared as bytes.
+fn c_type(
+  mod_name: &str,
+  module: &naga::Module,
+  layouter: &Layouter,
+  ty: Handle<Type>,
+) -> (String, Vec<Option<u32>>) {
+  let bytes = || ("uint8_t".to_string(), vec![Some(layouter[ty].size)]);
+  let scalar_or_bytes =
+    |scalar: Scalar, dimensions: Vec<Option<u32>>| match c_scalar(scalar) {
+      Some(c_scalar) => (c_scalar.to_string(), dimensions),
+      None => bytes(),
+    };
+
+  match &module.types[ty].inner {
+    TypeInner::Scalar(scalar) | TypeInner::Atomic(scalar) => {
+      scalar_or_bytes(*scalar, vec![])
+    }
+    TypeInner::Vector { size, scalar } => {
+      scalar_or_bytes(*scalar, vec![Some(*size as u32)])
+    }
+    TypeInner::Matrix {
+      columns, scalar, ..
+    } => {
+      let column_stride = layouter[ty].size / *columns as u32;
+      let column_length = column_stride / scalar.width as u32;
+      scalar_or_bytes(*scalar, vec![Some(*columns as u32), Some(column_length)])
+    }
+    TypeInner::Array { base, size, stride } => {
+      let (c_type, mut element_dimensions) = c_type(mod_name, module, layouter, *base);
+      // Elements smaller than the stride, eg: `vec3<f32>`, are padded to it.
+      if let TypeInner::Vector { scalar, .. } = module.types[*base].inner {
+        if *stride != layouter[*base].size && c_type != "uint8_t" {
+          element_dimensions = vec![Some(stride / scalar.width as u32)];
+        }
+      }
+      let length = match size {
+        ArraySize::Constant(length) => Some(length.get()),
+        ArraySize::Dynamic => None,
+      };
+      let mut dimensions = vec![length];
+      dimensions.extend(element_dimensions);
+      (c_type, dimensions)
+    }
+    TypeInner::Struct { .. } => (c_name(mod_name, &module.types[ty]), vec![]),
+    _ => bytes(),
+  }
+}
+
+fn c_scalar(scalar: Scalar) -> Option<&'static str> {
+  match (scalar.kind, scalar.width) {
+    (ScalarKind::Float, 4) => Some("float"),
+    (ScalarKind::Float, 8) => Some("double"),
+    // C has no standard half precision type, so the bits are stored instead.
+    (ScalarKind::Float, 2) => Some("uint16_t"),
+    (ScalarKind::Sint, 4) => Some("int32_t"),
+    (ScalarKind::Sint, 8) => Some("int64_t"),
+    (ScalarKind::Uint, 4) => Some("uint32_t"),
+    (ScalarKind::Uint, 8) => Some("uint64_t"),
+    _ => None,
+  }
+}
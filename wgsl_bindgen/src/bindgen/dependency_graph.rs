@@ -0,0 +1,57 @@
+//! The import graph of the shaders, written alongside the output to visualize which entries pull
+//! in which shared imports.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::bevy_util::DependencyTree;
+
+/// Renders the graph as a Mermaid flowchart when `path` has the `mmd` extension, and as a
+/// GraphViz digraph otherwise.
+pub(crate) fn render(dependency_tree: &DependencyTree, path: &Path) -> String {
+  let is_mermaid = path.extension().is_some_and(|ext| ext == "mmd");
+  let files = dependency_tree.all_files_including_dependencies();
+  let node = |path| files.get_index_of(path).unwrap();
+
+  let mut graph = String::new();
+  if is_mermaid {
+    writeln!(graph, "flowchart LR").unwrap();
+  } else {
+    writeln!(graph, "digraph shaders {{").unwrap();
+    writeln!(graph, "  rankdir=LR;").unwrap();
+  }
+
+  // Entries are labelled by their path and drawn as boxes, imports by their module name.
+  for (index, path) in files.iter().enumerate() {
+    let is_entry = dependency_tree.entry_points().contains(path);
+    let module_name = &dependency_tree.source_file(path).unwrap().module_name;
+    let label = match module_name {
+      Some(module_name) if !is_entry => module_name.to_string(),
+      _ => path.to_string(),
+    };
+
+    match (is_mermaid, is_entry) {
+      (true, true) => writeln!(graph, "  n{index}[\"{}\"]", label.replace('"', "#quot;")),
+      (true, false) => {
+        writeln!(graph, "  n{index}(\"{}\")", label.replace('"', "#quot;"))
+      }
+      (false, true) => writeln!(graph, "  n{index} [label={label:?}, shape=box];"),
+      (false, false) => writeln!(graph, "  n{index} [label={label:?}];"),
+    }
+    .unwrap();
+  }
+
+  for (importer, imported) in dependency_tree.import_edges() {
+    let (from, to) = (node(importer), node(imported));
+    match is_mermaid {
+      true => writeln!(graph, "  n{from} --> n{to}"),
+      false => writeln!(graph, "  n{from} -> n{to};"),
+    }
+    .unwrap();
+  }
+
+  if !is_mermaid {
+    writeln!(graph, "}}").unwrap();
+  }
+  graph
+}
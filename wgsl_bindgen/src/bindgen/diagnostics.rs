@@ -0,0 +1,187 @@
+//! Non-fatal issues found while generating the bindings, reported instead of being decided silently.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::generate::entry::float64_vertex_attributes;
+use crate::generate::requirements::{
+  downlevel_webgl2_incompatibilities, oversized_uniform_bindings,
+};
+use crate::naga_util::validation_warnings;
+use crate::quote_gen::is_fallback_type_mapping;
+use crate::{structs, wgsl, WgslBindgenOption, WgslDownlevelCheck, WgslEntryResult};
+
+/// The kind of issue a [`WgslDiagnostic`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslDiagnosticKind {
+  /// A binding none of the entry points use.
+  UnusedBinding,
+  /// A vector or matrix type missing from the type map, generated as an array instead.
+  FallbackTypeMapping,
+  /// Padding fields inserted in a struct to match the WGSL memory layout, when using bytemuck.
+  PaddingInserted,
+  /// A binding made visible to all the stages of the shader, while only some of them use it.
+  VisibilityBroadened,
+  /// A reason the shader will not run on WebGL2, when `downlevel_check` is enabled.
+  DownlevelIncompatible,
+  /// A vertex input read from `Float64` data, which only Vulkan supports.
+  Float64VertexAttribute,
+  /// A uniform binding larger than the `max_uniform_buffer_binding_size` of the options.
+  UniformBindingTooLarge,
+}
+
+/// A non-fatal issue found in a shader entry while generating its bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WgslDiagnostic {
+  pub kind: WgslDiagnosticKind,
+  /// The path of the entry shader.
+  pub entry: String,
+  pub message: String,
+}
+
+impl fmt::Display for WgslDiagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {}", self.entry, self.message)
+  }
+}
+
+pub(crate) fn entry_diagnostics(
+  entry: &WgslEntryResult<'_>,
+  options: &WgslBindgenOption,
+) -> Vec<WgslDiagnostic> {
+  let WgslEntryResult {
+    mod_name,
+    naga_module: module,
+    module_info,
+    source_including_deps,
+  } = entry;
+  let info = module_info.as_ref();
+
+  let mut issues = validation_warnings(module, info, options.strip_unused_bindings);
+  issues.extend(broadened_visibilities(module, info));
+
+  let mut fallback_types = structs::generated_types(module)
+    .into_iter()
+    .filter(|handle| is_fallback_type_mapping(module, &module.types[*handle], options))
+    .map(|handle| module.types[handle].inner.to_wgsl(&module.to_ctx()))
+    .collect::<Vec<_>>();
+  fallback_types.sort();
+  fallback_types.dedup();
+  issues.extend(fallback_types.into_iter().map(|ty| {
+    let message =
+      format!("`{ty}` is missing from the type map and is generated as an array");
+    (WgslDiagnosticKind::FallbackTypeMapping, message)
+  }));
+
+  issues.extend(
+    structs::inserted_padding(mod_name, module, options)
+      .into_iter()
+      .flat_map(|(name, padding)| {
+        padding.into_iter().map(move |padding| {
+          let size = padding
+            .size
+            .map(|size| format!(" of {size} bytes"))
+            .unwrap_or_default();
+          let message = format!(
+            "padding `{}`{size} inserted in `{name}` at offset 0x{:X}, after `{}`",
+            padding.name, padding.offset, padding.field
+          );
+          (WgslDiagnosticKind::PaddingInserted, message)
+        })
+      }),
+  );
+
+  if options.downlevel_check != WgslDownlevelCheck::Skip {
+    issues.extend(
+      downlevel_webgl2_incompatibilities(
+        module,
+        info,
+        source_including_deps.extension_features(),
+      )
+      .into_iter()
+      .map(|reason| (WgslDiagnosticKind::DownlevelIncompatible, reason)),
+    );
+  }
+
+  issues.extend(
+    float64_vertex_attributes(mod_name, module, options)
+      .into_iter()
+      .map(|attribute| (WgslDiagnosticKind::Float64VertexAttribute, attribute)),
+  );
+
+  if let Some(max_size) = options.max_uniform_buffer_binding_size {
+    issues.extend(
+      oversized_uniform_bindings(module, max_size)
+        .into_iter()
+        .map(|binding| (WgslDiagnosticKind::UniformBindingTooLarge, binding)),
+    );
+  }
+
+  let entry = source_including_deps.source_file.file_path.to_string();
+  issues
+    .into_iter()
+    .map(|(kind, message)| WgslDiagnostic {
+      kind,
+      entry: entry.clone(),
+      message,
+    })
+    .collect()
+}
+
+/// Lists the bindings only used by some of the shader stages, since the generated layouts
+/// make every binding visible to all the stages of the shader.
+fn broadened_visibilities(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+) -> Vec<(WgslDiagnosticKind, String)> {
+  let Some(info) = info else {
+    return Vec::new();
+  };
+
+  let shader_stages = wgsl::shader_stages(module);
+
+  module
+    .global_variables
+    .iter()
+    .filter_map(|(handle, var)| {
+      let binding = var.binding.as_ref()?;
+      let used_stages = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !info.get_entry_point(*index)[handle].is_empty())
+        .map(|(_, entry)| wgsl::shader_stage(entry.stage))
+        .collect::<wgpu::ShaderStages>();
+
+      // Unused bindings are reported on their own.
+      if used_stages.is_empty() || used_stages == shader_stages {
+        return None;
+      }
+
+      let name = var.name.as_deref().unwrap_or("<unnamed>");
+      let message = format!(
+        "binding `{name}` at @group({}) @binding({}) is only used by {}, but is visible to {}",
+        binding.group,
+        binding.binding,
+        stage_names(used_stages),
+        stage_names(shader_stages),
+      );
+      Some((WgslDiagnosticKind::VisibilityBroadened, message))
+    })
+    .collect()
+}
+
+pub(crate) fn stage_names(stages: wgpu::ShaderStages) -> String {
+  [
+    (wgpu::ShaderStages::VERTEX, "vertex"),
+    (wgpu::ShaderStages::FRAGMENT, "fragment"),
+    (wgpu::ShaderStages::COMPUTE, "compute"),
+  ]
+  .into_iter()
+  .filter(|(stage, _)| stages.contains(*stage))
+  .map(|(_, name)| name)
+  .collect::<Vec<_>>()
+  .join(" and ")
+}
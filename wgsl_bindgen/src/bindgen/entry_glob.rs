@@ -0,0 +1,73 @@
+//! Expands the entry point glob patterns into the shader files they match.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::WgslBindgenError;
+
+/// Lists the files matching the pattern, in alphabetical order.
+pub(crate) fn expand(pattern: &str) -> Result<Vec<PathBuf>, WgslBindgenError> {
+  let glob_error = |msg: String| WgslBindgenError::EntryPointGlobError {
+    pattern: pattern.to_string(),
+    msg,
+  };
+
+  glob::glob(pattern)
+    .map_err(|err| glob_error(err.to_string()))?
+    .filter(|path| !path.as_ref().is_ok_and(|path| path.is_dir()))
+    .map(|path| path.map_err(|err| glob_error(err.to_string())))
+    .collect()
+}
+
+/// The leading directory of the pattern without any wildcards, where new matches can appear.
+pub(crate) fn base_dir(pattern: &str) -> PathBuf {
+  let is_wildcard = |part: &str| part.contains(['*', '?', '[']);
+
+  let base_dir = Path::new(pattern)
+    .components()
+    .take_while(|component| match component {
+      Component::Normal(part) => !is_wildcard(&part.to_string_lossy()),
+      _ => true,
+    })
+    .collect::<PathBuf>();
+
+  // A pattern without wildcards names a single file, so use its directory instead.
+  let base_dir = if base_dir.as_os_str() == pattern {
+    base_dir.parent().map(Path::to_path_buf).unwrap_or_default()
+  } else {
+    base_dir
+  };
+
+  if base_dir.as_os_str().is_empty() {
+    PathBuf::from(".")
+  } else {
+    base_dir
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn base_dir_stops_at_first_wildcard() {
+    assert_eq!(PathBuf::from("assets/shaders"), base_dir("assets/shaders/**/*.wgsl"));
+    assert_eq!(PathBuf::from("shaders"), base_dir("shaders/pbr_*/main.wgsl"));
+    assert_eq!(PathBuf::from("shaders"), base_dir("shaders/main.wgsl"));
+    assert_eq!(PathBuf::from("."), base_dir("*.wgsl"));
+  }
+
+  #[test]
+  fn expands_matching_files_in_order() {
+    let paths = expand("tests/shaders/basic/*.wgsl").unwrap();
+    assert_eq!(
+      vec![
+        PathBuf::from("tests/shaders/basic/bindings.wgsl"),
+        PathBuf::from("tests/shaders/basic/main.wgsl"),
+        PathBuf::from("tests/shaders/basic/path_import.wgsl"),
+      ],
+      paths
+    );
+  }
+}
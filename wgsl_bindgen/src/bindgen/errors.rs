@@ -1,8 +1,13 @@
-use miette::Diagnostic;
+use std::ops::Range;
+
+use miette::{Diagnostic, LabeledSpan, NamedSource};
+use naga_oil::compose::{Composer, ComposerError, ComposerErrorInner};
 use thiserror::Error;
 
-use crate::bevy_util::DependencyTreeError;
-use crate::{CreateModuleError, WgslBindgenOptionBuilderError};
+use crate::bevy_util::{demangle_str, DependencyTreeError};
+use crate::{
+  CreateModuleError, WgslBindgenOptionBuilderError, WgslDiagnostic, WgslShaderSourceType,
+};
 
 /// Enum representing the possible errors that can occur in the `wgsl_bindgen` process.
 ///
@@ -12,25 +17,301 @@ use crate::{CreateModuleError, WgslBindgenOptionBuilderError};
 #[derive(Debug, Error, Diagnostic)]
 pub enum WgslBindgenError {
   #[error("All required fields need to be set upfront: {0}")]
+  #[diagnostic(
+    code(wgsl_bindgen::option_builder),
+    help("Set the missing field on `WgslBindgenOptionBuilder` before calling `build`.")
+  )]
   OptionBuilderError(#[from] WgslBindgenOptionBuilderError),
 
   #[error(transparent)]
   #[diagnostic(transparent)]
   DependencyTreeError(#[from] DependencyTreeError),
 
-  #[error("Failed to compose modules with entry `{entry}`\n{msg}")]
+  #[error("Failed to compose modules with entry `{entry}`: {msg}")]
+  #[diagnostic(code(wgsl_bindgen::compose))]
   NagaModuleComposeError {
     entry: String,
     msg: String,
-    inner: naga_oil::compose::ComposerErrorInner,
+    inner: ComposerErrorInner,
+
+    #[source_code]
+    src: NamedSource<String>,
+
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+
+    #[help]
+    notes: Option<String>,
+  },
+
+  #[error("Shader `{entry}` will not run on WebGL2:\n{}", .reasons.join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::downlevel_incompatible),
+    help("Remove the listed usages, or set `downlevel_check` to `Warn` or `Skip`.")
+  )]
+  DownlevelIncompatibleError { entry: String, reasons: Vec<String> },
+
+  #[error("Vertex layouts of shader `{entry}` break the WebGPU rules:\n{}", .reasons.join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::invalid_vertex_layout),
+    help("Change the types of the listed vertex inputs, or their formats in `override_vertex_format`.")
+  )]
+  InvalidVertexLayoutError { entry: String, reasons: Vec<String> },
+
+  #[error("Push constants of shader `{entry}` don't fit in a pipeline layout:\n{}", .reasons.join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::invalid_push_constants),
+    help("Shrink the listed push constants, or raise `max_push_constant_size` along with the limits of the device.")
+  )]
+  InvalidPushConstantsError { entry: String, reasons: Vec<String> },
+
+  #[error("Compute entry points of shader `{entry}` use too much workgroup storage:\n{}", .reasons.join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::workgroup_storage_exceeded),
+    help("Shrink the listed workgroup variables, or raise `max_compute_workgroup_storage_size` along with the limits of the device.")
+  )]
+  WorkgroupStorageExceededError { entry: String, reasons: Vec<String> },
+
+  #[error("Unknown vertex format `{format}` in the `@vertex_format` comment at line {line} of `{path}`")]
+  #[diagnostic(
+    code(wgsl_bindgen::invalid_vertex_format_directive),
+    help("Name a `wgpu::VertexFormat`, eg: `// @vertex_format(Unorm16x2)`.")
+  )]
+  InvalidVertexFormatDirective {
+    path: String,
+    line: usize,
+    format: String,
   },
 
+  #[error("Invalid `@sampler` comment at line {line} of `{path}`: {reason}")]
+  #[diagnostic(
+    code(wgsl_bindgen::invalid_sampler_directive),
+    help("Name a filter, an address mode and a comparison, eg: `// @sampler(linear, repeat)`.")
+  )]
+  InvalidSamplerDirective {
+    path: String,
+    line: usize,
+    reason: String,
+  },
+
+  #[error("Generation reported denied diagnostics:\n{}", .diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::denied_diagnostic),
+    help("Fix the listed issues, or remove their kinds from `deny_diagnostics`.")
+  )]
+  DeniedDiagnosticsError { diagnostics: Vec<WgslDiagnostic> },
+
+  #[error("Failed to expand entry point glob `{pattern}`: {msg}")]
+  #[diagnostic(
+    code(wgsl_bindgen::entry_point_glob),
+    help("Check the pattern syntax and that the matched directories are readable.")
+  )]
+  EntryPointGlobError { pattern: String, msg: String },
+
+  #[error("Entries {} all generate the module `{mod_name}`", .paths.join(", "))]
+  #[diagnostic(
+    code(wgsl_bindgen::module_name_collision),
+    help("Set `module_name_collision` to disambiguate them, or name the entries with `add_entry_point_named`.")
+  )]
+  ModuleNameCollisionError {
+    mod_name: String,
+    paths: Vec<String>,
+  },
+
+  #[error("`{source_type:?}` can't be generated with `no_std`, since it requires `std`")]
+  #[diagnostic(
+    code(wgsl_bindgen::no_std),
+    help("Use `WgslShaderSourceType::UseEmbed` as the `shader_source_type`, or turn off `no_std`.")
+  )]
+  NoStdIncompatibleSourceType { source_type: WgslShaderSourceType },
+
+  #[error("`{source_type:?}` compiles the shader sources as written, so it can't follow `remap_vertex_location`")]
+  #[diagnostic(
+    code(wgsl_bindgen::remap_vertex_location),
+    help("Use `WgslShaderSourceType::UseEmbed` or `UseNagaIr`, which embed the composed shader with the remapped locations, or change the `@location`s in the shaders.")
+  )]
+  RemapVertexLocationIncompatibleSourceType { source_type: WgslShaderSourceType },
+
+  #[error("`{source_type:?}` compiles the shader sources as written, so it can't follow `vertex_pulling`")]
+  #[diagnostic(
+    code(wgsl_bindgen::vertex_pulling::incompatible_source_type),
+    help("Use `WgslShaderSourceType::UseEmbed` or `UseNagaIr`, which embed the composed shader reading the storage buffers.")
+  )]
+  VertexPullingIncompatibleSourceType { source_type: WgslShaderSourceType },
+
+  #[error("Vertex inputs of shader `{entry}` can't be pulled from storage buffers:\n{}", .reasons.join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::vertex_pulling),
+    help("Move the `@location` inputs of the vertex entry points into structs of `@location`s only.")
+  )]
+  VertexPullingError { entry: String, reasons: Vec<String> },
+
+  #[error("Failed to load configuration file `{path}`: {msg}")]
+  #[diagnostic(
+    code(wgsl_bindgen::config_file),
+    help("Check that the file exists and that its keys match the options of `WgslBindgenOptionBuilder`.")
+  )]
+  ConfigFileError { path: String, msg: String },
+
   #[error(transparent)]
+  #[diagnostic(transparent)]
   ModuleCreationError(#[from] CreateModuleError),
 
   #[error(transparent)]
+  #[diagnostic(
+    code(wgsl_bindgen::write_output),
+    help("Check that the output directory exists and is writable.")
+  )]
   WriteOutputError(#[from] std::io::Error),
 
+  #[cfg(feature = "watch")]
+  #[error(transparent)]
+  #[diagnostic(code(wgsl_bindgen::watch))]
+  WatchError(#[from] notify::Error),
+
+  #[error("Generated bindings are out of date:\n{}", .files.join("\n"))]
+  #[diagnostic(
+    code(wgsl_bindgen::stale_output),
+    help("Regenerate the bindings with `generate` and commit them.")
+  )]
+  StaleOutputError { files: Vec<String> },
+
+  #[error("The user region starting at line {line} of `{path}` is never ended")]
+  #[diagnostic(
+    code(wgsl_bindgen::unterminated_user_region),
+    help("End the region with `// wgsl_bindgen: end user`, so the generated code following it isn't carried over.")
+  )]
+  UnterminatedUserRegion { path: String, line: usize },
+
+  #[error("Entry points are partitioned into several outputs, but `output_module_path` is {}", match .path {
+    Some(path) => format!("not a Rust path: `{path}`"),
+    None => "not specified".to_string(),
+  })]
+  #[diagnostic(
+    code(wgsl_bindgen::invalid_output_module_path),
+    help("Set `output_module_path` to the path `output` is declared at, eg: `crate::shader_bindings`, so the partitions can import the shared types.")
+  )]
+  InvalidOutputModulePath { path: Option<String> },
+
   #[error("Output file is not specified. Maybe use `generate_string` instead")]
+  #[diagnostic(
+    code(wgsl_bindgen::output_not_specified),
+    help("Set `output` on `WgslBindgenOptionBuilder`, or call `generate_string`.")
+  )]
   OutputFileNotSpecified,
 }
+
+/// naga_oil stores the index of the originating module in the upper bits of each span.
+/// Mirrors `naga_oil::compose::SPAN_SHIFT`, which is private.
+const COMPOSER_SPAN_SHIFT: usize = 21;
+
+impl WgslBindgenError {
+  /// Maps a composer error back onto the file it originates from, which may be
+  /// one of the dependencies rather than the entry itself.
+  pub(crate) fn from_compose_error(
+    entry: String,
+    composer: &Composer,
+    err: ComposerError,
+  ) -> Self {
+    let path = err.source.path(composer).clone();
+    let source = err.source.source(composer).into_owned();
+    let offset = err.source.offset();
+
+    let map_span = |range: Option<Range<usize>>| {
+      let range = range.unwrap_or(0..0);
+      let mask = (1 << COMPOSER_SPAN_SHIFT) - 1;
+      let start = (range.start & mask).saturating_sub(offset);
+      let end = (range.end & mask).saturating_sub(offset);
+      (start, end.saturating_sub(start))
+    };
+    let label = |message: String, (start, len): (usize, usize)| {
+      LabeledSpan::new(Some(demangle_str(&message).into_owned()), start, len)
+    };
+
+    let (labels, notes) = match &err.inner {
+      ComposerErrorInner::WgslParseError(e) => (
+        e.labels()
+          .map(|(span, msg)| label(msg.to_string(), map_span(span.to_range())))
+          .collect(),
+        vec![e.message().to_string()],
+      ),
+      ComposerErrorInner::HeaderValidationError(v)
+      | ComposerErrorInner::ShaderValidationError(v) => {
+        let mut notes = vec![];
+        let mut source = std::error::Error::source(v.as_inner());
+        while let Some(inner) = source {
+          notes.push(inner.to_string());
+          source = inner.source();
+        }
+        (
+          v.spans()
+            .map(|(span, desc)| label(desc.clone(), map_span(span.to_range())))
+            .collect(),
+          notes,
+        )
+      }
+      ComposerErrorInner::ImportNotFound(msg, pos) => {
+        (vec![label(format!("missing import `{msg}`"), (*pos, 0))], vec![])
+      }
+      ComposerErrorInner::ImportParseError(msg, pos) => {
+        (vec![label(format!("invalid import spec: `{msg}`"), (*pos, 0))], vec![])
+      }
+      ComposerErrorInner::InvalidIdentifier { at, .. } => {
+        (vec![label(err.inner.to_string(), map_span(at.to_range()))], vec![])
+      }
+      ComposerErrorInner::DecorationInSource(range) => {
+        (vec![label(err.inner.to_string(), (range.start, range.len()))], vec![])
+      }
+      ComposerErrorInner::NotEnoughEndIfs(pos)
+      | ComposerErrorInner::TooManyEndIfs(pos)
+      | ComposerErrorInner::ElseWithoutCondition(pos)
+      | ComposerErrorInner::UnknownShaderDef { pos, .. }
+      | ComposerErrorInner::UnknownShaderDefOperator { pos, .. }
+      | ComposerErrorInner::InvalidShaderDefComparisonValue { pos, .. }
+      | ComposerErrorInner::OverrideNotVirtual { pos, .. }
+      | ComposerErrorInner::GlslInvalidVersion(pos)
+      | ComposerErrorInner::DefineInModule(pos)
+      | ComposerErrorInner::InvalidShaderDefDefinitionValue { pos, .. } => {
+        (vec![label(err.inner.to_string(), (*pos, 0))], vec![])
+      }
+      _ => (vec![], vec![]),
+    };
+
+    let notes = (!notes.is_empty()).then(|| demangle_str(&notes.join("\n")).into_owned());
+
+    Self::NagaModuleComposeError {
+      entry,
+      msg: demangle_str(&err.inner.to_string()).into_owned(),
+      src: NamedSource::new(path, source),
+      labels,
+      notes,
+      inner: err.inner,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use regex::Regex;
+
+  #[test]
+  fn diagnostic_codes_are_unique() {
+    let sources = [
+      include_str!("errors.rs"),
+      include_str!("../lib.rs"),
+      include_str!("../bevy_util/deptree.rs"),
+    ];
+    let code = Regex::new(r"code\((wgsl_bindgen::[\w:]+)\)").unwrap();
+
+    let mut codes = HashSet::new();
+    for source in sources {
+      for captures in code.captures_iter(source) {
+        let code = captures[1].to_string();
+        assert!(codes.insert(code.clone()), "`{code}` is used more than once");
+      }
+    }
+    assert!(codes.contains("wgsl_bindgen::vertex_pulling::incompatible_source_type"));
+  }
+}
@@ -1,7 +1,21 @@
 mod bindgen;
+mod binding_reference;
+mod c_header;
+mod dependency_graph;
+mod diagnostics;
+mod entry_glob;
 mod errors;
+mod module_cache;
+mod module_names;
 mod options;
+mod report;
+mod typescript;
+mod user_regions;
+#[cfg(feature = "watch")]
+mod watch;
 
 pub use bindgen::*;
+pub use diagnostics::*;
 pub use errors::*;
 pub use options::*;
+pub use report::*;
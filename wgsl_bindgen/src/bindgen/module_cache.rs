@@ -0,0 +1,55 @@
+//! On-disk cache of the composed naga module of each entry, keyed by the contents of the entry
+//! and its imports, so clean builds skip recomposing unchanged shaders.
+
+use std::path::{Path, PathBuf};
+
+use super::write_atomically;
+use crate::{
+  SourceWithFullDependenciesResult, WgslBindgenError, WgslShaderIrCapabilities,
+};
+
+const PKG_VER: &str = env!("CARGO_PKG_VERSION");
+
+/// Returns the cached module of `entry`, composing and caching it when it's missing.
+pub(crate) fn get_or_compose(
+  cache_dir: &Path,
+  entry: &SourceWithFullDependenciesResult<'_>,
+  capabilities: Option<WgslShaderIrCapabilities>,
+  compose: impl FnOnce() -> Result<naga::Module, WgslBindgenError>,
+) -> Result<naga::Module, WgslBindgenError> {
+  let path = cache_path(cache_dir, entry, capabilities);
+
+  // A cached module that can't be read, eg: written by another version, is composed again.
+  let cached = std::fs::read(&path)
+    .ok()
+    .and_then(|bytes| bincode::deserialize(&bytes).ok());
+  if let Some(module) = cached {
+    return Ok(module);
+  }
+
+  let module = compose()?;
+  let bytes = bincode::serialize(&module).expect("failed to serialize naga module");
+  std::fs::create_dir_all(cache_dir)?;
+  write_atomically(&path, &bytes)?;
+  Ok(module)
+}
+
+fn cache_path(
+  cache_dir: &Path,
+  entry: &SourceWithFullDependenciesResult<'_>,
+  capabilities: Option<WgslShaderIrCapabilities>,
+) -> PathBuf {
+  let mut hasher = blake3::Hasher::new();
+  hasher.update(PKG_VER.as_bytes());
+  hasher.update(format!("{capabilities:?}").as_bytes());
+
+  for source in
+    std::iter::once(entry.source_file).chain(entry.full_dependencies.iter().copied())
+  {
+    hasher.update(source.file_path.to_string().as_bytes());
+    hasher.update(format!("{:?}", source.module_name).as_bytes());
+    hasher.update(source.content.as_bytes());
+  }
+
+  cache_dir.join(format!("{}.naga", hasher.finalize()))
+}
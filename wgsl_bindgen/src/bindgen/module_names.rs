@@ -0,0 +1,187 @@
+//! Names the generated module of each entry, disambiguating entries that would generate the same one.
+
+use crate::{
+  FastIndexMap, SourceFilePath, WgslBindgenError, WgslBindgenOption,
+  WgslModuleNameCollision,
+};
+
+/// Returns the module name of each entry, in the order of `entries`.
+pub(crate) fn resolve<'a>(
+  entries: impl IntoIterator<Item = &'a SourceFilePath>,
+  options: &WgslBindgenOption,
+) -> Result<FastIndexMap<SourceFilePath, String>, WgslBindgenError> {
+  let mut names = entries
+    .into_iter()
+    .map(|path| {
+      let name = options
+        .entry_point_module_names
+        .get(&path.to_string())
+        .cloned()
+        .unwrap_or_else(|| options.module_name_case.apply(&path.file_prefix()));
+      (path.clone(), name)
+    })
+    .collect::<FastIndexMap<_, _>>();
+
+  let collisions = find_collisions(&names);
+  if collisions.is_empty() {
+    return Ok(names);
+  }
+
+  for path in collisions.into_values().flatten() {
+    let name = &names[&path];
+    let renamed = match options.module_name_collision {
+      WgslModuleNameCollision::Error => return Err(collision_error(&names, name)),
+      WgslModuleNameCollision::PrefixParentDirectory => {
+        let parent = path.dir();
+        let parent = parent
+          .file_name()
+          .map(|dir| dir.to_string_lossy())
+          .unwrap_or_default()
+          .replace(|ch: char| !ch.is_alphanumeric() && ch != '_', "_");
+        format!("{parent}_{name}")
+      }
+      WgslModuleNameCollision::Rename(rename) => rename(name, &path),
+    };
+    names.insert(path, renamed);
+  }
+
+  // Disambiguating may still leave duplicates, eg: with identically named parent directories.
+  match find_collisions(&names).into_keys().next() {
+    Some(name) => Err(collision_error(&names, &name)),
+    None => Ok(names),
+  }
+}
+
+/// Groups the entries sharing a module name by that name.
+fn find_collisions(
+  names: &FastIndexMap<SourceFilePath, String>,
+) -> FastIndexMap<String, Vec<SourceFilePath>> {
+  let mut entries_by_name = FastIndexMap::<String, Vec<SourceFilePath>>::default();
+  for (path, name) in names {
+    entries_by_name
+      .entry(name.clone())
+      .or_default()
+      .push(path.clone());
+  }
+
+  entries_by_name.retain(|_, paths| paths.len() > 1);
+  entries_by_name
+}
+
+fn collision_error(
+  names: &FastIndexMap<SourceFilePath, String>,
+  mod_name: &str,
+) -> WgslBindgenError {
+  WgslBindgenError::ModuleNameCollisionError {
+    mod_name: mod_name.to_string(),
+    paths: names
+      .iter()
+      .filter(|(_, name)| *name == mod_name)
+      .map(|(path, _)| path.to_string())
+      .collect(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::WgslNameCase;
+
+  fn resolve_names(
+    paths: &[&str],
+    collision: WgslModuleNameCollision,
+  ) -> Result<Vec<String>, WgslBindgenError> {
+    let options = WgslBindgenOption {
+      module_name_collision: collision,
+      ..Default::default()
+    };
+
+    let paths = paths.iter().map(SourceFilePath::new).collect::<Vec<_>>();
+    Ok(resolve(&paths, &options)?.into_values().collect())
+  }
+
+  #[test]
+  fn keeps_unique_names() {
+    let names = resolve_names(
+      &["shaders/fx/blur.wgsl", "shaders/ui/text.wgsl"],
+      WgslModuleNameCollision::Error,
+    )
+    .unwrap();
+    assert_eq!(vec!["blur", "text"], names);
+  }
+
+  #[test]
+  fn errors_on_collision() {
+    let err = resolve_names(
+      &["shaders/fx/blur.wgsl", "shaders/ui/blur.wgsl"],
+      WgslModuleNameCollision::Error,
+    )
+    .unwrap_err();
+    assert_eq!(
+      "Entries shaders/fx/blur.wgsl, shaders/ui/blur.wgsl all generate the module `blur`",
+      err.to_string()
+    );
+  }
+
+  #[test]
+  fn prefixes_parent_directory() {
+    let names = resolve_names(
+      &[
+        "shaders/fx/blur.wgsl",
+        "shaders/post-fx/blur.wgsl",
+        "shaders/text.wgsl",
+      ],
+      WgslModuleNameCollision::PrefixParentDirectory,
+    )
+    .unwrap();
+    assert_eq!(vec!["fx_blur", "post_fx_blur", "text"], names);
+  }
+
+  #[test]
+  fn renames_with_callback() {
+    let names = resolve_names(
+      &["shaders/fx/blur.wgsl", "shaders/ui/blur.wgsl"],
+      WgslModuleNameCollision::Rename(|name, path| {
+        let dir = path.parent().unwrap().file_name().unwrap();
+        format!("{name}_{}", dir.to_str().unwrap())
+      }),
+    )
+    .unwrap();
+    assert_eq!(vec!["blur_fx", "blur_ui"], names);
+  }
+
+  #[test]
+  fn applies_module_name_case() {
+    let options = WgslBindgenOption {
+      module_name_case: WgslNameCase::SnakeCase,
+      entry_point_module_names: FastIndexMap::from_iter([(
+        "shaders/ui/TextPass.wgsl".to_string(),
+        "TextPass".to_string(),
+      )]),
+      ..Default::default()
+    };
+
+    let paths =
+      ["shaders/fx/BlurPass.wgsl", "shaders/ui/TextPass.wgsl"].map(SourceFilePath::new);
+    let names = resolve(&paths, &options)
+      .unwrap()
+      .into_values()
+      .collect::<Vec<_>>();
+    assert_eq!(vec!["blur_pass", "TextPass"], names);
+  }
+
+  #[test]
+  fn errors_when_still_colliding() {
+    let err = resolve_names(
+      &["a/fx/blur.wgsl", "b/fx/blur.wgsl"],
+      WgslModuleNameCollision::PrefixParentDirectory,
+    )
+    .unwrap_err();
+    assert_eq!(
+      "Entries a/fx/blur.wgsl, b/fx/blur.wgsl all generate the module `fx_blur`",
+      err.to_string()
+    );
+  }
+}
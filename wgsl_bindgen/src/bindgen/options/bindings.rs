@@ -4,7 +4,8 @@ use syn::Ident;
 use crate::qs::{quote, Index, TokenStream};
 use crate::FastIndexMap;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BindResourceType {
   Buffer,
   Sampler,
@@ -0,0 +1,908 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use regex::Regex;
+use serde::Deserialize;
+
+use super::*;
+
+/// The built-in type maps that can be selected from a configuration file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ConfigTypeMap {
+  Rust,
+  Glam,
+  Nalgebra,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOverrideStruct {
+  from: String,
+  to: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOverrideStructFieldType {
+  struct_regex: String,
+  field_regex: String,
+  override_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOverrideStructAlignment {
+  struct_regex: String,
+  alignment: u16,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigRemapVertexLocation {
+  module_regex: String,
+  from: u32,
+  to: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigVertexPulling {
+  module_regex: String,
+  group: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOverrideVertexFormat {
+  struct_regex: String,
+  field_regex: String,
+  /// The WebGPU name of the format, eg: `unorm8x4`.
+  format: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigSamplerPreset {
+  binding_regex: String,
+  /// The arguments of a `@sampler` comment, eg: `linear, repeat`.
+  preset: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOverrideVertexStride {
+  struct_regex: String,
+  stride: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOverrideShaderLabel {
+  mod_name: String,
+  label: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigEntryPointFeature {
+  mod_name: String,
+  feature: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigNamedEntryPoint {
+  path: PathBuf,
+  mod_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigExcludeItem {
+  kind: WgslItemKind,
+  name_regex: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigOutputPartition {
+  output: PathBuf,
+  entry_regex: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigScanDirectory {
+  module_import_root: Option<String>,
+  directory: PathBuf,
+}
+
+/// The contents of a `wgsl_bindgen.toml` file.
+/// Fields left out keep the defaults of `WgslBindgenOptionBuilder`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WgslBindgenConfig {
+  workspace_root: PathBuf,
+  #[serde(default)]
+  entry_points: Vec<PathBuf>,
+  #[serde(default)]
+  entry_point_globs: Vec<String>,
+  #[serde(default)]
+  named_entry_point: Vec<ConfigNamedEntryPoint>,
+  module_import_root: Option<String>,
+  output: Option<PathBuf>,
+  output_mode: Option<WgslOutputMode>,
+  #[serde(default)]
+  output_partition: Vec<ConfigOutputPartition>,
+  output_module_path: Option<String>,
+  serialization_strategy: Option<WgslTypeSerializeStrategy>,
+  type_map: Option<ConfigTypeMap>,
+  shader_source_type: Option<Vec<WgslShaderSourceType>>,
+  type_visibility: Option<WgslTypeVisibility>,
+  struct_name_case: Option<WgslNameCase>,
+  entry_point_const_prefix: Option<String>,
+  downlevel_check: Option<WgslDownlevelCheck>,
+  max_push_constant_size: Option<u32>,
+  max_compute_workgroup_storage_size: Option<u32>,
+  max_uniform_buffer_binding_size: Option<u32>,
+  short_constructor: Option<i32>,
+  module_name_collision: Option<WgslModuleNameCollision>,
+  module_name_case: Option<WgslNameCase>,
+  /// Names of `naga::valid::Capabilities` flags, eg: `["push_constant"]`.
+  #[serde(default)]
+  ir_capabilities: Vec<String>,
+  #[serde(default)]
+  deny_ir_capabilities: Vec<String>,
+  emit_rerun_if_change: Option<bool>,
+  skip_header_comments: Option<bool>,
+  skip_hash_check: Option<bool>,
+  hash_storage: Option<WgslHashStorage>,
+  module_cache_dir: Option<PathBuf>,
+  dependency_graph_output: Option<PathBuf>,
+  binding_reference_output: Option<PathBuf>,
+  reflection_output: Option<PathBuf>,
+  c_header_output: Option<PathBuf>,
+  typescript_output: Option<PathBuf>,
+  bevy_feature: Option<String>,
+  wasm_fetch_feature: Option<String>,
+  derive_serde: Option<bool>,
+  shader_entry_derive_serde: Option<bool>,
+  shader_entry_derive_strum: Option<bool>,
+  generate_try_create_shader_module: Option<bool>,
+  generic_device: Option<bool>,
+  emit_validation_warnings: Option<bool>,
+  strip_unused_bindings: Option<bool>,
+  #[serde(default)]
+  deny_diagnostics: Vec<WgslDiagnosticKind>,
+  always_generate_init_struct: Option<bool>,
+  no_std: Option<bool>,
+  staging_belt_feature: Option<String>,
+  frames_in_flight_buffers: Option<bool>,
+  uniform_arenas: Option<bool>,
+  shadowed_uniforms: Option<bool>,
+  bind_group_bundles: Option<bool>,
+  ping_pong_buffers: Option<bool>,
+  #[serde(default)]
+  additional_scan_dirs: Vec<ConfigScanDirectory>,
+  #[serde(default)]
+  instance_vertex_inputs: Vec<String>,
+  #[serde(default)]
+  indirect_args_structs: Vec<String>,
+  #[serde(default)]
+  sampler_presets: Vec<ConfigSamplerPreset>,
+  #[serde(default)]
+  custom_padding_field_regexps: Vec<String>,
+  #[serde(default)]
+  override_struct: Vec<ConfigOverrideStruct>,
+  #[serde(default)]
+  override_struct_field_type: Vec<ConfigOverrideStructFieldType>,
+  #[serde(default)]
+  override_struct_alignment: Vec<ConfigOverrideStructAlignment>,
+  #[serde(default)]
+  override_vertex_format: Vec<ConfigOverrideVertexFormat>,
+  #[serde(default)]
+  override_vertex_struct: Vec<ConfigOverrideStruct>,
+  #[serde(default)]
+  override_vertex_stride: Vec<ConfigOverrideVertexStride>,
+  #[serde(default)]
+  remap_vertex_location: Vec<ConfigRemapVertexLocation>,
+  #[serde(default)]
+  vertex_pulling: Vec<ConfigVertexPulling>,
+  #[serde(default)]
+  override_shader_label: Vec<ConfigOverrideShaderLabel>,
+  #[serde(default)]
+  entry_point_feature: Vec<ConfigEntryPointFeature>,
+  #[serde(default)]
+  exclude_item: Vec<ConfigExcludeItem>,
+}
+
+impl WgslBindgenOptionBuilder {
+  /// Creates a builder from a TOML configuration file, eg: `wgsl_bindgen.toml`.
+  ///
+  /// The keys mirror the options of this builder. Relative paths are resolved against
+  /// the directory containing the file, so the same file can be shared between a build script
+  /// and other tools. The returned builder can be further customized before calling `build`.
+  ///
+  /// ```toml
+  /// workspace_root = "shaders"
+  /// entry_points = ["shaders/triangle.wgsl"]
+  /// output = "src/shader_bindings.rs"
+  /// serialization_strategy = "bytemuck"
+  /// type_map = "glam"
+  /// shader_source_type = ["use_embed", "use_composer_with_path"]
+  ///
+  /// [[override_struct]]
+  /// from = "types::Scalars"
+  /// to = "crate::MyScalars"
+  /// ```
+  pub fn from_config_file(path: impl AsRef<Path>) -> Result<Self, WgslBindgenError> {
+    let path = path.as_ref();
+    let error = |msg: String| WgslBindgenError::ConfigFileError {
+      path: path.display().to_string(),
+      msg,
+    };
+
+    let contents = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+    let config = toml::from_str::<WgslBindgenConfig>(&contents)
+      .map_err(|e| error(e.message().to_string()))?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+
+    config.into_builder(base_dir).map_err(error)
+  }
+}
+
+impl WgslBindgenConfig {
+  fn into_builder(self, base_dir: &Path) -> Result<WgslBindgenOptionBuilder, String> {
+    let resolve = |path: &Path| base_dir.join(path);
+    let resolve_str = |path: &Path| resolve(path).to_string_lossy().into_owned();
+    let regex = |pattern: &str| {
+      Regex::new(pattern).map_err(|e| format!("invalid regex `{pattern}`: {e}"))
+    };
+    let rust_type = |ty: &str| {
+      syn::parse_str::<syn::Type>(ty)
+        .map(|ty| quote::quote!(#ty))
+        .map_err(|e| format!("invalid rust type `{ty}`: {e}"))
+    };
+
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .workspace_root(resolve(&self.workspace_root))
+      .entry_points(
+        self
+          .entry_points
+          .iter()
+          .map(|p| resolve_str(p))
+          .collect::<Vec<_>>(),
+      )
+      .entry_point_globs(
+        self
+          .entry_point_globs
+          .iter()
+          .map(|p| resolve_str(Path::new(p)))
+          .collect::<Vec<_>>(),
+      )
+      .additional_scan_dirs(
+        self
+          .additional_scan_dirs
+          .iter()
+          .map(|dir| AdditionalScanDirectory {
+            module_import_root: dir.module_import_root.clone(),
+            directory: resolve_str(&dir.directory),
+          })
+          .collect::<Vec<_>>(),
+      )
+      .instance_vertex_inputs(
+        self
+          .instance_vertex_inputs
+          .iter()
+          .map(|p| regex(p))
+          .collect::<Result<Vec<_>, _>>()?,
+      )
+      .indirect_args_structs(
+        self
+          .indirect_args_structs
+          .iter()
+          .map(|p| regex(p))
+          .collect::<Result<Vec<_>, _>>()?,
+      )
+      .sampler_presets(
+        self
+          .sampler_presets
+          .iter()
+          .map(|p| sampler_preset(regex(&p.binding_regex)?, &p.preset))
+          .collect::<Result<Vec<_>, _>>()?,
+      )
+      .custom_padding_field_regexps(
+        self
+          .custom_padding_field_regexps
+          .iter()
+          .map(|p| regex(p))
+          .collect::<Result<Vec<_>, _>>()?,
+      )
+      .override_struct(
+        self
+          .override_struct
+          .iter()
+          .map(|o| Ok((o.from.as_str(), rust_type(&o.to)?).into()))
+          .collect::<Result<Vec<OverrideStruct>, String>>()?,
+      )
+      .override_struct_field_type(
+        self
+          .override_struct_field_type
+          .iter()
+          .map(|o| {
+            let override_type: TokenStream = rust_type(&o.override_type)?;
+            Ok((regex(&o.struct_regex)?, regex(&o.field_regex)?, override_type).into())
+          })
+          .collect::<Result<Vec<OverrideStructFieldType>, String>>()?,
+      )
+      .override_struct_alignment(
+        self
+          .override_struct_alignment
+          .iter()
+          .map(|o| Ok((regex(&o.struct_regex)?, o.alignment).into()))
+          .collect::<Result<Vec<OverrideStructAlignment>, String>>()?,
+      )
+      .override_vertex_format(
+        self
+          .override_vertex_format
+          .iter()
+          .map(|o| {
+            let format = vertex_format(&o.format)?;
+            Ok((regex(&o.struct_regex)?, regex(&o.field_regex)?, format).into())
+          })
+          .collect::<Result<Vec<OverrideVertexFormat>, String>>()?,
+      )
+      .override_vertex_struct(
+        self
+          .override_vertex_struct
+          .iter()
+          .map(|o| Ok((o.from.as_str(), rust_type(&o.to)?).into()))
+          .collect::<Result<Vec<OverrideStruct>, String>>()?,
+      )
+      .override_vertex_stride(
+        self
+          .override_vertex_stride
+          .iter()
+          .map(|o| Ok((regex(&o.struct_regex)?, o.stride).into()))
+          .collect::<Result<Vec<OverrideVertexStride>, String>>()?,
+      )
+      .remap_vertex_location(
+        self
+          .remap_vertex_location
+          .iter()
+          .map(|r| Ok((regex(&r.module_regex)?, r.from, r.to).into()))
+          .collect::<Result<Vec<RemapVertexLocation>, String>>()?,
+      )
+      .vertex_pulling(
+        self
+          .vertex_pulling
+          .iter()
+          .map(|p| Ok((regex(&p.module_regex)?, p.group).into()))
+          .collect::<Result<Vec<VertexPulling>, String>>()?,
+      )
+      .override_shader_label(
+        self
+          .override_shader_label
+          .iter()
+          .map(|o| (o.mod_name.as_str(), o.label.as_str()).into())
+          .collect::<Vec<OverrideShaderLabel>>(),
+      )
+      .entry_point_features(
+        self
+          .entry_point_feature
+          .iter()
+          .map(|e| (e.mod_name.as_str(), e.feature.as_str()).into())
+          .collect::<Vec<EntryPointFeature>>(),
+      )
+      .exclude_items(
+        self
+          .exclude_item
+          .iter()
+          .map(|e| Ok((e.kind, regex(&e.name_regex)?).into()))
+          .collect::<Result<Vec<ExcludeItem>, String>>()?,
+      )
+      .output_partitions(
+        self
+          .output_partition
+          .iter()
+          .map(|p| Ok((resolve(&p.output), regex(&p.entry_regex)?).into()))
+          .collect::<Result<Vec<WgslOutputPartition>, String>>()?,
+      );
+
+    for entry in &self.named_entry_point {
+      builder.add_entry_point_named(resolve_str(&entry.path), entry.mod_name.as_str());
+    }
+    if let Some(root) = self.module_import_root {
+      builder.module_import_root(root);
+    }
+    if let Some(output) = self.output {
+      builder.output(resolve(&output));
+    }
+    if let Some(mode) = self.output_mode {
+      builder.output_mode(mode);
+    }
+    if let Some(path) = self.output_module_path {
+      builder.output_module_path(path);
+    }
+    if let Some(sources) = self.shader_source_type {
+      builder.shader_source_type(sources.into_iter().collect::<BitFlags<_>>());
+    }
+    if let Some(visibility) = self.type_visibility {
+      builder.type_visibility(visibility);
+    }
+    if let Some(case) = self.struct_name_case {
+      builder.struct_name_case(case);
+    }
+    if let Some(prefix) = self.entry_point_const_prefix {
+      builder.entry_point_const_prefix(prefix);
+    }
+    if let Some(check) = self.downlevel_check {
+      builder.downlevel_check(check);
+    }
+    if let Some(size) = self.max_push_constant_size {
+      builder.max_push_constant_size(size);
+    }
+    if let Some(size) = self.max_compute_workgroup_storage_size {
+      builder.max_compute_workgroup_storage_size(size);
+    }
+    if let Some(size) = self.max_uniform_buffer_binding_size {
+      builder.max_uniform_buffer_binding_size(size);
+    }
+    if let Some(collision) = self.module_name_collision {
+      builder.module_name_collision(collision);
+    }
+    if let Some(case) = self.module_name_case {
+      builder.module_name_case(case);
+    }
+    if let Some(threshold) = self.short_constructor {
+      builder.short_constructor(threshold);
+    }
+    if !self.ir_capabilities.is_empty() {
+      builder.ir_capabilities(ir_capabilities(&self.ir_capabilities)?);
+    }
+    if !self.deny_ir_capabilities.is_empty() {
+      builder.deny_ir_capabilities(ir_capabilities(&self.deny_ir_capabilities)?);
+    }
+
+    if let Some(value) = self.emit_rerun_if_change {
+      builder.emit_rerun_if_change(value);
+    }
+    if let Some(value) = self.skip_header_comments {
+      builder.skip_header_comments(value);
+    }
+    if let Some(value) = self.skip_hash_check {
+      builder.skip_hash_check(value);
+    }
+    if let Some(storage) = self.hash_storage {
+      builder.hash_storage(storage);
+    }
+    if let Some(dir) = self.module_cache_dir {
+      builder.module_cache_dir(resolve(&dir));
+    }
+    if let Some(path) = self.dependency_graph_output {
+      builder.dependency_graph_output(resolve(&path));
+    }
+    if let Some(path) = self.binding_reference_output {
+      builder.binding_reference_output(resolve(&path));
+    }
+    if let Some(path) = self.reflection_output {
+      builder.reflection_output(resolve(&path));
+    }
+    if let Some(path) = self.c_header_output {
+      builder.c_header_output(resolve(&path));
+    }
+    if let Some(path) = self.typescript_output {
+      builder.typescript_output(resolve(&path));
+    }
+    if let Some(feature) = self.bevy_feature {
+      builder.bevy_feature(feature);
+    }
+    if let Some(feature) = self.wasm_fetch_feature {
+      builder.wasm_fetch_feature(feature);
+    }
+    if let Some(value) = self.derive_serde {
+      builder.derive_serde(value);
+    }
+    if let Some(value) = self.shader_entry_derive_serde {
+      builder.shader_entry_derive_serde(value);
+    }
+    if let Some(value) = self.shader_entry_derive_strum {
+      builder.shader_entry_derive_strum(value);
+    }
+    if let Some(value) = self.generate_try_create_shader_module {
+      builder.generate_try_create_shader_module(value);
+    }
+    if let Some(value) = self.generic_device {
+      builder.generic_device(value);
+    }
+    if let Some(value) = self.emit_validation_warnings {
+      builder.emit_validation_warnings(value);
+    }
+    if let Some(value) = self.strip_unused_bindings {
+      builder.strip_unused_bindings(value);
+    }
+    if !self.deny_diagnostics.is_empty() {
+      builder.deny_diagnostics(self.deny_diagnostics);
+    }
+    if let Some(value) = self.always_generate_init_struct {
+      builder.always_generate_init_struct(value);
+    }
+    if let Some(value) = self.no_std {
+      builder.no_std(value);
+    }
+    if let Some(feature) = self.staging_belt_feature {
+      builder.staging_belt_feature(feature);
+    }
+    if let Some(value) = self.frames_in_flight_buffers {
+      builder.frames_in_flight_buffers(value);
+    }
+    if let Some(value) = self.uniform_arenas {
+      builder.uniform_arenas(value);
+    }
+    if let Some(value) = self.shadowed_uniforms {
+      builder.shadowed_uniforms(value);
+    }
+    if let Some(value) = self.bind_group_bundles {
+      builder.bind_group_bundles(value);
+    }
+    if let Some(value) = self.ping_pong_buffers {
+      builder.ping_pong_buffers(value);
+    }
+
+    // The type map is built for the serialization strategy, so it has to be set first.
+    builder.serialization_strategy(self.serialization_strategy.unwrap_or_default());
+    match self.type_map {
+      Some(ConfigTypeMap::Rust) => builder.type_map(RustWgslTypeMap),
+      Some(ConfigTypeMap::Glam) => builder.type_map(GlamWgslTypeMap),
+      Some(ConfigTypeMap::Nalgebra) => builder.type_map(NalgebraWgslTypeMap),
+      None => &mut builder,
+    };
+
+    Ok(builder)
+  }
+}
+
+/// Parses the names of `naga::valid::Capabilities` flags, eg: `push_constant`.
+fn ir_capabilities(names: &[String]) -> Result<WgslShaderIrCapabilities, String> {
+  names
+    .iter()
+    .map(|name| {
+      WgslShaderIrCapabilities::from_name(&name.to_uppercase())
+        .ok_or_else(|| format!("unknown ir capability `{name}`"))
+    })
+    .collect()
+}
+
+/// Parses the WebGPU name of a vertex format, eg: `unorm8x4`.
+pub(crate) fn vertex_format(name: &str) -> Result<WgslVertexFormat, String> {
+  use WgslVertexFormat as F;
+
+  let format = match name {
+    "uint8x2" => F::Uint8x2,
+    "uint8x4" => F::Uint8x4,
+    "sint8x2" => F::Sint8x2,
+    "sint8x4" => F::Sint8x4,
+    "unorm8x2" => F::Unorm8x2,
+    "unorm8x4" => F::Unorm8x4,
+    "snorm8x2" => F::Snorm8x2,
+    "snorm8x4" => F::Snorm8x4,
+    "uint16x2" => F::Uint16x2,
+    "uint16x4" => F::Uint16x4,
+    "sint16x2" => F::Sint16x2,
+    "sint16x4" => F::Sint16x4,
+    "unorm16x2" => F::Unorm16x2,
+    "unorm16x4" => F::Unorm16x4,
+    "snorm16x2" => F::Snorm16x2,
+    "snorm16x4" => F::Snorm16x4,
+    "float16x2" => F::Float16x2,
+    "float16x4" => F::Float16x4,
+    "float32" => F::Float32,
+    "float32x2" => F::Float32x2,
+    "float32x3" => F::Float32x3,
+    "float32x4" => F::Float32x4,
+    "uint32" => F::Uint32,
+    "uint32x2" => F::Uint32x2,
+    "uint32x3" => F::Uint32x3,
+    "uint32x4" => F::Uint32x4,
+    "sint32" => F::Sint32,
+    "sint32x2" => F::Sint32x2,
+    "sint32x3" => F::Sint32x3,
+    "sint32x4" => F::Sint32x4,
+    "float64" => F::Float64,
+    "float64x2" => F::Float64x2,
+    "float64x3" => F::Float64x3,
+    "float64x4" => F::Float64x4,
+    "unorm10-10-10-2" => F::Unorm10_10_10_2,
+    _ => return Err(format!("unknown vertex format `{name}`")),
+  };
+  Ok(format)
+}
+
+/// Parses the arguments of a `@sampler` comment, eg: `linear, repeat`, naming the filter, the
+/// address mode and the comparison of the sampler by their WebGPU names, in any order.
+pub(crate) fn sampler_preset(
+  binding_regex: Regex,
+  arguments: &str,
+) -> Result<SamplerPreset, String> {
+  use WgslAddressMode as A;
+  use WgslCompareFunction as C;
+  use WgslFilterMode as F;
+
+  fn set<T>(slot: &mut Option<T>, value: T, argument: &str) -> Result<(), String> {
+    match slot.replace(value) {
+      Some(_) => {
+        Err(format!("sampler argument `{argument}` conflicts with a previous one"))
+      }
+      None => Ok(()),
+    }
+  }
+
+  let (mut filter, mut address_mode, mut compare) = (None, None, None);
+  let arguments = arguments
+    .split(',')
+    .map(str::trim)
+    .filter(|a| !a.is_empty());
+  for argument in arguments {
+    // Both the WebGPU and the Rust names are accepted, eg: `clamp-to-edge` or `clamp_to_edge`.
+    match argument.to_lowercase().replace('_', "-").as_str() {
+      "nearest" => set(&mut filter, F::Nearest, argument)?,
+      "linear" => set(&mut filter, F::Linear, argument)?,
+      "clamp-to-edge" => set(&mut address_mode, A::ClampToEdge, argument)?,
+      "repeat" => set(&mut address_mode, A::Repeat, argument)?,
+      "mirror-repeat" => set(&mut address_mode, A::MirrorRepeat, argument)?,
+      "never" => set(&mut compare, C::Never, argument)?,
+      "less" => set(&mut compare, C::Less, argument)?,
+      "equal" => set(&mut compare, C::Equal, argument)?,
+      "less-equal" => set(&mut compare, C::LessEqual, argument)?,
+      "greater" => set(&mut compare, C::Greater, argument)?,
+      "not-equal" => set(&mut compare, C::NotEqual, argument)?,
+      "greater-equal" => set(&mut compare, C::GreaterEqual, argument)?,
+      "always" => set(&mut compare, C::Always, argument)?,
+      _ => return Err(format!("unknown sampler argument `{argument}`")),
+    }
+  }
+
+  Ok(SamplerPreset {
+    binding_regex,
+    filter: filter.unwrap_or(F::Nearest),
+    address_mode: address_mode.unwrap_or(A::ClampToEdge),
+    compare,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn load(contents: &str) -> Result<WgslBindgenOptionBuilder, String> {
+    toml::from_str::<WgslBindgenConfig>(contents)
+      .map_err(|e| e.message().to_string())?
+      .into_builder(Path::new("config"))
+  }
+
+  #[test]
+  fn test_resolves_paths_relative_to_config() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      entry_points = ["shaders/main.wgsl"]
+      entry_point_globs = ["shaders/**/*.wgsl"]
+      output = "src/shader.rs"
+      type_map = "rust"
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    assert_eq!(Path::new("config/shaders"), options.workspace_root);
+    assert_eq!(vec!["config/shaders/main.wgsl"], options.entry_points);
+    assert_eq!(vec!["config/shaders/**/*.wgsl"], options.entry_point_globs);
+    assert_eq!(Some(PathBuf::from("config/src/shader.rs")), options.output);
+  }
+
+  #[test]
+  fn test_named_entry_points() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[named_entry_point]]
+      path = "shaders/fx/blur_h.wgsl"
+      mod_name = "blur_horizontal"
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    assert_eq!(vec!["config/shaders/fx/blur_h.wgsl"], options.entry_points);
+    assert_eq!(
+      Some(&"blur_horizontal".to_string()),
+      options
+        .entry_point_module_names
+        .get("config/shaders/fx/blur_h.wgsl")
+    );
+  }
+
+  #[test]
+  fn test_rejects_unknown_keys() {
+    let err = load("workspace_root = \"shaders\"\nentry_point = []")
+      .err()
+      .unwrap();
+    assert!(err.starts_with("unknown field `entry_point`"), "{err}");
+  }
+
+  #[test]
+  fn test_rejects_invalid_override() {
+    let err = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+
+      [[override_struct]]
+      from = "types::Scalars"
+      to = "crate::"
+    "#})
+    .err()
+    .unwrap();
+    assert!(err.starts_with("invalid rust type `crate::`"), "{err}");
+  }
+
+  #[test]
+  fn test_override_vertex_format() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[override_vertex_format]]
+      struct_regex = "VertexInput"
+      field_regex = "color"
+      format = "unorm8x4"
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    assert_eq!(
+      Some(WgslVertexFormat::Unorm8x4),
+      options.vertex_format_override("triangle::VertexInput", "color")
+    );
+    assert_eq!(None, options.vertex_format_override("triangle::VertexInput", "uv"));
+
+    let err = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+
+      [[override_vertex_format]]
+      struct_regex = "VertexInput"
+      field_regex = "color"
+      format = "rgba8unorm"
+    "#})
+    .err()
+    .unwrap();
+    assert_eq!("unknown vertex format `rgba8unorm`", err);
+  }
+
+  #[test]
+  fn test_override_vertex_struct() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[override_vertex_struct]]
+      from = "mesh::VertexInput"
+      to = "crate::assets::Vertex"
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    let vertex_struct = options.vertex_struct_override("mesh::VertexInput");
+    assert_eq!(
+      Some("crate :: assets :: Vertex".to_string()),
+      vertex_struct.map(|ty| ty.to_string())
+    );
+  }
+
+  #[test]
+  fn test_instance_vertex_inputs() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+      instance_vertex_inputs = ["Instance$"]
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    assert!(options.is_instance_vertex_input("sprites::SpriteInstance"));
+    assert!(!options.is_instance_vertex_input("sprites::VertexInput"));
+  }
+
+  #[test]
+  fn test_sampler_presets() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[sampler_presets]]
+      binding_regex = "^main::color_sampler$"
+      preset = "linear, mirror_repeat"
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    let preset = options.sampler_preset("main::color_sampler").unwrap();
+    assert_eq!(WgslFilterMode::Linear, preset.filter);
+    assert_eq!(WgslAddressMode::MirrorRepeat, preset.address_mode);
+    assert_eq!(None, preset.compare);
+    assert!(options.sampler_preset("main::shadow_sampler").is_none());
+
+    let err = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+
+      [[sampler_presets]]
+      binding_regex = "color_sampler"
+      preset = "linear, nearest"
+    "#})
+    .err()
+    .unwrap();
+    assert_eq!("sampler argument `nearest` conflicts with a previous one", err);
+  }
+
+  #[test]
+  fn test_override_vertex_stride() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[override_vertex_stride]]
+      struct_regex = "^mesh::VertexInput$"
+      stride = 48
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    assert_eq!(Some(48), options.vertex_stride_override("mesh::VertexInput"));
+    assert_eq!(None, options.vertex_stride_override("mesh::Instance"));
+  }
+
+  #[test]
+  fn test_remap_vertex_location() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[remap_vertex_location]]
+      module_regex = "^mesh$"
+      from = 1
+      to = 3
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    let remaps = &options.remap_vertex_location;
+    assert_eq!(Some(3), RemapVertexLocation::find(remaps, "mesh", 1));
+    assert_eq!(None, RemapVertexLocation::find(remaps, "mesh", 0));
+    assert_eq!(None, RemapVertexLocation::find(remaps, "sprites", 1));
+  }
+
+  #[test]
+  fn test_vertex_pulling() {
+    let options = load(indoc::indoc! {r#"
+      workspace_root = "shaders"
+      type_map = "rust"
+
+      [[vertex_pulling]]
+      module_regex = "^mesh$"
+      group = 2
+    "#})
+    .unwrap()
+    .fallible_build()
+    .unwrap();
+
+    let pullings = &options.vertex_pulling;
+    assert_eq!(Some(2), VertexPulling::find(pullings, "mesh"));
+    assert_eq!(None, VertexPulling::find(pullings, "sprites"));
+  }
+}
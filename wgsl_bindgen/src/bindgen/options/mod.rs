@@ -1,25 +1,35 @@
 mod bindings;
+mod config;
 mod types;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub use bindings::*;
+pub(crate) use config::{sampler_preset, vertex_format};
 use derive_builder::Builder;
 use derive_more::IsVariant;
 use enumflags2::{bitflags, BitFlags};
+use heck::{ToPascalCase, ToSnakeCase};
 pub use naga::valid::Capabilities as WgslShaderIrCapabilities;
 use proc_macro2::TokenStream;
 use regex::Regex;
+use serde::Deserialize;
 pub use types::*;
+pub use wgpu::{
+  AddressMode as WgslAddressMode, CompareFunction as WgslCompareFunction,
+  FilterMode as WgslFilterMode, VertexFormat as WgslVertexFormat,
+};
 
 use crate::{
-  FastIndexMap, WGSLBindgen, WgslBindgenError, WgslType, WgslTypeSerializeStrategy,
+  FastIndexMap, WGSLBindgen, WgslBindgenError, WgslDiagnosticKind, WgslType,
+  WgslTypeSerializeStrategy,
 };
 
 /// An enum representing the source type that will be generated for the output.
 #[bitflags(default = UseEmbed)]
 #[repr(u8)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, IsVariant)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IsVariant, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WgslShaderSourceType {
   /// Preparse the shader modules and embed the final shader string in the output.
   /// This option skips the naga_oil dependency in the output, and but doesn't allow shader defines.
@@ -32,6 +42,17 @@ pub enum WgslShaderSourceType {
   /// Use Composer with absolute path to shaders, useful for hot-reloading
   /// This option allows shader defines and is useful for hot-reloading.
   UseComposerWithPath = 0b0100,
+
+  /// Serialize the composed naga module at build time and create the shader module from
+  /// `wgpu::ShaderSource::Naga`, skipping WGSL parsing on startup.
+  /// The generated code requires the `naga-ir` feature of wgpu, the `deserialize` feature of naga
+  /// and a dependency on `bincode`.
+  UseNagaIr = 0b1000,
+
+  /// Read the composed shader string at runtime from the entry's path relative to the workspace root,
+  /// joined onto a base directory supplied by the caller.
+  /// This is useful for shipping composed shaders as loose files next to the executable.
+  UseRelativePath = 0b10000,
 }
 
 /// A struct representing a directory to scan for additional source files.
@@ -56,6 +77,15 @@ impl From<(Option<&str>, &str)> for AdditionalScanDirectory {
   }
 }
 
+/// A WGSL source given as a string rather than a file, eg: generated procedurally or expanded from a template.
+#[derive(Debug, Clone)]
+pub struct WgslInlineSource {
+  /// The name of the entry point, or the module name a composable source is imported as, eg: `procedural::noise`.
+  pub name: String,
+  /// The WGSL source.
+  pub source: String,
+}
+
 pub type WgslTypeMap = FastIndexMap<WgslType, TokenStream>;
 
 /// A trait for building `WgslType` to `TokenStream` map.
@@ -151,8 +181,316 @@ impl From<(&str, u16)> for OverrideStructAlignment {
   }
 }
 
+/// Struct for overriding the vertex format of specific fields of vertex input structs,
+/// eg: to feed a `vec4<f32>` from packed `Unorm8x4` colors.
+#[derive(Clone, Debug)]
+pub struct OverrideVertexFormat {
+  pub struct_regex: Regex,
+  pub field_regex: Regex,
+  pub format: WgslVertexFormat,
+}
+impl From<(Regex, Regex, WgslVertexFormat)> for OverrideVertexFormat {
+  fn from((struct_regex, field_regex, format): (Regex, Regex, WgslVertexFormat)) -> Self {
+    Self {
+      struct_regex,
+      field_regex,
+      format,
+    }
+  }
+}
+impl From<(&str, &str, WgslVertexFormat)> for OverrideVertexFormat {
+  fn from((struct_regex, field_regex, format): (&str, &str, WgslVertexFormat)) -> Self {
+    Self {
+      struct_regex: Regex::new(struct_regex).expect("Failed to create struct regex"),
+      field_regex: Regex::new(field_regex).expect("Failed to create field regex"),
+      format,
+    }
+  }
+}
+
+/// Struct for overriding the `array_stride` of the vertex buffer layout of matching vertex input
+/// structs, eg: when each vertex is followed by a payload the shader doesn't read.
+#[derive(Clone, Debug)]
+pub struct OverrideVertexStride {
+  pub struct_regex: Regex,
+  pub stride: u64,
+}
+impl From<(Regex, u64)> for OverrideVertexStride {
+  fn from((struct_regex, stride): (Regex, u64)) -> Self {
+    Self {
+      struct_regex,
+      stride,
+    }
+  }
+}
+impl From<(&str, u64)> for OverrideVertexStride {
+  fn from((struct_regex, stride): (&str, u64)) -> Self {
+    Self {
+      struct_regex: Regex::new(struct_regex).expect("Failed to create struct regex"),
+      stride,
+    }
+  }
+}
+
+/// Struct for the sampler descriptor of the sampler bindings matching `binding_regex`, generating a
+/// `create_{binding}_sampler(device)` function for them, eg: for `linear, repeat` samplers.
+#[derive(Clone, Debug)]
+pub struct SamplerPreset {
+  /// Matches the fully qualified name of the binding, eg: `main::color_sampler`.
+  pub binding_regex: Regex,
+  /// The filter of the magnification, minification and mipmaps alike.
+  pub filter: WgslFilterMode,
+  /// The address mode of all the coordinates.
+  pub address_mode: WgslAddressMode,
+  /// The comparison of `sampler_comparison` bindings, `LessEqual` if not given.
+  pub compare: Option<WgslCompareFunction>,
+}
+impl From<(Regex, WgslFilterMode, WgslAddressMode)> for SamplerPreset {
+  fn from(
+    (binding_regex, filter, address_mode): (Regex, WgslFilterMode, WgslAddressMode),
+  ) -> Self {
+    Self {
+      binding_regex,
+      filter,
+      address_mode,
+      compare: None,
+    }
+  }
+}
+impl From<(&str, WgslFilterMode, WgslAddressMode)> for SamplerPreset {
+  fn from(
+    (binding_regex, filter, address_mode): (&str, WgslFilterMode, WgslAddressMode),
+  ) -> Self {
+    let binding_regex =
+      Regex::new(binding_regex).expect("Failed to create binding regex");
+    (binding_regex, filter, address_mode).into()
+  }
+}
+
+/// Struct for remapping the `@location` of the vertex inputs of the entries matching `module_regex`,
+/// eg: to follow the attribute locations an engine uses for all of its shaders.
+#[derive(Clone, Debug)]
+pub struct RemapVertexLocation {
+  /// Matches the name of the generated module of the entry, eg: `triangle`.
+  pub module_regex: Regex,
+  pub from: u32,
+  pub to: u32,
+}
+impl From<(Regex, u32, u32)> for RemapVertexLocation {
+  fn from((module_regex, from, to): (Regex, u32, u32)) -> Self {
+    Self {
+      module_regex,
+      from,
+      to,
+    }
+  }
+}
+impl RemapVertexLocation {
+  /// The location the first of `remaps` matching the entry `mod_name` moves the vertex input at
+  /// `location` to, if any.
+  pub(crate) fn find(remaps: &[Self], mod_name: &str, location: u32) -> Option<u32> {
+    remaps.iter().find_map(|remap| {
+      let matches = remap.module_regex.is_match(mod_name) && remap.from == location;
+      matches.then_some(remap.to)
+    })
+  }
+}
+impl From<(&str, u32, u32)> for RemapVertexLocation {
+  fn from((module_regex, from, to): (&str, u32, u32)) -> Self {
+    Self {
+      module_regex: Regex::new(module_regex).expect("Failed to create module regex"),
+      from,
+      to,
+    }
+  }
+}
+
+/// Struct for pulling the vertex inputs of the entries matching `module_regex` from storage
+/// buffers in `group`, instead of from vertex buffers.
+#[derive(Clone, Debug)]
+pub struct VertexPulling {
+  /// Matches the name of the generated module of the entry, eg: `triangle`.
+  pub module_regex: Regex,
+  pub group: u32,
+}
+impl From<(Regex, u32)> for VertexPulling {
+  fn from((module_regex, group): (Regex, u32)) -> Self {
+    Self {
+      module_regex,
+      group,
+    }
+  }
+}
+impl From<(&str, u32)> for VertexPulling {
+  fn from((module_regex, group): (&str, u32)) -> Self {
+    Self {
+      module_regex: Regex::new(module_regex).expect("Failed to create module regex"),
+      group,
+    }
+  }
+}
+impl VertexPulling {
+  /// The group of the storage buffers of the first of `pullings` matching the entry `mod_name`, if any.
+  pub(crate) fn find(pullings: &[Self], mod_name: &str) -> Option<u32> {
+    pullings
+      .iter()
+      .find(|pulling| pulling.module_regex.is_match(mod_name))
+      .map(|pulling| pulling.group)
+  }
+}
+
+/// The kind of generated item matched by an `ExcludeItem`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslItemKind {
+  /// Structs generated from WGSL structs, along with their impls.
+  Struct,
+
+  /// Structs only used as entry point inputs, eg: vertex inputs. These are also matched by `Struct`.
+  EntryPointIo,
+
+  /// Constants generated from WGSL `const` declarations.
+  Const,
+}
+
+/// Struct for skipping the generation of items, eg: ones duplicating hand-written types.
+/// Generated code referring to an excluded struct, such as bind groups or vertex buffer layouts, still does so by its generated path.
+#[derive(Clone, Debug)]
+pub struct ExcludeItem {
+  pub kind: WgslItemKind,
+  /// matched against the fully qualified name of the item, eg: `types::InternalData`
+  pub name_regex: Regex,
+}
+impl From<(WgslItemKind, Regex)> for ExcludeItem {
+  fn from((kind, name_regex): (WgslItemKind, Regex)) -> Self {
+    Self { kind, name_regex }
+  }
+}
+impl From<(WgslItemKind, &str)> for ExcludeItem {
+  fn from((kind, name_regex): (WgslItemKind, &str)) -> Self {
+    Self {
+      kind,
+      name_regex: Regex::new(name_regex).expect("Failed to create name regex"),
+    }
+  }
+}
+
+/// An additional output generating a subset of the entry points, eg: `src/compute.rs` for the simulation kernels.
+#[derive(Clone, Debug)]
+pub struct WgslOutputPartition {
+  /// path of the single file output
+  pub output: PathBuf,
+  /// matched against the path of the entry shaders, eg: `shaders/compute/`
+  pub entry_regex: Regex,
+}
+impl<P: Into<PathBuf>> From<(P, Regex)> for WgslOutputPartition {
+  fn from((output, entry_regex): (P, Regex)) -> Self {
+    Self {
+      output: output.into(),
+      entry_regex,
+    }
+  }
+}
+impl<P: Into<PathBuf>> From<(P, &str)> for WgslOutputPartition {
+  fn from((output, entry_regex): (P, &str)) -> Self {
+    Self {
+      output: output.into(),
+      entry_regex: Regex::new(entry_regex).expect("Failed to create entry regex"),
+    }
+  }
+}
+
+/// A callback formatting the label of the generated `wgpu::ShaderModuleDescriptor`,
+/// given the generated module name and the path of the entry shader.
+pub type WgslShaderLabelFormatter = fn(&str, &Path) -> String;
+
+/// A callback naming the module of an entry whose module name collides with another entry's,
+/// given the colliding module name and the path of the entry shader.
+pub type WgslModuleNameRenamer = fn(&str, &Path) -> String;
+
+/// An enum representing how entries generating the same module name are disambiguated,
+/// eg: `fx/blur.wgsl` and `ui/blur.wgsl`.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslModuleNameCollision {
+  /// Fail the generation, listing the colliding entries.
+  #[default]
+  Error,
+
+  /// Prefix the colliding module names with their parent directory, eg: `fx_blur` and `ui_blur`.
+  PrefixParentDirectory,
+
+  /// Name each of the colliding modules with a callback. The bindings are then regenerated on
+  /// every build, since the output hash can't tell callbacks apart.
+  #[serde(skip)]
+  Rename(WgslModuleNameRenamer),
+}
+
+/// An enum representing the casing of generated identifiers, eg: module or struct names.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslNameCase {
+  /// Keep the names as written.
+  #[default]
+  Verbatim,
+
+  /// Convert the names to snake case, eg: `BlurPass` becomes `blur_pass`.
+  SnakeCase,
+
+  /// Convert the names to pascal case, eg: `light_data` becomes `LightData`.
+  PascalCase,
+}
+
+impl WgslNameCase {
+  /// Converts `name` to this casing.
+  pub(crate) fn apply(self, name: &str) -> String {
+    match self {
+      Self::Verbatim => name.to_string(),
+      Self::SnakeCase => name.to_snake_case(),
+      Self::PascalCase => name.to_pascal_case(),
+    }
+  }
+}
+
+/// Struct for overriding the shader module label of a specific entry point.
+#[derive(Clone, Debug)]
+pub struct OverrideShaderLabel {
+  /// name of the generated module for the entry point, eg: `triangle`
+  pub mod_name: String,
+  /// label to use for the `wgpu::ShaderModuleDescriptor`, eg: `Triangle Shader`
+  pub label: String,
+}
+
+impl From<(&str, &str)> for OverrideShaderLabel {
+  fn from((mod_name, label): (&str, &str)) -> Self {
+    Self {
+      mod_name: mod_name.to_owned(),
+      label: label.to_owned(),
+    }
+  }
+}
+
+/// Struct for gating the generated module of a specific entry point behind a cargo feature.
+#[derive(Clone, Debug)]
+pub struct EntryPointFeature {
+  /// name of the generated module for the entry point, eg: `bloom`
+  pub mod_name: String,
+  /// cargo feature of the crate including the bindings, eg: `postfx`
+  pub feature: String,
+}
+
+impl From<(&str, &str)> for EntryPointFeature {
+  fn from((mod_name, feature): (&str, &str)) -> Self {
+    Self {
+      mod_name: mod_name.to_owned(),
+      feature: feature.to_owned(),
+    }
+  }
+}
+
 /// An enum representing the visibility of the type generated in the output
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WgslTypeVisibility {
   /// All exported types set to `pub` visiblity
   #[default]
@@ -165,6 +503,54 @@ pub enum WgslTypeVisibility {
   RestrictedSuper,
 }
 
+/// An enum representing how shaders that will not run on WebGL2 are reported.
+///
+/// Shaders are checked against `wgpu::Limits::downlevel_webgl2_defaults()`,
+/// which rules out compute shaders, storage buffers and textures and any optional features.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslDownlevelCheck {
+  /// Don't check shaders for WebGL2 compatibility.
+  #[default]
+  Skip,
+
+  /// Emit a cargo warning listing the reasons for each incompatible shader.
+  Warn,
+
+  /// Fail the generation with the reasons for the first incompatible shader.
+  Deny,
+}
+
+/// An enum representing how the generated bindings are written to `output`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslOutputMode {
+  /// Write all the bindings to the single file given as `output`.
+  #[default]
+  SingleFile,
+
+  /// Treat `output` as a directory, writing a `mod.rs` with the shader registry
+  /// and one file for each top level module, eg: one per shader entry.
+  Directory,
+}
+
+/// An enum representing where the hash of the shaders, checked to skip regenerating
+/// up to date bindings, is recorded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WgslHashStorage {
+  /// A `// SourceHash:` comment in the header of the output, which is left out with `skip_header_comments`.
+  #[default]
+  HeaderComment,
+
+  /// A sidecar file next to the output, eg: `shader.rs.hash`, so formatting the output
+  /// or leaving out the header comments doesn't force regenerating the bindings.
+  SidecarFile,
+
+  /// Both the header comment and the sidecar file, checking against the sidecar file.
+  Both,
+}
+
 #[derive(Debug, Default, Builder)]
 #[builder(
   setter(into),
@@ -173,9 +559,38 @@ pub enum WgslTypeVisibility {
 )]
 pub struct WgslBindgenOption {
   /// A vector of entry points to be added. Each entry point is represented as a `String`.
-  #[builder(setter(each(name = "add_entry_point", into)))]
+  #[builder(default, setter(each(name = "add_entry_point", into)))]
   pub entry_points: Vec<String>,
 
+  /// Module names of the entry points added with `add_entry_point_named`, keyed by their path.
+  /// Other entry points are named after their file.
+  #[builder(default, setter(custom))]
+  pub entry_point_module_names: FastIndexMap<String, String>,
+
+  /// How entries generating the same module name are disambiguated.
+  #[builder(default)]
+  pub module_name_collision: WgslModuleNameCollision,
+
+  /// The casing of the module names derived from the entry file names, eg: `snake_case` to
+  /// generate `blur_pass` for `BlurPass.wgsl`. Names given in `entry_point_module_names` are
+  /// kept as is. Defaults to `WgslNameCase::Verbatim`.
+  #[builder(default)]
+  pub module_name_case: WgslNameCase,
+
+  /// Glob patterns of entry points to be added, eg: `assets/shaders/**/*.wgsl`.
+  /// These are expanded every time the shaders are scanned, so new files are picked up automatically.
+  #[builder(default, setter(each(name = "add_entry_points_glob", into)))]
+  pub entry_point_globs: Vec<String>,
+
+  /// Entry points given as WGSL strings, which are generated as if they were files named after the entry
+  /// in the workspace root. They can't be used with `UseComposerWithPath` or `UseRelativePath`, which load files at runtime.
+  #[builder(default, setter(custom))]
+  pub entry_point_sources: Vec<WgslInlineSource>,
+
+  /// Composable modules given as WGSL strings, which entry points can import by their module name.
+  #[builder(default, setter(custom))]
+  pub composable_sources: Vec<WgslInlineSource>,
+
   /// The root prefix/namespace if any applied to all shaders given as the entrypoints.
   #[builder(default, setter(strip_option, into))]
   pub module_import_root: Option<String>,
@@ -197,6 +612,60 @@ pub struct WgslBindgenOption {
   #[builder(default = "false")]
   pub skip_hash_check: bool,
 
+  /// A directory caching the composed naga module of each entry, keyed by the contents of the entry
+  /// and its imports, so clean builds skip recomposing unchanged shaders. Defaults to `None`, composing every entry.
+  #[builder(default, setter(strip_option, into))]
+  pub module_cache_dir: Option<PathBuf>,
+
+  /// Where the hash of the shaders is recorded. Defaults to `WgslHashStorage::HeaderComment`.
+  #[builder(default)]
+  pub hash_storage: WgslHashStorage,
+
+  /// A file the import graph of the shaders is written to alongside the output, as a Mermaid
+  /// flowchart when its extension is `mmd` and as a GraphViz digraph otherwise. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub dependency_graph_output: Option<PathBuf>,
+
+  /// A Markdown file listing the bind groups, bindings, push constants and vertex layouts of
+  /// each shader entry, written alongside the output. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub binding_reference_output: Option<PathBuf>,
+
+  /// A JSON file describing the struct layouts, bind groups, entry points and vertex layouts of
+  /// each shader entry, written alongside the output for tools that don't parse Rust, eg: engine
+  /// editors. Its content is the serialized `WgslBindgenReport`. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub reflection_output: Option<PathBuf>,
+
+  /// A C header declaring the structs of the uniform, storage and push constant buffers of the
+  /// shaders, with explicit padding and `static_assert`s of their sizes and offsets, for C and
+  /// C++ code filling these buffers. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub c_header_output: Option<PathBuf>,
+
+  /// A TypeScript file with the entry point names, bind group and binding indices, and buffer
+  /// struct layouts of each shader entry, the latter with helpers creating typed arrays over
+  /// their fields, for the JavaScript side of a WebGPU application. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub typescript_output: Option<PathBuf>,
+
+  /// Generates helpers for Bevy 0.15 gated behind this cargo feature of the crate including the
+  /// bindings, eg: `bevy`. Each entry gets a `bevy_helpers` module with its shader handle, the
+  /// layout entries of its bind groups and the `VertexState` and `FragmentState` of its entry
+  /// points, and `ShaderEntry` gets `shader_handle`. The bindings must use the `wgpu` of Bevy.
+  /// Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub bevy_feature: Option<String>,
+
+  /// Generates an async `fetch_sources(base_url)` in each entry module gated behind this cargo
+  /// feature of the crate including the bindings on `wasm32`, eg: `web`. It fetches the shader
+  /// files the `*_from_reader` functions of `UseComposerWithPath` and `UseRelativePath` read,
+  /// with the `fetch` of the browser window. The feature has to enable dependencies on
+  /// `wasm-bindgen`, `wasm-bindgen-futures` and `web-sys` with its `Window` and `Response`
+  /// features. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub wasm_fetch_feature: Option<String>,
+
   /// Derive [encase::ShaderType](https://docs.rs/encase/latest/encase/trait.ShaderType.html#)
   /// for user defined WGSL structs when `WgslTypeSerializeStrategy::Encase`.
   /// else derive bytemuck
@@ -209,6 +678,16 @@ pub struct WgslBindgenOption {
   #[builder(default = "false")]
   pub derive_serde: bool,
 
+  /// Derive `serde::Serialize` and `serde::Deserialize` for the generated `ShaderEntry` enum when `true`.
+  /// Each variant is (de)serialized as the module name of its entry.
+  #[builder(default = "false")]
+  pub shader_entry_derive_serde: bool,
+
+  /// Derive strum's `EnumIter`, `EnumCount`, `IntoStaticStr` and `AsRefStr` for the generated
+  /// `ShaderEntry` enum when `true`. The string conversions use the module name of each entry.
+  #[builder(default = "false")]
+  pub shader_entry_derive_strum: bool,
+
   /// The shader source type generated bitflags. Defaults to `WgslShaderSourceType::UseSingleString`.
   #[builder(default)]
   pub shader_source_type: BitFlags<WgslShaderSourceType>,
@@ -217,6 +696,25 @@ pub struct WgslBindgenOption {
   #[builder(default, setter(strip_option, into))]
   pub output: Option<PathBuf>,
 
+  /// Whether `output` is a single file or a directory of files, one per module.
+  /// Defaults to `WgslOutputMode::SingleFile`.
+  #[builder(default)]
+  pub output_mode: WgslOutputMode,
+
+  /// Whether to write the single file output so it can be pulled in with `include!`, which doesn't accept
+  /// the inner attributes at the top of the bindings. Defaults to `false`, and is set by `output_in_out_dir`.
+  #[builder(default = "false")]
+  pub includable_output: bool,
+
+  /// Additional single file outputs, each generating the entry points matched by its regex instead of `output`.
+  /// Types shared with other entries are only generated in `output`, and imported from `output_module_path`.
+  #[builder(default, setter(each(name = "add_output_partition", into)))]
+  pub output_partitions: Vec<WgslOutputPartition>,
+
+  /// The path `output` is declared at, eg: `crate::shader_bindings`, which is required with `output_partitions`.
+  #[builder(default, setter(strip_option, into))]
+  pub output_module_path: Option<String>,
+
   /// The additional set of directories to scan for source files.
   #[builder(default, setter(into, each(name = "additional_scan_dir", into)))]
   pub additional_scan_dirs: Vec<AdditionalScanDirectory>,
@@ -225,6 +723,81 @@ pub struct WgslBindgenOption {
   #[builder(default, setter(strip_option))]
   pub ir_capabilities: Option<WgslShaderIrCapabilities>,
 
+  /// The naga capabilities never enabled for the shaders, even when the sources use them or
+  /// `ir_capabilities` lists them, eg: to keep push constants out of shaders targeting the web.
+  /// The capabilities the sources use are enabled otherwise. Defaults to none.
+  #[builder(default = "WgslShaderIrCapabilities::empty()")]
+  pub deny_ir_capabilities: WgslShaderIrCapabilities,
+
+  /// A vector of labels to use for the shader modules of matching entry points.
+  /// These take precedence over `shader_label_formatter`.
+  #[builder(default, setter(each(name = "add_override_shader_label", into)))]
+  pub override_shader_label: Vec<OverrideShaderLabel>,
+
+  /// Cargo features gating the generated modules of matching entry points, along with their
+  /// `ShaderEntry` variants, so their shaders aren't compiled nor embedded without the feature.
+  #[builder(default, setter(each(name = "add_entry_point_feature", into)))]
+  pub entry_point_features: Vec<EntryPointFeature>,
+
+  /// A callback to format the shader module label of each entry point, regenerating the bindings
+  /// on every build when set, since the output hash can't tell callbacks apart.
+  /// Defaults to `None`, which labels the shader modules with the entry file name.
+  #[builder(default, setter(custom))]
+  pub shader_label_formatter: Option<WgslShaderLabelFormatter>,
+
+  /// Whether to generate an async `try_` variant of each shader module constructor, which captures
+  /// validation errors in an error scope and returns them instead of raising an uncaptured error.
+  /// Defaults to `false`.
+  #[builder(default = "false")]
+  pub generate_try_create_shader_module: bool,
+
+  /// Whether to generate a `GpuDevice` trait implemented for `wgpu::Device`, and make the
+  /// functions creating bind group layouts, pipeline layouts and embedded shader modules generic
+  /// over it, so tests can assert on the descriptors with a mock device instead of a GPU.
+  /// Defaults to `false`.
+  #[builder(default = "false")]
+  pub generic_device: bool,
+
+  /// Whether to check each shader against the WebGL2 downlevel defaults, and how to report the ones that fail.
+  /// Defaults to `WgslDownlevelCheck::Skip`.
+  #[builder(default)]
+  pub downlevel_check: WgslDownlevelCheck,
+
+  /// The `max_push_constant_size` limit of the devices the shaders target, failing the generation
+  /// for a shader whose push constants are larger instead of panicking when creating its pipeline
+  /// layout. Defaults to `None`, only checking the push constants against the rules of wgpu.
+  #[builder(default, setter(strip_option, into = false))]
+  pub max_push_constant_size: Option<u32>,
+
+  /// The `max_compute_workgroup_storage_size` limit of the devices the shaders target, failing the
+  /// generation for a compute entry point whose workgroup variables take more storage. Defaults to
+  /// `None`, not checking the workgroup storage.
+  #[builder(default, setter(strip_option, into = false))]
+  pub max_compute_workgroup_storage_size: Option<u32>,
+
+  /// The `max_uniform_buffer_binding_size` limit of the devices the shaders target, warning about
+  /// the uniform bindings larger than it, or failing the generation when
+  /// `WgslDiagnosticKind::UniformBindingTooLarge` is denied. Defaults to `None`, not checking them.
+  #[builder(default, setter(strip_option, into = false))]
+  pub max_uniform_buffer_binding_size: Option<u32>,
+
+  /// Whether to emit a cargo warning for each shader hygiene issue found while validating the shaders,
+  /// such as unused bindings. Unreachable code isn't among them, since naga already fails the
+  /// validation on statements after a `return`. Defaults to `false`.
+  #[builder(default = "false")]
+  pub emit_validation_warnings: bool,
+
+  /// Whether to leave the bindings none of the entry points use out of the generated layouts and
+  /// bind groups, since naga drops them from the interface of each stage anyway. The warnings of
+  /// the stripped bindings note it. Defaults to `false`.
+  #[builder(default = "false")]
+  pub strip_unused_bindings: bool,
+
+  /// Kinds of diagnostics failing the generation instead of only being reported, eg: to keep arrays
+  /// generated for types missing from the type map out of a project. Defaults to none.
+  #[builder(default, setter(each(name = "deny_diagnostic")))]
+  pub deny_diagnostics: Vec<WgslDiagnosticKind>,
+
   /// Whether to generate short constructor similar to enums constructors instead of `new`, if number of parameters are below the specified threshold
   /// Defaults to `None`
   #[builder(default, setter(strip_option, into))]
@@ -234,6 +807,17 @@ pub struct WgslBindgenOption {
   #[builder(default)]
   pub type_visibility: WgslTypeVisibility,
 
+  /// The casing of the generated structs, eg: `pascal_case` to generate `LightData` for the WGSL
+  /// struct `light_data`. Overrides and exclusions match the renamed structs, and a struct is kept
+  /// verbatim when its new name is taken. Defaults to `WgslNameCase::Verbatim`.
+  #[builder(default)]
+  pub struct_name_case: WgslNameCase,
+
+  /// The prefix of the constants naming the entry points, eg: `ENTRY_` for `ENTRY_FS_MAIN`.
+  /// Defaults to `ENTRY_`.
+  #[builder(default, setter(strip_option, into))]
+  pub entry_point_const_prefix: Option<String>,
+
   /// A mapping operation for WGSL built-in types. This is used to map WGSL built-in types to their corresponding representations.
   #[builder(setter(custom))]
   pub type_map: WgslTypeMap,
@@ -253,16 +837,106 @@ pub struct WgslBindgenOption {
   #[builder(default, setter(into))]
   pub override_struct_alignment: Vec<OverrideStructAlignment>,
 
+  /// A vector of `OverrideVertexFormat` to override the vertex format of fields in matching vertex input structs.
+  /// Both the vertex attributes and the Rust field types follow the format, eg: `[u8; 4]` for `Unorm8x4`.
+  #[builder(default, setter(into))]
+  pub override_vertex_format: Vec<OverrideVertexFormat>,
+
+  /// A vector of vertex input structs read from your own Rust vertex structs, eg: a vertex type shared with an asset pipeline.
+  /// The matching struct isn't generated, and its `VERTEX_ATTRIBUTES` and `vertex_buffer_layout` use the offsets of the
+  /// fields with the same names in your struct, which may also have fields the shader doesn't read.
+  #[builder(default, setter(each(name = "add_override_vertex_struct", into)))]
+  pub override_vertex_struct: Vec<OverrideStruct>,
+
+  /// A vector of `OverrideVertexStride` to override the `array_stride` of the `vertex_buffer_layout` of matching vertex
+  /// input structs, which is the size of the struct otherwise. The attributes must still be read within the stride.
+  #[builder(default, setter(each(name = "add_override_vertex_stride", into)))]
+  pub override_vertex_stride: Vec<OverrideVertexStride>,
+
+  /// The regular expressions of the vertex input structs stepping per instance, matching their fully qualified names,
+  /// eg: `Instance$` for the structs named with an `Instance` suffix. Structs marked with a `// @instance` comment are added too.
+  /// The `*_entry` functions set their buffers to `wgpu::VertexStepMode::Instance` instead of taking a step mode for them.
+  #[builder(default, setter(each(name = "add_instance_vertex_input", into)))]
+  pub instance_vertex_inputs: Vec<Regex>,
+
+  /// The regular expressions of the structs laid out as indirect draw commands, matching their fully qualified names,
+  /// eg: `DrawArgs$`. Those of the matching structs a shader can write to storage buffers convert from and to
+  /// `wgpu::util::DrawIndirectArgs` or `DrawIndexedIndirectArgs`, and their bindings get `create_{binding}_indirect_buffer`
+  /// functions. Matching structs laid out as neither command are left as is.
+  #[builder(default, setter(each(name = "add_indirect_args_struct", into)))]
+  pub indirect_args_structs: Vec<Regex>,
+
+  /// A vector of `SamplerPreset` generating `create_{binding}_sampler(device)` functions for the matching sampler bindings.
+  /// Sampler bindings with a `// @sampler(linear, repeat)` comment are added too, taking the filter, address mode and
+  /// comparison by their WebGPU names.
+  #[builder(default, setter(each(name = "add_sampler_preset", into)))]
+  pub sampler_presets: Vec<SamplerPreset>,
+
+  /// Remaps the `@location` of vertex inputs, in the composed shader and in the generated vertex attributes alike.
+  /// Only the shader source types embedding the composed shader, `UseEmbed` and `UseNagaIr`, can follow the remaps.
+  #[builder(default, setter(each(name = "add_remap_vertex_location", into)))]
+  pub remap_vertex_location: Vec<RemapVertexLocation>,
+
+  /// Pulls the vertex input structs of the matching entries from read-only storage buffers indexed by
+  /// `@builtin(vertex_index)`, eg: for GPU-driven renderers. The buffers are added to the bind groups
+  /// instead of generating vertex buffer layouts, which requires `UseEmbed` or `UseNagaIr` like `remap_vertex_location`.
+  #[builder(default, setter(each(name = "add_vertex_pulling", into)))]
+  pub vertex_pulling: Vec<VertexPulling>,
+
   /// The regular expression of the padding fields used in the shader struct types.
   /// These fields will be omitted in the *Init structs generated, and will automatically be assigned the default values.
   #[builder(default, setter(each(name = "add_custom_padding_field_regexp", into)))]
   pub custom_padding_field_regexps: Vec<Regex>,
 
+  /// Items to skip generating, by kind and fully qualified name.
+  #[builder(default, setter(each(name = "add_exclude_item", into)))]
+  pub exclude_items: Vec<ExcludeItem>,
+
+  /// Whether to generate code for `no_std` crates using `alloc`, referring to `core` and `alloc` instead of `std`.
+  /// Only `WgslShaderSourceType::UseEmbed` can be used, and pipeline-overridable constants can't be passed to
+  /// the generated entries, since wgpu takes them as a `std::collections::HashMap`.
+  #[builder(default = "false")]
+  pub no_std: bool,
+
+  /// Generates `write_{binding}_staged` functions for uniform and storage bindings gated behind
+  /// this cargo feature of the crate including the bindings, eg: `staging-belt`. They write
+  /// through a `wgpu::util::StagingBelt` instead of `wgpu::Queue::write_buffer`, eg: for engines
+  /// batching their uploads per frame. Defaults to `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub staging_belt_feature: Option<String>,
+
+  /// Whether to generate an `NBuffered<T, N>` wrapper in the `_support` module, owning `N` uniform
+  /// buffers of a `T` and a bind group per buffer, so each of the frames in flight writes its own.
+  #[builder(default = "false")]
+  pub frames_in_flight_buffers: bool,
+
+  /// Whether to generate a `UniformArena<T>` in the `_support` module, sub-allocating many
+  /// values of a `T` from one uniform buffer and bound with the dynamic offsets of their handles.
+  #[builder(default = "false")]
+  pub uniform_arenas: bool,
+
+  /// Whether to generate a `Shadowed<T>` in the `_support` module, keeping a CPU copy of the
+  /// value of a uniform buffer and only writing it to the buffer when modified.
+  #[builder(default = "false")]
+  pub shadowed_uniforms: bool,
+
+  /// Whether to generate a `WgpuBindGroup{N}Bundle` for each bind group with uniform bindings of
+  /// structs, owning their buffers along with the bind group, and updated from a value of each.
+  #[builder(default = "false")]
+  pub bind_group_bundles: bool,
+
+  /// Whether to generate a `PingPong` in the `_support` module, and `create_{binding}_ping_pong`
+  /// for each read only storage binding of a compute shader paired with a writable one of the
+  /// same type in its bind group, swapping the two buffers between dispatches.
+  #[builder(default = "false")]
+  pub ping_pong_buffers: bool,
+
   /// Whether to always have the init struct generated in the out. This is only applicable when using bytemuck mode.
   #[builder(default = "false")]
   pub always_generate_init_struct: bool,
 
   /// This field can be used to provide a custom generator for extra bindings that are not covered by the default generator.
+  /// Setting it regenerates the bindings on every build, since the output hash can't tell generators apart.
   #[builder(default, setter(custom))]
   pub extra_binding_generator: Option<BindingGenerator>,
 
@@ -271,6 +945,347 @@ pub struct WgslBindgenOption {
   pub wgpu_binding_generator: BindingGenerator,
 }
 
+macro_rules! hash_options {
+  ($hasher:expr, [$($field:ident),* $(,)?]) => {
+    $($hasher.update(format!("{}: {:?}\n", stringify!($field), $field).as_bytes());)*
+  };
+}
+
+impl WgslBindgenOption {
+  /// Hashes the options affecting the generated code and the files written alongside it,
+  /// grouped below by what they affect. The options only steering the build are left out, so
+  /// changing them doesn't regenerate the bindings:
+  /// * `emit_rerun_if_change` and `emit_validation_warnings`, only printing cargo directives.
+  /// * `skip_hash_check`, deciding whether this hash is checked at all.
+  /// * `module_cache_dir`, caching the composed modules without changing them.
+  ///
+  /// Callbacks are hashed by whether they are set, since their address changes between builds,
+  /// so `has_unhashed_callbacks` forces the regeneration when they are.
+  pub(crate) fn hash_output_options(&self, hasher: &mut blake3::Hasher) {
+    // Destructured exhaustively, so a new option has to be added to a group or left out here.
+    let Self {
+      emit_rerun_if_change: _,
+      emit_validation_warnings: _,
+      skip_hash_check: _,
+      module_cache_dir: _,
+      // The generators are callbacks without a stable identity to hash.
+      wgpu_binding_generator: _,
+      entry_points,
+      entry_point_globs,
+      entry_point_sources,
+      composable_sources,
+      additional_scan_dirs,
+      module_import_root,
+      workspace_root,
+      entry_point_module_names,
+      module_name_collision,
+      module_name_case,
+      struct_name_case,
+      entry_point_const_prefix,
+      override_shader_label,
+      shader_label_formatter,
+      output,
+      output_mode,
+      includable_output,
+      output_partitions,
+      output_module_path,
+      skip_header_comments,
+      hash_storage,
+      dependency_graph_output,
+      binding_reference_output,
+      reflection_output,
+      c_header_output,
+      typescript_output,
+      shader_source_type,
+      ir_capabilities,
+      deny_ir_capabilities,
+      remap_vertex_location,
+      vertex_pulling,
+      strip_unused_bindings,
+      entry_point_features,
+      downlevel_check,
+      max_push_constant_size,
+      max_compute_workgroup_storage_size,
+      max_uniform_buffer_binding_size,
+      deny_diagnostics,
+      serialization_strategy,
+      derive_serde,
+      shader_entry_derive_serde,
+      shader_entry_derive_strum,
+      bevy_feature,
+      wasm_fetch_feature,
+      generate_try_create_shader_module,
+      generic_device,
+      short_constructor,
+      type_visibility,
+      type_map,
+      override_struct,
+      override_struct_field_type,
+      override_struct_alignment,
+      override_vertex_format,
+      override_vertex_struct,
+      override_vertex_stride,
+      instance_vertex_inputs,
+      indirect_args_structs,
+      sampler_presets,
+      custom_padding_field_regexps,
+      exclude_items,
+      no_std,
+      staging_belt_feature,
+      frames_in_flight_buffers,
+      uniform_arenas,
+      shadowed_uniforms,
+      bind_group_bundles,
+      ping_pong_buffers,
+      always_generate_init_struct,
+      extra_binding_generator,
+    } = self;
+
+    let module_name_collision = std::mem::discriminant(module_name_collision);
+    let shader_label_formatter = shader_label_formatter.is_some();
+    let extra_binding_generator = extra_binding_generator.is_some();
+
+    // The shaders generated for and where they are found.
+    hash_options!(
+      hasher,
+      [
+        entry_points,
+        entry_point_globs,
+        entry_point_sources,
+        composable_sources,
+        additional_scan_dirs,
+        module_import_root,
+        workspace_root,
+      ]
+    );
+
+    // The names of the generated modules, items and shader labels.
+    hash_options!(
+      hasher,
+      [
+        entry_point_module_names,
+        module_name_collision,
+        module_name_case,
+        struct_name_case,
+        entry_point_const_prefix,
+        override_shader_label,
+        shader_label_formatter,
+      ]
+    );
+
+    // The files written and their layout.
+    hash_options!(
+      hasher,
+      [
+        output,
+        output_mode,
+        includable_output,
+        output_partitions,
+        output_module_path,
+        skip_header_comments,
+        hash_storage,
+        dependency_graph_output,
+        binding_reference_output,
+        reflection_output,
+        c_header_output,
+        typescript_output,
+      ]
+    );
+
+    // How the shaders are composed, transformed and loaded.
+    hash_options!(
+      hasher,
+      [
+        shader_source_type,
+        ir_capabilities,
+        deny_ir_capabilities,
+        remap_vertex_location,
+        vertex_pulling,
+        strip_unused_bindings,
+        entry_point_features,
+      ]
+    );
+
+    // The checks failing the generation, so changing them checks the shaders again.
+    hash_options!(
+      hasher,
+      [
+        downlevel_check,
+        max_push_constant_size,
+        max_compute_workgroup_storage_size,
+        max_uniform_buffer_binding_size,
+        deny_diagnostics,
+      ]
+    );
+
+    // The generated Rust items.
+    hash_options!(
+      hasher,
+      [
+        serialization_strategy,
+        derive_serde,
+        shader_entry_derive_serde,
+        shader_entry_derive_strum,
+        bevy_feature,
+        wasm_fetch_feature,
+        generate_try_create_shader_module,
+        generic_device,
+        short_constructor,
+        type_visibility,
+        type_map,
+        override_struct,
+        override_struct_field_type,
+        override_struct_alignment,
+        override_vertex_format,
+        override_vertex_struct,
+        override_vertex_stride,
+        instance_vertex_inputs,
+        indirect_args_structs,
+        sampler_presets,
+        custom_padding_field_regexps,
+        exclude_items,
+        no_std,
+        staging_belt_feature,
+        frames_in_flight_buffers,
+        uniform_arenas,
+        shadowed_uniforms,
+        bind_group_bundles,
+        ping_pong_buffers,
+        always_generate_init_struct,
+        extra_binding_generator,
+      ]
+    );
+  }
+
+  /// Whether a callback is set whose changes the output hash misses, so the bindings have to
+  /// be regenerated on every build instead of being left stale.
+  pub(crate) fn has_unhashed_callbacks(&self) -> bool {
+    matches!(self.module_name_collision, WgslModuleNameCollision::Rename(_))
+      || self.shader_label_formatter.is_some()
+      || self.extra_binding_generator.is_some()
+  }
+
+  /// The crate `core` items are referenced from in the generated code, eg: `core::mem::size_of`.
+  pub(crate) fn core_crate(&self) -> TokenStream {
+    if self.no_std {
+      quote::quote!(core)
+    } else {
+      quote::quote!(std)
+    }
+  }
+
+  /// The crate `alloc` items are referenced from in the generated code, eg: `alloc::borrow::Cow`.
+  pub(crate) fn alloc_crate(&self) -> TokenStream {
+    if self.no_std {
+      quote::quote!(alloc)
+    } else {
+      quote::quote!(std)
+    }
+  }
+
+  /// The constant naming the entry point `name`, eg: `ENTRY_FS_MAIN` for `fs_main`.
+  pub(crate) fn entry_point_const(&self, name: &str) -> String {
+    let prefix = self.entry_point_const_prefix.as_deref().unwrap_or("ENTRY_");
+    format!("{prefix}{}", name.to_uppercase())
+  }
+
+  /// The feature gating the module `mod_name` of an entry in `entry_point_features`.
+  pub(crate) fn entry_feature(&self, mod_name: &str) -> Option<&str> {
+    self
+      .entry_point_features
+      .iter()
+      .find(|entry| entry.mod_name == mod_name)
+      .map(|entry| entry.feature.as_str())
+  }
+
+  /// The `#[cfg(feature = "...")]` attribute gating the module `mod_name` of an entry,
+  /// or nothing for entries without a feature.
+  pub(crate) fn entry_cfg(&self, mod_name: &str) -> TokenStream {
+    self
+      .entry_feature(mod_name)
+      .map(|feature| quote::quote!(#[cfg(feature = #feature)]))
+      .unwrap_or_default()
+  }
+
+  /// The vertex format of the field `member_name` of the struct `fully_qualified_name` in
+  /// `override_vertex_format`, if any.
+  pub(crate) fn vertex_format_override(
+    &self,
+    fully_qualified_name: &str,
+    member_name: &str,
+  ) -> Option<WgslVertexFormat> {
+    self.override_vertex_format.iter().find_map(|o| {
+      let struct_matches = o.struct_regex.is_match(fully_qualified_name);
+      let field_matches = o.field_regex.is_match(member_name);
+      (struct_matches && field_matches).then_some(o.format)
+    })
+  }
+
+  /// The preset of the sampler binding `fully_qualified_name` in `sampler_presets`, if any.
+  pub(crate) fn sampler_preset(
+    &self,
+    fully_qualified_name: &str,
+  ) -> Option<&SamplerPreset> {
+    self
+      .sampler_presets
+      .iter()
+      .find(|preset| preset.binding_regex.is_match(fully_qualified_name))
+  }
+
+  /// The array stride of the vertex input struct `fully_qualified_name` in
+  /// `override_vertex_stride`, if any.
+  pub(crate) fn vertex_stride_override(&self, fully_qualified_name: &str) -> Option<u64> {
+    self
+      .override_vertex_stride
+      .iter()
+      .find(|o| o.struct_regex.is_match(fully_qualified_name))
+      .map(|o| o.stride)
+  }
+
+  /// Whether the vertex input struct `fully_qualified_name` steps per instance in
+  /// `instance_vertex_inputs`.
+  pub(crate) fn is_instance_vertex_input(&self, fully_qualified_name: &str) -> bool {
+    self
+      .instance_vertex_inputs
+      .iter()
+      .any(|regex| regex.is_match(fully_qualified_name))
+  }
+
+  /// Whether the struct `fully_qualified_name` is laid out as an indirect command in
+  /// `indirect_args_structs`.
+  pub(crate) fn is_indirect_args_struct(&self, fully_qualified_name: &str) -> bool {
+    self
+      .indirect_args_structs
+      .iter()
+      .any(|regex| regex.is_match(fully_qualified_name))
+  }
+
+  /// The Rust vertex struct the vertex input struct `fully_qualified_name` is read from in
+  /// `override_vertex_struct`, if any.
+  pub(crate) fn vertex_struct_override(
+    &self,
+    fully_qualified_name: &str,
+  ) -> Option<&TokenStream> {
+    self
+      .override_vertex_struct
+      .iter()
+      .find(|o| o.from == fully_qualified_name)
+      .map(|o| &o.to)
+  }
+
+  /// Whether an item of `kind` named `fully_qualified_name` is excluded with `exclude_items`.
+  pub(crate) fn is_excluded(
+    &self,
+    kind: WgslItemKind,
+    fully_qualified_name: &str,
+  ) -> bool {
+    self
+      .exclude_items
+      .iter()
+      .any(|e| e.kind == kind && e.name_regex.is_match(fully_qualified_name))
+  }
+}
+
 impl WgslBindgenOptionBuilder {
   pub fn build(&mut self) -> Result<WGSLBindgen, WgslBindgenError> {
     self.merge_struct_type_overrides();
@@ -294,6 +1309,83 @@ impl WgslBindgenOptionBuilder {
     self
   }
 
+  /// Writes the bindings to `file_name` in cargo's `OUT_DIR`, to be pulled in with
+  /// [`include_generated!`](crate::include_generated), so the generated code doesn't need to be committed.
+  /// This can only be used from a build script.
+  pub fn output_in_out_dir(&mut self, file_name: impl AsRef<Path>) -> &mut Self {
+    let out_dir = std::env::var_os("OUT_DIR")
+      .expect("`OUT_DIR` is only set when running from a build script");
+
+    self.output = Some(Some(PathBuf::from(out_dir).join(file_name)));
+    self.includable_output = Some(true);
+    self
+  }
+
+  /// Adds an entry point generated as the module `mod_name` rather than one named after its file,
+  /// eg: `add_entry_point_named("fx/blur_h.wgsl", "blur_horizontal")`.
+  pub fn add_entry_point_named(
+    &mut self,
+    path: impl Into<String>,
+    mod_name: impl Into<String>,
+  ) -> &mut Self {
+    let path = path.into();
+    self
+      .entry_point_module_names
+      .get_or_insert_with(FastIndexMap::default)
+      .insert(path.clone(), mod_name.into());
+    self.add_entry_point(path)
+  }
+
+  /// Adds an entry point from a WGSL string rather than a file, generated as the module `name`.
+  pub fn add_entry_point_source(
+    &mut self,
+    name: impl Into<String>,
+    source: impl Into<String>,
+  ) -> &mut Self {
+    self
+      .entry_point_sources
+      .get_or_insert_with(Vec::new)
+      .push(WgslInlineSource {
+        name: name.into(),
+        source: source.into(),
+      });
+    self
+  }
+
+  /// Adds a composable module from a WGSL string, which can be imported as `module_name`,
+  /// eg: `#import procedural::noise`.
+  pub fn add_composable_source(
+    &mut self,
+    module_name: impl Into<String>,
+    source: impl Into<String>,
+  ) -> &mut Self {
+    self
+      .composable_sources
+      .get_or_insert_with(Vec::new)
+      .push(WgslInlineSource {
+        name: module_name.into(),
+        source: source.into(),
+      });
+    self
+  }
+
+  /// Registers `directory` as the import root of modules under `prefix`, eg: with the `game` prefix,
+  /// `#import game::materials` resolves to `materials.wgsl` in the directory.
+  /// This can be called several times to import from multiple directory trees.
+  pub fn add_import_root(
+    &mut self,
+    prefix: impl Into<String>,
+    directory: impl Into<String>,
+  ) -> &mut Self {
+    self.additional_scan_dirs.get_or_insert_with(Vec::new).push(
+      AdditionalScanDirectory {
+        module_import_root: Some(prefix.into()),
+        directory: directory.into(),
+      },
+    );
+    self
+  }
+
   fn merge_struct_type_overrides(&mut self) {
     let struct_mappings = self
       .override_struct
@@ -318,4 +1410,44 @@ impl WgslBindgenOptionBuilder {
     self.extra_binding_generator = Some(generator);
     self
   }
+
+  pub fn shader_label_formatter(
+    &mut self,
+    formatter: WgslShaderLabelFormatter,
+  ) -> &mut Self {
+    self.shader_label_formatter = Some(Some(formatter));
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::{assert_eq, assert_ne};
+
+  use super::*;
+
+  fn output_hash(options: &WgslBindgenOption) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new();
+    options.hash_output_options(&mut hasher);
+    hasher.finalize()
+  }
+
+  #[test]
+  fn regenerates_with_callbacks() {
+    let with_callbacks = |formatter: WgslShaderLabelFormatter,
+                          renamer: WgslModuleNameRenamer| {
+      WgslBindgenOption {
+        shader_label_formatter: Some(formatter),
+        module_name_collision: WgslModuleNameCollision::Rename(renamer),
+        ..Default::default()
+      }
+    };
+
+    let options = with_callbacks(|name, _| name.to_string(), |name, _| name.to_string());
+    let other_options =
+      with_callbacks(|name, _| name.to_uppercase(), |name, _| format!("{name}_2"));
+    assert_eq!(output_hash(&options), output_hash(&other_options));
+    assert!(options.has_unhashed_callbacks());
+    assert!(!WgslBindgenOption::default().has_unhashed_callbacks());
+  }
 }
@@ -0,0 +1,268 @@
+use serde::Serialize;
+
+use super::diagnostics::entry_diagnostics;
+use crate::generate::bind_group::generated_bind_group_data;
+use crate::quote_gen::{RustItemPath, RustItemType};
+use crate::{
+  structs, wgsl, BindResourceType, CreateModuleError, FxIndexSet, WgslBindgenOption,
+  WgslDiagnostic, WgslEntryResult,
+};
+
+/// A summary of the generated bindings, eg: for asset pipelines indexing the shaders
+/// without parsing the generated code.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WgslBindgenReport {
+  /// One report for each shader entry, in the order of `ShaderEntry`.
+  pub entries: Vec<WgslEntryReport>,
+}
+
+impl WgslBindgenReport {
+  /// The warnings of all the entries, eg: to print them or fail on some of them.
+  pub fn diagnostics(&self) -> impl Iterator<Item = &WgslDiagnostic> {
+    self.entries.iter().flat_map(|entry| &entry.warnings)
+  }
+}
+
+/// What was generated for a single shader entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslEntryReport {
+  /// The name of the generated module, eg: `triangle`.
+  pub mod_name: String,
+  /// The path of the entry shader.
+  pub source_path: String,
+  /// The fully qualified names of the generated structs, eg: `triangle::VertexInput`.
+  pub structs: Vec<String>,
+  /// The bind groups, ordered by their group index.
+  pub bind_groups: Vec<WgslBindGroupReport>,
+  /// The entry point functions of the shader.
+  pub entry_points: Vec<WgslEntryPointReport>,
+  /// The fully qualified names of the structs used as vertex inputs.
+  pub vertex_inputs: Vec<String>,
+  /// The WGSL memory layout of each struct in `structs`.
+  pub struct_layouts: Vec<WgslStructLayoutReport>,
+  /// The attributes of each struct in `vertex_inputs`.
+  pub vertex_layouts: Vec<WgslVertexLayoutReport>,
+  /// Non-fatal issues found in the shader, such as unused bindings or inserted padding, and
+  /// the reasons it will not run on WebGL2 when `downlevel_check` is enabled.
+  pub warnings: Vec<WgslDiagnostic>,
+}
+
+/// A bind group of a shader entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslBindGroupReport {
+  /// The `@group` index.
+  pub group: u32,
+  /// The fully qualified name of the generated bind group, eg: `triangle::WgpuBindGroup0`.
+  pub name: String,
+  /// The bindings, ordered as declared in the shader.
+  pub bindings: Vec<WgslBindingReport>,
+}
+
+/// A binding of a bind group.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslBindingReport {
+  /// The `@binding` index.
+  pub binding: u32,
+  /// The name of the binding, eg: `color_texture`.
+  pub name: String,
+  /// The kind of resource bound.
+  pub resource: BindResourceType,
+}
+
+/// An entry point function of a shader entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslEntryPointReport {
+  /// The name of the function, eg: `vs_main`.
+  pub name: String,
+  /// The shader stage, one of `vertex`, `fragment` or `compute`.
+  pub stage: String,
+}
+
+/// The WGSL memory layout of a generated struct.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslStructLayoutReport {
+  /// The fully qualified name of the struct, eg: `triangle::Uniforms`.
+  pub name: String,
+  /// The size in bytes.
+  pub size: u32,
+  /// The alignment in bytes.
+  pub alignment: u32,
+  /// The fields, ordered as declared in the shader.
+  pub fields: Vec<WgslFieldLayoutReport>,
+}
+
+/// A field of a struct layout.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslFieldLayoutReport {
+  /// The name of the field.
+  pub name: String,
+  /// The type as written in WGSL, eg: `vec4<f32>`.
+  pub wgsl_type: String,
+  /// The offset in bytes from the start of the struct.
+  pub offset: u32,
+  /// The size in bytes.
+  pub size: u32,
+}
+
+/// The vertex attributes of a vertex input struct.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslVertexLayoutReport {
+  /// The fully qualified name of the struct, eg: `triangle::VertexInput`.
+  pub name: String,
+  /// The attributes, ordered as declared in the shader.
+  pub attributes: Vec<WgslVertexAttributeReport>,
+}
+
+/// A vertex attribute of a vertex layout.
+#[derive(Debug, Clone, Serialize)]
+pub struct WgslVertexAttributeReport {
+  /// The `@location` index.
+  pub location: u32,
+  /// The name of the field.
+  pub name: String,
+  /// The vertex format, eg: `Float32x3`.
+  pub format: String,
+}
+
+pub(crate) fn entry_report(
+  entry: &WgslEntryResult<'_>,
+  options: &WgslBindgenOption,
+) -> Result<WgslEntryReport, CreateModuleError> {
+  let WgslEntryResult {
+    mod_name,
+    naga_module: module,
+    module_info,
+    source_including_deps,
+  } = entry;
+
+  let structs = structs::structs_items(mod_name, module, options)
+    .into_iter()
+    .filter(|item| item.types.contains(RustItemType::TypeDefs))
+    .map(|item| item.path.get_fully_qualified_name().to_string())
+    .collect::<FxIndexSet<_>>();
+
+  let bind_group_prefix = &options.wgpu_binding_generator.bind_group_layout.name_prefix;
+  let bind_groups = generated_bind_group_data(module, module_info.as_ref(), options)?
+    .into_iter()
+    .map(|(group, data)| WgslBindGroupReport {
+      group,
+      name: format!("{mod_name}::{bind_group_prefix}{group}"),
+      bindings: data
+        .bindings
+        .iter()
+        .map(|binding| WgslBindingReport {
+          binding: binding.binding_index,
+          name: RustItemPath::from_mangled(
+            binding.name.as_deref().unwrap_or(""),
+            mod_name,
+          )
+          .name
+          .to_string(),
+          resource: match binding.binding_type.inner {
+            naga::TypeInner::Image { .. } => BindResourceType::Texture,
+            naga::TypeInner::Sampler { .. } => BindResourceType::Sampler,
+            _ => BindResourceType::Buffer,
+          },
+        })
+        .collect(),
+    })
+    .collect();
+
+  let entry_points = module
+    .entry_points
+    .iter()
+    .map(|entry_point| WgslEntryPointReport {
+      name: entry_point.name.clone(),
+      stage: match entry_point.stage {
+        naga::ShaderStage::Vertex => "vertex",
+        naga::ShaderStage::Fragment => "fragment",
+        naga::ShaderStage::Compute => "compute",
+      }
+      .to_string(),
+    })
+    .collect();
+
+  let vertex_input_structs = wgsl::get_vertex_input_structs(mod_name, module);
+  let vertex_inputs = vertex_input_structs
+    .iter()
+    .map(|input| input.item_path.get_fully_qualified_name().to_string())
+    .collect();
+
+  let vertex_layouts = vertex_input_structs
+    .iter()
+    .map(|input| {
+      let name = input.item_path.get_fully_qualified_name();
+      WgslVertexLayoutReport {
+        name: name.to_string(),
+        attributes: input
+          .fields
+          .iter()
+          .map(|(location, member)| WgslVertexAttributeReport {
+            location: *location,
+            name: member.name.clone().unwrap_or_default(),
+            format: format!(
+              "{:?}",
+              wgsl::vertex_attribute_format(&name, member, module, options)
+            ),
+          })
+          .collect(),
+      }
+    })
+    .collect();
+
+  Ok(WgslEntryReport {
+    mod_name: mod_name.clone(),
+    source_path: source_including_deps.source_file.file_path.to_string(),
+    struct_layouts: struct_layouts(mod_name, module, &structs),
+    structs: structs.into_iter().collect(),
+    bind_groups,
+    entry_points,
+    vertex_inputs,
+    vertex_layouts,
+    warnings: entry_diagnostics(entry, options),
+  })
+}
+
+/// The layouts of the structs of `module` named in `structs`, in the same order.
+fn struct_layouts(
+  mod_name: &str,
+  module: &naga::Module,
+  structs: &FxIndexSet<String>,
+) -> Vec<WgslStructLayoutReport> {
+  let mut layouter = naga::proc::Layouter::default();
+  layouter.update(module.to_ctx()).unwrap();
+
+  let mut layouts = module
+    .types
+    .iter()
+    .filter_map(|(handle, ty)| {
+      let naga::TypeInner::Struct { members, span } = &ty.inner else {
+        return None;
+      };
+      let path = RustItemPath::from_mangled(ty.name.as_deref()?, mod_name);
+      let name = path.get_fully_qualified_name().to_string();
+      let index = structs.get_index_of(&name)?;
+
+      let fields = members
+        .iter()
+        .map(|member| WgslFieldLayoutReport {
+          name: member.name.clone().unwrap_or_default(),
+          wgsl_type: wgsl::type_name(module, &module.types[member.ty]),
+          offset: member.offset,
+          size: layouter[member.ty].size,
+        })
+        .collect();
+
+      let layout = WgslStructLayoutReport {
+        name,
+        size: *span,
+        alignment: layouter[handle].alignment.round_up(1),
+        fields,
+      };
+      Some((index, layout))
+    })
+    .collect::<Vec<_>>();
+
+  layouts.sort_by_key(|(index, _)| *index);
+  layouts.into_iter().map(|(_, layout)| layout).collect()
+}
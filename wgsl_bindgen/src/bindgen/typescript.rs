@@ -0,0 +1,228 @@
+//! TypeScript definitions of the entry points, bindings and buffer struct layouts, so the
+//! JavaScript side of a WebGPU application shares the shader contract of the Rust side.
+
+use std::fmt::Write;
+
+use naga::proc::Layouter;
+use naga::{ArraySize, Handle, ScalarKind, Type, TypeInner};
+
+use crate::generate::bind_group::generated_bind_group_data;
+use crate::quote_gen::RustItemPath;
+use crate::{
+  wgsl, CreateModuleError, FxIndexMap, FxIndexSet, WgslBindgenOption, WgslEntryResult,
+};
+
+pub(crate) fn render(
+  entries: &[WgslEntryResult<'_>],
+  options: &WgslBindgenOption,
+) -> Result<String, CreateModuleError> {
+  // The items of each namespace, eg: `triangle` or `types` for the structs of a shared import.
+  let mut namespaces = FxIndexMap::<String, Vec<String>>::default();
+  let mut declared = FxIndexSet::default();
+
+  for entry in entries {
+    let module = &entry.naga_module;
+    let mod_name = entry.mod_name.as_str();
+    let items = namespaces.entry(mod_name.to_string()).or_default();
+    items.extend(entry_point_items(module, options));
+    items.extend(bind_group_items(
+      mod_name,
+      module,
+      entry.module_info.as_ref(),
+      options,
+    )?);
+
+    let mut layouter = Layouter::default();
+    layouter.update(module.to_ctx()).unwrap();
+    for handle in wgsl::buffer_structs(module) {
+      let path = struct_path(mod_name, &module.types[handle]);
+      if declared.insert(path.get_fully_qualified_name()) {
+        let item = struct_item(mod_name, module, &layouter, handle);
+        let namespace = path.module.replace("::", ".");
+        namespaces.entry(namespace).or_default().push(item);
+      }
+    }
+  }
+
+  let mut ts = String::new();
+  writeln!(ts, "// File automatically generated by wgsl_bindgen.").unwrap();
+  writeln!(ts, "// Changes made to this file will not be saved.").unwrap();
+  for (namespace, items) in namespaces {
+    writeln!(ts, "\nexport namespace {namespace} {{").unwrap();
+    write!(ts, "{}", items.join("\n")).unwrap();
+    writeln!(ts, "}}").unwrap();
+  }
+  Ok(ts)
+}
+
+fn entry_point_items(
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Option<String> {
+  let mut item = String::new();
+  for entry_point in &module.entry_points {
+    let const_name = options.entry_point_const(&entry_point.name);
+    writeln!(item, "  export const {const_name} = {:?};", entry_point.name).unwrap();
+  }
+  (!item.is_empty()).then_some(item)
+}
+
+fn bind_group_items(
+  mod_name: &str,
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  options: &WgslBindgenOption,
+) -> Result<Vec<String>, CreateModuleError> {
+  let bind_group_prefix = &options.wgpu_binding_generator.bind_group_layout.name_prefix;
+  let items = generated_bind_group_data(module, info, options)?
+    .into_iter()
+    .map(|(group, data)| {
+      let mut item = String::new();
+      writeln!(item, "  /** The bindings of `@group({group})`. */").unwrap();
+      writeln!(item, "  export const {bind_group_prefix}{group} = {{").unwrap();
+      writeln!(item, "    group: {group},").unwrap();
+      writeln!(item, "    bindings: {{").unwrap();
+      for binding in &data.bindings {
+        let name = binding.name.as_deref().unwrap_or("");
+        let name = RustItemPath::from_mangled(name, mod_name).name;
+        writeln!(item, "      {name}: {},", binding.binding_index).unwrap();
+      }
+      writeln!(item, "    }},").unwrap();
+      writeln!(item, "  }} as const;").unwrap();
+      item
+    })
+    .collect();
+  Ok(items)
+}
+
+fn struct_path(mod_name: &str, ty: &Type) -> RustItemPath {
+  RustItemPath::from_mangled(ty.name.as_deref().unwrap_or_default(), mod_name)
+}
+
+/// The size and alignment of a struct, with a `view` function returning typed arrays over the
+/// fields of a struct in an `ArrayBuffer`, eg: to fill a uniform buffer before writing it.
+fn struct_item(
+  mod_name: &str,
+  module: &naga::Module,
+  layouter: &Layouter,
+  handle: Handle<Type>,
+) -> String {
+  let TypeInner::Struct { members, span } = &module.types[handle].inner else {
+    unreachable!("only structs are collected");
+  };
+  let path = struct_path(mod_name, &module.types[handle]);
+  let name = &path.name;
+  let alignment = layouter[handle].alignment.round_up(1);
+
+  let mut item = String::new();
+  writeln!(item, "  /** The layout of `{name}`, {span} bytes aligned to {alignment}. */")
+    .unwrap();
+  writeln!(item, "  export const {name} = {{").unwrap();
+  writeln!(item, "    size: {span},").unwrap();
+  writeln!(item, "    alignment: {alignment},").unwrap();
+  writeln!(
+    item,
+    "    /** Typed arrays over the fields of a `{name}` at `byteOffset` in `buffer`. */"
+  )
+  .unwrap();
+  writeln!(item, "    view(buffer: ArrayBuffer, byteOffset = 0) {{").unwrap();
+  writeln!(item, "      return {{").unwrap();
+  for member in members {
+    let field = member.name.as_deref().unwrap_or_default();
+    let view = field_view(mod_name, &path, module, layouter, member.ty, member.offset);
+    writeln!(item, "        {field}: {view},").unwrap();
+  }
+  writeln!(item, "      }};").unwrap();
+  writeln!(item, "    }},").unwrap();
+  writeln!(item, "  }} as const;").unwrap();
+  item
+}
+
+/// An expression viewing a field of type `ty` at `offset` in the struct at `byteOffset`.
+fn field_view(
+  mod_name: &str,
+  owner: &RustItemPath,
+  module: &naga::Module,
+  layouter: &Layouter,
+  ty: Handle<Type>,
+  offset: u32,
+) -> String {
+  let byte_offset = format!("byteOffset + {offset}");
+  let size = layouter[ty].size;
+
+  match &module.types[ty].inner {
+    TypeInner::Struct { .. } => {
+      let name = struct_reference(mod_name, owner, module, ty);
+      format!("{name}.view(buffer, {byte_offset})")
+    }
+    TypeInner::Array { base, size, stride }
+      if matches!(module.types[*base].inner, TypeInner::Struct { .. }) =>
+    {
+      let name = struct_reference(mod_name, owner, module, *base);
+      let length = match size {
+        ArraySize::Constant(length) => length.to_string(),
+        ArraySize::Dynamic => {
+          format!("Math.floor((buffer.byteLength - {byte_offset}) / {stride})")
+        }
+      };
+      format!(
+        "Array.from({{ length: {length} }}, (_, i) => {name}.view(buffer, {byte_offset} + i * {stride}))"
+      )
+    }
+    TypeInner::Array {
+      size: ArraySize::Dynamic,
+      ..
+    } => match typed_array(module, ty) {
+      // A runtime-sized array extends to the end of the buffer.
+      Some((typed_array, _)) => format!("new {typed_array}(buffer, {byte_offset})"),
+      None => format!("new Uint8Array(buffer, {byte_offset})"),
+    },
+    _ => match typed_array(module, ty) {
+      Some((typed_array, width)) => {
+        format!("new {typed_array}(buffer, {byte_offset}, {})", size / width)
+      }
+      None => format!("new Uint8Array(buffer, {byte_offset}, {size})"),
+    },
+  }
+}
+
+/// The name of the struct `ty` as seen from the namespace of `owner`, eg: `types.Fp64`.
+fn struct_reference(
+  mod_name: &str,
+  owner: &RustItemPath,
+  module: &naga::Module,
+  ty: Handle<Type>,
+) -> String {
+  let path = struct_path(mod_name, &module.types[ty]);
+  match path.module == owner.module {
+    true => path.name.to_string(),
+    false => path.get_fully_qualified_name().replace("::", "."),
+  }
+}
+
+/// The typed array and element width of the scalars of `ty`, with vectors, matrices and arrays
+/// viewed as flat arrays of scalars, including their padding.
+fn typed_array(module: &naga::Module, ty: Handle<Type>) -> Option<(&'static str, u32)> {
+  let scalar = match &module.types[ty].inner {
+    TypeInner::Scalar(scalar)
+    | TypeInner::Atomic(scalar)
+    | TypeInner::Vector { scalar, .. }
+    | TypeInner::Matrix { scalar, .. } => *scalar,
+    TypeInner::Array { base, .. } => return typed_array(module, *base),
+    _ => return None,
+  };
+
+  let typed_array = match (scalar.kind, scalar.width) {
+    (ScalarKind::Float, 4) => "Float32Array",
+    (ScalarKind::Float, 8) => "Float64Array",
+    // Half precision floats are viewed as their bits, since `Float16Array` isn't widely
+    // available yet.
+    (ScalarKind::Float, 2) => "Uint16Array",
+    (ScalarKind::Sint, 4) => "Int32Array",
+    (ScalarKind::Sint, 8) => "BigInt64Array",
+    (ScalarKind::Uint, 4) => "Uint32Array",
+    (ScalarKind::Uint, 8) => "BigUint64Array",
+    _ => return None,
+  };
+  Some((typed_array, scalar.width as u32))
+}
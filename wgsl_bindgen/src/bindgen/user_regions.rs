@@ -0,0 +1,93 @@
+//! Hand-written regions of the output, carried over verbatim when the bindings are regenerated.
+
+const BEGIN_MARKER: &str = "// wgsl_bindgen: begin user";
+const END_MARKER: &str = "// wgsl_bindgen: end user";
+
+/// Appends the user regions of `old_content`, markers included, to the regenerated `content`.
+/// Returns the line of the first region that is never ended instead, since everything following
+/// it would otherwise be carried over as well.
+pub(crate) fn append(old_content: &str, mut content: String) -> Result<String, usize> {
+  let lines = old_content.lines().collect::<Vec<_>>();
+  let mut regions = Vec::new();
+  let mut region_start = None;
+
+  for (index, line) in lines.iter().enumerate() {
+    match (region_start, line.trim()) {
+      (None, BEGIN_MARKER) => region_start = Some(index),
+      (Some(start), END_MARKER) => {
+        regions.push(lines[start..=index].join("\n"));
+        region_start = None;
+      }
+      _ => {}
+    }
+  }
+
+  if let Some(start) = region_start {
+    return Err(start + 1);
+  }
+
+  for region in regions {
+    content.push('\n');
+    content.push_str(&region);
+    content.push('\n');
+  }
+  Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn keeps_content_without_regions() {
+    let content = append("pub mod old {}\n", "pub mod new {}\n".to_string());
+    assert_eq!(Ok("pub mod new {}\n".to_string()), content);
+  }
+
+  #[test]
+  fn appends_regions() {
+    let old_content = indoc! {"
+      pub mod old {}
+      // wgsl_bindgen: begin user
+      pub fn helper() {}
+      // wgsl_bindgen: end user
+      pub mod other {}
+          // wgsl_bindgen: begin user
+          pub const SCALE: f32 = 2.0;
+          // wgsl_bindgen: end user
+    "};
+
+    let content = append(old_content, "pub mod new {}\n".to_string()).unwrap();
+    assert_eq!(
+      indoc! {"
+        pub mod new {}
+
+        // wgsl_bindgen: begin user
+        pub fn helper() {}
+        // wgsl_bindgen: end user
+
+            // wgsl_bindgen: begin user
+            pub const SCALE: f32 = 2.0;
+            // wgsl_bindgen: end user
+      "},
+      content
+    );
+
+    // Regenerating again leaves the output unchanged.
+    assert_eq!(Ok(content.clone()), append(&content, "pub mod new {}\n".to_string()));
+  }
+
+  #[test]
+  fn errors_on_unterminated_region() {
+    let old_content = indoc! {"
+      pub mod old {}
+      // wgsl_bindgen: begin user
+      pub fn helper() {}
+    "};
+
+    assert_eq!(Err(2), append(old_content, String::new()));
+  }
+}
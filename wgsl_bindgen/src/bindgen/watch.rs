@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use notify::{RecursiveMode, Watcher};
+
+use super::entry_glob;
+use crate::{FxIndexSet, SourceFilePath, WGSLBindgen, WgslBindgenError};
+
+/// How long to wait for more events before acting on a change, as editors
+/// usually touch a file several times when saving.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// The paths changed in a burst of events, or the error that stopped the watcher.
+type Changes = Result<FxIndexSet<PathBuf>, notify::Error>;
+
+impl WGSLBindgen {
+  /// Watches the entry points and all of their imports, regenerating the bindings whenever one
+  /// of them changes. This blocks the current thread until the callback breaks or watching fails.
+  ///
+  /// The callback receives the entry points affected by the change, along with the result of
+  /// regenerating the bindings. Nothing is written when no `output` is set, which allows
+  /// handling the changed entries entirely in the callback, eg: to recreate shader modules.
+  pub fn watch<F>(mut self, mut callback: F) -> Result<(), WgslBindgenError>
+  where
+    F: FnMut(&[SourceFilePath], Result<(), WgslBindgenError>) -> ControlFlow<()>,
+  {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = ShaderWatcher::new(&self, move |changes| tx.send(changes).is_ok())?;
+
+    loop {
+      watcher.update(&self)?;
+      let changed_paths = rx
+        .recv()
+        .map_err(|_| notify::Error::generic("watcher stopped"))??;
+
+      if let Some((entries, result)) = self.regenerate_changed(&watcher, changed_paths) {
+        if callback(&entries, result).is_break() {
+          return Ok(());
+        }
+      }
+    }
+  }
+
+  /// Like [`WGSLBindgen::watch`], but waits for changes asynchronously instead of blocking the thread.
+  /// The bindgen isn't `Send`, so the future has to run on a local executor, eg: `futures::executor::block_on`.
+  pub async fn watch_async<F>(mut self, mut callback: F) -> Result<(), WgslBindgenError>
+  where
+    F: FnMut(&[SourceFilePath], Result<(), WgslBindgenError>) -> ControlFlow<()>,
+  {
+    let (tx, mut rx) = futures::channel::mpsc::unbounded();
+    let mut watcher =
+      ShaderWatcher::new(&self, move |changes| tx.unbounded_send(changes).is_ok())?;
+
+    loop {
+      watcher.update(&self)?;
+      let changed_paths = rx
+        .next()
+        .await
+        .ok_or_else(|| notify::Error::generic("watcher stopped"))??;
+
+      if let Some((entries, result)) = self.regenerate_changed(&watcher, changed_paths) {
+        if callback(&entries, result).is_break() {
+          return Ok(());
+        }
+      }
+    }
+  }
+
+  /// Writes the bindings to `output`, then rewrites them whenever an entry point or one of its
+  /// imports changes, reporting each regeneration and error on stderr. Errors don't stop watching,
+  /// so this suits a developer loop outside of the build script, eg: `cargo xtask shaders --watch`.
+  /// This blocks the current thread until watching fails.
+  pub fn run_watch(self) -> Result<(), WgslBindgenError> {
+    if let Err(err) = self.generate() {
+      report_error(err);
+    }
+    self.watch(report_regeneration)
+  }
+
+  /// Like [`WGSLBindgen::run_watch`], but waits for changes asynchronously instead of blocking the thread.
+  pub async fn run_watch_async(self) -> Result<(), WgslBindgenError> {
+    if let Err(err) = self.generate() {
+      report_error(err);
+    }
+    self.watch_async(report_regeneration).await
+  }
+
+  /// Reloads the shaders when the changed paths affect them, and regenerates the bindings.
+  /// Returns the affected entries along with the result, or `None` when nothing relevant changed.
+  fn regenerate_changed(
+    &mut self,
+    watcher: &ShaderWatcher,
+    changed_paths: FxIndexSet<PathBuf>,
+  ) -> Option<(Vec<SourceFilePath>, Result<(), WgslBindgenError>)> {
+    let changed_files = changed_paths
+      .iter()
+      .filter_map(|path| watcher.files.get(&canonicalize(path)))
+      .collect::<FxIndexSet<_>>();
+
+    let in_glob_dir = changed_paths
+      .iter()
+      .any(|path| watcher.glob_dirs.iter().any(|dir| path.starts_with(dir)));
+
+    if changed_files.is_empty() && !in_glob_dir {
+      return None;
+    }
+
+    let entries_before = self.entry_paths();
+    let mut changed_entries = self.entries_depending_on(&changed_files);
+    let reloaded = self.reload();
+
+    let added_entries = self.entry_paths();
+    changed_entries.extend(
+      added_entries
+        .into_iter()
+        .filter(|entry| !entries_before.contains(entry)),
+    );
+
+    if changed_entries.is_empty() && reloaded.is_ok() {
+      return None;
+    }
+
+    let result = reloaded.and_then(|_| {
+      if self.options().output.is_some() {
+        self.generate()
+      } else {
+        Ok(())
+      }
+    });
+
+    Some((changed_entries, result))
+  }
+
+  fn files_by_canonical_path(&self) -> HashMap<PathBuf, SourceFilePath> {
+    self
+      .dependency_tree()
+      .all_files_including_dependencies()
+      .into_iter()
+      .map(|file| (canonicalize(&file), file))
+      .collect()
+  }
+
+  fn entry_paths(&self) -> FxIndexSet<SourceFilePath> {
+    self.dependency_tree().entry_points().clone()
+  }
+
+  fn entries_depending_on(
+    &self,
+    changed_files: &FxIndexSet<&SourceFilePath>,
+  ) -> Vec<SourceFilePath> {
+    self
+      .dependency_tree()
+      .get_source_files_with_full_dependencies()
+      .into_iter()
+      .filter(|entry| {
+        std::iter::once(entry.source_file)
+          .chain(entry.full_dependencies.iter().copied())
+          .any(|source| changed_files.contains(&source.file_path))
+      })
+      .map(|entry| entry.source_file.file_path.clone())
+      .collect()
+  }
+}
+
+/// Watches the directories of the shaders, forwarding the paths changed in each burst of events.
+struct ShaderWatcher {
+  watcher: notify::RecommendedWatcher,
+  watched_dirs: FxIndexSet<PathBuf>,
+  glob_dirs: Vec<PathBuf>,
+  files: HashMap<PathBuf, SourceFilePath>,
+}
+
+impl ShaderWatcher {
+  /// Starts watching, passing the changes to `send` until it returns `false`.
+  fn new(
+    bindgen: &WGSLBindgen,
+    mut send: impl FnMut(Changes) -> bool + Send + 'static,
+  ) -> Result<Self, WgslBindgenError> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+
+    // The events are debounced on a thread of their own, which ends along with the watcher.
+    std::thread::spawn(move || {
+      while let Ok(event) = rx.recv() {
+        if !send(collect_changes(event, &rx)) {
+          break;
+        }
+      }
+    });
+
+    // New shaders may show up anywhere below the globbed directories.
+    let glob_dirs = bindgen
+      .options()
+      .entry_point_globs
+      .iter()
+      .map(|pattern| canonicalize(&entry_glob::base_dir(pattern)))
+      .collect::<Vec<_>>();
+
+    let mut watched_dirs = FxIndexSet::default();
+    for dir in &glob_dirs {
+      if watched_dirs.insert(dir.clone()) {
+        watcher.watch(dir, RecursiveMode::Recursive)?;
+      }
+    }
+
+    Ok(Self {
+      watcher,
+      watched_dirs,
+      glob_dirs,
+      files: HashMap::new(),
+    })
+  }
+
+  /// Picks up the files of the shaders after they were reloaded.
+  fn update(&mut self, bindgen: &WGSLBindgen) -> Result<(), WgslBindgenError> {
+    self.files = bindgen.files_by_canonical_path();
+
+    // Watch the parent directories, since editors often replace files rather than writing them.
+    for dir in self.files.keys().filter_map(|path| path.parent()) {
+      if self.watched_dirs.insert(dir.to_path_buf()) {
+        self.watcher.watch(dir, RecursiveMode::NonRecursive)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Collects the paths changed by an event and the ones following it closely.
+fn collect_changes(
+  first: notify::Result<notify::Event>,
+  rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+) -> Changes {
+  // Reading the shaders while regenerating raises access events, which must not retrigger it.
+  let mut changed_paths = FxIndexSet::default();
+  let mut extend_changed = |event: notify::Result<notify::Event>| {
+    let event = event?;
+    if !event.kind.is_access() {
+      changed_paths.extend(event.paths);
+    }
+    Ok::<_, notify::Error>(())
+  };
+
+  extend_changed(first)?;
+  while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+    extend_changed(event)?;
+  }
+  Ok(changed_paths)
+}
+
+/// Reports a regeneration on stderr, and keeps watching.
+fn report_regeneration(
+  entries: &[SourceFilePath],
+  result: Result<(), WgslBindgenError>,
+) -> ControlFlow<()> {
+  match result {
+    Ok(()) => entries
+      .iter()
+      .for_each(|entry| eprintln!("regenerated: {entry}")),
+    Err(err) => report_error(err),
+  }
+  ControlFlow::Continue(())
+}
+
+fn report_error(err: WgslBindgenError) {
+  eprintln!("{:?}", miette::Report::new(err));
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+  std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,132 @@
+//! Helpers for Bevy's render pipelines, generated in a `bevy_helpers` module of each entry
+//! when `bevy_feature` is set.
+//!
+//! Bevy re-exports the wgpu types in `bevy::render::render_resource`, so the layouts of the
+//! bindings are reused as is, provided the `wgpu` used by the bindings is the one of Bevy.
+
+use std::collections::BTreeMap;
+
+use case::CaseExt;
+use naga::ShaderStage;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::LitInt;
+
+use super::bind_group::GroupData;
+use crate::quote_gen::mod_reference_root;
+use crate::{wgsl, WgslBindgenOption, WgslEntryResult};
+
+pub(crate) fn entry_helpers(
+  entry: &WgslEntryResult<'_>,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let shader_handle = shader_handle_u128(&entry.mod_name);
+  let bind_group_layout_entries = bind_group_data.keys().map(|group| {
+    let bind_group = options
+      .wgpu_binding_generator
+      .bind_group_layout
+      .bind_group_name_ident(*group);
+    let fn_name = format_ident!("bind_group_layout_entries_{group}");
+    let doc = format!(
+      " The layout entries of `@group({group})`, eg: to implement `AsBindGroup::bind_group_layout_entries`."
+    );
+
+    quote! {
+      #[doc = #doc]
+      pub fn #fn_name() -> Vec<::bevy::render::render_resource::BindGroupLayoutEntry> {
+        super::#bind_group::LAYOUT_DESCRIPTOR.entries.to_vec()
+      }
+    }
+  });
+
+  let stage_states = entry
+    .naga_module
+    .entry_points
+    .iter()
+    .filter_map(|entry_point| match entry_point.stage {
+      ShaderStage::Vertex => Some(vertex_state_fn(entry, &entry_point.name, options)),
+      ShaderStage::Fragment => Some(fragment_state_fn(&entry_point.name, options)),
+      ShaderStage::Compute => None,
+    });
+
+  quote! {
+    /// The handle of the shader in Bevy's `Assets<Shader>`.
+    pub const SHADER_HANDLE: ::bevy::asset::Handle<::bevy::render::render_resource::Shader> =
+      ::bevy::asset::Handle::weak_from_u128(#shader_handle);
+
+    #(#bind_group_layout_entries)*
+    #(#stage_states)*
+  }
+}
+
+/// A stable id of the shader of the entry, so its handle doesn't change between builds.
+fn shader_handle_u128(mod_name: &str) -> LitInt {
+  let hash = blake3::hash(format!("wgsl_bindgen::{mod_name}").as_bytes());
+  let id = u128::from_le_bytes(hash.as_bytes()[..16].try_into().unwrap());
+  LitInt::new(&format!("{id:#034x}"), Span::call_site())
+}
+
+fn vertex_state_fn(
+  entry: &WgslEntryResult<'_>,
+  name: &str,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let root = mod_reference_root();
+  let fn_name = format_ident!("{name}_vertex_state");
+  let const_name = format_ident!("{}", options.entry_point_const(name));
+
+  let vertex_inputs = wgsl::get_vertex_input_structs(&entry.mod_name, &entry.naga_module);
+  let step_modes = vertex_inputs
+    .iter()
+    .map(|input| format_ident!("{}", input.item_path.name.to_snake()))
+    .collect::<Vec<_>>();
+  let layouts = vertex_inputs
+    .iter()
+    .zip(&step_modes)
+    .map(|(input, step_mode)| {
+      let path = &input.item_path;
+      quote!(#root::#path::vertex_buffer_layout(#step_mode))
+    });
+
+  quote! {
+    pub fn #fn_name(
+      #(#step_modes: wgpu::VertexStepMode,)*
+      shader_defs: Vec<::bevy::render::render_resource::ShaderDefVal>,
+    ) -> ::bevy::render::render_resource::VertexState {
+      let buffers: &[wgpu::VertexBufferLayout<'static>] = &[#(#layouts),*];
+      ::bevy::render::render_resource::VertexState {
+        shader: SHADER_HANDLE,
+        shader_defs,
+        entry_point: super::#const_name.into(),
+        buffers: buffers
+          .iter()
+          .map(|layout| ::bevy::render::render_resource::VertexBufferLayout {
+            array_stride: layout.array_stride,
+            step_mode: layout.step_mode,
+            attributes: layout.attributes.to_vec(),
+          })
+          .collect(),
+      }
+    }
+  }
+}
+
+fn fragment_state_fn(name: &str, options: &WgslBindgenOption) -> TokenStream {
+  let fn_name = format_ident!("{name}_fragment_state");
+  let const_name = format_ident!("{}", options.entry_point_const(name));
+
+  quote! {
+    pub fn #fn_name(
+      targets: Vec<Option<::bevy::render::render_resource::ColorTargetState>>,
+      shader_defs: Vec<::bevy::render::render_resource::ShaderDefVal>,
+    ) -> ::bevy::render::render_resource::FragmentState {
+      ::bevy::render::render_resource::FragmentState {
+        shader: SHADER_HANDLE,
+        shader_defs,
+        entry_point: super::#const_name.into(),
+        targets,
+      }
+    }
+  }
+}
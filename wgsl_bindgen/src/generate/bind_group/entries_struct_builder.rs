@@ -3,6 +3,19 @@ use derive_more::Constructor;
 use self::quote_gen::RustItemPath;
 use super::*;
 
+/// The kind of resource bound to `binding`.
+pub(super) fn bind_resource_type(binding: &GroupBinding) -> BindResourceType {
+  // TODO: Support more types.
+  match binding.binding_type.inner {
+    naga::TypeInner::Struct { .. } => BindResourceType::Buffer,
+    naga::TypeInner::Image { .. } => BindResourceType::Texture,
+    naga::TypeInner::Sampler { .. } => BindResourceType::Sampler,
+    naga::TypeInner::Array { .. } => BindResourceType::Buffer,
+    naga::TypeInner::Scalar(_) => BindResourceType::Buffer,
+    _ => panic!("Unsupported type for binding fields."),
+  }
+}
+
 #[derive(Constructor)]
 pub(super) struct BindGroupEntriesStructBuilder<'a> {
   invoking_entry_module: &'a str,
@@ -73,16 +86,7 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
     );
     let field_name = format_ident!("{}", &rust_item_path.name.as_str());
 
-    // TODO: Support more types.
-    let resource_type = match binding.binding_type.inner {
-      naga::TypeInner::Struct { .. } => BindResourceType::Buffer,
-      naga::TypeInner::Image { .. } => BindResourceType::Texture,
-      naga::TypeInner::Sampler { .. } => BindResourceType::Sampler,
-      naga::TypeInner::Array { .. } => BindResourceType::Buffer,
-      naga::TypeInner::Scalar(_) => BindResourceType::Buffer,
-      _ => panic!("Unsupported type for binding fields."),
-    };
-
+    let resource_type = bind_resource_type(binding);
     let param_field_type = self.generator.binding_type_map[&resource_type].clone();
     let field_type = self.generator.entry_struct_type.clone();
 
@@ -0,0 +1,112 @@
+//! Builds a top-level module listing every distinct bind group layout across the entries,
+//! so they can be created once at startup and shared across pipelines.
+use quote::ToTokens;
+
+use super::*;
+
+pub fn bind_group_layouts_module(
+  entries: &[WgslEntryResult],
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  // Layouts keyed by their entries, so identical layouts from different shaders share an index.
+  // Layouts of feature gated entries are only shared between entries gated by the same feature.
+  let mut layouts = FxIndexMap::<(Option<&str>, String), TokenStream>::default();
+  let mut index_match_arms = Vec::new();
+
+  for entry in entries {
+    let naga_module = &entry.naga_module;
+    let bind_group_data =
+      generated_bind_group_data(naga_module, entry.module_info.as_ref(), options)?;
+    let shader_stages = wgsl::shader_stages(naga_module);
+    let mod_path = format_ident!("{}", entry.mod_name);
+    let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+    let feature = options.entry_feature(&entry.mod_name);
+    let cfg = options.entry_cfg(&entry.mod_name);
+
+    for (group_no, group) in bind_group_data.iter() {
+      let key = group
+        .bindings
+        .iter()
+        .map(|binding| {
+          bind_group_layout_entry(
+            &entry.mod_name,
+            naga_module,
+            options,
+            shader_stages,
+            binding,
+          )
+          .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+      let bind_group_name = options
+        .wgpu_binding_generator
+        .bind_group_layout
+        .bind_group_name_ident(*group_no);
+
+      let layout = layouts.entry((feature, key));
+      let index = layout.index();
+      layout
+        .or_insert_with(|| quote!(#cfg #mod_path::#bind_group_name::LAYOUT_DESCRIPTOR));
+
+      let group_no = Index::from(*group_no as usize);
+      index_match_arms
+        .push((index, quote!(#cfg (ShaderEntry::#enum_variant, #group_no))));
+    }
+  }
+
+  if layouts.is_empty() {
+    return Ok(quote!());
+  }
+
+  // The ungated layouts come first. The index of each gated layout counts the enabled
+  // gated layouts before it, since the disabled ones are left out of `DESCRIPTORS`.
+  let (ungated, gated) = layouts
+    .iter()
+    .enumerate()
+    .partition::<Vec<_>, _>(|(_, ((feature, _), _))| feature.is_none());
+  let mut indices = vec![quote!(); layouts.len()];
+  let mut count = vec![Index::from(ungated.len()).to_token_stream()];
+  for (position, (index, _)) in ungated.iter().enumerate() {
+    indices[*index] = Index::from(position).to_token_stream();
+  }
+  for (index, ((feature, _), _)) in &gated {
+    indices[*index] = quote!(#(#count)+*);
+    count.push(quote!(cfg!(feature = #feature) as usize));
+  }
+
+  let count = quote!(#(#count)+*);
+  let descriptors = ungated
+    .iter()
+    .chain(&gated)
+    .map(|(_, (_, descriptor))| descriptor);
+  let index_match_arms = index_match_arms.into_iter().map(|(index, arm)| {
+    let index = &indices[index];
+    quote!(#arm => Some(#index))
+  });
+
+  let (generics, device, bind_group_layout) = device_fn_types(options, "BindGroupLayout");
+
+  Ok(quote! {
+    pub const COUNT: usize = #count;
+
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+      #( #descriptors ),*
+    ];
+
+    pub fn create_all #generics(device: &#device) -> Vec<#bind_group_layout> {
+      DESCRIPTORS
+        .iter()
+        .map(|descriptor| device.create_bind_group_layout(descriptor))
+        .collect()
+    }
+
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+      match (entry, group_no) {
+        #( #index_match_arms, )*
+        _ => None,
+      }
+    }
+  })
+}
@@ -1,15 +1,19 @@
 use std::collections::BTreeMap;
 
 use derive_more::Constructor;
-use generate::quote_shader_stages;
+use generate::{device_fn_types, quote_shader_stages};
+use proc_macro2::Literal;
 use quote::{format_ident, quote};
-use quote_gen::{demangle_and_fully_qualify_str, rust_type};
+use quote_gen::{demangle_and_fully_qualify_str, rust_type, RustItemPath};
+use support::TypedBuffer;
 
 use crate::wgsl::buffer_binding_type;
 use crate::*;
 
 mod entries_struct_builder;
+mod layout_registry;
 use entries_struct_builder::*;
+pub use layout_registry::*;
 
 pub struct GroupData<'a> {
   pub bindings: Vec<GroupBinding<'a>>,
@@ -91,6 +95,8 @@ impl<'a> BindGroupBuilder<'a> {
       .bind_group_entries_struct_name_ident(self.group_no);
 
     let bind_group_layout_descriptor = self.bind_group_layout_descriptor();
+    let (generics, device, bind_group_layout) =
+      device_fn_types(self.options, "BindGroupLayout");
 
     let group_no = Index::from(self.group_no as usize);
     let bind_group_label =
@@ -100,19 +106,17 @@ impl<'a> BindGroupBuilder<'a> {
         impl #bind_group_name {
             pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = #bind_group_layout_descriptor;
 
-            pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            pub fn get_bind_group_layout #generics(device: &#device) -> #bind_group_layout {
                 device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
             }
 
             pub fn from_bindings(device: &wgpu::Device, bindings: #bind_group_entries_struct_name) -> Self {
-                let bind_group_layout = Self::get_bind_group_layout(&device);
-                let entries = bindings.as_array();
-                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some(#bind_group_label),
-                    layout: &bind_group_layout,
-                    entries: &entries,
-                });
-                Self(bind_group)
+                Self(_root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    #bind_group_label,
+                    &bindings.as_array(),
+                ))
             }
 
             pub fn set<'a>(&self, render_pass: &mut #render_pass) {
@@ -122,8 +126,255 @@ impl<'a> BindGroupBuilder<'a> {
     }
   }
 
+  /// Whether the buffer of `binding` is created and written by the bundle of the bind group.
+  fn is_bundled(&self, binding: &GroupBinding) -> bool {
+    binding.address_space == naga::AddressSpace::Uniform
+      && is_serializable_struct(
+        self.invoking_entry_name,
+        self.options,
+        self.naga_module,
+        binding.binding_type,
+      )
+  }
+
+  /// A bundle owning the uniform buffers of the bind group along with the bind group, taking the
+  /// other resources of the bind group on creation.
+  fn bundle(&self) -> Option<TokenStream> {
+    let bindings = &self.data.bindings;
+    if !self.options.bind_group_bundles || !bindings.iter().any(|b| self.is_bundled(b)) {
+      return None;
+    }
+
+    let generator = &self.options.wgpu_binding_generator.bind_group_layout;
+    let bind_group_name = self.struct_name();
+    let bundle_name = format_ident!("{bind_group_name}Bundle");
+    let values_name = format_ident!("{bind_group_name}Values");
+    let entries_name = generator.bind_group_entries_struct_name_ident(self.group_no);
+    let params_name = format_ident!("{entries_name}Params");
+
+    let render_pass = if self.shader_stages == wgpu::ShaderStages::COMPUTE {
+      quote!(wgpu::ComputePass<'a>)
+    } else {
+      quote!(wgpu::RenderPass<'a>)
+    };
+
+    let binding_name = |binding: &GroupBinding| {
+      let name = RustItemPath::from_mangled(
+        binding.name.as_ref().unwrap(),
+        self.invoking_entry_name,
+      )
+      .name;
+      format_ident!("{}", name.as_str())
+    };
+    let (bundled, resources): (Vec<_>, Vec<_>) =
+      bindings.iter().partition(|b| self.is_bundled(b));
+
+    let value_fields = bundled.iter().map(|binding| {
+      let name = binding_name(binding);
+      let ty = rust_type(
+        Some(self.invoking_entry_name),
+        self.naga_module,
+        binding.binding_type,
+        self.options,
+      );
+      quote!(pub #name: #ty)
+    });
+    let buffer_fields = bundled.iter().map(|binding| {
+      let name = binding_name(binding);
+      quote!(pub #name: wgpu::Buffer)
+    });
+    let buffer_names: Vec<_> = bundled.iter().map(|b| binding_name(b)).collect();
+    let create_buffers = bundled.iter().map(|binding| {
+      let name = binding_name(binding);
+      let create_fn = format_ident!("create_{name}_buffer_init");
+      quote!(let #name = #create_fn(device, &values.#name);)
+    });
+    let writes = bundled.iter().map(|binding| {
+      let name = binding_name(binding);
+      let write_fn = format_ident!("write_{name}");
+      quote!(#write_fn(queue, &self.#name, &values.#name);)
+    });
+    let resource_params = resources.iter().map(|binding| {
+      let name = binding_name(binding);
+      let ty = &generator.binding_type_map[&bind_resource_type(binding)];
+      quote!(#name: #ty)
+    });
+    let params = bindings.iter().map(|binding| {
+      let name = binding_name(binding);
+      if self.is_bundled(binding) {
+        quote!(#name: #name.as_entire_buffer_binding())
+      } else {
+        quote!(#name)
+      }
+    });
+
+    let values_doc = format!(" The values of the uniform buffers of [`{bundle_name}`].");
+    let lifetime = (!resources.is_empty()).then(|| quote!(<'a>));
+
+    Some(quote! {
+      #[doc = #values_doc]
+      #[derive(Debug, PartialEq, Clone)]
+      pub struct #values_name {
+        #(#value_fields),*
+      }
+
+      /// The bind group together with the uniform buffers it binds, eg: for a material.
+      #[derive(Debug)]
+      pub struct #bundle_name {
+        pub bind_group: #bind_group_name,
+        #(#buffer_fields),*
+      }
+
+      impl #bundle_name {
+        /// Creates the uniform buffers initialized with `values`, and the bind group binding
+        /// them with the other resources.
+        pub fn new #lifetime(
+          device: &wgpu::Device,
+          values: &#values_name,
+          #(#resource_params),*
+        ) -> Self {
+          #(#create_buffers)*
+          let bind_group = #bind_group_name::from_bindings(
+            device,
+            #entries_name::new(#params_name {
+              #(#params),*
+            }),
+          );
+          Self {
+            bind_group,
+            #(#buffer_names),*
+          }
+        }
+
+        /// Writes `values` to the uniform buffers.
+        pub fn update(&self, queue: &wgpu::Queue, values: &#values_name) {
+          #(#writes)*
+        }
+
+        pub fn set<'a>(&self, pass: &mut #render_pass) {
+          self.bind_group.set(pass);
+        }
+      }
+    })
+  }
+
+  /// The read only storage bindings of the bind group paired with a writable storage binding of
+  /// the same type, in binding order.
+  fn ping_pong_pairs(&self) -> Vec<(&GroupBinding<'_>, &GroupBinding<'_>)> {
+    let access = |binding: &GroupBinding| match binding.address_space {
+      naga::AddressSpace::Storage { access } => Some(access),
+      _ => None,
+    };
+    let bindings = &self.data.bindings;
+    let mut paired = Vec::new();
+    bindings
+      .iter()
+      .filter(|src| access(src) == Some(naga::StorageAccess::LOAD))
+      .filter_map(|src| {
+        let dst = bindings.iter().find(|dst| {
+          access(dst).is_some_and(|a| a.contains(naga::StorageAccess::STORE))
+            && dst.binding_type == src.binding_type
+            && !paired.contains(&dst.binding_index)
+        })?;
+        paired.push(dst.binding_index);
+        Some((src, dst))
+      })
+      .collect()
+  }
+
+  /// `create_{src}_ping_pong` for each pair of [`Self::ping_pong_pairs`], creating the two
+  /// buffers and the bind group of each orientation, taking the other resources of the bind group.
+  fn ping_pong_fns(&self) -> Vec<TokenStream> {
+    if !self.options.ping_pong_buffers
+      || self.shader_stages != wgpu::ShaderStages::COMPUTE
+    {
+      return Vec::new();
+    }
+
+    let generator = &self.options.wgpu_binding_generator.bind_group_layout;
+    let bind_group_name = self.struct_name();
+    let entries_name = generator.bind_group_entries_struct_name_ident(self.group_no);
+    let params_name = format_ident!("{entries_name}Params");
+    let usage = TypedBuffer::Storage.usage();
+    let binding_name = |binding: &GroupBinding| {
+      RustItemPath::from_mangled(binding.name.as_ref().unwrap(), self.invoking_entry_name)
+        .name
+    };
+
+    self
+      .ping_pong_pairs()
+      .into_iter()
+      .map(|(src, dst)| {
+        let src_name = binding_name(src);
+        let dst_name = binding_name(dst);
+        let fn_name = format_ident!("create_{}_ping_pong", src_name.as_str());
+        let labels =
+          [0, 1].map(|i| format!("{}::{src_name}[{i}]", self.sanitized_entry_name));
+        let doc = format!(
+          " Two buffers of `size` bytes, bound as `{src_name}` and `{dst_name}` in turn."
+        );
+
+        let others: Vec<_> = self
+          .data
+          .bindings
+          .iter()
+          .filter(|b| {
+            b.binding_index != src.binding_index && b.binding_index != dst.binding_index
+          })
+          .collect();
+        let other_params = others.iter().map(|binding| {
+          let name = format_ident!("{}", binding_name(binding).as_str());
+          let ty = &generator.binding_type_map[&bind_resource_type(binding)];
+          quote!(#name: #ty)
+        });
+        let lifetime = (!others.is_empty()).then(|| quote!(<'a>));
+        let fields = self.data.bindings.iter().map(|binding| {
+          let name = format_ident!("{}", binding_name(binding).as_str());
+          if binding.binding_index == src.binding_index {
+            quote!(#name: src.as_entire_buffer_binding())
+          } else if binding.binding_index == dst.binding_index {
+            quote!(#name: dst.as_entire_buffer_binding())
+          } else if bind_resource_type(binding) == BindResourceType::Buffer {
+            // Buffer bindings aren't `Copy`, and each orientation binds them.
+            quote!(#name: #name.clone())
+          } else {
+            quote!(#name)
+          }
+        });
+
+        quote! {
+          #[doc = #doc]
+          pub fn #fn_name #lifetime(
+            device: &wgpu::Device,
+            size: wgpu::BufferAddress,
+            #(#other_params),*
+          ) -> _root::_support::PingPong<#bind_group_name> {
+            let buffers = [#(#labels),*].map(|label| {
+              device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: #usage,
+                mapped_at_creation: false,
+              })
+            });
+            _root::_support::PingPong::new(buffers, |src, dst| {
+              #bind_group_name::from_bindings(
+                device,
+                #entries_name::new(#params_name {
+                  #(#fields),*
+                }),
+              )
+            })
+          }
+        }
+      })
+      .collect()
+  }
+
   fn build(self) -> TokenStream {
     let bind_group_name = self.struct_name();
+    let bundle = self.bundle();
+    let ping_pong_fns = self.ping_pong_fns();
 
     let group_struct = quote! {
         #[derive(Debug)]
@@ -135,6 +386,8 @@ impl<'a> BindGroupBuilder<'a> {
     quote! {
         #group_struct
         #group_impl
+        #bundle
+        #(#ping_pong_fns)*
     }
   }
 }
@@ -266,6 +519,696 @@ pub fn bind_groups_module(
   }
 }
 
+/// Whether `ty` is a struct of a fixed size generated by wgsl_bindgen, so values of it can be
+/// serialized with the strategy.
+fn is_serializable_struct(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  naga_module: &naga::Module,
+  ty: &naga::Type,
+) -> bool {
+  let naga::TypeInner::Struct { members, .. } = &ty.inner else {
+    return false;
+  };
+  // Structs ending with a runtime sized array have no fixed size to write.
+  let is_runtime_sized = members.last().is_some_and(|member| {
+    matches!(
+      naga_module.types[member.ty].inner,
+      naga::TypeInner::Array {
+        size: naga::ArraySize::Dynamic,
+        ..
+      }
+    )
+  });
+  // Structs mapped to or provided by the user may not be serializable with the strategy.
+  let struct_path =
+    RustItemPath::from_mangled(ty.name.as_ref().unwrap(), invoking_entry_module);
+  let struct_name = struct_path.get_fully_qualified_name();
+  let is_user_struct = options.type_map.contains_key(&WgslType::Struct {
+    fully_qualified_name: struct_name.to_string(),
+  }) || options.is_excluded(WgslItemKind::Struct, &struct_name);
+
+  !is_runtime_sized && !is_user_struct
+}
+
+/// Generates `create_{binding}_buffer_init` for each uniform and storage binding of a struct,
+/// creating a buffer initialized with a value of it, labelled after the entry and the binding,
+/// and `write_{binding}` and `write_{binding}_at` writing a value of it to such a buffer, the
+/// latter at an offset, eg: for dynamic offset bindings.
+///
+/// Uniform bindings also get `create_{binding}_dynamic_buffer_init`, packing a slice of values
+/// at offsets aligned to `min_uniform_buffer_offset_alignment`, eg: for per-object uniforms.
+/// With `staging_belt_feature`, they also get `write_{binding}_staged` writing through a
+/// `wgpu::util::StagingBelt`, gated behind that feature.
+pub fn buffer_fns(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  naga_module: &naga::Module,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter_map(|binding| {
+      let kind = match binding.address_space {
+        naga::AddressSpace::Uniform => TypedBuffer::Uniform,
+        naga::AddressSpace::Storage { .. } => TypedBuffer::Storage,
+        _ => return None,
+      };
+      if !is_serializable_struct(
+        invoking_entry_module,
+        options,
+        naga_module,
+        binding.binding_type,
+      ) {
+        return None;
+      }
+
+      let binding_name =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module)
+          .name;
+      let fn_name = format_ident!("create_{}_buffer_init", binding_name.as_str());
+      let write_name = format_ident!("write_{}", binding_name.as_str());
+      let write_at_name = format_ident!("write_{}_at", binding_name.as_str());
+      let label = format!("{sanitized_entry_name}::{binding_name}");
+      let rust_type = rust_type(
+        Some(invoking_entry_module),
+        naga_module,
+        binding.binding_type,
+        options,
+      );
+      let usage = kind.usage();
+      let (_, contents) = support::buffer_contents(options, kind);
+
+      let staged_fn = options.staging_belt_feature.as_ref().map(|feature| {
+        let staged_name = format_ident!("write_{}_staged", binding_name.as_str());
+        quote! {
+          #[cfg(feature = #feature)]
+          pub fn #staged_name(
+            belt: &mut wgpu::util::StagingBelt,
+            encoder: &mut wgpu::CommandEncoder,
+            device: &wgpu::Device,
+            buffer: &wgpu::Buffer,
+            offset: wgpu::BufferAddress,
+            value: &#rust_type,
+          ) {
+            let bytes = #contents;
+            let size = wgpu::BufferSize::new(bytes.len() as u64).unwrap();
+            belt
+              .write_buffer(encoder, buffer, offset, size, device)
+              .copy_from_slice(bytes);
+          }
+        }
+      });
+
+      let dynamic_fn = matches!(kind, TypedBuffer::Uniform).then(|| {
+        let alloc = options.alloc_crate();
+        let dynamic_name =
+          format_ident!("create_{}_dynamic_buffer_init", binding_name.as_str());
+        quote! {
+          /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+          /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+          pub fn #dynamic_name(
+            device: &wgpu::Device,
+            limits: &wgpu::Limits,
+            values: &[#rust_type],
+          ) -> (wgpu::Buffer, #alloc::vec::Vec<wgpu::DynamicOffset>) {
+            use wgpu::util::DeviceExt;
+
+            let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+            let mut packed = #alloc::vec::Vec::new();
+            let mut offsets = #alloc::vec::Vec::with_capacity(values.len());
+            for value in values {
+              let offset = packed.len().next_multiple_of(alignment);
+              packed.resize(offset, 0);
+              packed.extend_from_slice(#contents);
+              offsets.push(offset as wgpu::DynamicOffset);
+            }
+
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+              label: Some(#label),
+              contents: &packed,
+              usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+            (buffer, offsets)
+          }
+        }
+      });
+
+      Some(quote! {
+        pub fn #fn_name(device: &wgpu::Device, value: &#rust_type) -> wgpu::Buffer {
+          use wgpu::util::DeviceExt;
+
+          device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(#label),
+            contents: #contents,
+            usage: #usage,
+          })
+        }
+
+        pub fn #write_name(queue: &wgpu::Queue, buffer: &wgpu::Buffer, value: &#rust_type) {
+          #write_at_name(queue, buffer, 0, value)
+        }
+
+        pub fn #write_at_name(
+          queue: &wgpu::Queue,
+          buffer: &wgpu::Buffer,
+          offset: wgpu::BufferAddress,
+          value: &#rust_type,
+        ) {
+          queue.write_buffer(buffer, offset, #contents);
+        }
+
+        #staged_fn
+        #dynamic_fn
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+/// Generates `encode_{binding}` and `decode_{binding}` for each storage binding of an array with
+/// the bytemuck strategy, converting a slice of its elements to and from its bytes with the WGSL
+/// stride, which exceeds the size of the Rust elements when they have no tail padding.
+pub fn array_codec_fns(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  naga_module: &naga::Module,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  if options.serialization_strategy.is_encase() {
+    return quote!();
+  }
+  let core = options.core_crate();
+  let alloc = options.alloc_crate();
+
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter(|binding| matches!(binding.address_space, naga::AddressSpace::Storage { .. }))
+    .filter_map(|binding| {
+      let naga::TypeInner::Array { base, stride, .. } = binding.binding_type.inner else {
+        return None;
+      };
+      let element = &naga_module.types[base];
+      let is_pod = match element.inner {
+        naga::TypeInner::Struct { .. } => {
+          is_serializable_struct(invoking_entry_module, options, naga_module, element)
+        }
+        naga::TypeInner::Scalar(_)
+        | naga::TypeInner::Vector { .. }
+        | naga::TypeInner::Matrix { .. }
+        | naga::TypeInner::Atomic(_) => true,
+        _ => false,
+      };
+      if !is_pod {
+        return None;
+      }
+
+      let ty = rust_type(Some(invoking_entry_module), naga_module, element, options);
+      let binding_name =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module)
+          .name;
+      let encode_name = format_ident!("encode_{}", binding_name.as_str());
+      let decode_name = format_ident!("decode_{}", binding_name.as_str());
+      let stride = Index::from(stride as usize);
+      let message = format!(
+        "the elements of `{binding_name}` are larger than their WGSL stride of {} bytes",
+        stride.index
+      );
+
+      Some(quote! {
+        /// Encodes `values` as the bytes of the binding, padding each element to the WGSL stride.
+        pub fn #encode_name(values: &[#ty]) -> #alloc::vec::Vec<u8> {
+          let size = #core::mem::size_of::<#ty>();
+          assert!(size <= #stride, #message);
+          let mut bytes = #alloc::vec![0; values.len() * #stride];
+          for (chunk, value) in bytes.chunks_exact_mut(#stride).zip(values) {
+            chunk[..size].copy_from_slice(bytemuck::bytes_of(value));
+          }
+          bytes
+        }
+
+        /// Decodes the elements of the binding from `bytes`, skipping the padding after each
+        /// element.
+        pub fn #decode_name(bytes: &[u8]) -> #alloc::vec::Vec<#ty> {
+          let size = #core::mem::size_of::<#ty>();
+          assert!(size <= #stride, #message);
+          bytes
+            .chunks_exact(#stride)
+            .map(|chunk| bytemuck::pod_read_unaligned(&chunk[..size]))
+            .collect()
+        }
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+/// Generates `read_{binding}` for each storage binding of a struct or of an array of structs,
+/// blocking while it reads back a buffer of it with `_support::read_buffer`, eg: for the results
+/// of compute shaders. Arrays are read as many elements as the buffer holds, with
+/// `decode_{binding}` for the bytemuck strategy.
+pub fn readback_fns(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  naga_module: &naga::Module,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  if options.no_std {
+    return quote!();
+  }
+
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter(|binding| matches!(binding.address_space, naga::AddressSpace::Storage { .. }))
+    .filter_map(|binding| {
+      let is_serializable =
+        |ty| is_serializable_struct(invoking_entry_module, options, naga_module, ty);
+      let rust_type =
+        |ty| rust_type(Some(invoking_entry_module), naga_module, ty, options);
+
+      let binding_name =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module)
+          .name;
+
+      let (output, decode) = match binding.binding_type.inner {
+        naga::TypeInner::Struct { .. } if is_serializable(binding.binding_type) => {
+          let ty = rust_type(binding.binding_type);
+          let decode = match options.serialization_strategy {
+            WgslTypeSerializeStrategy::Bytemuck => quote! {
+              bytemuck::pod_read_unaligned(&bytes[..std::mem::size_of::<#ty>()])
+            },
+            WgslTypeSerializeStrategy::Encase => {
+              quote!(encase::StorageBuffer::new(bytes).create().unwrap())
+            }
+          };
+          (quote!(#ty), decode)
+        }
+        naga::TypeInner::Array { base, .. }
+          if is_serializable(&naga_module.types[base]) =>
+        {
+          let ty = rust_type(&naga_module.types[base]);
+          let decode = match options.serialization_strategy {
+            WgslTypeSerializeStrategy::Bytemuck => {
+              let decode_name = format_ident!("decode_{}", binding_name.as_str());
+              quote!(#decode_name(&bytes))
+            }
+            WgslTypeSerializeStrategy::Encase => {
+              quote!(encase::StorageBuffer::new(bytes).create().unwrap())
+            }
+          };
+          (quote!(std::vec::Vec<#ty>), decode)
+        }
+        _ => return None,
+      };
+
+      let fn_name = format_ident!("read_{}", binding_name.as_str());
+
+      Some(quote! {
+        #[cfg(not(target_arch = "wasm32"))]
+        pub fn #fn_name(
+          device: &wgpu::Device,
+          queue: &wgpu::Queue,
+          buffer: &wgpu::Buffer,
+        ) -> Result<#output, wgpu::BufferAsyncError> {
+          let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+          Ok(#decode)
+        }
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+/// Generates `create_{binding}_indirect_buffer` for each storage binding the shader can write
+/// indirect commands to, sized for its commands, or for `count` commands for runtime sized
+/// arrays, and usable as the indirect buffer of draws.
+pub fn indirect_buffer_fns(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  naga_module: &naga::Module,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
+  let core = options.core_crate();
+  let usage = TypedBuffer::Storage.usage();
+
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter(|binding| match binding.address_space {
+      naga::AddressSpace::Storage { access } => {
+        access.contains(naga::StorageAccess::STORE)
+      }
+      _ => false,
+    })
+    .filter_map(|binding| {
+      let (indirect_args, count) = match binding.binding_type.inner {
+        naga::TypeInner::Array { base, size, .. } => {
+          let indirect_args = wgsl::indirect_args(
+            invoking_entry_module,
+            naga_module,
+            &naga_module.types[base],
+            options,
+          )?;
+          match size {
+            naga::ArraySize::Constant(size) => (indirect_args, Some(size.get())),
+            _ => (indirect_args, None),
+          }
+        }
+        _ => {
+          let indirect_args = wgsl::indirect_args(
+            invoking_entry_module,
+            naga_module,
+            binding.binding_type,
+            options,
+          )?;
+          (indirect_args, Some(1))
+        }
+      };
+      let args = indirect_args.quote_type();
+      let args_size = quote!(#core::mem::size_of::<#args>() as u64);
+      let (param, size) = match count {
+        Some(1) => (quote!(), args_size),
+        Some(count) => {
+          let count = Index::from(count as usize);
+          (quote!(), quote!(#count * #args_size))
+        }
+        None => (quote!(count: u64), quote!(count * #args_size)),
+      };
+
+      let binding_name =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module)
+          .name;
+      let fn_name = format_ident!("create_{}_indirect_buffer", binding_name.as_str());
+      let label = format!("{sanitized_entry_name}::{binding_name}");
+
+      Some(quote! {
+        pub fn #fn_name(device: &wgpu::Device, #param) -> wgpu::Buffer {
+          device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(#label),
+            size: #size,
+            usage: #usage | wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+          })
+        }
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+/// The offset and stride of the runtime sized array `ty` ends in, and the alignment of `ty`.
+fn runtime_sized_array_layout(
+  naga_module: &naga::Module,
+  ty: &naga::Type,
+) -> Option<(u32, u32, naga::proc::Alignment)> {
+  let (offset, array) = match &ty.inner {
+    naga::TypeInner::Struct { members, .. } => {
+      let last = members.last()?;
+      (last.offset, &naga_module.types[last.ty].inner)
+    }
+    inner => (0, inner),
+  };
+  let naga::TypeInner::Array {
+    size: naga::ArraySize::Dynamic,
+    stride,
+    ..
+  } = array
+  else {
+    return None;
+  };
+
+  let mut layouter = naga::proc::Layouter::default();
+  layouter.update(naga_module.to_ctx()).unwrap();
+  let alignment = layouter[naga_module.types.get(ty).unwrap()].alignment;
+
+  Some((offset, *stride, alignment))
+}
+
+/// Generates `{binding}_min_buffer_size` and `create_{binding}_buffer_for` for each storage
+/// binding ending in a runtime sized array, sizing a buffer for `n` elements of the array after
+/// the fixed size members before it.
+pub fn runtime_array_buffer_fns(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
+  let usage = TypedBuffer::Storage.usage();
+
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter(|binding| matches!(binding.address_space, naga::AddressSpace::Storage { .. }))
+    .filter_map(|binding| {
+      let (offset, stride, alignment) =
+        runtime_sized_array_layout(naga_module, binding.binding_type)?;
+
+      let binding_name =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module)
+          .name;
+      let size_fn = format_ident!("{}_min_buffer_size", binding_name.as_str());
+      let create_fn = format_ident!("create_{}_buffer_for", binding_name.as_str());
+      let label = format!("{sanitized_entry_name}::{binding_name}");
+
+      let is_aligned = alignment.is_aligned(offset) && alignment.is_aligned(stride);
+      let stride = Literal::u32_unsuffixed(stride);
+      let elements = quote!(n as u64 * #stride);
+      let size = match offset {
+        0 => elements,
+        offset => {
+          let offset = Literal::u32_unsuffixed(offset);
+          quote!(#offset + #elements)
+        }
+      };
+      // The size of a struct is a multiple of its alignment, which may exceed the stride.
+      let size = if is_aligned {
+        size
+      } else {
+        // naga only exposes the value of an alignment as the multiple 1 rounds up to.
+        let alignment = Literal::u32_unsuffixed(alignment.round_up(1));
+        quote!((#size).next_multiple_of(#alignment))
+      };
+
+      Some(quote! {
+        /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+        pub const fn #size_fn(n: usize) -> u64 {
+          #size
+        }
+
+        pub fn #create_fn(device: &wgpu::Device, n: usize) -> wgpu::Buffer {
+          device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(#label),
+            size: #size_fn(n),
+            usage: #usage,
+            mapped_at_creation: false,
+          })
+        }
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+/// Generates `{binding}_texture_descriptor` for each texture binding, a descriptor with the
+/// dimension, sample count and usages the shader implies, and the format of storage textures.
+pub fn texture_fns(
+  invoking_entry_module: &str,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter_map(|binding| {
+      let naga::TypeInner::Image {
+        dim,
+        arrayed,
+        class,
+      } = binding.binding_type.inner
+      else {
+        return None;
+      };
+
+      let binding_name =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module)
+          .name;
+      let fn_name = format_ident!("{}_texture_descriptor", binding_name.as_str());
+      let label = format!("{sanitized_entry_name}::{binding_name}");
+      // Cube textures are 2D textures with 6 layers per cube.
+      let dimension = match dim {
+        naga::ImageDimension::D1 => quote!(wgpu::TextureDimension::D1),
+        naga::ImageDimension::D2 | naga::ImageDimension::Cube => {
+          quote!(wgpu::TextureDimension::D2)
+        }
+        naga::ImageDimension::D3 => quote!(wgpu::TextureDimension::D3),
+      };
+
+      // Multisampled textures can only be written by render passes, so take the sample count
+      // instead of assuming one, and storage textures have the format of the shader.
+      let (params, format, sample_count, usage) = match class {
+        naga::ImageClass::Sampled { multi: false, .. } => (
+          quote!(format: wgpu::TextureFormat),
+          quote!(format),
+          quote!(sample_count: 1),
+          quote!(wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST),
+        ),
+        naga::ImageClass::Depth { multi: false } => (
+          quote!(format: wgpu::TextureFormat),
+          quote!(format),
+          quote!(sample_count: 1),
+          quote!(
+            wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT
+          ),
+        ),
+        naga::ImageClass::Sampled { multi: true, .. }
+        | naga::ImageClass::Depth { multi: true } => (
+          quote!(format: wgpu::TextureFormat, sample_count: u32),
+          quote!(format),
+          quote!(sample_count),
+          quote!(
+            wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT
+          ),
+        ),
+        naga::ImageClass::Storage { format, .. } => {
+          let format = syn::Ident::new(&format!("{format:?}"), Span::call_site());
+          (
+            quote!(),
+            quote!(format: wgpu::TextureFormat::#format),
+            quote!(sample_count: 1),
+            quote!(wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_DST),
+          )
+        }
+      };
+
+      // The view has to match the binding, eg: only the depth aspect of a depth-stencil texture
+      // can be sampled, and array textures are bound from a range of their layers.
+      let view_fn_name = format_ident!("create_view_for_{}", binding_name.as_str());
+      let view_dimension = texture_view_dimension(dim, arrayed);
+      let aspect = match class {
+        naga::ImageClass::Depth { .. } => quote!(wgpu::TextureAspect::DepthOnly),
+        _ => quote!(wgpu::TextureAspect::All),
+      };
+      let (layer_params, layers) = if arrayed {
+        (
+          quote!(base_array_layer: u32, array_layer_count: u32),
+          quote! {
+            base_array_layer,
+            array_layer_count: Some(array_layer_count),
+          },
+        )
+      } else {
+        (quote!(), quote!())
+      };
+
+      Some(quote! {
+        pub fn #fn_name(
+          size: wgpu::Extent3d,
+          #params
+        ) -> wgpu::TextureDescriptor<'static> {
+          wgpu::TextureDescriptor {
+            label: Some(#label),
+            size,
+            mip_level_count: 1,
+            #sample_count,
+            dimension: #dimension,
+            #format,
+            usage: #usage,
+            view_formats: &[],
+          }
+        }
+
+        pub fn #view_fn_name(
+          texture: &wgpu::Texture,
+          #layer_params
+        ) -> wgpu::TextureView {
+          texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some(#label),
+            dimension: Some(#view_dimension),
+            aspect: #aspect,
+            #layers
+            ..Default::default()
+          })
+        }
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+pub fn sampler_fns(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
+  let fns = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter_map(|binding| {
+      let naga::TypeInner::Sampler { comparison } = binding.binding_type.inner else {
+        return None;
+      };
+
+      let item_path =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+      let preset = options.sampler_preset(&item_path.get_fully_qualified_name())?;
+
+      // Samplers are usually named after their use already, eg: `color_sampler`.
+      let binding_name = item_path.name.as_str();
+      let name = binding_name
+        .strip_suffix("_sampler")
+        .unwrap_or(binding_name);
+      let fn_name = format_ident!("create_{name}_sampler");
+      let label = format!("{sanitized_entry_name}::{binding_name}");
+      let variant = |variant: &dyn std::fmt::Debug| {
+        syn::Ident::new(&format!("{variant:?}"), Span::call_site())
+      };
+      let filter = variant(&preset.filter);
+      let address_mode = variant(&preset.address_mode);
+      // Only comparison samplers can be bound to `sampler_comparison` bindings, and the other
+      // bindings can't take them.
+      let compare = comparison.then(|| {
+        let compare = preset.compare.unwrap_or(wgpu::CompareFunction::LessEqual);
+        let compare = variant(&compare);
+        quote!(compare: Some(wgpu::CompareFunction::#compare),)
+      });
+
+      Some(quote! {
+        pub fn #fn_name(device: &wgpu::Device) -> wgpu::Sampler {
+          device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(#label),
+            address_mode_u: wgpu::AddressMode::#address_mode,
+            address_mode_v: wgpu::AddressMode::#address_mode,
+            address_mode_w: wgpu::AddressMode::#address_mode,
+            mag_filter: wgpu::FilterMode::#filter,
+            min_filter: wgpu::FilterMode::#filter,
+            mipmap_filter: wgpu::FilterMode::#filter,
+            #compare
+            ..Default::default()
+          })
+        }
+      })
+    });
+
+  quote!(#(#fns)*)
+}
+
+fn texture_view_dimension(dim: naga::ImageDimension, arrayed: bool) -> TokenStream {
+  match (dim, arrayed) {
+    (naga::ImageDimension::D1, _) => quote!(wgpu::TextureViewDimension::D1),
+    (naga::ImageDimension::D2, false) => quote!(wgpu::TextureViewDimension::D2),
+    (naga::ImageDimension::D2, true) => quote!(wgpu::TextureViewDimension::D2Array),
+    (naga::ImageDimension::D3, _) => quote!(wgpu::TextureViewDimension::D3),
+    (naga::ImageDimension::Cube, false) => quote!(wgpu::TextureViewDimension::Cube),
+    (naga::ImageDimension::Cube, true) => quote!(wgpu::TextureViewDimension::CubeArray),
+  }
+}
+
 fn bind_group_layout_entry(
   invoking_entry_module: &str,
   naga_module: &naga::Module,
@@ -293,7 +1236,7 @@ fn bind_group_layout_entry(
         options,
       );
 
-      let min_binding_size = rust_type.quote_min_binding_size();
+      let min_binding_size = rust_type.quote_min_binding_size(options);
 
       quote!(wgpu::BindingType::Buffer {
           ty: #buffer_binding_type,
@@ -301,13 +1244,12 @@ fn bind_group_layout_entry(
           min_binding_size: #min_binding_size,
       })
     }
-    naga::TypeInner::Image { dim, class, .. } => {
-      let view_dim = match dim {
-        naga::ImageDimension::D1 => quote!(wgpu::TextureViewDimension::D1),
-        naga::ImageDimension::D2 => quote!(wgpu::TextureViewDimension::D2),
-        naga::ImageDimension::D3 => quote!(wgpu::TextureViewDimension::D3),
-        naga::ImageDimension::Cube => quote!(wgpu::TextureViewDimension::Cube),
-      };
+    naga::TypeInner::Image {
+      dim,
+      arrayed,
+      class,
+    } => {
+      let view_dim = texture_view_dimension(dim, arrayed);
 
       match class {
         naga::ImageClass::Sampled { kind, multi } => {
@@ -432,6 +1374,31 @@ pub fn get_bind_group_data(
   }
 }
 
+/// The bind groups to generate for the module, leaving out the unused bindings which can be
+/// stripped when `strip_unused_bindings` is set, along with the groups left empty.
+pub fn generated_bind_group_data<'a>(
+  module: &'a naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  options: &WgslBindgenOption,
+) -> Result<BTreeMap<u32, GroupData<'a>>, CreateModuleError> {
+  let mut groups = get_bind_group_data(module)?;
+  if !options.strip_unused_bindings {
+    return Ok(groups);
+  }
+
+  for handle in naga_util::strippable_bindings(module, info) {
+    let binding = module.global_variables[handle].binding.as_ref().unwrap();
+    if let Some(group) = groups.get_mut(&binding.group) {
+      group
+        .bindings
+        .retain(|b| b.binding_index != binding.binding);
+    }
+  }
+  groups.retain(|_, group| !group.bindings.is_empty());
+
+  Ok(groups)
+}
+
 #[cfg(test)]
 mod tests {
   use indoc::indoc;
@@ -612,17 +1579,12 @@ mod tests {
                   device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
               }
               pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup0Entries) -> Self {
-                  let bind_group_layout = Self::get_bind_group_layout(&device);
-                  let entries = bindings.as_array();
-                  let bind_group = device
-                      .create_bind_group(
-                          &wgpu::BindGroupDescriptor {
-                              label: Some("Test::BindGroup0"),
-                              layout: &bind_group_layout,
-                              entries: &entries,
-                          },
-                      );
-                  Self(bind_group)
+                  Self(_root::_support::create_bind_group(
+                      device,
+                      &Self::LAYOUT_DESCRIPTOR,
+                      "Test::BindGroup0",
+                      &bindings.as_array(),
+                  ))
               }
               pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
@@ -678,17 +1640,12 @@ mod tests {
                   device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
               }
               pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup1Entries) -> Self {
-                  let bind_group_layout = Self::get_bind_group_layout(&device);
-                  let entries = bindings.as_array();
-                  let bind_group = device
-                      .create_bind_group(
-                          &wgpu::BindGroupDescriptor {
-                              label: Some("Test::BindGroup1"),
-                              layout: &bind_group_layout,
-                              entries: &entries,
-                          },
-                      );
-                  Self(bind_group)
+                  Self(_root::_support::create_bind_group(
+                      device,
+                      &Self::LAYOUT_DESCRIPTOR,
+                      "Test::BindGroup1",
+                      &bindings.as_array(),
+                  ))
               }
               pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
                   render_pass.set_bind_group(1, &self.0, &[]);
@@ -1022,17 +1979,12 @@ mod tests {
                   device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
               }
               pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup0Entries) -> Self {
-                  let bind_group_layout = Self::get_bind_group_layout(&device);
-                  let entries = bindings.as_array();
-                  let bind_group = device
-                      .create_bind_group(
-                          &wgpu::BindGroupDescriptor {
-                              label: Some("Test::BindGroup0"),
-                              layout: &bind_group_layout,
-                              entries: &entries,
-                          },
-                      );
-                  Self(bind_group)
+                  Self(_root::_support::create_bind_group(
+                      device,
+                      &Self::LAYOUT_DESCRIPTOR,
+                      "Test::BindGroup0",
+                      &bindings.as_array(),
+                  ))
               }
               pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
@@ -1106,17 +2058,12 @@ mod tests {
                   device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
               }
               pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup1Entries) -> Self {
-                  let bind_group_layout = Self::get_bind_group_layout(&device);
-                  let entries = bindings.as_array();
-                  let bind_group = device
-                      .create_bind_group(
-                          &wgpu::BindGroupDescriptor {
-                              label: Some("Test::BindGroup1"),
-                              layout: &bind_group_layout,
-                              entries: &entries,
-                          },
-                      );
-                  Self(bind_group)
+                  Self(_root::_support::create_bind_group(
+                      device,
+                      &Self::LAYOUT_DESCRIPTOR,
+                      "Test::BindGroup1",
+                      &bindings.as_array(),
+                  ))
               }
               pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(1, &self.0, &[]);
@@ -1224,17 +2171,12 @@ mod tests {
                   device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
               }
               pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup0Entries) -> Self {
-                  let bind_group_layout = Self::get_bind_group_layout(&device);
-                  let entries = bindings.as_array();
-                  let bind_group = device
-                      .create_bind_group(
-                          &wgpu::BindGroupDescriptor {
-                              label: Some("Test::BindGroup0"),
-                              layout: &bind_group_layout,
-                              entries: &entries,
-                          },
-                      );
-                  Self(bind_group)
+                  Self(_root::_support::create_bind_group(
+                      device,
+                      &Self::LAYOUT_DESCRIPTOR,
+                      "Test::BindGroup0",
+                      &bindings.as_array(),
+                  ))
               }
               pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
@@ -1337,17 +2279,12 @@ mod tests {
                   device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
               }
               pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup0Entries) -> Self {
-                  let bind_group_layout = Self::get_bind_group_layout(&device);
-                  let entries = bindings.as_array();
-                  let bind_group = device
-                      .create_bind_group(
-                          &wgpu::BindGroupDescriptor {
-                              label: Some("Test::BindGroup0"),
-                              layout: &bind_group_layout,
-                              entries: &entries,
-                          },
-                      );
-                  Self(bind_group)
+                  Self(_root::_support::create_bind_group(
+                      device,
+                      &Self::LAYOUT_DESCRIPTOR,
+                      "Test::BindGroup0",
+                      &bindings.as_array(),
+                  ))
               }
               pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
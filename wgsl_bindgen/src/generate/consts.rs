@@ -121,7 +121,16 @@ pub fn pipeline_overridable_constants(
     quote!(let mut entries = std::collections::HashMap::from([#(#required_entries),*]);)
   };
 
-  if !fields.is_empty() {
+  if fields.is_empty() {
+    quote!()
+  } else if options.no_std {
+    // wgpu takes the constants as a `std::collections::HashMap`, so only the values can be described.
+    quote! {
+        pub struct OverrideConstants {
+            #(#fields),*
+        }
+    }
+  } else {
     // Create a Rust struct that can initialize the constants dictionary.
     quote! {
         pub struct OverrideConstants {
@@ -137,8 +146,6 @@ pub fn pipeline_overridable_constants(
             }
         }
     }
-  } else {
-    quote!()
   }
 }
 
@@ -5,9 +5,9 @@ use quote::quote;
 use syn::{Ident, Index};
 
 use crate::quote_gen::{RustItem, RustItemType};
-use crate::wgsl;
+use crate::{wgsl, WgslBindgenOption};
 
-fn fragment_target_count(module: &naga::Module, f: &naga::Function) -> usize {
+pub(crate) fn fragment_target_count(module: &naga::Module, f: &naga::Function) -> usize {
   match &f.result {
     Some(r) => match &r.binding {
       Some(b) => {
@@ -33,16 +33,22 @@ fn fragment_target_count(module: &naga::Module, f: &naga::Function) -> usize {
   }
 }
 
-pub fn entry_point_constants(module: &naga::Module) -> TokenStream {
+/// Pipeline-overridable constants are passed to wgpu as a `std::collections::HashMap`, so they can't be used with `no_std`.
+fn takes_overrides(module: &naga::Module, options: &WgslBindgenOption) -> bool {
+  !module.overrides.is_empty() && !options.no_std
+}
+
+pub fn entry_point_constants(
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> TokenStream {
   let entry_points: Vec<TokenStream> = module
     .entry_points
     .iter()
     .map(|entry_point| {
       let entry_name = Literal::string(&entry_point.name);
-      let const_name = Ident::new(
-        &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
-        Span::call_site(),
-      );
+      let const_name =
+        Ident::new(&options.entry_point_const(&entry_point.name), Span::call_site());
       quote! {
           pub const #const_name: &str = #entry_name;
       }
@@ -54,21 +60,58 @@ pub fn entry_point_constants(module: &naga::Module) -> TokenStream {
   }
 }
 
-pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> TokenStream {
+pub fn vertex_states(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> TokenStream {
   let vertex_input_structs =
     wgsl::get_vertex_input_structs(invoking_entry_module, module);
 
   let mut step_mode_params = vec![];
+  let mut default_layout_expressions = vec![];
   let layout_expressions: Vec<TokenStream> = vertex_input_structs
     .iter()
     .map(|input| {
       let struct_ref = input.item_path.short_token_stream(invoking_entry_module);
+      let fully_qualified_name = input.item_path.get_fully_qualified_name();
+      if options.is_instance_vertex_input(&fully_qualified_name) {
+        let layout =
+          quote!(#struct_ref::vertex_buffer_layout(wgpu::VertexStepMode::Instance));
+        default_layout_expressions.push(layout.clone());
+        return layout;
+      }
+
       let step_mode = Ident::new(&input.item_path.name.to_snake(), Span::call_site());
       step_mode_params.push(quote!(#step_mode: wgpu::VertexStepMode));
+      default_layout_expressions.push(quote!(
+        #struct_ref::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)
+      ));
       quote!(#struct_ref::vertex_buffer_layout(#step_mode))
     })
     .collect();
 
+  // A per-vertex and a per-instance input get helpers with the step modes already set. The
+  // per-instance input is either in `instance_vertex_inputs` or told apart by its name,
+  // eg: `InstanceInput`.
+  let is_instance_vertex_input = |input: &wgsl::VertexInput| {
+    options.is_instance_vertex_input(&input.item_path.get_fully_qualified_name())
+  };
+  let instance_slot = match vertex_input_structs.as_slice() {
+    [first, second] => {
+      let is_instance = |input: &wgsl::VertexInput| {
+        is_instance_vertex_input(input)
+          || input.item_path.name.to_lowercase().contains("instance")
+      };
+      match (is_instance(first), is_instance(second)) {
+        (false, true) => Some(1),
+        (true, false) => Some(0),
+        _ => None,
+      }
+    }
+    _ => None,
+  };
+
   let vertex_entries: Vec<TokenStream> = module
     .entry_points
     .iter()
@@ -77,25 +120,21 @@ pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> Toke
         let fn_name =
           Ident::new(&format!("{}_entry", &entry_point.name), Span::call_site());
 
-        let const_name = Ident::new(
-          &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
-          Span::call_site(),
-        );
+        let const_name =
+          Ident::new(&options.entry_point_const(&entry_point.name), Span::call_site());
 
         let n = vertex_input_structs.len();
         let n = Literal::usize_unsuffixed(n);
 
-        let overrides = if !module.overrides.is_empty() {
-          Some(quote!(overrides: &OverrideConstants))
-        } else {
-          None
-        };
+        let overrides =
+          takes_overrides(module, options).then(|| quote!(overrides: &OverrideConstants));
 
-        let constants = if !module.overrides.is_empty() {
+        let constants = if takes_overrides(module, options) {
           quote!(overrides.constants())
         } else {
           quote!(Default::default())
         };
+        let constants = (!options.no_std).then(|| quote!(constants: #constants));
 
         let params = if step_mode_params.is_empty() {
           quote!(#overrides)
@@ -103,6 +142,77 @@ pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> Toke
           quote!(#(#step_mode_params),*, #overrides)
         };
 
+        // The constants of an entry are a `HashMap`, which can't be created in a const fn, so
+        // the const form is a `ConstVertexEntry` without them.
+        let default_entry = (!step_mode_params.is_empty()).then(|| {
+          let default_fn_name = Ident::new(
+            &format!("{}_entry_default", &entry_point.name),
+            Span::call_site(),
+          );
+          let const_fn_name = Ident::new(
+            &format!("{}_const_entry", &entry_point.name),
+            Span::call_site(),
+          );
+          let doc = format!(
+            " `{fn_name}` with `wgpu::VertexStepMode::Vertex` for all of its step modes."
+          );
+          let const_doc = format!(
+            " The buffers of `{default_fn_name}` in a const fn, eg: to keep them in a static."
+          );
+
+          quote! {
+              #[doc = #doc]
+              pub fn #default_fn_name(#overrides) -> VertexEntry<#n> {
+                  VertexEntry {
+                      entry_point: #const_name,
+                      buffers: [
+                          #(#default_layout_expressions),*
+                      ],
+                      #constants
+                  }
+              }
+              #[doc = #const_doc]
+              pub const fn #const_fn_name() -> ConstVertexEntry<#n> {
+                  ConstVertexEntry {
+                      entry_point: #const_name,
+                      buffers: [
+                          #(#default_layout_expressions),*
+                      ],
+                  }
+              }
+          }
+        });
+
+        let instanced_entry = instance_slot.map(|instance_slot| {
+          let instanced_fn_name = Ident::new(
+            &format!("{}_instanced_entry", &entry_point.name),
+            Span::call_site(),
+          );
+          // Inputs in `instance_vertex_inputs` don't take a step mode.
+          let step_modes = vertex_input_structs
+            .iter()
+            .enumerate()
+            .filter(|(_, input)| !is_instance_vertex_input(input))
+            .map(|(slot, _)| match slot == instance_slot {
+              true => quote!(wgpu::VertexStepMode::Instance),
+              false => quote!(wgpu::VertexStepMode::Vertex),
+            });
+          let override_arg = overrides.is_some().then(|| quote!(overrides));
+          let args = step_modes.chain(override_arg);
+          let doc = format!(
+            " `{fn_name}` stepping through `{}` per vertex and `{}` per instance.",
+            vertex_input_structs[1 - instance_slot].item_path.name,
+            vertex_input_structs[instance_slot].item_path.name,
+          );
+
+          quote! {
+              #[doc = #doc]
+              pub fn #instanced_fn_name(#overrides) -> VertexEntry<2> {
+                  #fn_name(#(#args),*)
+              }
+          }
+        });
+
         Some(quote! {
             pub fn #fn_name(#params) -> VertexEntry<#n> {
                 VertexEntry {
@@ -110,9 +220,12 @@ pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> Toke
                     buffers: [
                         #(#layout_expressions),*
                     ],
-                    constants: #constants
+                    #constants
                 }
             }
+
+            #default_entry
+            #instanced_entry
         })
       }
       _ => None,
@@ -123,30 +236,33 @@ pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> Toke
   if vertex_entries.is_empty() {
     quote!()
   } else {
-    quote! {
-        #[derive(Debug)]
-        pub struct VertexEntry<const N: usize> {
-            pub entry_point: &'static str,
-            pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-            pub constants: std::collections::HashMap<String, f64>,
-        }
+    let set_buffers = instance_slot.map(|instance_slot| {
+      let vertex_slot = Literal::usize_unsuffixed(1 - instance_slot);
+      let instance_slot_index = Literal::usize_unsuffixed(instance_slot);
+      let doc = format!(
+        " Sets the buffers of `{}` and `{}` to the slots of the `*_instanced_entry` layouts.",
+        vertex_input_structs[1 - instance_slot].item_path.name,
+        vertex_input_structs[instance_slot].item_path.name,
+      );
 
-        pub fn vertex_state<'a, const N: usize>(
-            module: &'a wgpu::ShaderModule,
-            entry: &'a VertexEntry<N>,
-        ) -> wgpu::VertexState<'a> {
-            wgpu::VertexState {
-                module,
-                entry_point: Some(entry.entry_point),
-                buffers: &entry.buffers,
-                compilation_options: wgpu::PipelineCompilationOptions {
-                  constants: &entry.constants,
-                  ..Default::default()
-                },
-            }
-        }
+      quote! {
+          #[doc = #doc]
+          pub fn set_buffers(
+              pass: &mut wgpu::RenderPass<'_>,
+              vertices: wgpu::BufferSlice<'_>,
+              instances: wgpu::BufferSlice<'_>,
+          ) {
+              pass.set_vertex_buffer(#vertex_slot, vertices);
+              pass.set_vertex_buffer(#instance_slot_index, instances);
+          }
+      }
+    });
+
+    quote! {
+          pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
 
         #(#vertex_entries)*
+        #set_buffers
     }
   }
 }
@@ -154,18 +270,26 @@ pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> Toke
 pub fn vertex_struct_impls(
   invoking_entry_module: &str,
   module: &naga::Module,
+  options: &WgslBindgenOption,
 ) -> Vec<RustItem> {
-  let structs = vertex_input_structs_impls(invoking_entry_module, module);
+  let structs = vertex_input_structs_impls(invoking_entry_module, module, options);
   structs
 }
 
 fn vertex_input_structs_impls(
   invoking_entry_module: &str,
   module: &naga::Module,
+  options: &WgslBindgenOption,
 ) -> Vec<RustItem> {
+  let core = options.core_crate();
   let vertex_inputs = wgsl::get_vertex_input_structs(invoking_entry_module, module);
   vertex_inputs.iter().map(|input|  {
     let name = Ident::new(&input.item_path.name, Span::call_site());
+    let fully_qualified_name = input.item_path.get_fully_qualified_name();
+
+    // Inputs read from a user provided vertex struct get a type holding their layout instead.
+    let vertex_struct = options.vertex_struct_override(&fully_qualified_name);
+    let source = vertex_struct.cloned().unwrap_or_else(|| quote!(Self));
 
     // Use index to avoid adding prefix to literals.
     let count = Index::from(input.fields.len());
@@ -175,14 +299,14 @@ fn vertex_input_structs_impls(
         .map(|(location, m)| {
             let field_name: TokenStream = m.name.as_ref().unwrap().parse().unwrap();
             let location = Index::from(*location as usize);
-            let format = wgsl::vertex_format(&module.types[m.ty]);
+            let format = wgsl::vertex_attribute_format(&fully_qualified_name, m, module, options);
             // TODO: Will the debug implementation always work with the macro?
             let format = Ident::new(&format!("{format:?}"), Span::call_site());
 
             quote! {
                 wgpu::VertexAttribute {
                     format: wgpu::VertexFormat::#format,
-                    offset: std::mem::offset_of!(Self, #field_name) as u64,
+                    offset: #core::mem::offset_of!(#source, #field_name) as u64,
                     shader_location: #location,
                 }
             }
@@ -198,26 +322,212 @@ fn vertex_input_structs_impls(
     // TODO: Should this enforce WebGPU alignment requirements for compatibility?
     // https://gpuweb.github.io/gpuweb/#abstract-opdef-validating-gpuvertexbufferlayout
 
-    // TODO: Support vertex inputs that aren't in a struct.
+    // The stride of `override_vertex_stride` is quoted as a `u64` literal, eg: `40u64`.
+    let array_stride = match options.vertex_stride_override(&fully_qualified_name) {
+        Some(stride) => quote!(#stride),
+        None => quote!(#core::mem::size_of::<#source>() as u64),
+    };
+    let layout_source = vertex_struct.cloned().unwrap_or_else(|| quote!(#name));
+    let layout_asserts = vertex_layout_assertions(layout_source, &fully_qualified_name, input, module, options);
+
+    let definition = vertex_struct.map(|ty| {
+        let doc = format!(" The vertex layout of `{name}`, read from `{}`.", ty.to_string().replace(' ', ""));
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug)]
+            pub struct #name;
+        }
+    });
+
     let ts = quote! {
+        #definition
+
         impl #name {
-            pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; #count] = [#(#attributes),*];
+            pub const ATTRIBUTE_COUNT: usize = #count;
+            pub const STRIDE: u64 = #array_stride;
+            pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [#(#attributes),*];
 
             pub const fn vertex_buffer_layout(step_mode: wgpu::VertexStepMode) -> wgpu::VertexBufferLayout<'static> {
                 wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<Self>() as u64,
+                    array_stride: Self::STRIDE,
                     step_mode,
                     attributes: &Self::VERTEX_ATTRIBUTES
                 }
             }
         }
+
+        #layout_asserts
     };
 
-    RustItem { types: RustItemType::TypeImpls.into(), path: input.item_path.clone(), item: ts }
+    let types = match vertex_struct {
+        Some(_) => RustItemType::TypeDefs | RustItemType::TypeImpls,
+        None => RustItemType::TypeImpls.into(),
+    };
+    RustItem { types, path: input.item_path.clone(), item: ts }
     }).collect()
 }
 
-pub fn fragment_states(module: &naga::Module) -> TokenStream {
+/// The vertex input rules of WebGPU broken when generating the bindings, eg: a format the shader
+/// can't read the attribute with. The rules depending on the Rust layout of the vertex structs
+/// are checked when compiling the bindings instead, see `vertex_layout_assertions`.
+pub(crate) fn vertex_layout_errors(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Vec<String> {
+  let mut errors = Vec::new();
+  for input in wgsl::get_vertex_input_structs(invoking_entry_module, module) {
+    let name = input.item_path.get_fully_qualified_name();
+    for (location, member) in &input.fields {
+      let ty = &module.types[member.ty];
+      let scalar = match &ty.inner {
+        naga::TypeInner::Scalar(scalar) | naga::TypeInner::Vector { scalar, .. } => {
+          scalar
+        }
+        _ => continue,
+      };
+
+      let format = wgsl::vertex_attribute_format(&name, member, module, options);
+      if wgsl::vertex_format_scalar_kind(format) != scalar.kind {
+        let field = member.name.as_deref().unwrap_or_default();
+        errors.push(format!(
+          "`{name}::{field}` at @location({location}) can't be read as `{}` from `{format:?}` data",
+          wgsl::type_name(module, ty)
+        ));
+      }
+    }
+  }
+  errors
+}
+
+/// The vertex inputs read from `Float64` data, which requires `wgpu::Features::VERTEX_ATTRIBUTE_64BIT`:
+/// creating a pipeline reading them panics on the backends without it, ie: all but Vulkan.
+pub(crate) fn float64_vertex_attributes(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Vec<String> {
+  use wgpu::VertexFormat as F;
+
+  let mut attributes = Vec::new();
+  for input in wgsl::get_vertex_input_structs(invoking_entry_module, module) {
+    let name = input.item_path.get_fully_qualified_name();
+    for (location, member) in &input.fields {
+      let format = wgsl::vertex_attribute_format(&name, member, module, options);
+      if matches!(format, F::Float64 | F::Float64x2 | F::Float64x3 | F::Float64x4) {
+        let field = member.name.as_deref().unwrap_or_default();
+        attributes.push(format!(
+          "`{name}::{field}` at @location({location}) is read from `{format:?}` data, which requires `wgpu::Features::VERTEX_ATTRIBUTE_64BIT`"
+        ));
+      }
+    }
+  }
+  attributes
+}
+
+/// The array stride of the vertex struct `source`, its size unless `override_vertex_stride` sets it.
+fn vertex_stride(
+  source: TokenStream,
+  fully_qualified_name: &str,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let core = options.core_crate();
+  match options.vertex_stride_override(fully_qualified_name) {
+    Some(stride) => {
+      let stride = Index::from(stride as usize);
+      quote!(#stride)
+    }
+    None => quote!(#core::mem::size_of::<#source>()),
+  }
+}
+
+/// A constant checking the layout of the vertex struct `source` against the vertex buffer
+/// rules of WebGPU when compiling, since the offsets of the fields are only known to Rust.
+fn vertex_layout_assertions(
+  source: TokenStream,
+  fully_qualified_name: &str,
+  input: &wgsl::VertexInput,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let core = options.core_crate();
+  let const_name = Ident::new(
+    &format!("{}_LAYOUT_ASSERTS", input.item_path.name.to_snake().to_uppercase()),
+    Span::call_site(),
+  );
+  let max_stride = wgpu::Limits::default().max_vertex_buffer_array_stride;
+
+  let stride = vertex_stride(source.clone(), fully_qualified_name, options);
+  let stride_multiple =
+    format!("the array stride of `{fully_qualified_name}` must be a multiple of 4 bytes");
+  let stride_limit = format!(
+    "the array stride of `{fully_qualified_name}` must be at most {max_stride} bytes"
+  );
+  let max_stride = Index::from(max_stride as usize);
+
+  let attributes: Vec<_> = input
+    .fields
+    .iter()
+    .map(|(_, member)| {
+      let field = member.name.as_deref().unwrap_or_default();
+      let format =
+        wgsl::vertex_attribute_format(fully_qualified_name, member, module, options);
+      let field_name: TokenStream = field.parse().unwrap();
+      (field, quote!(#core::mem::offset_of!(#source, #field_name)), format.size())
+    })
+    .collect();
+
+  let attribute_asserts = attributes.iter().map(|(field, offset, size)| {
+    let alignment = u64::min(4, *size);
+    let aligned = format!(
+      "`{fully_qualified_name}::{field}` must be aligned to {alignment} bytes in the vertex buffer"
+    );
+    let in_stride =
+      format!("`{fully_qualified_name}::{field}` must be read within the array stride");
+    let alignment = Index::from(alignment as usize);
+    let size = Index::from(*size as usize);
+    quote! {
+      assert!(#offset % #alignment == 0, #aligned);
+      assert!(#offset + #size <= #stride, #in_stride);
+    }
+  });
+
+  // Attributes reading the same bytes, eg: when a field type override is smaller than the format.
+  let overlap_asserts = attributes
+    .iter()
+    .enumerate()
+    .flat_map(|(i, (field, offset, size))| {
+      attributes[i + 1..]
+        .iter()
+        .map(move |(other_field, other_offset, other_size)| {
+          let message = format!(
+            "`{fully_qualified_name}::{field}` and `{other_field}` must not overlap in the vertex buffer"
+          );
+          let size = Index::from(*size as usize);
+          let other_size = Index::from(*other_size as usize);
+          quote! {
+            assert!(
+              #offset + #size <= #other_offset || #other_offset + #other_size <= #offset,
+              #message
+            );
+          }
+        })
+    });
+
+  quote! {
+    const #const_name: () = {
+      assert!(#stride % 4 == 0, #stride_multiple);
+      assert!(#stride <= #max_stride, #stride_limit);
+      #(#attribute_asserts)*
+      #(#overlap_asserts)*
+    };
+  }
+}
+
+pub fn fragment_states(
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> TokenStream {
   let entries: Vec<TokenStream> = module
     .entry_points
     .iter()
@@ -226,26 +536,22 @@ pub fn fragment_states(module: &naga::Module) -> TokenStream {
         let fn_name =
           Ident::new(&format!("{}_entry", &entry_point.name), Span::call_site());
 
-        let const_name = Ident::new(
-          &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
-          Span::call_site(),
-        );
+        let const_name =
+          Ident::new(&options.entry_point_const(&entry_point.name), Span::call_site());
 
         // Use index to avoid adding prefix to literals.
         let target_count =
           Index::from(fragment_target_count(module, &entry_point.function));
 
-        let overrides = if !module.overrides.is_empty() {
-          Some(quote!(overrides: &OverrideConstants))
-        } else {
-          None
-        };
+        let overrides =
+          takes_overrides(module, options).then(|| quote!(overrides: &OverrideConstants));
 
-        let constants = if !module.overrides.is_empty() {
+        let constants = if takes_overrides(module, options) {
           quote!(overrides.constants())
         } else {
           quote!(Default::default())
         };
+        let constants = (!options.no_std).then(|| quote!(constants: #constants));
 
         Some(quote! {
             pub fn #fn_name(
@@ -255,7 +561,7 @@ pub fn fragment_states(module: &naga::Module) -> TokenStream {
                 FragmentEntry {
                     entry_point: #const_name,
                     targets,
-                    constants: #constants
+                    #constants
                 }
             }
         })
@@ -269,27 +575,7 @@ pub fn fragment_states(module: &naga::Module) -> TokenStream {
     quote!()
   } else {
     quote! {
-        #[derive(Debug)]
-        pub struct FragmentEntry<const N: usize> {
-            pub entry_point: &'static str,
-            pub targets: [Option<wgpu::ColorTargetState>; N],
-            pub constants: std::collections::HashMap<String, f64>,
-        }
-
-        pub fn fragment_state<'a, const N: usize>(
-            module: &'a wgpu::ShaderModule,
-            entry: &'a FragmentEntry<N>,
-        ) -> wgpu::FragmentState<'a> {
-            wgpu::FragmentState {
-                module,
-                entry_point: Some(entry.entry_point),
-                targets: &entry.targets,
-                compilation_options: wgpu::PipelineCompilationOptions {
-                    constants: &entry.constants,
-                    ..Default::default()
-                },
-            }
-        }
+          pub use _root::_support::{FragmentEntry, fragment_state};
 
         #(#entries)*
     }
@@ -299,6 +585,7 @@ pub fn fragment_states(module: &naga::Module) -> TokenStream {
 #[cfg(test)]
 mod test {
   use indoc::indoc;
+  use regex::Regex;
 
   use super::*;
   use crate::assert_tokens_eq;
@@ -311,7 +598,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -334,7 +621,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -342,7 +629,9 @@ mod test {
     assert_tokens_eq!(
       quote! {
           impl VertexInput0 {
-              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 4] = [
+              pub const ATTRIBUTE_COUNT: usize = 4;
+              pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
                   wgpu::VertexAttribute {
                       format: wgpu::VertexFormat::Float32,
                       offset: std::mem::offset_of!(Self, a) as u64,
@@ -368,12 +657,48 @@ mod test {
                   step_mode: wgpu::VertexStepMode,
               ) -> wgpu::VertexBufferLayout<'static> {
                   wgpu::VertexBufferLayout {
-                      array_stride: std::mem::size_of::<Self>() as u64,
+                      array_stride: Self::STRIDE,
                       step_mode,
                       attributes: &Self::VERTEX_ATTRIBUTES,
                   }
               }
           }
+          const VERTEX_INPUT0_LAYOUT_ASSERTS: () = {
+              assert!(std::mem::size_of::<VertexInput0>() % 4 == 0, "the array stride of `test::VertexInput0` must be a multiple of 4 bytes");
+              assert!(std::mem::size_of::<VertexInput0>() <= 2048, "the array stride of `test::VertexInput0` must be at most 2048 bytes");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, b) % 4 == 0, "`test::VertexInput0::b` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::b` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, c) % 4 == 0, "`test::VertexInput0::c` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::c` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, d) % 4 == 0, "`test::VertexInput0::d` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::d` must be read within the array stride");
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, b) || std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `b` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, c) || std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `c` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `d` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::offset_of!(VertexInput0, c) || std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::offset_of!(VertexInput0, b),
+                  "`test::VertexInput0::b` and `c` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::offset_of!(VertexInput0, b),
+                  "`test::VertexInput0::b` and `d` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::offset_of!(VertexInput0, c),
+                  "`test::VertexInput0::c` and `d` must not overlap in the vertex buffer"
+              );
+          };
       },
       actual
     );
@@ -394,7 +719,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -402,7 +727,9 @@ mod test {
     assert_tokens_eq!(
       quote! {
           impl VertexInput0 {
-              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 4] = [
+              pub const ATTRIBUTE_COUNT: usize = 4;
+              pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
                   wgpu::VertexAttribute {
                       format: wgpu::VertexFormat::Float64,
                       offset: std::mem::offset_of!(Self, a) as u64,
@@ -428,12 +755,48 @@ mod test {
                   step_mode: wgpu::VertexStepMode,
               ) -> wgpu::VertexBufferLayout<'static> {
                   wgpu::VertexBufferLayout {
-                      array_stride: std::mem::size_of::<Self>() as u64,
+                      array_stride: Self::STRIDE,
                       step_mode,
                       attributes: &Self::VERTEX_ATTRIBUTES,
                   }
               }
           }
+          const VERTEX_INPUT0_LAYOUT_ASSERTS: () = {
+              assert!(std::mem::size_of::<VertexInput0>() % 4 == 0, "the array stride of `test::VertexInput0` must be a multiple of 4 bytes");
+              assert!(std::mem::size_of::<VertexInput0>() <= 2048, "the array stride of `test::VertexInput0` must be at most 2048 bytes");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, b) % 4 == 0, "`test::VertexInput0::b` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, b) + 16 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::b` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, c) % 4 == 0, "`test::VertexInput0::c` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, c) + 24 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::c` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, d) % 4 == 0, "`test::VertexInput0::d` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, d) + 32 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::d` must be read within the array stride");
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::offset_of!(VertexInput0, b) || std::mem::offset_of!(VertexInput0, b) + 16 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `b` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::offset_of!(VertexInput0, c) || std::mem::offset_of!(VertexInput0, c) + 24 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `c` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 32 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `d` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, b) + 16 <= std::mem::offset_of!(VertexInput0, c) || std::mem::offset_of!(VertexInput0, c) + 24 <= std::mem::offset_of!(VertexInput0, b),
+                  "`test::VertexInput0::b` and `c` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, b) + 16 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 32 <= std::mem::offset_of!(VertexInput0, b),
+                  "`test::VertexInput0::b` and `d` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, c) + 24 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 32 <= std::mem::offset_of!(VertexInput0, c),
+                  "`test::VertexInput0::c` and `d` must not overlap in the vertex buffer"
+              );
+          };
       },
       actual
     );
@@ -455,7 +818,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -463,7 +826,9 @@ mod test {
     assert_tokens_eq!(
       quote! {
           impl VertexInput0 {
-              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 4] = [
+              pub const ATTRIBUTE_COUNT: usize = 4;
+              pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
                   wgpu::VertexAttribute {
                       format: wgpu::VertexFormat::Sint32,
                       offset: std::mem::offset_of!(Self, a) as u64,
@@ -489,12 +854,48 @@ mod test {
                   step_mode: wgpu::VertexStepMode,
               ) -> wgpu::VertexBufferLayout<'static> {
                   wgpu::VertexBufferLayout {
-                      array_stride: std::mem::size_of::<Self>() as u64,
+                      array_stride: Self::STRIDE,
                       step_mode,
                       attributes: &Self::VERTEX_ATTRIBUTES,
                   }
               }
           }
+          const VERTEX_INPUT0_LAYOUT_ASSERTS: () = {
+              assert!(std::mem::size_of::<VertexInput0>() % 4 == 0, "the array stride of `test::VertexInput0` must be a multiple of 4 bytes");
+              assert!(std::mem::size_of::<VertexInput0>() <= 2048, "the array stride of `test::VertexInput0` must be at most 2048 bytes");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 12 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 16 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, a) || std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `a` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, a) || std::mem::offset_of!(VertexInput0, a) + 12 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `a` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, a) || std::mem::offset_of!(VertexInput0, a) + 16 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `a` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::offset_of!(VertexInput0, a) || std::mem::offset_of!(VertexInput0, a) + 12 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `a` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 8 <= std::mem::offset_of!(VertexInput0, a) || std::mem::offset_of!(VertexInput0, a) + 16 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `a` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 12 <= std::mem::offset_of!(VertexInput0, a) || std::mem::offset_of!(VertexInput0, a) + 16 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `a` must not overlap in the vertex buffer"
+              );
+          };
       },
       actual
     );
@@ -515,7 +916,7 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -523,7 +924,9 @@ mod test {
     assert_tokens_eq!(
       quote! {
           impl VertexInput0 {
-              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 4] = [
+              pub const ATTRIBUTE_COUNT: usize = 4;
+              pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+              pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
                   wgpu::VertexAttribute {
                       format: wgpu::VertexFormat::Uint32,
                       offset: std::mem::offset_of!(Self, a) as u64,
@@ -549,12 +952,48 @@ mod test {
                   step_mode: wgpu::VertexStepMode,
               ) -> wgpu::VertexBufferLayout<'static> {
                   wgpu::VertexBufferLayout {
-                      array_stride: std::mem::size_of::<Self>() as u64,
+                      array_stride: Self::STRIDE,
                       step_mode,
                       attributes: &Self::VERTEX_ATTRIBUTES,
                   }
               }
           }
+          const VERTEX_INPUT0_LAYOUT_ASSERTS: () = {
+              assert!(std::mem::size_of::<VertexInput0>() % 4 == 0, "the array stride of `test::VertexInput0` must be a multiple of 4 bytes");
+              assert!(std::mem::size_of::<VertexInput0>() <= 2048, "the array stride of `test::VertexInput0` must be at most 2048 bytes");
+              assert!(std::mem::offset_of!(VertexInput0, a) % 4 == 0, "`test::VertexInput0::a` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::a` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, b) % 4 == 0, "`test::VertexInput0::b` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::b` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, c) % 4 == 0, "`test::VertexInput0::c` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::c` must be read within the array stride");
+              assert!(std::mem::offset_of!(VertexInput0, d) % 4 == 0, "`test::VertexInput0::d` must be aligned to 4 bytes in the vertex buffer");
+              assert!(std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::size_of::<VertexInput0>(), "`test::VertexInput0::d` must be read within the array stride");
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, b) || std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `b` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, c) || std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `c` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, a) + 4 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::offset_of!(VertexInput0, a),
+                  "`test::VertexInput0::a` and `d` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::offset_of!(VertexInput0, c) || std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::offset_of!(VertexInput0, b),
+                  "`test::VertexInput0::b` and `c` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, b) + 8 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::offset_of!(VertexInput0, b),
+                  "`test::VertexInput0::b` and `d` must not overlap in the vertex buffer"
+              );
+              assert!(
+                  std::mem::offset_of!(VertexInput0, c) + 12 <= std::mem::offset_of!(VertexInput0, d) || std::mem::offset_of!(VertexInput0, d) + 16 <= std::mem::offset_of!(VertexInput0, c),
+                  "`test::VertexInput0::c` and `d` must not overlap in the vertex buffer"
+              );
+          };
       },
       actual
     );
@@ -578,7 +1017,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = entry_point_constants(&module);
+    let actual = entry_point_constants(&module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
@@ -600,30 +1039,11 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
-          #[derive(Debug)]
-          pub struct VertexEntry<const N: usize> {
-              pub entry_point: &'static str,
-              pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-              pub constants: std::collections::HashMap<String, f64>,
-          }
-          pub fn vertex_state<'a, const N: usize>(
-              module: &'a wgpu::ShaderModule,
-              entry: &'a VertexEntry<N>,
-          ) -> wgpu::VertexState<'a> {
-              wgpu::VertexState {
-                  module,
-                  entry_point: entry.entry_point,
-                  buffers: &entry.buffers,
-                  compilation_options: wgpu::PipelineCompilationOptions {
-                    constants: &entry.constants,
-                    ..Default::default()
-                  },
-              }
-          }
+          pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
           pub fn vs_main_entry() -> VertexEntry<0> {
               VertexEntry {
                   entry_point: ENTRY_VS_MAIN,
@@ -651,30 +1071,11 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
-          #[derive(Debug)]
-          pub struct VertexEntry<const N: usize> {
-              pub entry_point: &'static str,
-              pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-              pub constants: std::collections::HashMap<String, f64>,
-          }
-          pub fn vertex_state<'a, const N: usize>(
-              module: &'a wgpu::ShaderModule,
-              entry: &'a VertexEntry<N>,
-          ) -> wgpu::VertexState<'a> {
-              wgpu::VertexState {
-                  module,
-                  entry_point: entry.entry_point,
-                  buffers: &entry.buffers,
-                  compilation_options: wgpu::PipelineCompilationOptions {
-                    constants: &entry.constants,
-                    ..Default::default()
-                  },
-              }
-          }
+          pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
           pub fn vs_main_1_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
               VertexEntry {
                   entry_point: ENTRY_VS_MAIN_1,
@@ -682,6 +1083,21 @@ mod test {
                   constants: Default::default()
               }
           }
+          #[doc = " `vs_main_1_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes."]
+          pub fn vs_main_1_entry_default() -> VertexEntry<1> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN_1,
+                  buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+                  constants: Default::default()
+              }
+          }
+          #[doc = " The buffers of `vs_main_1_entry_default` in a const fn, eg: to keep them in a static."]
+          pub const fn vs_main_1_const_entry() -> ConstVertexEntry<1> {
+              ConstVertexEntry {
+                  entry_point: ENTRY_VS_MAIN_1,
+                  buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+              }
+          }
           pub fn vs_main_2_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
               VertexEntry {
                   entry_point: ENTRY_VS_MAIN_2,
@@ -689,6 +1105,21 @@ mod test {
                   constants: Default::default()
               }
           }
+          #[doc = " `vs_main_2_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes."]
+          pub fn vs_main_2_entry_default() -> VertexEntry<1> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN_2,
+                  buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+                  constants: Default::default()
+              }
+          }
+          #[doc = " The buffers of `vs_main_2_entry_default` in a const fn, eg: to keep them in a static."]
+          pub const fn vs_main_2_const_entry() -> ConstVertexEntry<1> {
+              ConstVertexEntry {
+                  entry_point: ENTRY_VS_MAIN_2,
+                  buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+              }
+          }
       },
       actual
     )
@@ -710,30 +1141,11 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
-          #[derive(Debug)]
-          pub struct VertexEntry<const N: usize> {
-              pub entry_point: &'static str,
-              pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-              pub constants: std::collections::HashMap<String, f64>
-          }
-          pub fn vertex_state<'a, const N: usize>(
-              module: &'a wgpu::ShaderModule,
-              entry: &'a VertexEntry<N>,
-          ) -> wgpu::VertexState<'a> {
-              wgpu::VertexState {
-                  module,
-                  entry_point: entry.entry_point,
-                  buffers: &entry.buffers,
-                  compilation_options: wgpu::PipelineCompilationOptions {
-                    constants: &entry.constants,
-                    ..Default::default()
-                  },
-              }
-          }
+          pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
           pub fn vs_main_entry(
             input0: wgpu::VertexStepMode,
             input1: wgpu::VertexStepMode,
@@ -748,6 +1160,172 @@ mod test {
                   constants: overrides.constants(),
               }
           }
+          #[doc = " `vs_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes."]
+          pub fn vs_main_entry_default(overrides: &OverrideConstants) -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      Input0::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                      Input1::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                  ],
+                  constants: overrides.constants(),
+              }
+          }
+          #[doc = " The buffers of `vs_main_entry_default` in a const fn, eg: to keep them in a static."]
+          pub const fn vs_main_const_entry() -> ConstVertexEntry<2> {
+              ConstVertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      Input0::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                      Input1::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                  ],
+              }
+          }
+      },
+      actual
+    )
+  }
+
+  #[test]
+  fn write_vertex_shader_entry_vertex_and_instance_buffers() {
+    let source = indoc! {r#"
+            struct InstanceInput {
+                @location(0) offset: vec2<f32>,
+            };
+            struct VertexInput {
+                @location(1) position: vec2<f32>
+            }
+            @vertex
+            fn vs_main(instance: InstanceInput, vertex: VertexInput) {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default());
+
+    assert_tokens_eq!(
+      quote! {
+          pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
+          pub fn vs_main_entry(
+            instance_input: wgpu::VertexStepMode,
+            vertex_input: wgpu::VertexStepMode
+          ) -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      InstanceInput::vertex_buffer_layout(instance_input),
+                      VertexInput::vertex_buffer_layout(vertex_input),
+                  ],
+                  constants: Default::default(),
+              }
+          }
+          #[doc = " `vs_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes."]
+          pub fn vs_main_entry_default() -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      InstanceInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                      VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                  ],
+                  constants: Default::default(),
+              }
+          }
+          #[doc = " The buffers of `vs_main_entry_default` in a const fn, eg: to keep them in a static."]
+          pub const fn vs_main_const_entry() -> ConstVertexEntry<2> {
+              ConstVertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      InstanceInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                      VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                  ],
+              }
+          }
+          #[doc = " `vs_main_entry` stepping through `VertexInput` per vertex and `InstanceInput` per instance."]
+          pub fn vs_main_instanced_entry() -> VertexEntry<2> {
+              vs_main_entry(wgpu::VertexStepMode::Instance, wgpu::VertexStepMode::Vertex)
+          }
+          #[doc = " Sets the buffers of `VertexInput` and `InstanceInput` to the slots of the `*_instanced_entry` layouts."]
+          pub fn set_buffers(
+              pass: &mut wgpu::RenderPass<'_>,
+              vertices: wgpu::BufferSlice<'_>,
+              instances: wgpu::BufferSlice<'_>,
+          ) {
+              pass.set_vertex_buffer(1, vertices);
+              pass.set_vertex_buffer(0, instances);
+          }
+      },
+      actual
+    )
+  }
+
+  #[test]
+  fn write_vertex_shader_entry_instance_vertex_inputs() {
+    let source = indoc! {r#"
+            struct InstanceInput {
+                @location(0) offset: vec2<f32>,
+            };
+            struct VertexInput {
+                @location(1) position: vec2<f32>
+            }
+            @vertex
+            fn vs_main(instance: InstanceInput, vertex: VertexInput) {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let options = WgslBindgenOption {
+      instance_vertex_inputs: vec![Regex::new("::InstanceInput$").unwrap()],
+      ..Default::default()
+    };
+    let actual = vertex_states("test", &module, &options);
+
+    assert_tokens_eq!(
+      quote! {
+          pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
+          pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      InstanceInput::vertex_buffer_layout(wgpu::VertexStepMode::Instance),
+                      VertexInput::vertex_buffer_layout(vertex_input),
+                  ],
+                  constants: Default::default(),
+              }
+          }
+          #[doc = " `vs_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes."]
+          pub fn vs_main_entry_default() -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      InstanceInput::vertex_buffer_layout(wgpu::VertexStepMode::Instance),
+                      VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                  ],
+                  constants: Default::default(),
+              }
+          }
+          #[doc = " The buffers of `vs_main_entry_default` in a const fn, eg: to keep them in a static."]
+          pub const fn vs_main_const_entry() -> ConstVertexEntry<2> {
+              ConstVertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      InstanceInput::vertex_buffer_layout(wgpu::VertexStepMode::Instance),
+                      VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+                  ],
+              }
+          }
+          #[doc = " `vs_main_entry` stepping through `VertexInput` per vertex and `InstanceInput` per instance."]
+          pub fn vs_main_instanced_entry() -> VertexEntry<2> {
+              vs_main_entry(wgpu::VertexStepMode::Vertex)
+          }
+          #[doc = " Sets the buffers of `VertexInput` and `InstanceInput` to the slots of the `*_instanced_entry` layouts."]
+          pub fn set_buffers(
+              pass: &mut wgpu::RenderPass<'_>,
+              vertices: wgpu::BufferSlice<'_>,
+              instances: wgpu::BufferSlice<'_>,
+          ) {
+              pass.set_vertex_buffer(1, vertices);
+              pass.set_vertex_buffer(0, instances);
+          }
       },
       actual
     )
@@ -765,7 +1343,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(quote!(), actual)
   }
@@ -790,30 +1368,11 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = fragment_states(&module);
+    let actual = fragment_states(&module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
-          #[derive(Debug)]
-          pub struct FragmentEntry<const N: usize> {
-              pub entry_point: &'static str,
-              pub targets: [Option<wgpu::ColorTargetState>; N],
-              pub constants: std::collections::HashMap<String, f64>,
-          }
-          pub fn fragment_state<'a, const N: usize>(
-              module: &'a wgpu::ShaderModule,
-              entry: &'a FragmentEntry<N>,
-          ) -> wgpu::FragmentState<'a> {
-              wgpu::FragmentState {
-                  module,
-                  entry_point: entry.entry_point,
-                  targets: &entry.targets,
-                  compilation_options: wgpu::PipelineCompilationOptions {
-                      constants: &entry.constants,
-                      ..Default::default()
-                  },
-              }
-          }
+          pub use _root::_support::{FragmentEntry, fragment_state};
           pub fn fs_multiple_entry(
               targets: [Option<wgpu::ColorTargetState>; 2]
           ) -> FragmentEntry<2> {
@@ -865,30 +1424,11 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = fragment_states(&module);
+    let actual = fragment_states(&module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
-          #[derive(Debug)]
-          pub struct FragmentEntry<const N: usize> {
-              pub entry_point: &'static str,
-              pub targets: [Option<wgpu::ColorTargetState>; N],
-              pub constants: std::collections::HashMap<String, f64>,
-          }
-          pub fn fragment_state<'a, const N: usize>(
-              module: &'a wgpu::ShaderModule,
-              entry: &'a FragmentEntry<N>,
-          ) -> wgpu::FragmentState<'a> {
-              wgpu::FragmentState {
-                  module,
-                  entry_point: entry.entry_point,
-                  targets: &entry.targets,
-                  compilation_options: wgpu::PipelineCompilationOptions {
-                      constants: &entry.constants,
-                      ..Default::default()
-                  },
-              }
-          }
+          pub use _root::_support::{FragmentEntry, fragment_state};
           pub fn fs_single_entry(
               targets: [Option<wgpu::ColorTargetState>; 1],
               overrides: &OverrideConstants
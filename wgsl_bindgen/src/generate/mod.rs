@@ -1,12 +1,17 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
+use crate::WgslBindgenOption;
+
+pub(crate) mod bevy_helpers;
 pub(crate) mod bind_group;
 pub(crate) mod consts;
 pub(crate) mod entry;
 pub(crate) mod pipeline;
+pub(crate) mod requirements;
 pub(crate) mod shader_module;
 pub(crate) mod shader_registry;
+pub(crate) mod support;
 
 pub(crate) fn quote_shader_stages(shader_stages: wgpu::ShaderStages) -> TokenStream {
   match shader_stages {
@@ -30,3 +35,17 @@ pub(crate) fn quote_shader_stages(shader_stages: wgpu::ShaderStages) -> TokenStr
     }
   }
 }
+
+/// The generic parameters, device type and return type of a function creating a `resource`,
+/// eg: `BindGroupLayout`, which are generic over the generated `GpuDevice` with
+/// `generic_device`.
+pub(crate) fn device_fn_types(
+  options: &WgslBindgenOption,
+  resource: &str,
+) -> (TokenStream, TokenStream, TokenStream) {
+  let resource = format_ident!("{resource}");
+  match options.generic_device {
+    true => (quote!(<D: _root::GpuDevice>), quote!(D), quote!(D::#resource)),
+    false => (quote!(), quote!(wgpu::Device), quote!(wgpu::#resource)),
+  }
+}
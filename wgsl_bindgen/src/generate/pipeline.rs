@@ -39,18 +39,92 @@ impl<'a> PipelineLayoutDataEntriesBuilder<'a> {
   }
 }
 
-fn push_constant_range(
-  module: &naga::Module,
-  shader_stages: wgpu::ShaderStages,
-) -> Option<TokenStream> {
+pub(crate) fn push_constant_size(module: &naga::Module) -> Option<u32> {
   // Assume only one variable is used with var<push_constant> in WGSL.
-  let push_constant_size = module.global_variables.iter().find_map(|g| {
+  module.global_variables.iter().find_map(|g| {
     if g.1.space == naga::AddressSpace::PushConstant {
       Some(module.types[g.1.ty].inner.size(module.to_ctx()))
     } else {
       None
     }
-  });
+  })
+}
+
+/// The reasons the push constant range of the generated pipeline layout breaks the rules of wgpu,
+/// or the `max_push_constant_size` of the options, which would panic when creating the layout.
+pub(crate) fn push_constant_errors(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  options: &WgslBindgenOption,
+) -> Vec<String> {
+  let Some(size) = push_constant_size(module) else {
+    return Vec::new();
+  };
+
+  let mut errors = Vec::new();
+  let push_constants = module
+    .global_variables
+    .iter()
+    .filter(|(_, var)| var.space == naga::AddressSpace::PushConstant)
+    .collect::<Vec<_>>();
+  let describe = |var: &naga::GlobalVariable| {
+    let name = var.name.as_deref().unwrap_or("<unnamed>");
+    let ty = &module.types[var.ty];
+    let size = ty.inner.size(module.to_ctx());
+    format!("`{name}` of `{}` ({size} bytes)", wgsl::type_name(module, ty))
+  };
+
+  let (_, first) = push_constants[0];
+  if size % wgpu::PUSH_CONSTANT_ALIGNMENT != 0 {
+    errors.push(format!(
+      "{} is not a multiple of {} bytes, as wgpu requires of the range end",
+      describe(first),
+      wgpu::PUSH_CONSTANT_ALIGNMENT
+    ));
+  }
+  if let Some(max) = options.max_push_constant_size.filter(|max| size > *max) {
+    errors.push(format!(
+      "{} exceeds the `max_push_constant_size` of {max} bytes",
+      describe(first)
+    ));
+  }
+
+  // The range of the layout is sized after the first push constants, and shared by all the
+  // stages since each stage can only be part of one range.
+  for (handle, var) in push_constants.into_iter().skip(1) {
+    if module.types[var.ty].inner.size(module.to_ctx()) <= size {
+      continue;
+    }
+
+    let stages = match info {
+      Some(info) => module
+        .entry_points
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !info.get_entry_point(*index)[handle].is_empty())
+        .map(|(_, entry)| wgsl::shader_stage(entry.stage))
+        .collect::<wgpu::ShaderStages>(),
+      None => wgsl::shader_stages(module),
+    };
+    let noun = match stages.bits().count_ones() {
+      1 => "stage",
+      _ => "stages",
+    };
+    errors.push(format!(
+      "{} used by the {} {noun} exceeds the range 0..{size} of the pipeline layout",
+      describe(var),
+      crate::bindgen::stage_names(stages)
+    ));
+  }
+
+  errors
+}
+
+fn push_constant_range(
+  module: &naga::Module,
+  shader_stages: wgpu::ShaderStages,
+) -> Option<TokenStream> {
+  let push_constant_size = push_constant_size(module);
 
   let stages = quote_shader_stages(shader_stages);
 
@@ -101,9 +175,20 @@ pub fn create_pipeline_layout_fn(
 
   let pipeline_layout_name = format!("{}::PipelineLayout", entry_name);
 
-  quote! {
-    #additional_pipeline_entries_struct
-    #wgpu_pipeline_entries_struct
+  let create_pipeline_layout = if options.generic_device {
+    quote! {
+      pub fn create_pipeline_layout<D: _root::GpuDevice>(device: &D) -> D::PipelineLayout {
+          device.create_pipeline_layout(
+              Some(#pipeline_layout_name),
+              &[
+                  #(&#bind_group_layouts),*
+              ],
+              &[#push_constant_range],
+          )
+      }
+    }
+  } else {
+    quote! {
       pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
           device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
               label: Some(#pipeline_layout_name),
@@ -113,5 +198,12 @@ pub fn create_pipeline_layout_fn(
               push_constant_ranges: &[#push_constant_range],
           })
       }
+    }
+  };
+
+  quote! {
+    #additional_pipeline_entries_struct
+    #wgpu_pipeline_entries_struct
+    #create_pipeline_layout
   }
 }
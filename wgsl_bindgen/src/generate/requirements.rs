@@ -0,0 +1,889 @@
+//! This module derives the device features and limits required by a shader module:
+//! such as `REQUIRED_FEATURES` and `REQUIRED_LIMITS`
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::wgsl;
+
+/// The features and limits a device must support for a shader module to be usable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ShaderRequirements {
+  pub features: wgpu::Features,
+  pub limits: wgpu::Limits,
+}
+
+impl ShaderRequirements {
+  /// The limits every requirement is raised from.
+  /// These are the lowest limits wgpu will create a device with.
+  pub fn base_limits() -> wgpu::Limits {
+    wgpu::Limits::downlevel_webgl2_defaults()
+  }
+
+  pub fn from_module(
+    module: &naga::Module,
+    info: Option<&naga::valid::ModuleInfo>,
+  ) -> Self {
+    Self {
+      features: required_features(module),
+      limits: required_limits(module, info),
+    }
+  }
+
+  /// Adds features requested outside of the module itself, such as by `enable` directives.
+  pub fn with_features(mut self, features: wgpu::Features) -> Self {
+    self.features |= features;
+    self
+  }
+}
+
+fn scalar_features(scalar: naga::Scalar) -> wgpu::Features {
+  match (scalar.kind, scalar.width) {
+    (naga::ScalarKind::Float, 2) => wgpu::Features::SHADER_F16,
+    (naga::ScalarKind::Float, 8) => wgpu::Features::SHADER_F64,
+    (naga::ScalarKind::Sint | naga::ScalarKind::Uint, 8) => wgpu::Features::SHADER_INT64,
+    _ => wgpu::Features::empty(),
+  }
+}
+
+fn binding_array_features(
+  module: &naga::Module,
+  base: naga::Handle<naga::Type>,
+) -> wgpu::Features {
+  match &module.types[base].inner {
+    naga::TypeInner::Image {
+      class: naga::ImageClass::Storage { .. },
+      ..
+    } => {
+      wgpu::Features::TEXTURE_BINDING_ARRAY
+        | wgpu::Features::STORAGE_RESOURCE_BINDING_ARRAY
+    }
+    naga::TypeInner::Image { .. } | naga::TypeInner::Sampler { .. } => {
+      wgpu::Features::TEXTURE_BINDING_ARRAY
+    }
+    _ => wgpu::Features::BUFFER_BINDING_ARRAY,
+  }
+}
+
+/// Iterates the bindings of all entry point arguments with their types, including struct members.
+fn entry_argument_bindings<'a>(
+  module: &'a naga::Module,
+  entry: &'a naga::EntryPoint,
+) -> impl Iterator<Item = (&'a naga::Binding, naga::Handle<naga::Type>)> + 'a {
+  entry.function.arguments.iter().flat_map(move |arg| {
+    let bindings: Vec<_> = match (&arg.binding, &module.types[arg.ty].inner) {
+      (Some(binding), _) => vec![(binding, arg.ty)],
+      (None, naga::TypeInner::Struct { members, .. }) => members
+        .iter()
+        .filter_map(|m| Some((m.binding.as_ref()?, m.ty)))
+        .collect(),
+      _ => vec![],
+    };
+    bindings
+  })
+}
+
+fn is_f64(module: &naga::Module, ty: naga::Handle<naga::Type>) -> bool {
+  match module.types[ty].inner {
+    naga::TypeInner::Scalar(scalar) | naga::TypeInner::Vector { scalar, .. } => {
+      scalar == naga::Scalar::F64
+    }
+    _ => false,
+  }
+}
+
+fn required_features(module: &naga::Module) -> wgpu::Features {
+  let mut features = wgpu::Features::empty();
+
+  for (_, ty) in module.types.iter() {
+    features |= match &ty.inner {
+      naga::TypeInner::Scalar(scalar)
+      | naga::TypeInner::Vector { scalar, .. }
+      | naga::TypeInner::Matrix { scalar, .. }
+      | naga::TypeInner::Atomic(scalar) => scalar_features(*scalar),
+      naga::TypeInner::BindingArray { base, .. } => binding_array_features(module, *base),
+      _ => wgpu::Features::empty(),
+    };
+  }
+
+  if module
+    .global_variables
+    .iter()
+    .any(|(_, g)| g.space == naga::AddressSpace::PushConstant)
+  {
+    features |= wgpu::Features::PUSH_CONSTANTS;
+  }
+
+  for entry in module.entry_points.iter() {
+    if entry.early_depth_test.is_some() {
+      features |= wgpu::Features::SHADER_EARLY_DEPTH_TEST;
+    }
+
+    for (binding, ty) in entry_argument_bindings(module, entry) {
+      features |= match binding {
+        naga::Binding::BuiltIn(naga::BuiltIn::PrimitiveIndex) => {
+          wgpu::Features::SHADER_PRIMITIVE_INDEX
+        }
+        naga::Binding::BuiltIn(naga::BuiltIn::ViewIndex) => wgpu::Features::MULTIVIEW,
+        // The `Float64` vertex formats of `f64` vertex inputs aren't core formats.
+        naga::Binding::Location { .. }
+          if entry.stage == naga::ShaderStage::Vertex && is_f64(module, ty) =>
+        {
+          wgpu::Features::VERTEX_ATTRIBUTE_64BIT
+        }
+        _ => wgpu::Features::empty(),
+      };
+    }
+  }
+
+  features
+}
+
+/// The bindings of an entry point counted towards the per-stage limits.
+#[derive(Debug, Default, Clone, Copy)]
+struct StageBindings {
+  uniform_buffers: u32,
+  storage_buffers: u32,
+  sampled_textures: u32,
+  samplers: u32,
+  storage_textures: u32,
+}
+
+impl StageBindings {
+  fn add(self, other: Self) -> Self {
+    Self {
+      uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+      storage_buffers: self.storage_buffers + other.storage_buffers,
+      sampled_textures: self.sampled_textures + other.sampled_textures,
+      samplers: self.samplers + other.samplers,
+      storage_textures: self.storage_textures + other.storage_textures,
+    }
+  }
+}
+
+fn required_limits(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+) -> wgpu::Limits {
+  let mut limits = ShaderRequirements::base_limits();
+  let ctx = module.to_ctx();
+
+  let raise = |limit: &mut u32, value: u32| *limit = (*limit).max(value);
+
+  let mut group_count = 0;
+  let mut bindings = Vec::new();
+
+  for (handle, global) in module.global_variables.iter() {
+    let ty = &module.types[global.ty];
+
+    if global.space == naga::AddressSpace::PushConstant {
+      raise(&mut limits.max_push_constant_size, ty.inner.size(ctx));
+    }
+
+    let Some(binding) = &global.binding else {
+      continue;
+    };
+
+    group_count = group_count.max(binding.group + 1);
+    raise(&mut limits.max_bindings_per_bind_group, binding.binding + 1);
+
+    // Binding arrays count once per element towards the per-stage limits.
+    let (inner, count) = match &ty.inner {
+      naga::TypeInner::BindingArray { base, size } => {
+        let count = match size {
+          naga::ArraySize::Constant(size) => size.get(),
+          naga::ArraySize::Dynamic => 1,
+        };
+        (&module.types[*base].inner, count)
+      }
+      inner => (inner, 1),
+    };
+
+    let mut stage_bindings = StageBindings::default();
+    match (global.space, inner) {
+      (naga::AddressSpace::Uniform, _) => {
+        stage_bindings.uniform_buffers = count;
+        raise(&mut limits.max_uniform_buffer_binding_size, inner.size(ctx));
+      }
+      (naga::AddressSpace::Storage { .. }, _) => {
+        stage_bindings.storage_buffers = count;
+        raise(&mut limits.max_storage_buffer_binding_size, inner.size(ctx));
+      }
+      (_, naga::TypeInner::Image { class, .. }) => match class {
+        naga::ImageClass::Storage { .. } => stage_bindings.storage_textures = count,
+        _ => stage_bindings.sampled_textures = count,
+      },
+      (_, naga::TypeInner::Sampler { .. }) => stage_bindings.samplers = count,
+      _ => {}
+    }
+    bindings.push((handle, stage_bindings));
+  }
+
+  raise(&mut limits.max_bind_groups, group_count);
+
+  // Each entry point is a single stage, so the per-stage limits are those of the entry point
+  // using the most bindings. Every binding is counted when the module doesn't validate.
+  for index in 0..module.entry_points.len() {
+    let used = bindings
+      .iter()
+      .filter(|(handle, _)| {
+        !info.is_some_and(|info| info.get_entry_point(index)[*handle].is_empty())
+      })
+      .fold(StageBindings::default(), |total, (_, bindings)| total.add(*bindings));
+
+    raise(&mut limits.max_uniform_buffers_per_shader_stage, used.uniform_buffers);
+    raise(&mut limits.max_storage_buffers_per_shader_stage, used.storage_buffers);
+    raise(&mut limits.max_sampled_textures_per_shader_stage, used.sampled_textures);
+    raise(&mut limits.max_samplers_per_shader_stage, used.samplers);
+    raise(&mut limits.max_storage_textures_per_shader_stage, used.storage_textures);
+  }
+
+  let vertex_inputs = wgsl::get_vertex_input_structs("", module);
+  let vertex_attributes = vertex_inputs.iter().map(|i| i.fields.len() as u32).sum();
+  // The shader locations of the attributes have to be below the limit too.
+  let vertex_locations = vertex_inputs
+    .iter()
+    .flat_map(|i| i.fields.iter().map(|(location, _)| location + 1))
+    .max()
+    .unwrap_or(0);
+  raise(&mut limits.max_vertex_buffers, vertex_inputs.len() as u32);
+  raise(&mut limits.max_vertex_attributes, vertex_attributes);
+  raise(&mut limits.max_vertex_attributes, vertex_locations);
+
+  for entry in module.entry_points.iter() {
+    match entry.stage {
+      naga::ShaderStage::Fragment => {
+        let targets = super::entry::fragment_target_count(module, &entry.function) as u32;
+        raise(&mut limits.max_color_attachments, targets);
+      }
+      naga::ShaderStage::Compute => {
+        let [x, y, z] = entry.workgroup_size;
+        raise(&mut limits.max_compute_workgroup_size_x, x);
+        raise(&mut limits.max_compute_workgroup_size_y, y);
+        raise(&mut limits.max_compute_workgroup_size_z, z);
+        raise(&mut limits.max_compute_invocations_per_workgroup, x * y * z);
+        raise(&mut limits.max_compute_workgroups_per_dimension, 1);
+      }
+      naga::ShaderStage::Vertex => {}
+    }
+  }
+
+  for (_, variables) in workgroup_storage(module, info) {
+    let size = variables.iter().map(|(_, size)| size).sum();
+    raise(&mut limits.max_compute_workgroup_storage_size, size);
+  }
+
+  limits
+}
+
+/// The workgroup variables used by each compute entry point, along with the size each takes in
+/// the workgroup storage, since WebGPU rounds it up to a multiple of 16 bytes.
+pub(crate) fn workgroup_storage<'a>(
+  module: &'a naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+) -> Vec<(&'a naga::EntryPoint, Vec<(&'a naga::GlobalVariable, u32)>)> {
+  // Every workgroup variable is counted for each entry point when the module doesn't validate.
+
+  module
+    .entry_points
+    .iter()
+    .enumerate()
+    .filter(|(_, entry)| entry.stage == naga::ShaderStage::Compute)
+    .map(|(index, entry)| {
+      let variables = module
+        .global_variables
+        .iter()
+        .filter(|(_, var)| var.space == naga::AddressSpace::WorkGroup)
+        .filter(|(handle, _)| {
+          !info.is_some_and(|info| info.get_entry_point(index)[*handle].is_empty())
+        })
+        .map(|(_, var)| {
+          let size = module.types[var.ty].inner.size(module.to_ctx());
+          (var, size.next_multiple_of(16))
+        })
+        .collect();
+      (entry, variables)
+    })
+    .collect()
+}
+
+/// The uniform bindings larger than `max_size`, which would fail creating their bind group
+/// layout on the devices with that `max_uniform_buffer_binding_size`.
+pub(crate) fn oversized_uniform_bindings(
+  module: &naga::Module,
+  max_size: u32,
+) -> Vec<String> {
+  module
+    .global_variables
+    .iter()
+    .filter(|(_, var)| var.space == naga::AddressSpace::Uniform)
+    .filter_map(|(_, var)| {
+      let binding = var.binding.as_ref()?;
+      // The elements of binding arrays are bound as buffers of their own.
+      let ty = match module.types[var.ty].inner {
+        naga::TypeInner::BindingArray { base, .. } => &module.types[base],
+        _ => &module.types[var.ty],
+      };
+      let size = ty.inner.size(module.to_ctx());
+      (size > max_size).then(|| {
+        let name = var.name.as_deref().unwrap_or("<unnamed>");
+        format!(
+          "uniform binding `{name}` at @group({}) @binding({}) of `{}` takes {size} bytes, over the `max_uniform_buffer_binding_size` of {max_size} bytes; bind it as `var<storage, read>` instead",
+          binding.group,
+          binding.binding,
+          wgsl::type_name(module, ty),
+        )
+      })
+    })
+    .collect()
+}
+
+/// The compute entry points using more workgroup storage than `max_size`, listing the
+/// workgroup variables they use from the largest.
+pub(crate) fn workgroup_storage_errors(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  max_size: u32,
+) -> Vec<String> {
+  workgroup_storage(module, info)
+    .into_iter()
+    .filter_map(|(entry, mut variables)| {
+      let size = variables.iter().map(|(_, size)| size).sum::<u32>();
+      if size <= max_size {
+        return None;
+      }
+
+      variables.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+      let variables = variables
+        .into_iter()
+        .map(|(var, size)| {
+          let name = var.name.as_deref().unwrap_or("<unnamed>");
+          let ty = wgsl::type_name(module, &module.types[var.ty]);
+          format!("`{name}` of `{ty}` ({size} bytes)")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+      Some(format!(
+        "`{}` uses {size} bytes of workgroup storage, over the `max_compute_workgroup_storage_size` of {max_size} bytes: {variables}",
+        entry.name
+      ))
+    })
+    .collect()
+}
+
+/// Quotes a `wgpu::Features` value as a const expression.
+pub(crate) fn quote_features(features: wgpu::Features) -> TokenStream {
+  let flags: Vec<_> = features
+    .iter_names()
+    .map(|(name, _)| {
+      let name = format_ident!("{}", name);
+      quote!(wgpu::Features::#name)
+    })
+    .collect();
+
+  match flags.split_first() {
+    None => quote!(wgpu::Features::empty()),
+    Some((first, rest)) => quote!(#first #(.union(#rest))*),
+  }
+}
+
+macro_rules! quote_changed_limits {
+  ($limits:expr, $base:expr, [$($field:ident),* $(,)?]) => {
+    [$(
+      ($limits.$field != $base.$field).then(|| {
+        let value = Literal::u32_unsuffixed($limits.$field);
+        quote!($field: #value)
+      })
+    ),*]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+  };
+}
+
+/// Quotes a `wgpu::Limits` value as a const expression,
+/// spelling out only the fields raised above [`ShaderRequirements::base_limits`].
+pub(crate) fn quote_limits(limits: &wgpu::Limits) -> TokenStream {
+  let base = ShaderRequirements::base_limits();
+  let fields = quote_changed_limits!(
+    limits,
+    base,
+    [
+      max_bind_groups,
+      max_bindings_per_bind_group,
+      max_sampled_textures_per_shader_stage,
+      max_samplers_per_shader_stage,
+      max_storage_buffers_per_shader_stage,
+      max_storage_textures_per_shader_stage,
+      max_uniform_buffers_per_shader_stage,
+      max_uniform_buffer_binding_size,
+      max_storage_buffer_binding_size,
+      max_vertex_buffers,
+      max_vertex_attributes,
+      max_color_attachments,
+      max_compute_workgroup_storage_size,
+      max_compute_invocations_per_workgroup,
+      max_compute_workgroup_size_x,
+      max_compute_workgroup_size_y,
+      max_compute_workgroup_size_z,
+      max_compute_workgroups_per_dimension,
+      max_push_constant_size,
+    ]
+  );
+
+  quote! {
+    wgpu::Limits {
+      #(#fields,)*
+      ..wgpu::Limits::downlevel_webgl2_defaults()
+    }
+  }
+}
+
+/// Lists the reasons a shader module will not run on WebGL2 with the downlevel defaults.
+/// Returns an empty list when the module is compatible.
+pub(crate) fn downlevel_webgl2_incompatibilities(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  extension_features: wgpu::Features,
+) -> Vec<String> {
+  let ShaderRequirements { features, limits } =
+    ShaderRequirements::from_module(module, info).with_features(extension_features);
+  let mut reasons = Vec::new();
+
+  let uses_compute = module
+    .entry_points
+    .iter()
+    .any(|e| e.stage == naga::ShaderStage::Compute);
+  if uses_compute {
+    reasons.push("compute shaders are not supported".to_string());
+  }
+
+  for (name, _) in features.iter_names() {
+    reasons.push(format!("requires feature `{name}`"));
+  }
+
+  limits.check_limits_with_fail_fn(
+    &ShaderRequirements::base_limits(),
+    false,
+    |name, requested, allowed| {
+      // Compute limits are already covered by the compute shader reason.
+      if uses_compute && name.starts_with("max_compute_") {
+        return;
+      }
+      reasons.push(format!("`{name}` of {requested} exceeds the limit of {allowed}"));
+    },
+  );
+
+  reasons
+}
+
+pub(crate) fn required_features_and_limits(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  extension_features: wgpu::Features,
+) -> TokenStream {
+  let ShaderRequirements { features, limits } =
+    ShaderRequirements::from_module(module, info).with_features(extension_features);
+  let features = quote_features(features);
+  let limits = quote_limits(&limits);
+
+  quote! {
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = #features;
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = #limits;
+  }
+}
+
+/// The resources a shader module binds, summed over its bindings.
+/// Binding arrays count once per element, and runtime-sized ones once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ShaderResources {
+  pub uniform_buffers: u32,
+  pub uniform_bytes: u64,
+  pub storage_buffers: u32,
+  pub textures_1d: u32,
+  pub textures_2d: u32,
+  pub textures_2d_array: u32,
+  pub textures_3d: u32,
+  pub textures_cube: u32,
+  pub textures_cube_array: u32,
+  pub storage_textures: u32,
+  pub samplers: u32,
+  pub push_constant_size: u32,
+}
+
+impl ShaderResources {
+  pub fn from_module(module: &naga::Module) -> Self {
+    let ctx = module.to_ctx();
+    let mut resources = Self {
+      push_constant_size: super::pipeline::push_constant_size(module).unwrap_or(0),
+      ..Default::default()
+    };
+
+    for (_, global) in module.global_variables.iter() {
+      if global.binding.is_none() {
+        continue;
+      }
+
+      let (inner, count) = match &module.types[global.ty].inner {
+        naga::TypeInner::BindingArray { base, size } => {
+          let count = match size {
+            naga::ArraySize::Constant(size) => size.get(),
+            naga::ArraySize::Dynamic => 1,
+          };
+          (&module.types[*base].inner, count)
+        }
+        inner => (inner, 1),
+      };
+
+      match (global.space, inner) {
+        (naga::AddressSpace::Uniform, _) => {
+          resources.uniform_buffers += count;
+          resources.uniform_bytes += u64::from(inner.size(ctx)) * u64::from(count);
+        }
+        (naga::AddressSpace::Storage { .. }, _) => resources.storage_buffers += count,
+        (
+          _,
+          naga::TypeInner::Image {
+            class: naga::ImageClass::Storage { .. },
+            ..
+          },
+        ) => resources.storage_textures += count,
+        (_, naga::TypeInner::Image { dim, arrayed, .. }) => {
+          use naga::ImageDimension as D;
+          *match (dim, arrayed) {
+            (D::D1, _) => &mut resources.textures_1d,
+            (D::D2, false) => &mut resources.textures_2d,
+            (D::D2, true) => &mut resources.textures_2d_array,
+            (D::D3, _) => &mut resources.textures_3d,
+            (D::Cube, false) => &mut resources.textures_cube,
+            (D::Cube, true) => &mut resources.textures_cube_array,
+          } += count;
+        }
+        (_, naga::TypeInner::Sampler { .. }) => resources.samplers += count,
+        _ => {}
+      }
+    }
+
+    resources
+  }
+}
+
+pub(crate) fn resources_const(module: &naga::Module) -> TokenStream {
+  let resources = ShaderResources::from_module(module);
+  let fields = [
+    ("uniform_buffers", resources.uniform_buffers.into()),
+    ("uniform_bytes", resources.uniform_bytes),
+    ("storage_buffers", resources.storage_buffers.into()),
+    ("textures_1d", resources.textures_1d.into()),
+    ("textures_2d", resources.textures_2d.into()),
+    ("textures_2d_array", resources.textures_2d_array.into()),
+    ("textures_3d", resources.textures_3d.into()),
+    ("textures_cube", resources.textures_cube.into()),
+    ("textures_cube_array", resources.textures_cube_array.into()),
+    ("storage_textures", resources.storage_textures.into()),
+    ("samplers", resources.samplers.into()),
+    ("push_constant_size", resources.push_constant_size.into()),
+  ]
+  .map(|(name, value): (&str, u64)| {
+    let name = format_ident!("{name}");
+    let value = Literal::u64_unsuffixed(value);
+    quote!(#name: #value)
+  });
+
+  quote! {
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+      #(#fields,)*
+    };
+  }
+}
+
+pub(crate) fn is_supported_fn() -> TokenStream {
+  quote! {
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(adapter: &wgpu::Adapter) -> Result<(), _root::UnsupportedReason> {
+      _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::assert_tokens_eq;
+  use crate::naga_util::module_info;
+
+  #[test]
+  fn requirements_for_empty_module() {
+    let source = indoc! {r#"
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = required_features_and_limits(
+      &module,
+      module_info(&module).as_ref(),
+      wgpu::Features::empty(),
+    );
+
+    assert_tokens_eq!(
+      quote! {
+        /// The device features required by this shader module.
+        pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+        /// The minimal device limits required by this shader module.
+        pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+          ..wgpu::Limits::downlevel_webgl2_defaults()
+        };
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn requirements_for_push_constants_and_storage() {
+    let source = indoc! {r#"
+            var<push_constant> consts: vec4<f32>;
+            @group(0) @binding(0) var<storage, read_write> a: array<vec4<f32>, 4>;
+            @group(0) @binding(3) var<storage, read> b: array<u32>;
+            @group(1) @binding(0) var<uniform> c: vec4<f32>;
+
+            @compute
+            @workgroup_size(16, 16)
+            fn main() {
+                a[0] = c + consts + f32(b[0]);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = required_features_and_limits(
+      &module,
+      module_info(&module).as_ref(),
+      wgpu::Features::empty(),
+    );
+
+    assert_tokens_eq!(
+      quote! {
+        /// The device features required by this shader module.
+        pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS;
+        /// The minimal device limits required by this shader module.
+        pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+          max_storage_buffers_per_shader_stage: 2,
+          max_storage_buffer_binding_size: 64,
+          max_compute_invocations_per_workgroup: 256,
+          max_compute_workgroup_size_x: 16,
+          max_compute_workgroup_size_y: 16,
+          max_compute_workgroup_size_z: 1,
+          max_compute_workgroups_per_dimension: 1,
+          max_push_constant_size: 16,
+          ..wgpu::Limits::downlevel_webgl2_defaults()
+        };
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn per_stage_limits_per_entry_point() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<storage, read> a: vec4<f32>;
+            @group(0) @binding(1) var<storage, read> b: vec4<f32>;
+            @group(0) @binding(2) var<storage, read> c: vec4<f32>;
+            @group(0) @binding(3) var<storage, read> unused: vec4<f32>;
+
+            fn get_b() -> vec4<f32> {
+                return b;
+            }
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+                return a;
+            }
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return get_b() + c;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let limits = required_limits(&module, module_info(&module).as_ref());
+
+    // `fs_main` uses the most storage buffers, through the functions it calls too.
+    assert_eq!(2, limits.max_storage_buffers_per_shader_stage);
+  }
+
+  #[test]
+  fn workgroup_storage_per_entry_point() {
+    let source = indoc! {r#"
+            var<workgroup> tile: array<f32, 65>;
+            var<workgroup> counter: atomic<u32>;
+            var<workgroup> unused: array<vec4<f32>, 8>;
+
+            @compute
+            @workgroup_size(64)
+            fn blur() {
+                tile[0] = 1.0;
+                atomicAdd(&counter, 1u);
+            }
+
+            @compute
+            @workgroup_size(64)
+            fn count() {
+                atomicAdd(&counter, 1u);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let sizes = workgroup_storage(&module, module_info(&module).as_ref())
+      .into_iter()
+      .map(|(entry, variables)| {
+        let size = variables.iter().map(|(_, size)| size).sum::<u32>();
+        (entry.name.as_str(), size)
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(vec![("blur", 288), ("count", 16)], sizes);
+
+    assert_eq!(
+      vec![
+        "`blur` uses 288 bytes of workgroup storage, over the `max_compute_workgroup_storage_size` of 256 bytes: `tile` of `array<f32, 65>` (272 bytes), `counter` of `atomic<u32>` (16 bytes)"
+      ],
+      workgroup_storage_errors(&module, module_info(&module).as_ref(), 256)
+    );
+  }
+
+  #[test]
+  fn resources_by_binding_kind() {
+    let source = indoc! {r#"
+            var<push_constant> consts: vec4<f32>;
+            @group(0) @binding(0) var<uniform> a: vec4<f32>;
+            @group(0) @binding(1) var<uniform> b: mat4x4<f32>;
+            @group(0) @binding(2) var<storage> c: array<u32>;
+            @group(1) @binding(0) var d: texture_2d<f32>;
+            @group(1) @binding(1) var e: binding_array<texture_cube<f32>, 3>;
+            @group(1) @binding(2) var f: texture_depth_2d_array;
+            @group(1) @binding(3) var g: texture_storage_2d<rgba8unorm, write>;
+            @group(1) @binding(4) var h: sampler;
+            @group(1) @binding(5) var i: sampler_comparison;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    assert_eq!(
+      ShaderResources {
+        uniform_buffers: 2,
+        uniform_bytes: 80,
+        storage_buffers: 1,
+        textures_2d: 1,
+        textures_2d_array: 1,
+        textures_cube: 3,
+        storage_textures: 1,
+        samplers: 2,
+        push_constant_size: 16,
+        ..Default::default()
+      },
+      ShaderResources::from_module(&module)
+    );
+  }
+
+  #[test]
+  fn features_for_wide_scalars_and_builtins() {
+    let source = indoc! {r#"
+            struct Input {
+              @builtin(primitive_index) index: u32,
+            };
+
+            @group(0) @binding(0) var<storage> a: vec2<i64>;
+
+            @fragment
+            fn main(in: Input) {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let features =
+      ShaderRequirements::from_module(&module, module_info(&module).as_ref()).features;
+
+    assert_eq!(
+      wgpu::Features::SHADER_INT64 | wgpu::Features::SHADER_PRIMITIVE_INDEX,
+      features
+    );
+  }
+
+  #[test]
+  fn features_for_f64_vertex_inputs() {
+    let source = indoc! {r#"
+            struct Input {
+              @location(0) position: vec3<f64>,
+            };
+
+            @vertex
+            fn main(in: Input) -> @builtin(position) vec4<f32> {
+              return vec4<f32>(vec3<f32>(in.position), 1.0);
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let features =
+      ShaderRequirements::from_module(&module, module_info(&module).as_ref()).features;
+
+    assert_eq!(
+      wgpu::Features::SHADER_F64 | wgpu::Features::VERTEX_ATTRIBUTE_64BIT,
+      features
+    );
+  }
+
+  #[test]
+  fn downlevel_incompatibilities_for_compute_with_storage() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<storage, read_write> a: array<u32>;
+
+            @compute
+            @workgroup_size(64)
+            fn main() {
+                a[0] = 1u;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    assert_eq!(
+      vec![
+        "compute shaders are not supported".to_string(),
+        "`max_storage_buffers_per_shader_stage` of 1 exceeds the limit of 0".to_string(),
+        "`max_storage_buffer_binding_size` of 4 exceeds the limit of 0".to_string(),
+      ],
+      downlevel_webgl2_incompatibilities(
+        &module,
+        module_info(&module).as_ref(),
+        wgpu::Features::empty()
+      )
+    );
+  }
+
+  #[test]
+  fn downlevel_compatible_render_module() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> a: vec4<f32>;
+
+            @vertex
+            fn vs_main() -> @builtin(position) vec4<f32> {
+              return a;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert!(downlevel_webgl2_incompatibilities(
+      &module,
+      module_info(&module).as_ref(),
+      wgpu::Features::empty()
+    )
+    .is_empty());
+  }
+}
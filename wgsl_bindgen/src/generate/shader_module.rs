@@ -5,25 +5,34 @@ use std::path::Path;
 
 use derive_more::Constructor;
 use enumflags2::BitFlags;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::{format_ident, quote, TokenStreamExt};
 use syn::{Ident, Index};
 
+use super::device_fn_types;
+use crate::bevy_util::SourceWithFullDependenciesResult;
 use crate::naga_util::module_to_source;
 use crate::quote_gen::create_shader_raw_string_literal;
-use crate::{WgslBindgenOption, WgslEntryResult, WgslShaderSourceType};
+use crate::{WgslBindgenOption, WgslEntryResult, WgslOutputMode, WgslShaderSourceType};
 
 impl<'a> WgslEntryResult<'a> {
-  fn get_label(&self) -> TokenStream {
+  fn get_label(&self, options: &WgslBindgenOption) -> TokenStream {
+    let file_path = &self.source_including_deps.source_file.file_path;
+
+    let override_label = options
+      .override_shader_label
+      .iter()
+      .find(|o| o.mod_name == self.mod_name)
+      .map(|o| o.label.clone());
+
     let get_label = || {
-      Some(
-        self
-          .source_including_deps
-          .source_file
-          .file_path
-          .file_name()?
-          .to_str()?,
-      )
+      override_label
+        .or_else(|| {
+          options
+            .shader_label_formatter
+            .map(|format| format(&self.mod_name, file_path.as_path()))
+        })
+        .or_else(|| Some(file_path.file_name()?.to_str()?.to_owned()))
     };
 
     match get_label() {
@@ -31,6 +40,21 @@ impl<'a> WgslEntryResult<'a> {
       None => quote!(None),
     }
   }
+
+  /// Hash of the entry shader contents including all of its dependencies.
+  fn get_source_hash(&self) -> String {
+    let SourceWithFullDependenciesResult {
+      source_file,
+      full_dependencies,
+    } = &self.source_including_deps;
+
+    let mut hasher = blake3::Hasher::new();
+    for source in std::iter::once(source_file).chain(full_dependencies.iter()) {
+      hasher.update(source.content.as_bytes());
+    }
+
+    hasher.finalize().to_string()
+  }
 }
 
 impl WgslShaderSourceType {
@@ -40,6 +64,8 @@ impl WgslShaderSourceType {
       UseEmbed => "create_shader_module_embed_source",
       UseComposerEmbed => "create_shader_module_embedded",
       UseComposerWithPath => "create_shader_module_from_path",
+      UseNagaIr => "create_shader_module_naga_ir",
+      UseRelativePath => "create_shader_module_relative_path",
     }
   }
 
@@ -49,23 +75,26 @@ impl WgslShaderSourceType {
       UseEmbed => format!("create_{}_pipeline_embed_source", name),
       UseComposerEmbed => format!("create_{}_pipeline_embedded", name),
       UseComposerWithPath => format!("create_{}_pipeline_from_path", name),
+      UseNagaIr => format!("create_{}_pipeline_naga_ir", name),
+      UseRelativePath => format!("create_{}_pipeline_relative_path", name),
     }
   }
 
   pub(crate) fn get_return_type(&self, type_to_return: TokenStream) -> TokenStream {
     use WgslShaderSourceType::*;
     match self {
-      UseEmbed | UseComposerEmbed => type_to_return,
+      UseEmbed | UseComposerEmbed | UseNagaIr => type_to_return,
       UseComposerWithPath => {
         quote!(Result<#type_to_return, naga_oil::compose::ComposerError>)
       }
+      UseRelativePath => quote!(Result<#type_to_return, std::io::Error>),
     }
   }
 
   pub(crate) fn wrap_return_stmt(&self, stm: TokenStream) -> TokenStream {
     use WgslShaderSourceType::*;
     match self {
-      UseComposerWithPath => quote!(Ok(#stm)),
+      UseComposerWithPath | UseRelativePath => quote!(Ok(#stm)),
       _ => stm,
     }
   }
@@ -73,7 +102,7 @@ impl WgslShaderSourceType {
   pub(crate) fn get_propagate_operator(&self) -> TokenStream {
     use WgslShaderSourceType::*;
     match self {
-      UseComposerWithPath => quote!(?),
+      UseComposerWithPath | UseRelativePath => quote!(?),
       _ => quote!(),
     }
   }
@@ -139,7 +168,7 @@ impl WgslShaderSourceType {
   pub(crate) fn unwrap_result(&self) -> TokenStream {
     use WgslShaderSourceType::*;
     match self {
-      UseComposerWithPath => quote!(.unwrap()),
+      UseComposerWithPath | UseRelativePath => quote!(.unwrap()),
       _ => quote!(),
     }
   }
@@ -149,7 +178,7 @@ impl WgslShaderSourceType {
   ) -> (TokenStream, TokenStream) {
     use WgslShaderSourceType::*;
     match self {
-      UseEmbed => {
+      UseEmbed | UseNagaIr => {
         let param_defs = quote!(device: &wgpu::Device);
         let params = quote!(device);
         (param_defs, params)
@@ -162,6 +191,11 @@ impl WgslShaderSourceType {
         let params = quote!(device, shader_defs);
         (param_defs, params)
       }
+      UseRelativePath => {
+        let param_defs = quote!(device: &wgpu::Device, base_dir: &std::path::Path);
+        let params = quote!(device, base_dir);
+        (param_defs, params)
+      }
     }
   }
 }
@@ -169,6 +203,7 @@ impl WgslShaderSourceType {
 #[derive(Constructor)]
 struct ComputeModuleBuilder<'a> {
   module: &'a naga::Module,
+  info: Option<&'a naga::valid::ModuleInfo>,
   source_type_flags: BitFlags<WgslShaderSourceType>,
 }
 
@@ -224,11 +259,22 @@ impl<'a> ComputeModuleBuilder<'a> {
       .filter(|e| e.stage == naga::ShaderStage::Compute)
   }
 
+  fn workgroup_storage_size(e: &naga::EntryPoint, size: u32) -> TokenStream {
+    let name = format_ident!("{}_WORKGROUP_STORAGE_SIZE", e.name.to_uppercase());
+    let size = Index::from(size as usize);
+    quote!(pub const #name: u32 = #size;)
+  }
+
   fn build(&self) -> TokenStream {
+    let workgroup_storage =
+      super::requirements::workgroup_storage(self.module, self.info);
     let entry_points: Vec<_> = self
       .entry_points_iter()
-      .map(|e| {
+      .zip(workgroup_storage)
+      .map(|(e, (_, variables))| {
         let workgroup_size_constant = Self::workgroup_size(e);
+        let workgroup_storage_size_constant =
+          Self::workgroup_storage_size(e, variables.iter().map(|(_, size)| size).sum());
 
         let create_pipeline_fns = self
           .source_type_flags
@@ -238,6 +284,7 @@ impl<'a> ComputeModuleBuilder<'a> {
 
         quote! {
             #workgroup_size_constant
+            #workgroup_storage_size_constant
             #(#create_pipeline_fns)*
         }
       })
@@ -257,20 +304,33 @@ impl<'a> ComputeModuleBuilder<'a> {
 }
 pub(crate) fn compute_module(
   module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
   source_type_flags: BitFlags<WgslShaderSourceType>,
 ) -> TokenStream {
-  ComputeModuleBuilder::new(module, source_type_flags).build()
+  ComputeModuleBuilder::new(module, info, source_type_flags).build()
 }
 
-fn generate_shader_module_embedded(entry: &WgslEntryResult) -> TokenStream {
-  let shader_content = module_to_source(&entry.naga_module).unwrap();
+fn generate_shader_module_embedded(
+  entry: &WgslEntryResult,
+  shader_label: &TokenStream,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  // naga drops the diagnostic directives, so carry them over as comments.
+  let diagnostics = entry
+    .source_including_deps
+    .diagnostics()
+    .into_iter()
+    .map(|directive| format!("// {directive}\n"))
+    .collect::<String>();
+  let shader_content = diagnostics + &module_to_source(&entry.naga_module).unwrap();
   let create_shader_module_fn =
     format_ident!("{}", WgslShaderSourceType::UseEmbed.create_shader_module_fn_name());
   let shader_literal = create_shader_raw_string_literal(&shader_content);
-  let shader_label = entry.get_label();
+  let alloc = options.alloc_crate();
+  let (generics, device, shader_module) = device_fn_types(options, "ShaderModule");
   let create_shader_module = quote! {
-      pub fn #create_shader_module_fn(device: &wgpu::Device) -> wgpu::ShaderModule {
-          let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+      pub fn #create_shader_module_fn #generics(device: &#device) -> #shader_module {
+          let source = #alloc::borrow::Cow::Borrowed(SHADER_STRING);
           device.create_shader_module(wgpu::ShaderModuleDescriptor {
               label: #shader_label,
               source: wgpu::ShaderSource::Wgsl(source)
@@ -285,12 +345,151 @@ fn generate_shader_module_embedded(entry: &WgslEntryResult) -> TokenStream {
   }
 }
 
+fn generate_shader_module_naga_ir(
+  entry: &WgslEntryResult,
+  shader_label: &TokenStream,
+) -> TokenStream {
+  let module_bytes =
+    bincode::serialize(&entry.naga_module).expect("failed to serialize naga module");
+  let module_bytes = Literal::byte_string(&module_bytes);
+  let create_shader_module_fn =
+    format_ident!("{}", WgslShaderSourceType::UseNagaIr.create_shader_module_fn_name());
+
+  quote! {
+      pub fn #create_shader_module_fn(device: &wgpu::Device) -> wgpu::ShaderModule {
+          let module: wgpu::naga::Module = bincode::deserialize(NAGA_MODULE_BYTES)
+              .expect("failed to deserialize naga module");
+          device.create_shader_module(wgpu::ShaderModuleDescriptor {
+              label: #shader_label,
+              source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(module))
+          })
+      }
+      pub const NAGA_MODULE_BYTES: &[u8] = #module_bytes;
+  }
+}
+
+fn generate_shader_module_relative_path(
+  entry: &WgslEntryResult,
+  workspace_root: &Path,
+  shader_label: &TokenStream,
+) -> TokenStream {
+  let relative_file_path = get_path_relative_to(
+    workspace_root,
+    &entry.source_including_deps.source_file.file_path,
+  );
+  let entry_source_path =
+    reader_path(workspace_root, &entry.source_including_deps.source_file.file_path);
+  let source_type = WgslShaderSourceType::UseRelativePath;
+  let create_shader_module_fn =
+    format_ident!("{}", source_type.create_shader_module_fn_name());
+  let (param_defs, _) = source_type.shader_module_params_defs_and_params();
+  let return_type = source_type.get_return_type(quote!(wgpu::ShaderModule));
+
+  quote! {
+      pub fn #create_shader_module_fn(#param_defs) -> #return_type {
+          let source = std::fs::read_to_string(base_dir.join(#relative_file_path))?;
+          Ok(device.create_shader_module(wgpu::ShaderModuleDescriptor {
+              label: #shader_label,
+              source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(source))
+          }))
+      }
+
+      pub fn create_shader_module_relative_path_from_reader(
+          device: &wgpu::Device,
+          read_source: impl Fn(&str) -> String,
+      ) -> wgpu::ShaderModule {
+          device.create_shader_module(wgpu::ShaderModuleDescriptor {
+              label: #shader_label,
+              source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(read_source(#entry_source_path)))
+          })
+      }
+  }
+}
+
+/// Fetches the files the `*_from_reader` functions read on the web: the entry and its
+/// dependencies when composing them, and only the entry when reading a composed shader.
+fn generate_fetch_sources(
+  source_type: BitFlags<WgslShaderSourceType>,
+  feature: &str,
+) -> TokenStream {
+  let paths = if source_type.contains(WgslShaderSourceType::UseComposerWithPath) {
+    quote!(DEPENDENCY_PATHS)
+  } else if source_type.contains(WgslShaderSourceType::UseRelativePath) {
+    quote!(&DEPENDENCY_PATHS[..1])
+  } else {
+    return quote!();
+  };
+
+  quote! {
+      #[cfg(all(target_arch = "wasm32", feature = #feature))]
+      pub async fn fetch_sources(
+          base_url: &str,
+      ) -> Result<std::collections::HashMap<String, String>, wasm_bindgen::JsValue> {
+          _root::_support::fetch_shader_sources(base_url, #paths).await
+      }
+  }
+}
+
+fn generate_try_create_shader_module(source_type: WgslShaderSourceType) -> TokenStream {
+  let create_shader_module_fn =
+    format_ident!("{}", source_type.create_shader_module_fn_name());
+  let try_create_shader_module_fn =
+    format_ident!("try_{}", source_type.create_shader_module_fn_name());
+  let (param_defs, params) = source_type.shader_module_params_defs_and_params();
+  let return_type =
+    source_type.get_return_type(quote!(Result<wgpu::ShaderModule, wgpu::Error>));
+  let propagate_operator = source_type.get_propagate_operator();
+  let return_stmt = source_type.wrap_return_stmt(quote! {
+    match error {
+      Some(error) => Err(error),
+      None => Ok(module),
+    }
+  });
+
+  quote! {
+      pub async fn #try_create_shader_module_fn(#param_defs) -> #return_type {
+          device.push_error_scope(wgpu::ErrorFilter::Validation);
+          let module = #create_shader_module_fn(#params);
+          // Pop the scope before propagating any composer error so it isn't left dangling.
+          let error = device.pop_error_scope().await;
+          let module = module #propagate_operator;
+          #return_stmt
+      }
+  }
+}
+
+fn generate_source_constants(
+  entry: &WgslEntryResult,
+  workspace_root: &Path,
+) -> TokenStream {
+  let SourceWithFullDependenciesResult {
+    source_file,
+    full_dependencies,
+  } = &entry.source_including_deps;
+
+  let source_hash = entry.get_source_hash();
+  let source_path = source_file.file_path.to_string();
+
+  // Relative to the workspace root, so watchers can resolve them from any base directory.
+  let dependency_paths = std::iter::once(*source_file)
+    .chain(full_dependencies.iter().copied())
+    .map(|source| reader_path(workspace_root, &source.file_path));
+
+  quote! {
+    pub const SOURCE_HASH: &str = #source_hash;
+    pub const SOURCE_PATH: &str = #source_path;
+    pub const DEPENDENCY_PATHS: &[&str] = &[#( #dependency_paths ),*];
+  }
+}
+
 struct ComposeShaderModuleBuilder<'a, 'b> {
   entry: &'a WgslEntryResult<'b>,
   capabilities: Option<naga::valid::Capabilities>,
   entry_source_path: &'a Path,
   output_dir: &'a Path,
+  workspace_root: &'a Path,
   source_type: WgslShaderSourceType,
+  shader_label: &'a TokenStream,
 }
 
 impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
@@ -298,7 +497,9 @@ impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
     entry: &'a WgslEntryResult<'b>,
     capabilities: Option<naga::valid::Capabilities>,
     output_dir: &'a Path,
+    workspace_root: &'a Path,
     source_type: WgslShaderSourceType,
+    shader_label: &'a TokenStream,
   ) -> Self {
     let entry_source_path = entry.source_including_deps.source_file.file_path.as_path();
 
@@ -306,8 +507,10 @@ impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
       entry,
       capabilities,
       output_dir,
+      workspace_root,
       source_type,
       entry_source_path,
+      shader_label,
     }
   }
 
@@ -457,23 +660,10 @@ impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
     }
   }
 
-  fn create_shader_module_fn(&self) -> TokenStream {
-    let create_shader_module_fn = self.create_shader_module_fn_name();
-    let load_shader_module_fn = self.load_shader_modules_fn_name();
-    let load_naga_module_fn = self.load_naga_module_fn_name();
-    let shader_label = self.entry.get_label();
-    let return_type = self.source_type.get_return_type(quote!(wgpu::ShaderModule));
-    let propagate_operator = self.source_type.get_propagate_operator();
-    let return_stmt = self.source_type.wrap_return_stmt(quote! {
-        device.create_shader_module(wgpu::ShaderModuleDescriptor {
-          label: #shader_label,
-          source: wgpu::ShaderSource::Wgsl(source)
-        })
-    });
-
+  fn composer_with_capabilities(&self) -> TokenStream {
     let composer = quote!(naga_oil::compose::Composer::default());
 
-    let composer_with_capabilities = match self.capabilities {
+    match self.capabilities {
       Some(capabilities) => {
         let capabilities = Index::from(capabilities.bits() as usize);
         quote! {
@@ -483,18 +673,94 @@ impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
       None => quote! {
         #composer
       },
-    };
+    }
+  }
+
+  /// Composes the module from the sources returned by a reader for their paths relative to the
+  /// workspace root, eg: fetched over HTTP on the web, or read under a base directory.
+  fn load_naga_module_from_reader_fn(&self) -> TokenStream {
+    if !self.source_type.is_use_composer_with_path() {
+      return quote!();
+    }
+
+    let dependency_modules = self
+      .entry
+      .source_including_deps
+      .full_dependencies
+      .iter()
+      .map(|dep| {
+        let as_name = dep
+          .module_name
+          .as_ref()
+          .map(|name| name.to_string())
+          .unwrap();
+        let as_name_assignment = quote! { as_name: Some(#as_name.into()) };
+
+        let relative_file_path = reader_path(self.workspace_root, &dep.file_path);
+        let source = quote!(&read_source(#relative_file_path));
+
+        self.source_type.add_composable_naga_module_stmt(
+          source,
+          relative_file_path,
+          as_name_assignment,
+        )
+      })
+      .collect::<Vec<_>>();
+
+    let relative_file_path = reader_path(self.workspace_root, self.entry_source_path);
+    let source = quote!(&read_source(#relative_file_path));
+    let make_naga_module_stmt = self
+      .source_type
+      .naga_module_ret_stmt(source, relative_file_path);
+
+    let composer_with_capabilities = self.composer_with_capabilities();
+    let return_type = self.source_type.get_return_type(quote!(wgpu::naga::Module));
+    let shader_module_return_type =
+      self.source_type.get_return_type(quote!(wgpu::ShaderModule));
+    let create_shader_module_stmts = self.create_shader_module_stmts();
 
     quote! {
-      pub fn #create_shader_module_fn(
-        device: &wgpu::Device,
+      pub fn load_naga_module_from_reader(
+        read_source: impl Fn(&str) -> String,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>
       ) -> #return_type {
-
         let mut composer = #composer_with_capabilities;
-        #load_shader_module_fn (&mut composer, &shader_defs) #propagate_operator;
-        let module = #load_naga_module_fn (&mut composer, shader_defs) #propagate_operator;
+        #(#dependency_modules)*
+        #make_naga_module_stmt
+      }
+
+      pub fn load_naga_module_from_dir(
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>
+      ) -> #return_type {
+        load_naga_module_from_reader(
+          |path| std::fs::read_to_string(base_dir.join(path)).unwrap(),
+          shader_defs,
+        )
+      }
+
+      pub fn create_shader_module_from_reader(
+        device: &wgpu::Device,
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>
+      ) -> #shader_module_return_type {
+        let module = load_naga_module_from_reader(read_source, shader_defs)?;
+        #create_shader_module_stmts
+      }
+    }
+  }
+
+  /// Creates the shader module from the composed `module`, written back to WGSL.
+  fn create_shader_module_stmts(&self) -> TokenStream {
+    let shader_label = self.shader_label;
+    let return_stmt = self.source_type.wrap_return_stmt(quote! {
+        device.create_shader_module(wgpu::ShaderModuleDescriptor {
+          label: #shader_label,
+          source: wgpu::ShaderSource::Wgsl(source)
+        })
+    });
 
+    quote! {
         // Mini validation to get module info
         let info = wgpu::naga::valid::Validator::new(
           wgpu::naga::valid::ValidationFlags::empty(),
@@ -512,6 +778,28 @@ impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
 
         let source = std::borrow::Cow::Owned(shader_string);
         #return_stmt
+    }
+  }
+
+  fn create_shader_module_fn(&self) -> TokenStream {
+    let create_shader_module_fn = self.create_shader_module_fn_name();
+    let load_shader_module_fn = self.load_shader_modules_fn_name();
+    let load_naga_module_fn = self.load_naga_module_fn_name();
+    let return_type = self.source_type.get_return_type(quote!(wgpu::ShaderModule));
+    let propagate_operator = self.source_type.get_propagate_operator();
+    let composer_with_capabilities = self.composer_with_capabilities();
+    let create_shader_module_stmts = self.create_shader_module_stmts();
+
+    quote! {
+      pub fn #create_shader_module_fn(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>
+      ) -> #return_type {
+
+        let mut composer = #composer_with_capabilities;
+        #load_shader_module_fn (&mut composer, &shader_defs) #propagate_operator;
+        let module = #load_naga_module_fn (&mut composer, shader_defs) #propagate_operator;
+        #create_shader_module_stmts
       }
     }
   }
@@ -520,12 +808,14 @@ impl<'a, 'b> ComposeShaderModuleBuilder<'a, 'b> {
     let constants = self.generate_constants_for_paths();
     let load_shader_modules_fn = self.load_shader_modules_fn();
     let load_naga_module_fn = self.load_naga_module_fn();
+    let load_naga_module_from_reader_fn = self.load_naga_module_from_reader_fn();
     let create_shader_module_fn = self.create_shader_module_fn();
 
     quote! {
       #constants
       #load_shader_modules_fn
       #load_naga_module_fn
+      #load_naga_module_from_reader_fn
       #create_shader_module_fn
     }
   }
@@ -540,25 +830,73 @@ pub(crate) fn shader_module(
   let output_dir = options
     .output
     .as_ref()
-    .and_then(|output_file| output_file.parent().map(|p| p.to_path_buf()))
+    .and_then(|output| match options.output_mode {
+      WgslOutputMode::SingleFile => output.parent().map(|p| p.to_path_buf()),
+      WgslOutputMode::Directory => Some(output.clone()),
+    })
     .unwrap_or_else(|| {
       std::env::var("CARGO_MANIFEST_DIR")
         .unwrap_or_else(|_| ".".into())
         .into()
     });
 
-  let mut token_stream = TokenStream::new();
+  let shader_label = entry.get_label(options);
+  let mut token_stream = generate_source_constants(entry, &options.workspace_root);
 
   if source_type.contains(UseEmbed) {
-    token_stream.append_all(generate_shader_module_embedded(entry));
+    token_stream.append_all(generate_shader_module_embedded(
+      entry,
+      &shader_label,
+      options,
+    ));
+
+    if options.generate_try_create_shader_module {
+      token_stream.append_all(generate_try_create_shader_module(UseEmbed));
+    }
+  }
+
+  if source_type.contains(UseNagaIr) {
+    token_stream.append_all(generate_shader_module_naga_ir(entry, &shader_label));
+
+    if options.generate_try_create_shader_module {
+      token_stream.append_all(generate_try_create_shader_module(UseNagaIr));
+    }
+  }
+
+  if source_type.contains(UseRelativePath) {
+    token_stream.append_all(generate_shader_module_relative_path(
+      entry,
+      &options.workspace_root,
+      &shader_label,
+    ));
+
+    if options.generate_try_create_shader_module {
+      token_stream.append_all(generate_try_create_shader_module(UseRelativePath));
+    }
+  }
+
+  if let Some(feature) = &options.wasm_fetch_feature {
+    token_stream.append_all(generate_fetch_sources(source_type, feature));
   }
 
-  let capabilities = options.ir_capabilities.clone();
+  let capabilities = entry
+    .source_including_deps
+    .capabilities_with(options.ir_capabilities, options.deny_ir_capabilities);
 
   if source_type.contains(UseComposerEmbed) {
-    let builder =
-      ComposeShaderModuleBuilder::new(entry, capabilities, &output_dir, UseComposerEmbed);
+    let builder = ComposeShaderModuleBuilder::new(
+      entry,
+      capabilities,
+      &output_dir,
+      &options.workspace_root,
+      UseComposerEmbed,
+      &shader_label,
+    );
     token_stream.append_all(builder.build());
+
+    if options.generate_try_create_shader_module {
+      token_stream.append_all(generate_try_create_shader_module(UseComposerEmbed));
+    }
   }
 
   if source_type.contains(UseComposerWithPath) {
@@ -566,14 +904,26 @@ pub(crate) fn shader_module(
       entry,
       capabilities,
       &output_dir,
+      &options.workspace_root,
       UseComposerWithPath,
+      &shader_label,
     );
     token_stream.append_all(builder.build());
+
+    if options.generate_try_create_shader_module {
+      token_stream.append_all(generate_try_create_shader_module(UseComposerWithPath));
+    }
   }
 
   token_stream
 }
 
+/// The path of a source relative to the workspace root with `/` separators, as the readers and
+/// `DEPENDENCY_PATHS` take them.
+fn reader_path(workspace_root: &Path, file: &Path) -> String {
+  get_path_relative_to(workspace_root, file).replace('\\', "/")
+}
+
 fn get_path_relative_to(relative_to: &std::path::Path, file: &std::path::Path) -> String {
   pathdiff::diff_paths(file, relative_to)
     .expect("failed to get relative path")
@@ -603,6 +953,7 @@ mod tests {
 
   use super::*;
   use crate::assert_tokens_eq;
+  use crate::naga_util::module_info;
 
   #[test]
   fn test_create_canonical_variable_name() {
@@ -622,7 +973,11 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = compute_module(&module, WgslShaderSourceType::UseEmbed.into());
+    let actual = compute_module(
+      &module,
+      module_info(&module).as_ref(),
+      WgslShaderSourceType::UseEmbed.into(),
+    );
 
     assert_tokens_eq!(quote!(), actual);
   }
@@ -641,12 +996,17 @@ mod tests {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = compute_module(&module, WgslShaderSourceType::UseEmbed.into());
+    let actual = compute_module(
+      &module,
+      module_info(&module).as_ref(),
+      WgslShaderSourceType::UseEmbed.into(),
+    );
 
     assert_tokens_eq!(
       quote! {
           pub mod compute {
               pub const MAIN1_WORKGROUP_SIZE: [u32; 3] = [1, 2, 3];
+              pub const MAIN1_WORKGROUP_STORAGE_SIZE: u32 = 0;
               pub fn create_main1_pipeline_embed_source(device: &wgpu::Device) -> wgpu::ComputePipeline {
                   let module = super::create_shader_module_embed_source(device);
                   let layout = super::create_pipeline_layout(device);
@@ -663,6 +1023,7 @@ mod tests {
                       )
               }
               pub const MAIN2_WORKGROUP_SIZE: [u32; 3] = [256, 1, 1];
+              pub const MAIN2_WORKGROUP_STORAGE_SIZE: u32 = 0;
               pub fn create_main2_pipeline_embed_source(device: &wgpu::Device) -> wgpu::ComputePipeline {
                   let module = super::create_shader_module_embed_source(device);
                   let layout = super::create_pipeline_layout(device);
@@ -683,4 +1044,45 @@ mod tests {
       actual
     );
   }
+
+  #[test]
+  fn write_compute_module_naga_ir() {
+    let source = indoc! {r#"
+            @compute
+            @workgroup_size(1)
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = compute_module(
+      &module,
+      module_info(&module).as_ref(),
+      WgslShaderSourceType::UseNagaIr.into(),
+    );
+
+    assert_tokens_eq!(
+      quote! {
+          pub mod compute {
+              pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [1, 1, 1];
+              pub const MAIN_WORKGROUP_STORAGE_SIZE: u32 = 0;
+              pub fn create_main_pipeline_naga_ir(device: &wgpu::Device) -> wgpu::ComputePipeline {
+                  let module = super::create_shader_module_naga_ir(device);
+                  let layout = super::create_pipeline_layout(device);
+                  device
+                      .create_compute_pipeline(
+                          &wgpu::ComputePipelineDescriptor {
+                              label: Some("Compute Pipeline main"),
+                              layout: Some(&layout),
+                              module: &module,
+                              entry_point: Some("main"),
+                              compilation_options: Default::default(),
+                              cache: None,
+                          },
+                      )
+              }
+          }
+      },
+      actual
+    );
+  }
 }
@@ -2,36 +2,102 @@
 //!
 //! This will create a `ShaderEntry` enum with a variant for each entry in `entries`,
 //! and functions for creating the pipeline layout and shader module for each variant.
+
 use derive_more::Constructor;
-use enumflags2::BitFlags;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::{format_ident, quote};
 
-use crate::{sanitize_and_pascal_case, WgslEntryResult, WgslShaderSourceType};
+use super::bind_group::generated_bind_group_data;
+use super::device_fn_types;
+use super::pipeline::push_constant_size;
+use super::quote_shader_stages;
+use crate::quote_gen::MOD_BEVY_HELPERS;
+use crate::{
+  sanitize_and_pascal_case, wgsl, WgslBindgenOption, WgslEntryResult,
+  WgslShaderSourceType,
+};
 
 #[derive(Constructor)]
 struct ShaderEntryBuilder<'a, 'b> {
   entries: &'a [WgslEntryResult<'b>],
-  source_type: BitFlags<WgslShaderSourceType>,
+  options: &'a WgslBindgenOption,
 }
 
 impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
-  fn build_registry_enum(&self) -> TokenStream {
-    let variants = self
+  /// Builds the tokens of each entry, eg: a match arm, gated behind the feature of the entry
+  /// in `entry_point_features`.
+  fn for_each_entry(
+    &self,
+    tokens: impl Fn(&WgslEntryResult) -> TokenStream,
+  ) -> Vec<TokenStream> {
+    self
       .entries
       .iter()
-      .map(|entry| format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name)));
+      .map(|entry| {
+        let cfg = self.options.entry_cfg(&entry.mod_name);
+        let tokens = tokens(entry);
+        quote!(#cfg #tokens)
+      })
+      .collect()
+  }
+
+  fn build_registry_enum(&self) -> TokenStream {
+    let mut derives = vec![];
+    if self.options.shader_entry_derive_serde {
+      derives.push(quote!(serde::Serialize));
+      derives.push(quote!(serde::Deserialize));
+    }
+    if self.options.shader_entry_derive_strum {
+      derives.push(quote!(strum::EnumIter));
+      derives.push(quote!(strum::EnumCount));
+      derives.push(quote!(strum::IntoStaticStr));
+      derives.push(quote!(strum::AsRefStr));
+    }
+    let extra_derives = (!derives.is_empty()).then(|| quote!(#[derive(#(#derives),*)]));
+
+    let variants = self.for_each_entry(|entry| {
+      let name = entry.mod_name.as_str();
+      let variant = format_ident!("{}", sanitize_and_pascal_case(name));
+
+      // Use the module name for the serialized names, same as `Display` and `FromStr`.
+      let serde_rename = self
+        .options
+        .shader_entry_derive_serde
+        .then(|| quote!(#[serde(rename = #name)]));
+      let strum_serialize = self
+        .options
+        .shader_entry_derive_strum
+        .then(|| quote!(#[strum(serialize = #name)]));
+
+      quote! {
+        #serde_rename
+        #strum_serialize
+        #variant
+      }
+    });
 
     quote! {
       #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+      #extra_derives
       pub enum ShaderEntry {
         #( #variants, )*
       }
     }
   }
 
+  fn build_all_const(&self) -> TokenStream {
+    let variants = self.for_each_entry(|entry| {
+      let variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+      quote!(Self::#variant)
+    });
+
+    quote! {
+      pub const ALL: &'static [Self] = &[#( #variants ),*];
+    }
+  }
+
   fn build_create_pipeline_layout_fn(&self) -> TokenStream {
-    let match_arms = self.entries.iter().map(|entry| {
+    let match_arms = self.for_each_entry(|entry| {
       let mod_path = format_ident!("{}", entry.mod_name);
       let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
 
@@ -40,9 +106,154 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
       }
     });
 
+    let (generics, device, pipeline_layout) =
+      device_fn_types(self.options, "PipelineLayout");
+    quote! {
+      pub fn create_pipeline_layout #generics(&self, device: &#device) -> #pipeline_layout {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_is_supported_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        Self::#enum_variant => #mod_path::is_supported(adapter)
+      }
+    });
+
+    quote! {
+      pub fn is_supported(&self, adapter: &wgpu::Adapter) -> Result<(), UnsupportedReason> {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_entry_points_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+      let entry_points = entry.naga_module.entry_points.iter().map(|entry_point| {
+        let stage = quote_shader_stages(wgsl::shader_stage(entry_point.stage));
+        let const_name =
+          format_ident!("{}", self.options.entry_point_const(&entry_point.name));
+        quote!((#stage, #mod_path::#const_name))
+      });
+
+      quote! {
+        Self::#enum_variant => &[#( #entry_points ),*]
+      }
+    });
+
+    quote! {
+      pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_bind_group_count_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+      // The bind group data was already checked when generating the module of the entry.
+      let groups = generated_bind_group_data(
+        &entry.naga_module,
+        entry.module_info.as_ref(),
+        self.options,
+      )
+      .map_or(0, |groups| groups.len());
+      let count = Literal::usize_unsuffixed(groups);
+
+      quote! {
+        Self::#enum_variant => #count
+      }
+    });
+
+    quote! {
+      pub fn bind_group_count(&self) -> u32 {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_vertex_buffer_layouts_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+      let layouts = wgsl::get_vertex_input_structs(&entry.mod_name, &entry.naga_module)
+        .into_iter()
+        .map(|input| {
+          let item_path = &input.item_path;
+          let fully_qualified_name = item_path.get_fully_qualified_name();
+          if self.options.is_instance_vertex_input(&fully_qualified_name) {
+            quote!(#item_path::vertex_buffer_layout(wgpu::VertexStepMode::Instance))
+          } else {
+            quote!(#item_path::vertex_buffer_layout(step_mode))
+          }
+        });
+
+      quote! {
+        Self::#enum_variant => vec![#( #layouts ),*]
+      }
+    });
+
+    quote! {
+      /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+      /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+      pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+      ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_push_constant_size_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+      let size = push_constant_size(&entry.naga_module).unwrap_or(0);
+      let size = Literal::u32_unsuffixed(size);
+
+      quote! {
+        Self::#enum_variant => #size
+      }
+    });
+
+    quote! {
+      pub fn push_constant_size(&self) -> u32 {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_resources_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        Self::#enum_variant => #mod_path::RESOURCES
+      }
+    });
+
     quote! {
-      pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-        match self {
+      pub const fn resources(&self) -> ShaderResources {
+        match *self {
           #( #match_arms, )*
         }
       }
@@ -53,7 +264,7 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
     let fn_name = format_ident!("{}", source_type.create_shader_module_fn_name());
     let (param_defs, params) = source_type.shader_module_params_defs_and_params();
 
-    let match_arms = self.entries.iter().map(|entry| {
+    let match_arms = self.for_each_entry(|entry| {
       let mod_path = format_ident!("{}", entry.mod_name);
       let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
 
@@ -68,22 +279,185 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
 
     quote! {
       pub fn #fn_name(&self, #param_defs) -> #return_type {
-        match self {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_create_compute_pipelines(
+    &self,
+    source_type: WgslShaderSourceType,
+  ) -> TokenStream {
+    let has_compute = self.entries.iter().any(|entry| {
+      entry
+        .naga_module
+        .entry_points
+        .iter()
+        .any(|e| e.stage == naga::ShaderStage::Compute)
+    });
+
+    if !has_compute {
+      return quote!();
+    }
+
+    let create_shader_module_fn =
+      format_ident!("{}", source_type.create_shader_module_fn_name());
+    let fn_name = format_ident!(
+      "{}",
+      source_type.create_shader_module_fn_name().replacen(
+        "create_shader_module",
+        "create_compute_pipelines",
+        1
+      )
+    );
+    let (param_defs, params) = source_type.shader_module_params_defs_and_params();
+
+    let match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+      let entry_points = entry
+        .naga_module
+        .entry_points
+        .iter()
+        .filter(|e| e.stage == naga::ShaderStage::Compute)
+        .map(|e| format_ident!("{}", self.options.entry_point_const(&e.name)));
+
+      quote! {
+        Self::#enum_variant => &[#( #mod_path::#entry_points ),*]
+      }
+    });
+
+    let return_type = source_type.get_return_type(quote!(Vec<wgpu::ComputePipeline>));
+    let propagate_operator = source_type.get_propagate_operator();
+    let empty_return = source_type.wrap_return_stmt(quote!(Vec::new()));
+    let pipelines_return = source_type.wrap_return_stmt(quote!(pipelines));
+
+    quote! {
+      pub fn #fn_name(&self, #param_defs) -> #return_type {
+        let entry_points: &[&str] = match *self {
           #( #match_arms, )*
+        };
+
+        if entry_points.is_empty() {
+          return #empty_return;
+        }
+
+        let module = self.#create_shader_module_fn(#params) #propagate_operator;
+        let layout = self.create_pipeline_layout(device);
+        let pipelines = entry_points
+          .iter()
+          .map(|entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+              label: Some(&format!("Compute Pipeline {entry_point}")),
+              layout: Some(&layout),
+              module: &module,
+              entry_point: Some(entry_point),
+              compilation_options: Default::default(),
+              cache: None,
+            })
+          })
+          .collect();
+
+        #pipelines_return
+      }
+    }
+  }
+
+  fn build_create_render_pipeline(
+    &self,
+    source_type: WgslShaderSourceType,
+  ) -> TokenStream {
+    let has_stage = |stage: naga::ShaderStage| {
+      self.entries.iter().any(|entry| {
+        let entry_points = &entry.naga_module.entry_points;
+        entry_points.iter().any(|e| e.stage == stage)
+      })
+    };
+
+    if !has_stage(naga::ShaderStage::Vertex) {
+      return quote!();
+    }
+
+    let create_shader_module_fn =
+      format_ident!("{}", source_type.create_shader_module_fn_name());
+    let fn_name = format_ident!(
+      "{}",
+      source_type.create_shader_module_fn_name().replacen(
+        "create_shader_module",
+        "create_render_pipeline",
+        1
+      )
+    );
+    let (param_defs, params) = source_type.shader_module_params_defs_and_params();
+    let none_return = source_type.wrap_return_stmt(quote!(None));
+
+    // The entries come from the `*_entry` functions of the shader, which set the step modes
+    // and the pipeline constants.
+    let (fragment_generics, fragment_param, fragment) =
+      if has_stage(naga::ShaderStage::Fragment) {
+        (
+          quote!(, const M: usize),
+          quote!(fragment: Option<&_support::FragmentEntry<M>>,),
+          quote!(fragment.map(|entry| _support::fragment_state(&module, entry))),
+        )
+      } else {
+        (quote!(), quote!(), quote!(None))
+      };
+
+    let return_type = source_type.get_return_type(quote!(Option<wgpu::RenderPipeline>));
+    let propagate_operator = source_type.get_propagate_operator();
+    let pipeline_return = source_type.wrap_return_stmt(quote!(Some(pipeline)));
+
+    quote! {
+      /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+      /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+      pub fn #fn_name<const N: usize #fragment_generics>(
+        &self,
+        #param_defs,
+        vertex: &_support::VertexEntry<N>,
+        #fragment_param
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+      ) -> #return_type {
+        let has_vertex = self
+          .entry_points()
+          .iter()
+          .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+          return #none_return;
         }
+
+        let module = self.#create_shader_module_fn(#params) #propagate_operator;
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+          label: Some(&format!("Render Pipeline {self}")),
+          layout: Some(&layout),
+          vertex: _support::vertex_state(&module, vertex),
+          fragment: #fragment,
+          primitive,
+          depth_stencil,
+          multisample: Default::default(),
+          multiview: None,
+          cache: None,
+        });
+
+        #pipeline_return
       }
     }
   }
 
   fn build_shader_entry_filename_fn(&self) -> TokenStream {
     if !self
-      .source_type
+      .options
+      .shader_source_type
       .contains(WgslShaderSourceType::UseComposerWithPath)
     {
       return quote!();
     }
 
-    let match_arms = self.entries.iter().map(|entry| {
+    let match_arms = self.for_each_entry(|entry| {
       let filename = entry
         .source_including_deps
         .source_file
@@ -101,7 +475,106 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
 
     quote! {
       pub fn shader_entry_filename(&self) -> &'static str {
-        match self {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  /// The entry path relative to the workspace root, or the path as given when it lies outside.
+  fn relative_path(&self, entry: &WgslEntryResult) -> String {
+    let file_path = entry.source_including_deps.source_file.file_path.as_path();
+    file_path
+      .strip_prefix(&self.options.workspace_root)
+      .unwrap_or(file_path)
+      .to_string_lossy()
+      .replace('\\', "/")
+  }
+
+  fn build_from_path_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let relative_path = self.relative_path(entry);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        #relative_path => Some(Self::#enum_variant)
+      }
+    });
+
+    quote! {
+      pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+          #( #match_arms, )*
+          _ => None,
+        }
+      }
+    }
+  }
+
+  fn build_relative_path_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let relative_path = self.relative_path(entry);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        Self::#enum_variant => #relative_path
+      }
+    });
+
+    quote! {
+      pub fn relative_path(&self) -> &'static str {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_dependency_paths_fn(&self) -> TokenStream {
+    let match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        Self::#enum_variant => #mod_path::DEPENDENCY_PATHS
+      }
+    });
+
+    quote! {
+      pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+          #( #match_arms, )*
+        }
+      }
+    }
+  }
+
+  fn build_load_naga_module_fn(&self) -> TokenStream {
+    if !self
+      .options
+      .shader_source_type
+      .contains(WgslShaderSourceType::UseComposerWithPath)
+    {
+      return quote!();
+    }
+
+    let match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        Self::#enum_variant => #mod_path::load_naga_module_from_dir(base_dir, shader_defs)
+      }
+    });
+
+    quote! {
+      pub fn load_naga_module(
+        &self,
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+      ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        match *self {
           #( #match_arms, )*
         }
       }
@@ -110,13 +583,14 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
 
   fn build_shader_paths_fn(&self) -> TokenStream {
     if !self
-      .source_type
+      .options
+      .shader_source_type
       .contains(WgslShaderSourceType::UseComposerWithPath)
     {
       return quote!();
     }
 
-    let match_arms = self.entries.iter().map(|entry| {
+    let match_arms = self.for_each_entry(|entry| {
       let mod_path = format_ident!("{}", entry.mod_name);
       let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
 
@@ -127,7 +601,7 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
 
     quote! {
       pub fn shader_paths(&self) -> &[&str] {
-        match self {
+        match *self {
           #( #match_arms, )*
         }
       }
@@ -136,22 +610,339 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
 
   fn build_enum_impl(&self) -> TokenStream {
     let create_shader_module_fns = self
-      .source_type
+      .options
+      .shader_source_type
       .iter()
       .map(|source_ty| self.build_create_shader_module(source_ty))
       .collect::<Vec<_>>();
 
+    let create_pipeline_fns = self
+      .options
+      .shader_source_type
+      .iter()
+      .map(|source_ty| {
+        let compute_pipelines_fn = self.build_create_compute_pipelines(source_ty);
+        let render_pipeline_fn = self.build_create_render_pipeline(source_ty);
+        quote! {
+          #compute_pipelines_fn
+          #render_pipeline_fn
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let all_const = self.build_all_const();
     let create_pipeline_layout_fn = self.build_create_pipeline_layout_fn();
+    let is_supported_fn = self.build_is_supported_fn();
+    let entry_points_fn = self.build_entry_points_fn();
+    let bind_group_count_fn = self.build_bind_group_count_fn();
+    let vertex_buffer_layouts_fn = self.build_vertex_buffer_layouts_fn();
+    let push_constant_size_fn = self.build_push_constant_size_fn();
+    let resources_fn = self.build_resources_fn();
 
     let shader_paths_fn = self.build_shader_paths_fn();
+    let load_naga_module_fn = self.build_load_naga_module_fn();
     let shader_entry_filename_fn = self.build_shader_entry_filename_fn();
+    let from_path_fn = self.build_from_path_fn();
+    let relative_path_fn = self.build_relative_path_fn();
+    let dependency_paths_fn = self.build_dependency_paths_fn();
 
     quote! {
       impl ShaderEntry {
+        #all_const
         #create_pipeline_layout_fn
+        #is_supported_fn
+        #entry_points_fn
+        #bind_group_count_fn
+        #vertex_buffer_layouts_fn
+        #push_constant_size_fn
+        #resources_fn
         #(#create_shader_module_fns)*
+        #(#create_pipeline_fns)*
         #shader_entry_filename_fn
         #shader_paths_fn
+        #load_naga_module_fn
+        #from_path_fn
+        #relative_path_fn
+        #dependency_paths_fn
+      }
+    }
+  }
+
+  fn build_display_and_from_str_impls(&self) -> TokenStream {
+    let name_arms = self.for_each_entry(|entry| {
+      let name = entry.mod_name.as_str();
+      let variant = format_ident!("{}", sanitize_and_pascal_case(name));
+      quote!(Self::#variant => #name)
+    });
+    let from_str_arms = self.for_each_entry(|entry| {
+      let name = entry.mod_name.as_str();
+      let variant = format_ident!("{}", sanitize_and_pascal_case(name));
+      quote!(#name => Ok(Self::#variant))
+    });
+    let core = self.options.core_crate();
+
+    quote! {
+      impl #core::fmt::Display for ShaderEntry {
+        fn fmt(&self, f: &mut #core::fmt::Formatter<'_>) -> #core::fmt::Result {
+          let name = match *self {
+            #( #name_arms, )*
+          };
+          f.write_str(name)
+        }
+      }
+
+      impl #core::str::FromStr for ShaderEntry {
+        type Err = UnknownShaderEntry;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          match s {
+            #( #from_str_arms, )*
+            _ => Err(UnknownShaderEntry(s.to_string())),
+          }
+        }
+      }
+
+      /// The error returned when parsing the name of an unknown shader entry.
+      #[derive(Clone, Debug, PartialEq, Eq)]
+      pub struct UnknownShaderEntry(pub String);
+
+      impl #core::fmt::Display for UnknownShaderEntry {
+        fn fmt(&self, f: &mut #core::fmt::Formatter<'_>) -> #core::fmt::Result {
+          write!(f, "unknown shader entry `{}`", self.0)
+        }
+      }
+
+      impl #core::error::Error for UnknownShaderEntry {}
+    }
+  }
+
+  fn build_unsupported_reason(&self) -> TokenStream {
+    let core = self.options.core_crate();
+
+    quote! {
+      /// The reason an adapter can't run a shader module.
+      #[derive(Clone, Debug, PartialEq, Eq)]
+      pub enum UnsupportedReason {
+        /// The adapter is missing features required by the shader.
+        MissingFeatures(wgpu::Features),
+        /// The shader requires a higher limit than the adapter supports.
+        LimitExceeded {
+          name: &'static str,
+          required: u64,
+          supported: u64,
+        },
+      }
+
+      impl #core::fmt::Display for UnsupportedReason {
+        fn fmt(&self, f: &mut #core::fmt::Formatter<'_>) -> #core::fmt::Result {
+          match self {
+            Self::MissingFeatures(features) => {
+              write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => write!(
+              f,
+              "adapter limit `{name}` of {supported} is below the required {required}"
+            ),
+          }
+        }
+      }
+
+      impl #core::error::Error for UnsupportedReason {}
+    }
+  }
+
+  fn build_shader_resources(&self) -> TokenStream {
+    quote! {
+      /// The resources bound by a shader module. Binding arrays count once per element.
+      #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+      pub struct ShaderResources {
+        pub uniform_buffers: u32,
+        /// The total size of the uniform bindings.
+        pub uniform_bytes: u64,
+        pub storage_buffers: u32,
+        pub textures_1d: u32,
+        pub textures_2d: u32,
+        pub textures_2d_array: u32,
+        pub textures_3d: u32,
+        pub textures_cube: u32,
+        pub textures_cube_array: u32,
+        pub storage_textures: u32,
+        pub samplers: u32,
+        pub push_constant_size: u32,
+      }
+
+      impl ShaderResources {
+        /// Sums the resources of two shaders, keeping the largest push constant size.
+        pub const fn add(self, other: Self) -> Self {
+          Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+              self.push_constant_size
+            } else {
+              other.push_constant_size
+            },
+          }
+        }
+
+        /// The number of sampled textures of all dimensions.
+        pub const fn sampled_textures(&self) -> u32 {
+          self.textures_1d
+            + self.textures_2d
+            + self.textures_2d_array
+            + self.textures_3d
+            + self.textures_cube
+            + self.textures_cube_array
+        }
+
+        /// Sums the resources of the given shaders.
+        pub fn total(entries: &[ShaderEntry]) -> Self {
+          entries
+            .iter()
+            .fold(Self::default(), |total, entry| total.add(entry.resources()))
+        }
+      }
+    }
+  }
+
+  fn build_gpu_device_trait(&self) -> TokenStream {
+    if !self.options.generic_device {
+      return quote!();
+    }
+
+    quote! {
+      /// The device calls of the generated functions creating layouts and shader modules,
+      /// implemented for `wgpu::Device`. Implement it with a mock to assert on the descriptors
+      /// without a GPU, eg: in unit tests running on CI.
+      pub trait GpuDevice {
+        type BindGroupLayout;
+        type PipelineLayout;
+        type ShaderModule;
+
+        fn create_bind_group_layout(
+          &self,
+          descriptor: &wgpu::BindGroupLayoutDescriptor,
+        ) -> Self::BindGroupLayout;
+
+        fn create_pipeline_layout(
+          &self,
+          label: Option<&str>,
+          bind_group_layouts: &[&Self::BindGroupLayout],
+          push_constant_ranges: &[wgpu::PushConstantRange],
+        ) -> Self::PipelineLayout;
+
+        fn create_shader_module(
+          &self,
+          descriptor: wgpu::ShaderModuleDescriptor,
+        ) -> Self::ShaderModule;
+      }
+
+      impl GpuDevice for wgpu::Device {
+        type BindGroupLayout = wgpu::BindGroupLayout;
+        type PipelineLayout = wgpu::PipelineLayout;
+        type ShaderModule = wgpu::ShaderModule;
+
+        fn create_bind_group_layout(
+          &self,
+          descriptor: &wgpu::BindGroupLayoutDescriptor,
+        ) -> wgpu::BindGroupLayout {
+          wgpu::Device::create_bind_group_layout(self, descriptor)
+        }
+
+        fn create_pipeline_layout(
+          &self,
+          label: Option<&str>,
+          bind_group_layouts: &[&wgpu::BindGroupLayout],
+          push_constant_ranges: &[wgpu::PushConstantRange],
+        ) -> wgpu::PipelineLayout {
+          wgpu::Device::create_pipeline_layout(
+            self,
+            &wgpu::PipelineLayoutDescriptor {
+              label,
+              bind_group_layouts,
+              push_constant_ranges,
+            },
+          )
+        }
+
+        fn create_shader_module(
+          &self,
+          descriptor: wgpu::ShaderModuleDescriptor,
+        ) -> wgpu::ShaderModule {
+          wgpu::Device::create_shader_module(self, descriptor)
+        }
+      }
+    }
+  }
+
+  /// The shader handles of the entries in Bevy, and with the embedded source type, a function
+  /// adding the shaders to Bevy's `Assets<Shader>`.
+  fn build_bevy_impl(&self) -> TokenStream {
+    let Some(feature) = &self.options.bevy_feature else {
+      return quote!();
+    };
+    let bevy_helpers = format_ident!("{MOD_BEVY_HELPERS}");
+
+    let handle_match_arms = self.for_each_entry(|entry| {
+      let mod_path = format_ident!("{}", entry.mod_name);
+      let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
+
+      quote! {
+        Self::#enum_variant => #mod_path::#bevy_helpers::SHADER_HANDLE
+      }
+    });
+
+    let add_shaders_fn = self
+      .options
+      .shader_source_type
+      .contains(WgslShaderSourceType::UseEmbed)
+      .then(|| {
+        let add_shaders = self.for_each_entry(|entry| {
+          let mod_path = format_ident!("{}", entry.mod_name);
+          quote! {
+            shaders.insert(
+              &#mod_path::#bevy_helpers::SHADER_HANDLE,
+              ::bevy::render::render_resource::Shader::from_wgsl(
+                #mod_path::SHADER_STRING,
+                #mod_path::SOURCE_PATH,
+              ),
+            );
+          }
+        });
+
+        quote! {
+          /// Adds the embedded shader of each entry to Bevy's `Assets<Shader>`, under its
+          /// `shader_handle`.
+          pub fn add_shaders(
+            shaders: &mut ::bevy::asset::Assets<::bevy::render::render_resource::Shader>,
+          ) {
+            #( #add_shaders )*
+          }
+        }
+      });
+
+    quote! {
+      #[cfg(feature = #feature)]
+      impl ShaderEntry {
+        /// The handle of the shader of the entry in Bevy's `Assets<Shader>`.
+        pub const fn shader_handle(
+          &self,
+        ) -> ::bevy::asset::Handle<::bevy::render::render_resource::Shader> {
+          match *self {
+            #( #handle_match_arms, )*
+          }
+        }
+
+        #add_shaders_fn
       }
     }
   }
@@ -159,16 +950,26 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
   pub fn build(&self) -> TokenStream {
     let enum_def = self.build_registry_enum();
     let enum_impl = self.build_enum_impl();
+    let display_and_from_str_impls = self.build_display_and_from_str_impls();
+    let unsupported_reason = self.build_unsupported_reason();
+    let shader_resources = self.build_shader_resources();
+    let gpu_device_trait = self.build_gpu_device_trait();
+    let bevy_impl = self.build_bevy_impl();
     quote! {
       #enum_def
       #enum_impl
+      #display_and_from_str_impls
+      #unsupported_reason
+      #shader_resources
+      #gpu_device_trait
+      #bevy_impl
     }
   }
 }
 
 pub(crate) fn build_shader_registry(
   entries: &[WgslEntryResult<'_>],
-  source_type: BitFlags<WgslShaderSourceType>,
+  options: &WgslBindgenOption,
 ) -> TokenStream {
-  ShaderEntryBuilder::new(entries, source_type).build()
+  ShaderEntryBuilder::new(entries, options).build()
 }
@@ -0,0 +1,705 @@
+//! Items used by all the entry modules of an output, emitted once in its `_support` module and
+//! re-exported by the entry modules instead of being generated in each of them.
+
+use naga::ShaderStage;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+  WgslBindgenOption, WgslEntryResult, WgslShaderSourceType, WgslTypeSerializeStrategy,
+};
+
+pub(crate) fn support_module(
+  entries: &[WgslEntryResult<'_>],
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let has_stage = |stage: ShaderStage| {
+    entries.iter().any(|entry| {
+      let entry_points = &entry.naga_module.entry_points;
+      entry_points
+        .iter()
+        .any(|entry_point| entry_point.stage == stage)
+    })
+  };
+  let has_bindings = entries.iter().any(|entry| {
+    let globals = &entry.naga_module.global_variables;
+    globals.iter().any(|(_, var)| var.binding.is_some())
+  });
+  let has_space = |is_space: fn(&naga::AddressSpace) -> bool| {
+    entries.iter().any(|entry| {
+      let globals = &entry.naga_module.global_variables;
+      globals.iter().any(|(_, var)| is_space(&var.space))
+    })
+  };
+
+  // Don't generate unused code.
+  let vertex_entry = has_stage(ShaderStage::Vertex).then(|| vertex_entry(options));
+  let fragment_entry = has_stage(ShaderStage::Fragment).then(|| fragment_entry(options));
+  let create_bind_group = has_bindings.then(create_bind_group_fn);
+  let check_support = check_support_fn();
+  let has_uniform = has_space(|space| *space == naga::AddressSpace::Uniform);
+  let uniform_buffer = has_uniform.then(|| typed_buffer(options, TypedBuffer::Uniform));
+  let n_buffered =
+    (has_uniform && options.frames_in_flight_buffers).then(|| n_buffered(options));
+  let uniform_arena =
+    (has_uniform && options.uniform_arenas).then(|| uniform_arena(options));
+  let shadowed = (has_uniform && options.shadowed_uniforms).then(|| shadowed(options));
+  let has_storage =
+    has_space(|space| matches!(space, naga::AddressSpace::Storage { .. }));
+  let storage_buffer = has_storage.then(|| typed_buffer(options, TypedBuffer::Storage));
+  let ping_pong =
+    (has_storage && has_stage(ShaderStage::Compute) && options.ping_pong_buffers)
+      .then(ping_pong);
+  // Reading back waits on a mutex, which isn't available without std.
+  let read_buffer = (has_storage && !options.no_std).then(read_buffer_fn);
+  let reads_sources = options.shader_source_type.intersects(
+    WgslShaderSourceType::UseComposerWithPath | WgslShaderSourceType::UseRelativePath,
+  );
+  let fetch_shader_sources = options
+    .wasm_fetch_feature
+    .as_deref()
+    .filter(|_| reads_sources)
+    .map(fetch_shader_sources_fn);
+
+  quote! {
+    #check_support
+    #create_bind_group
+    #vertex_entry
+    #fragment_entry
+    #uniform_buffer
+    #n_buffered
+    #uniform_arena
+    #shadowed
+    #storage_buffer
+    #ping_pong
+    #read_buffer
+    #fetch_shader_sources
+  }
+}
+
+fn constants_field_and_compilation_options(
+  options: &WgslBindgenOption,
+) -> (Option<TokenStream>, TokenStream) {
+  if options.no_std {
+    (None, quote!(Default::default()))
+  } else {
+    (
+      Some(quote!(pub constants: std::collections::HashMap<String, f64>,)),
+      quote! {
+        wgpu::PipelineCompilationOptions {
+          constants: &entry.constants,
+          ..Default::default()
+        }
+      },
+    )
+  }
+}
+
+fn vertex_entry(options: &WgslBindgenOption) -> TokenStream {
+  let (constants_field, compilation_options) =
+    constants_field_and_compilation_options(options);
+  let to_entry = if options.no_std {
+    quote! {
+        /// A [`VertexEntry`] with these buffers.
+        pub fn to_entry(&self) -> VertexEntry<N> {
+            VertexEntry {
+                entry_point: self.entry_point,
+                buffers: self.buffers.clone(),
+            }
+        }
+    }
+  } else {
+    quote! {
+        /// A [`VertexEntry`] with these buffers and the pipeline-overridable `constants`.
+        pub fn to_entry(
+            &self,
+            constants: std::collections::HashMap<String, f64>,
+        ) -> VertexEntry<N> {
+            VertexEntry {
+                entry_point: self.entry_point,
+                buffers: self.buffers.clone(),
+                constants,
+            }
+        }
+    }
+  };
+
+  quote! {
+      #[derive(Debug)]
+      pub struct VertexEntry<const N: usize> {
+          pub entry_point: &'static str,
+          pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+          #constants_field
+      }
+
+      /// The entry point and buffers of a [`VertexEntry`] without its pipeline constants, so it
+      /// can be created in a const fn.
+      #[derive(Debug)]
+      pub struct ConstVertexEntry<const N: usize> {
+          pub entry_point: &'static str,
+          pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+      }
+
+      impl<const N: usize> ConstVertexEntry<N> {
+          #to_entry
+      }
+
+      pub fn vertex_state<'a, const N: usize>(
+          module: &'a wgpu::ShaderModule,
+          entry: &'a VertexEntry<N>,
+      ) -> wgpu::VertexState<'a> {
+          wgpu::VertexState {
+              module,
+              entry_point: Some(entry.entry_point),
+              buffers: &entry.buffers,
+              compilation_options: #compilation_options,
+          }
+      }
+  }
+}
+
+fn fragment_entry(options: &WgslBindgenOption) -> TokenStream {
+  let (constants_field, compilation_options) =
+    constants_field_and_compilation_options(options);
+
+  quote! {
+      #[derive(Debug)]
+      pub struct FragmentEntry<const N: usize> {
+          pub entry_point: &'static str,
+          pub targets: [Option<wgpu::ColorTargetState>; N],
+          #constants_field
+      }
+
+      pub fn fragment_state<'a, const N: usize>(
+          module: &'a wgpu::ShaderModule,
+          entry: &'a FragmentEntry<N>,
+      ) -> wgpu::FragmentState<'a> {
+          wgpu::FragmentState {
+              module,
+              entry_point: Some(entry.entry_point),
+              targets: &entry.targets,
+              compilation_options: #compilation_options,
+          }
+      }
+  }
+}
+
+fn create_bind_group_fn() -> TokenStream {
+  quote! {
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+      device: &wgpu::Device,
+      layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+      label: &str,
+      entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+      let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+      device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some(label),
+        layout: &bind_group_layout,
+        entries,
+      })
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum TypedBuffer {
+  Uniform,
+  Storage,
+}
+
+impl TypedBuffer {
+  /// The usages of buffers of `self`, which storage buffers extend with `COPY_SRC` to be read
+  /// back with `read_buffer`.
+  pub(crate) fn usage(self) -> TokenStream {
+    match self {
+      TypedBuffer::Uniform => {
+        quote!(wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST)
+      }
+      TypedBuffer::Storage => quote! {
+        wgpu::BufferUsages::STORAGE
+          | wgpu::BufferUsages::COPY_SRC
+          | wgpu::BufferUsages::COPY_DST
+      },
+    }
+  }
+}
+
+/// The trait bound of the values written to a buffer of `kind`, and the bytes of such a `value`.
+pub(crate) fn buffer_contents(
+  options: &WgslBindgenOption,
+  kind: TypedBuffer,
+) -> (TokenStream, TokenStream) {
+  let alloc = options.alloc_crate();
+  let encase_buffer = match kind {
+    TypedBuffer::Uniform => quote!(encase::UniformBuffer),
+    TypedBuffer::Storage => quote!(encase::StorageBuffer),
+  };
+
+  match options.serialization_strategy {
+    WgslTypeSerializeStrategy::Bytemuck => {
+      (quote!(bytemuck::Pod), quote!(bytemuck::bytes_of(value)))
+    }
+    WgslTypeSerializeStrategy::Encase => (
+      quote!(encase::ShaderType + encase::internal::WriteInto),
+      quote! {
+        &{
+          let mut bytes = #encase_buffer::new(#alloc::vec::Vec::new());
+          bytes.write(value).unwrap();
+          bytes.into_inner()
+        }
+      },
+    ),
+  }
+}
+
+/// A buffer holding a value of one of the generated structs, so the struct written to it and
+/// the struct bound from it can't disagree.
+fn typed_buffer(options: &WgslBindgenOption, kind: TypedBuffer) -> TokenStream {
+  let core = options.core_crate();
+  let usage = kind.usage();
+  let (name, doc) = match kind {
+    TypedBuffer::Uniform => (
+      quote!(UniformBuffer),
+      " A uniform buffer holding a `T`, written and bound as a whole.",
+    ),
+    TypedBuffer::Storage => (
+      quote!(StorageBuffer),
+      " A storage buffer holding a `T`, written and bound as a whole.",
+    ),
+  };
+  let (bound, contents) = buffer_contents(options, kind);
+
+  quote! {
+    #[doc = #doc]
+    #[derive(Debug)]
+    pub struct #name<T> {
+      buffer: wgpu::Buffer,
+      _marker: #core::marker::PhantomData<T>,
+    }
+
+    impl<T: #bound> #name<T> {
+      pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+        use wgpu::util::DeviceExt;
+
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+          label: None,
+          contents: #contents,
+          usage: #usage,
+        });
+        Self {
+          buffer,
+          _marker: #core::marker::PhantomData,
+        }
+      }
+
+      pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+        queue.write_buffer(&self.buffer, 0, #contents);
+      }
+
+      pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+      }
+
+      pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+        self.buffer.as_entire_binding()
+      }
+
+      pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+        self.buffer.as_entire_buffer_binding()
+      }
+    }
+  }
+}
+
+/// Uniform buffers for each of the frames in flight, so a frame doesn't overwrite the values
+/// of the previous ones while the GPU still reads them.
+fn n_buffered(options: &WgslBindgenOption) -> TokenStream {
+  let core = options.core_crate();
+  let (bound, _) = buffer_contents(options, TypedBuffer::Uniform);
+
+  quote! {
+    /// `N` uniform buffers holding a `T` each, with a bind group per buffer, eg: one of the
+    /// generated bind groups.
+    #[derive(Debug)]
+    pub struct NBuffered<T, const N: usize, G = wgpu::BindGroup> {
+      buffers: [UniformBuffer<T>; N],
+      bind_groups: [G; N],
+    }
+
+    impl<T: #bound, const N: usize, G> NBuffered<T, N, G> {
+      /// Creates the buffers initialized with `value`, and the bind group of each buffer.
+      pub fn new(
+        device: &wgpu::Device,
+        value: &T,
+        mut bind_group: impl FnMut(&wgpu::Buffer) -> G,
+      ) -> Self {
+        let buffers: [UniformBuffer<T>; N] =
+          #core::array::from_fn(|_| UniformBuffer::new_init(device, value));
+        let bind_groups = #core::array::from_fn(|i| bind_group(buffers[i].buffer()));
+        Self {
+          buffers,
+          bind_groups,
+        }
+      }
+
+      pub fn write_for_frame(&self, queue: &wgpu::Queue, frame_index: usize, value: &T) {
+        self.buffers[frame_index % N].write(queue, value);
+      }
+
+      pub fn buffer(&self, frame_index: usize) -> &wgpu::Buffer {
+        self.buffers[frame_index % N].buffer()
+      }
+
+      pub fn bind_group(&self, frame_index: usize) -> &G {
+        &self.bind_groups[frame_index % N]
+      }
+    }
+  }
+}
+
+/// Two storage buffers a compute shader alternately reads from and writes to, eg: for the steps
+/// of a simulation.
+fn ping_pong() -> TokenStream {
+  quote! {
+    /// Two buffers swapped after each dispatch, with a bind group for each orientation, eg: one
+    /// of the generated bind groups.
+    #[derive(Debug)]
+    pub struct PingPong<G = wgpu::BindGroup> {
+      buffers: [wgpu::Buffer; 2],
+      bind_groups: [G; 2],
+      current: usize,
+    }
+
+    impl<G> PingPong<G> {
+      /// Creates the bind group reading from the first buffer and writing to the second, and the
+      /// one the other way around, with `bind_group(src, dst)`.
+      pub fn new(
+        buffers: [wgpu::Buffer; 2],
+        mut bind_group: impl FnMut(&wgpu::Buffer, &wgpu::Buffer) -> G,
+      ) -> Self {
+        let bind_groups = [
+          bind_group(&buffers[0], &buffers[1]),
+          bind_group(&buffers[1], &buffers[0]),
+        ];
+        Self {
+          buffers,
+          bind_groups,
+          current: 0,
+        }
+      }
+
+      /// Swaps the buffers, so the next dispatch reads what the previous one wrote.
+      pub fn swap(&mut self) {
+        self.current ^= 1;
+      }
+
+      /// The buffer the current bind group reads from.
+      pub fn src(&self) -> &wgpu::Buffer {
+        &self.buffers[self.current]
+      }
+
+      /// The buffer the current bind group writes to.
+      pub fn dst(&self) -> &wgpu::Buffer {
+        &self.buffers[self.current ^ 1]
+      }
+
+      pub fn bind_group(&self) -> &G {
+        &self.bind_groups[self.current]
+      }
+    }
+  }
+}
+
+/// A uniform buffer with a copy of its value on the CPU, so values are only uploaded when they
+/// change.
+fn shadowed(options: &WgslBindgenOption) -> TokenStream {
+  let (bound, _) = buffer_contents(options, TypedBuffer::Uniform);
+
+  quote! {
+    /// A [`UniformBuffer`] with a CPU copy of its `T`, tracking modifications of the copy so
+    /// [`Shadowed::flush`] only writes the buffer when it changed.
+    #[derive(Debug)]
+    pub struct Shadowed<T> {
+      buffer: UniformBuffer<T>,
+      value: T,
+      dirty: bool,
+    }
+
+    impl<T: #bound> Shadowed<T> {
+      pub fn new(device: &wgpu::Device, value: T) -> Self {
+        Self {
+          buffer: UniformBuffer::new_init(device, &value),
+          value,
+          dirty: false,
+        }
+      }
+
+      pub fn get(&self) -> &T {
+        &self.value
+      }
+
+      /// The CPU copy of the value, marked as modified.
+      pub fn get_mut(&mut self) -> &mut T {
+        self.dirty = true;
+        &mut self.value
+      }
+
+      pub fn set(&mut self, value: T) {
+        self.value = value;
+        self.dirty = true;
+      }
+
+      /// Whether the value was modified since it was last written to the buffer.
+      pub fn is_dirty(&self) -> bool {
+        self.dirty
+      }
+
+      /// Writes the value to the buffer if it was modified, returning whether it was written.
+      pub fn flush(&mut self, queue: &wgpu::Queue) -> bool {
+        if !self.dirty {
+          return false;
+        }
+        self.buffer.write(queue, &self.value);
+        self.dirty = false;
+        true
+      }
+
+      pub fn buffer(&self) -> &wgpu::Buffer {
+        self.buffer.buffer()
+      }
+
+      pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+        self.buffer.as_entire_buffer_binding()
+      }
+    }
+
+    impl<T: #bound + PartialEq> Shadowed<T> {
+      /// Sets the value, only marking it as modified when it differs from the current one.
+      pub fn set_if_changed(&mut self, value: T) {
+        if self.value != value {
+          self.set(value);
+        }
+      }
+    }
+  }
+}
+
+/// One uniform buffer holding many values of a struct, so scenes with thousands of objects
+/// don't need a buffer per object.
+fn uniform_arena(options: &WgslBindgenOption) -> TokenStream {
+  let core = options.core_crate();
+  let (bound, contents) = buffer_contents(options, TypedBuffer::Uniform);
+  let size = match options.serialization_strategy {
+    WgslTypeSerializeStrategy::Bytemuck => quote!(#core::mem::size_of::<T>() as u64),
+    WgslTypeSerializeStrategy::Encase => quote!(T::min_size().get()),
+  };
+
+  quote! {
+    /// A uniform buffer sub-allocated into slots holding a `T` each, aligned to
+    /// `min_uniform_buffer_offset_alignment`. Its binding needs `has_dynamic_offset` in the
+    /// bind group layout, with the offset of the [`UniformHandle`] to bind.
+    #[derive(Debug)]
+    pub struct UniformArena<T> {
+      buffer: wgpu::Buffer,
+      stride: wgpu::BufferAddress,
+      capacity: u32,
+      len: u32,
+      _marker: #core::marker::PhantomData<T>,
+    }
+
+    /// A slot of a [`UniformArena`], carrying its dynamic offset.
+    #[derive(Debug)]
+    pub struct UniformHandle<T> {
+      offset: wgpu::DynamicOffset,
+      _marker: #core::marker::PhantomData<T>,
+    }
+
+    impl<T> Clone for UniformHandle<T> {
+      fn clone(&self) -> Self {
+        *self
+      }
+    }
+
+    impl<T> Copy for UniformHandle<T> {}
+
+    impl<T> UniformHandle<T> {
+      pub fn offset(&self) -> wgpu::DynamicOffset {
+        self.offset
+      }
+    }
+
+    impl<T: #bound> UniformArena<T> {
+      /// Creates an arena with room for `capacity` values.
+      pub fn new(device: &wgpu::Device, limits: &wgpu::Limits, capacity: u32) -> Self {
+        let alignment = limits.min_uniform_buffer_offset_alignment as wgpu::BufferAddress;
+        let stride = (#size).next_multiple_of(alignment);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+          label: None,
+          size: stride * capacity as wgpu::BufferAddress,
+          usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+          mapped_at_creation: false,
+        });
+        Self {
+          buffer,
+          stride,
+          capacity,
+          len: 0,
+          _marker: #core::marker::PhantomData,
+        }
+      }
+
+      /// Writes `value` to the next free slot, or returns `None` when the arena is full.
+      pub fn alloc(&mut self, queue: &wgpu::Queue, value: &T) -> Option<UniformHandle<T>> {
+        if self.len == self.capacity {
+          return None;
+        }
+        let handle = UniformHandle {
+          offset: (self.len as wgpu::BufferAddress * self.stride) as wgpu::DynamicOffset,
+          _marker: #core::marker::PhantomData,
+        };
+        self.len += 1;
+        self.write(queue, handle, value);
+        Some(handle)
+      }
+
+      pub fn write(&self, queue: &wgpu::Queue, handle: UniformHandle<T>, value: &T) {
+        queue.write_buffer(&self.buffer, handle.offset as wgpu::BufferAddress, #contents);
+      }
+
+      /// Frees all the slots, invalidating the handles handed out so far.
+      pub fn clear(&mut self) {
+        self.len = 0;
+      }
+
+      pub fn len(&self) -> u32 {
+        self.len
+      }
+
+      pub fn is_empty(&self) -> bool {
+        self.len == 0
+      }
+
+      pub fn capacity(&self) -> u32 {
+        self.capacity
+      }
+
+      pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+      }
+
+      /// The binding of a single slot, selected by the dynamic offset of its handle.
+      pub fn binding(&self) -> wgpu::BindingResource<'_> {
+        wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+          buffer: &self.buffer,
+          offset: 0,
+          size: wgpu::BufferSize::new(#size),
+        })
+      }
+    }
+  }
+}
+
+fn read_buffer_fn() -> TokenStream {
+  quote! {
+    /// Reads back the contents of `buffer`, which needs the `COPY_SRC` usage, by copying it to a
+    /// staging buffer mapped for reading. Blocks while polling the device until the mapping
+    /// finishes, so it isn't available on the web, where buffers are mapped asynchronously.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_buffer(
+      device: &wgpu::Device,
+      queue: &wgpu::Queue,
+      buffer: &wgpu::Buffer,
+    ) -> Result<std::vec::Vec<u8>, wgpu::BufferAsyncError> {
+      let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("read_buffer"),
+        size: buffer.size(),
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+      });
+      let mut encoder = device.create_command_encoder(&Default::default());
+      encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+      queue.submit([encoder.finish()]);
+
+      let slice = staging.slice(..);
+      let (sender, receiver) = std::sync::mpsc::channel();
+      slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+      });
+      device.poll(wgpu::Maintain::Wait);
+      // The callback is dropped without running if the device is lost.
+      receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+
+      let bytes = slice.get_mapped_range().to_vec();
+      staging.unmap();
+      Ok(bytes)
+    }
+  }
+}
+
+fn check_support_fn() -> TokenStream {
+  quote! {
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+      adapter: &wgpu::Adapter,
+      features: wgpu::Features,
+      limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+      let missing_features = features.difference(adapter.features());
+      if !missing_features.is_empty() {
+        return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+      }
+
+      let mut exceeded = None;
+      limits.check_limits_with_fail_fn(
+        &adapter.limits(),
+        true,
+        |name, required, supported| {
+          exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+            name,
+            required,
+            supported,
+          });
+        },
+      );
+
+      match exceeded {
+        Some(reason) => Err(reason),
+        None => Ok(()),
+      }
+    }
+  }
+}
+
+fn fetch_shader_sources_fn(feature: &str) -> TokenStream {
+  quote! {
+    /// Fetches the shader files at `paths` relative to `base_url`, eg: the `DEPENDENCY_PATHS` of
+    /// an entry, keyed by their path for the `*_from_reader` functions to read them.
+    #[cfg(all(target_arch = "wasm32", feature = #feature))]
+    pub async fn fetch_shader_sources(
+      base_url: &str,
+      paths: &[&str],
+    ) -> Result<std::collections::HashMap<String, String>, wasm_bindgen::JsValue> {
+      use wasm_bindgen::JsCast;
+
+      let window = web_sys::window()
+        .ok_or_else(|| wasm_bindgen::JsValue::from_str("no window to fetch the shaders with"))?;
+      let mut sources = std::collections::HashMap::new();
+      for path in paths {
+        let url = format!("{}/{}", base_url.trim_end_matches('/'), path);
+        let response: web_sys::Response =
+          wasm_bindgen_futures::JsFuture::from(window.fetch_with_str(&url))
+            .await?
+            .dyn_into()?;
+        if !response.ok() {
+          let message = format!("failed to fetch `{url}`: {}", response.status());
+          return Err(wasm_bindgen::JsValue::from_str(&message));
+        }
+        let text = wasm_bindgen_futures::JsFuture::from(response.text()?).await?;
+        sources.insert(path.to_string(), text.as_string().unwrap_or_default());
+      }
+      Ok(sources)
+    }
+  }
+}
@@ -43,11 +43,18 @@ use bevy_util::SourceWithFullDependenciesResult;
 use case::CaseExt;
 use derive_more::IsVariant;
 use generate::entry::{self, entry_point_constants, vertex_struct_impls};
-use generate::{bind_group, consts, pipeline, shader_module, shader_registry};
+use generate::{
+  bevy_helpers, bind_group, consts, pipeline, requirements, shader_module,
+  shader_registry, support,
+};
 use heck::ToPascalCase;
+use miette::Diagnostic;
 use proc_macro2::{Span, TokenStream};
 use qs::{format_ident, quote, Ident, Index};
-use quote_gen::{custom_vector_matrix_assertions, RustModBuilder, MOD_STRUCT_ASSERTIONS};
+use quote_gen::{
+  custom_vector_matrix_assertions, RustItem, RustModBuilder, MOD_BEVY_HELPERS,
+  MOD_BIND_GROUP_LAYOUTS, MOD_BYTEMUCK_IMPLS, MOD_STRUCT_ASSERTIONS, MOD_SUPPORT,
+};
 use thiserror::Error;
 
 pub mod bevy_util;
@@ -72,11 +79,26 @@ pub use regex::Regex;
 pub use types::*;
 pub use wgsl_type::*;
 
+/// Includes the bindings written to `OUT_DIR` by [`WgslBindgenOptionBuilder::output_in_out_dir`].
+///
+/// ```ignore
+/// mod shader_bindings {
+///     wgsl_bindgen::include_generated!("shader_bindings.rs");
+/// }
+/// ```
+#[macro_export]
+macro_rules! include_generated {
+  ($file:literal) => {
+    include!(concat!(env!("OUT_DIR"), "/", $file));
+  };
+}
+
 /// Enum representing the possible serialization strategies for WGSL types.
 ///
 /// This enum is used to specify how WGSL types should be serialized when converted
 /// to Rust types.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, IsVariant)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, IsVariant, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WgslTypeSerializeStrategy {
   #[default]
   Encase,
@@ -84,16 +106,24 @@ pub enum WgslTypeSerializeStrategy {
 }
 
 /// Errors while generating Rust source for a WGSl shader module.
-#[derive(Debug, PartialEq, Eq, Error)]
+#[derive(Debug, PartialEq, Eq, Error, Diagnostic)]
 pub enum CreateModuleError {
   /// Bind group sets must be consecutive and start from 0.
   /// See `bind_group_layouts` for
   /// [PipelineLayoutDescriptor](https://docs.rs/wgpu/latest/wgpu/struct.PipelineLayoutDescriptor.html#).
   #[error("bind groups are non-consecutive or do not start from 0")]
+  #[diagnostic(
+    code(wgsl_bindgen::non_consecutive_bind_groups),
+    help("Number the `@group` attributes of each shader from 0 without gaps.")
+  )]
   NonConsecutiveBindGroups,
 
   /// Each binding resource must be associated with exactly one binding index.
   #[error("duplicate binding found with index `{binding}`")]
+  #[diagnostic(
+    code(wgsl_bindgen::duplicate_binding),
+    help("Give each resource in a bind group its own `@binding` index.")
+  )]
   DuplicateBinding { binding: u32 },
 }
 
@@ -101,44 +131,233 @@ pub enum CreateModuleError {
 pub(crate) struct WgslEntryResult<'a> {
   mod_name: String,
   naga_module: naga::Module,
+  /// The validation info of `naga_module`, or `None` when it doesn't validate.
+  module_info: Option<naga::valid::ModuleInfo>,
   source_including_deps: SourceWithFullDependenciesResult<'a>,
 }
 
+/// The entries an output generates, when the entry points are partitioned into several outputs.
+pub(crate) enum OutputSelection<'a> {
+  /// The main output, generating its first `own_entries` entries, along with the modules
+  /// shared by all the entries.
+  Main { own_entries: usize },
+  /// A partition, generating all of its entries and importing the modules shared
+  /// with the other entries from the main output at `shared_path`.
+  Partition { shared_path: &'a syn::Path },
+}
+
+impl OutputSelection<'_> {
+  fn own_entries<'a, 'b>(
+    &self,
+    entries: &'a [WgslEntryResult<'b>],
+  ) -> &'a [WgslEntryResult<'b>] {
+    match self {
+      Self::Main { own_entries } => &entries[..*own_entries],
+      Self::Partition { .. } => entries,
+    }
+  }
+}
+
+/// The top level module an item belongs to, which for the struct assertions and trait impls
+/// is the module of the struct.
+fn item_owner(item: &RustItem) -> &str {
+  let path = match item.path.module.as_str() {
+    MOD_STRUCT_ASSERTIONS | MOD_BYTEMUCK_IMPLS => item.path.name.as_str(),
+    module => module,
+  };
+  path.split("::").next().unwrap_or(path)
+}
+
+/// Gates an item placed outside the module of its entry, eg: its struct assertions,
+/// behind the feature of the entry in `entry_point_features`.
+fn gate_item(mut item: RustItem, options: &WgslBindgenOption) -> RustItem {
+  let owner = item_owner(&item);
+  let cfg = options.entry_cfg(owner);
+  if cfg.is_empty() || item.path.module.split("::").next() == Some(owner) {
+    return item;
+  }
+
+  let file: syn::File = syn::parse2(item.item).unwrap();
+  let items = file.items.iter().map(|item| quote!(#cfg #item));
+  item.item = quote!(#(#items)*);
+  item
+}
+
 fn create_rust_bindings(
-  entries: Vec<WgslEntryResult<'_>>,
+  entries: &[WgslEntryResult<'_>],
+  selection: &OutputSelection,
   options: &WgslBindgenOption,
 ) -> Result<String, CreateModuleError> {
+  let (mod_builder, shader_registry) = build_rust_modules(entries, selection, options)?;
+  let mod_token_stream = mod_builder.generate();
+  let alloc_prelude = alloc_prelude(options);
+
+  let allow_lints =
+    quote!(allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals));
+
+  let output = if options.includable_output {
+    // `include!` doesn't accept inner attributes, so the lints are allowed on each item instead.
+    let file: syn::File =
+      syn::parse2(quote!(#alloc_prelude #shader_registry #mod_token_stream)).unwrap();
+    let items = file.items.iter().map(|item| quote!(#[#allow_lints] #item));
+    quote!(#(#items)*)
+  } else {
+    quote! {
+      #![#allow_lints]
+
+      #alloc_prelude
+      #shader_registry
+      #mod_token_stream
+    }
+  };
+
+  Ok(pretty_print(&output))
+}
+
+/// Creates the bindings split into files, starting with `mod.rs` and followed by
+/// one file for each top level module.
+fn create_rust_bindings_files(
+  entries: &[WgslEntryResult<'_>],
+  selection: &OutputSelection,
+  options: &WgslBindgenOption,
+) -> Result<Vec<(String, String)>, CreateModuleError> {
+  let (mod_builder, shader_registry) = build_rust_modules(entries, selection, options)?;
+  let (mod_declarations, mod_files) = mod_builder.generate_files();
+  let alloc_prelude = alloc_prelude(options);
+
+  let mod_rs = quote! {
+    #![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+
+    #alloc_prelude
+    #shader_registry
+    #mod_declarations
+  };
+
+  let files = std::iter::once(("mod.rs".to_string(), pretty_print(&mod_rs)))
+    .chain(
+      mod_files
+        .into_iter()
+        .map(|(name, contents)| (format!("{name}.rs"), pretty_print(&contents))),
+    )
+    .collect();
+
+  Ok(files)
+}
+
+/// Brings the `alloc` items of the std prelude into scope for `no_std` crates.
+/// Every module imports the items of the root through `_root::*`, so they are visible from all of them.
+fn alloc_prelude(options: &WgslBindgenOption) -> TokenStream {
+  if options.no_std {
+    quote! {
+      use alloc::{
+        borrow::ToOwned,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+      };
+    }
+  } else {
+    quote!()
+  }
+}
+
+fn build_rust_modules(
+  entries: &[WgslEntryResult<'_>],
+  selection: &OutputSelection,
+  options: &WgslBindgenOption,
+) -> Result<(RustModBuilder, TokenStream), CreateModuleError> {
   let mut mod_builder = RustModBuilder::new(true, true);
 
-  if let Some(custom_wgsl_type_asserts) = custom_vector_matrix_assertions(options) {
-    mod_builder.add(MOD_STRUCT_ASSERTIONS, custom_wgsl_type_asserts);
+  let own_entries = selection.own_entries(entries);
+  let own_modules = own_entries
+    .iter()
+    .map(|entry| entry.mod_name.as_str())
+    .collect::<FxIndexSet<_>>();
+  let mut shared_modules = FxIndexSet::default();
+
+  // Items of other entries are generated by their own output, while the modules shared by
+  // the entries are only generated by the main output.
+  let mut select = |items: Vec<RustItem>| -> Vec<RustItem> {
+    items
+      .into_iter()
+      .filter(|item| {
+        let owner = item_owner(item);
+        match selection {
+          OutputSelection::Main { .. } => {
+            own_modules.contains(owner)
+              || !entries.iter().any(|entry| entry.mod_name == owner)
+          }
+          OutputSelection::Partition { .. } => {
+            let is_own = own_modules.contains(owner);
+            if !is_own {
+              shared_modules.insert(owner.to_string());
+            }
+            is_own
+          }
+        }
+      })
+      .map(|item| gate_item(item, options))
+      .collect()
+  };
+
+  if let OutputSelection::Main { .. } = selection {
+    if let Some(custom_wgsl_type_asserts) = custom_vector_matrix_assertions(options) {
+      mod_builder.add(MOD_STRUCT_ASSERTIONS, custom_wgsl_type_asserts);
+    }
+  }
+
+  if !own_entries.is_empty() {
+    mod_builder.add(MOD_SUPPORT, support::support_module(own_entries, options));
   }
 
   for entry in entries.iter() {
     let WgslEntryResult {
       mod_name,
       naga_module,
+      module_info,
       ..
     } = entry;
-    let entry_name = sanitize_and_pascal_case(&mod_name);
-    let bind_group_data = bind_group::get_bind_group_data(naga_module)?;
-    let shader_stages = wgsl::shader_stages(naga_module);
+    let module_info = module_info.as_ref();
+    let is_own = own_modules.contains(mod_name.as_str());
 
     // Write all the structs, including uniforms and entry function inputs.
     mod_builder
-      .add_items(structs::structs_items(&mod_name, naga_module, options))
-      .unwrap();
-
-    mod_builder
-      .add_items(consts::consts_items(&mod_name, naga_module))
+      .add_items(select(structs::structs_items(&mod_name, naga_module, options)))
       .unwrap();
 
-    mod_builder
-      .add(mod_name, consts::pipeline_overridable_constants(naga_module, options));
+    let consts_items = consts::consts_items(mod_name, naga_module)
+      .into_iter()
+      .filter(|item| {
+        !options.is_excluded(WgslItemKind::Const, &item.path.get_fully_qualified_name())
+      })
+      .collect();
+    mod_builder.add_items(select(consts_items)).unwrap();
+
+    if is_own {
+      mod_builder
+        .add(mod_name, consts::pipeline_overridable_constants(naga_module, options));
+    }
+
+    // Vertex inputs are entry point inputs, so their impls go along with excluded structs.
+    let vertex_struct_impls = vertex_struct_impls(mod_name, naga_module, options)
+      .into_iter()
+      .filter(|item| {
+        let name = item.path.get_fully_qualified_name();
+        !options.is_excluded(WgslItemKind::Struct, &name)
+          && !options.is_excluded(WgslItemKind::EntryPointIo, &name)
+      })
+      .collect();
+    mod_builder.add_items(select(vertex_struct_impls)).unwrap();
+
+    if !is_own {
+      continue;
+    }
 
-    mod_builder
-      .add_items(vertex_struct_impls(mod_name, naga_module))
-      .unwrap();
+    let entry_name = sanitize_and_pascal_case(&mod_name);
+    let bind_group_data =
+      bind_group::generated_bind_group_data(naga_module, module_info, options)?;
+    let shader_stages = wgsl::shader_stages(naga_module);
 
     mod_builder.add(
       mod_name,
@@ -150,15 +369,48 @@ fn create_rust_bindings(
         shader_stages,
       ),
     );
+    mod_builder.add(
+      mod_name,
+      bind_group::buffer_fns(&mod_name, &options, naga_module, &bind_group_data),
+    );
+    mod_builder.add(
+      mod_name,
+      bind_group::readback_fns(&mod_name, &options, naga_module, &bind_group_data),
+    );
+    mod_builder.add(
+      mod_name,
+      bind_group::array_codec_fns(mod_name, options, naga_module, &bind_group_data),
+    );
+    mod_builder.add(
+      mod_name,
+      bind_group::indirect_buffer_fns(&mod_name, &options, naga_module, &bind_group_data),
+    );
+    mod_builder.add(
+      mod_name,
+      bind_group::runtime_array_buffer_fns(mod_name, naga_module, &bind_group_data),
+    );
+    mod_builder.add(mod_name, bind_group::texture_fns(&mod_name, &bind_group_data));
+    mod_builder
+      .add(mod_name, bind_group::sampler_fns(mod_name, options, &bind_group_data));
 
     mod_builder.add(
       mod_name,
-      shader_module::compute_module(naga_module, options.shader_source_type),
+      shader_module::compute_module(naga_module, module_info, options.shader_source_type),
     );
-    mod_builder.add(mod_name, entry_point_constants(naga_module));
+    mod_builder.add(mod_name, entry_point_constants(naga_module, options));
+    mod_builder.add(
+      mod_name,
+      requirements::required_features_and_limits(
+        naga_module,
+        module_info,
+        entry.source_including_deps.extension_features(),
+      ),
+    );
+    mod_builder.add(mod_name, requirements::is_supported_fn());
+    mod_builder.add(mod_name, requirements::resources_const(naga_module));
 
-    mod_builder.add(mod_name, entry::vertex_states(mod_name, naga_module));
-    mod_builder.add(mod_name, entry::fragment_states(naga_module));
+    mod_builder.add(mod_name, entry::vertex_states(mod_name, naga_module, options));
+    mod_builder.add(mod_name, entry::fragment_states(naga_module, options));
 
     let create_pipeline_layout = pipeline::create_pipeline_layout_fn(
       &entry_name,
@@ -170,20 +422,35 @@ fn create_rust_bindings(
 
     mod_builder.add(mod_name, create_pipeline_layout);
     mod_builder.add(mod_name, shader_module::shader_module(entry, options));
+
+    if let Some(feature) = &options.bevy_feature {
+      let bevy_mod = format!("{mod_name}::{MOD_BEVY_HELPERS}");
+      let helpers = bevy_helpers::entry_helpers(entry, &bind_group_data, options);
+      mod_builder.add(&bevy_mod, helpers);
+      mod_builder.add_module_attributes(&bevy_mod, quote!(#[cfg(feature = #feature)]));
+    }
+    mod_builder.add_module_attributes(mod_name, options.entry_cfg(mod_name));
   }
 
-  let mod_token_stream = mod_builder.generate();
-  let shader_registry =
-    shader_registry::build_shader_registry(&entries, options.shader_source_type);
+  let bind_group_layouts = bind_group::bind_group_layouts_module(own_entries, options)?;
+  if !bind_group_layouts.is_empty() {
+    mod_builder.add(MOD_BIND_GROUP_LAYOUTS, bind_group_layouts);
+  }
 
-  let output = quote! {
-    #![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+  let shader_registry = match own_entries.is_empty() {
+    true => quote!(),
+    false => shader_registry::build_shader_registry(own_entries, options),
+  };
 
-    #shader_registry
-    #mod_token_stream
+  let shared_imports = match selection {
+    OutputSelection::Partition { shared_path } if !shared_modules.is_empty() => {
+      let shared_modules = shared_modules.iter().map(|name| format_ident!("{name}"));
+      quote!(pub use #shared_path::{#(#shared_modules),*};)
+    }
+    _ => quote!(),
   };
 
-  Ok(pretty_print(&output))
+  Ok((mod_builder, quote!(#shared_imports #shader_registry)))
 }
 
 fn pretty_print(tokens: &TokenStream) -> String {
@@ -236,6 +503,7 @@ mod test {
     let dummy_source = SourceFile::create(SourceFilePath::new(""), None, "".into());
     let entry = WgslEntryResult {
       mod_name: "test".into(),
+      module_info: naga_util::module_info(&naga_module),
       naga_module,
       source_including_deps: SourceWithFullDependenciesResult {
         full_dependencies: Default::default(),
@@ -243,7 +511,9 @@ mod test {
       },
     };
 
-    Ok(create_rust_bindings(vec![entry], &options)?)
+    let selection = OutputSelection::Main { own_entries: 1 };
+    let output = create_rust_bindings(&[entry], &selection, &options)?;
+    Ok(output)
   }
 
   #[test]
@@ -265,26 +535,207 @@ mod test {
                     Test,
                 }
                 impl ShaderEntry {
+                    pub const ALL: &'static [Self] = &[Self::Test];
                     pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-                        match self {
+                        match *self {
                             Self::Test => test::create_pipeline_layout(device),
                         }
                     }
+                    pub fn is_supported(
+                        &self,
+                        adapter: &wgpu::Adapter,
+                    ) -> Result<(), UnsupportedReason> {
+                        match *self {
+                            Self::Test => test::is_supported(adapter),
+                        }
+                    }
+                    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+                        match *self {
+                            Self::Test => &[(wgpu::ShaderStages::FRAGMENT, test::ENTRY_FS_MAIN)],
+                        }
+                    }
+                    pub fn bind_group_count(&self) -> u32 {
+                        match *self {
+                            Self::Test => 0,
+                        }
+                    }
+                    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+                    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+                    pub fn vertex_buffer_layouts(
+                        &self,
+                        step_mode: wgpu::VertexStepMode,
+                    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+                        match *self {
+                            Self::Test => vec![],
+                        }
+                    }
+                    pub fn push_constant_size(&self) -> u32 {
+                        match *self {
+                            Self::Test => 16,
+                        }
+                    }
+                    pub const fn resources(&self) -> ShaderResources {
+                        match *self {
+                            Self::Test => test::RESOURCES,
+                        }
+                    }
                     pub fn create_shader_module_embed_source(
                         &self,
                         device: &wgpu::Device,
                     ) -> wgpu::ShaderModule {
-                        match self {
+                        match *self {
                             Self::Test => test::create_shader_module_embed_source(device),
                         }
                     }
+                    pub fn from_path(path: &str) -> Option<Self> {
+                        match path {
+                            "" => Some(Self::Test),
+                            _ => None,
+                        }
+                    }
+                    pub fn relative_path(&self) -> &'static str {
+                        match *self {
+                            Self::Test => "",
+                        }
+                    }
+                    pub fn dependency_paths(&self) -> &'static [&'static str] {
+                        match *self {
+                            Self::Test => test::DEPENDENCY_PATHS,
+                        }
+                    }
+                }
+                impl std::fmt::Display for ShaderEntry {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        let name = match *self {
+                            Self::Test => "test",
+                        };
+                        f.write_str(name)
+                    }
+                }
+                impl std::str::FromStr for ShaderEntry {
+                    type Err = UnknownShaderEntry;
+                    fn from_str(s: &str) -> Result<Self, Self::Err> {
+                        match s {
+                            "test" => Ok(Self::Test),
+                            _ => Err(UnknownShaderEntry(s.to_string())),
+                        }
+                    }
+                }
+                /// The error returned when parsing the name of an unknown shader entry.
+                #[derive(Clone, Debug, PartialEq, Eq)]
+                pub struct UnknownShaderEntry(pub String);
+                impl std::fmt::Display for UnknownShaderEntry {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(f, "unknown shader entry `{}`", self.0)
+                    }
+                }
+                impl std::error::Error for UnknownShaderEntry {}
+                /// The reason an adapter can't run a shader module.
+                #[derive(Clone, Debug, PartialEq, Eq)]
+                pub enum UnsupportedReason {
+                    /// The adapter is missing features required by the shader.
+                    MissingFeatures(wgpu::Features),
+                    /// The shader requires a higher limit than the adapter supports.
+                    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+                }
+                impl std::fmt::Display for UnsupportedReason {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        match self {
+                            Self::MissingFeatures(features) => {
+                                write!(f, "adapter is missing required features {features:?}")
+                            }
+                            Self::LimitExceeded { name, required, supported } => {
+                                write!(
+                                    f,
+                                    "adapter limit `{name}` of {supported} is below the required {required}"
+                                )
+                            }
+                        }
+                    }
+                }
+                impl std::error::Error for UnsupportedReason {}
+                /// The resources bound by a shader module. Binding arrays count once per element.
+                #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+                pub struct ShaderResources {
+                    pub uniform_buffers: u32,
+                    /// The total size of the uniform bindings.
+                    pub uniform_bytes: u64,
+                    pub storage_buffers: u32,
+                    pub textures_1d: u32,
+                    pub textures_2d: u32,
+                    pub textures_2d_array: u32,
+                    pub textures_3d: u32,
+                    pub textures_cube: u32,
+                    pub textures_cube_array: u32,
+                    pub storage_textures: u32,
+                    pub samplers: u32,
+                    pub push_constant_size: u32,
+                }
+                impl ShaderResources {
+                    /// Sums the resources of two shaders, keeping the largest push constant size.
+                    pub const fn add(self, other: Self) -> Self {
+                        Self {
+                            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+                            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+                            storage_buffers: self.storage_buffers + other.storage_buffers,
+                            textures_1d: self.textures_1d + other.textures_1d,
+                            textures_2d: self.textures_2d + other.textures_2d,
+                            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+                            textures_3d: self.textures_3d + other.textures_3d,
+                            textures_cube: self.textures_cube + other.textures_cube,
+                            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+                            storage_textures: self.storage_textures + other.storage_textures,
+                            samplers: self.samplers + other.samplers,
+                            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                                self.push_constant_size
+                            } else {
+                                other.push_constant_size
+                            },
+                        }
+                    }
+                    /// The number of sampled textures of all dimensions.
+                    pub const fn sampled_textures(&self) -> u32 {
+                        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+                            + self.textures_cube + self.textures_cube_array
+                    }
+                    /// Sums the resources of the given shaders.
+                    pub fn total(entries: &[ShaderEntry]) -> Self {
+                        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+                    }
                 }
                 mod _root {
                     pub use super::*;
                 }
-                pub mod test {
+                pub mod _support {
                     use super::{_root, _root::*};
-                    pub const ENTRY_FS_MAIN: &str = "fs_main";
+                    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+                    pub fn check_support(
+                        adapter: &wgpu::Adapter,
+                        features: wgpu::Features,
+                        limits: &wgpu::Limits,
+                    ) -> Result<(), _root::UnsupportedReason> {
+                        let missing_features = features.difference(adapter.features());
+                        if !missing_features.is_empty() {
+                            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+                        }
+                        let mut exceeded = None;
+                        limits
+                            .check_limits_with_fail_fn(
+                                &adapter.limits(),
+                                true,
+                                |name, required, supported| {
+                                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                                        name,
+                                        required,
+                                        supported,
+                                    });
+                                },
+                            );
+                        match exceeded {
+                            Some(reason) => Err(reason),
+                            None => Ok(()),
+                        }
+                    }
                     #[derive(Debug)]
                     pub struct FragmentEntry<const N: usize> {
                         pub entry_point: &'static str,
@@ -297,7 +748,7 @@ mod test {
                     ) -> wgpu::FragmentState<'a> {
                         wgpu::FragmentState {
                             module,
-                            entry_point: entry.entry_point,
+                            entry_point: Some(entry.entry_point),
                             targets: &entry.targets,
                             compilation_options: wgpu::PipelineCompilationOptions {
                                 constants: &entry.constants,
@@ -305,6 +756,39 @@ mod test {
                             },
                         }
                     }
+                }
+                pub mod test {
+                    use super::{_root, _root::*};
+                    pub const ENTRY_FS_MAIN: &str = "fs_main";
+                    /// The device features required by this shader module.
+                    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS;
+                    /// The minimal device limits required by this shader module.
+                    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+                        max_push_constant_size: 16,
+                        ..wgpu::Limits::downlevel_webgl2_defaults()
+                    };
+                    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+                    pub fn is_supported(
+                        adapter: &wgpu::Adapter,
+                    ) -> Result<(), _root::UnsupportedReason> {
+                        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+                    }
+                    /// The resources bound by this shader module, for budgeting across shaders.
+                    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+                        uniform_buffers: 0,
+                        uniform_bytes: 0,
+                        storage_buffers: 0,
+                        textures_1d: 0,
+                        textures_2d: 0,
+                        textures_2d_array: 0,
+                        textures_3d: 0,
+                        textures_cube: 0,
+                        textures_cube_array: 0,
+                        storage_textures: 0,
+                        samplers: 0,
+                        push_constant_size: 16,
+                    };
+                    pub use _root::_support::{FragmentEntry, fragment_state};
                     pub fn fs_main_entry(
                         targets: [Option<wgpu::ColorTargetState>; 0],
                     ) -> FragmentEntry<0> {
@@ -338,6 +822,9 @@ mod test {
                                 },
                             )
                     }
+                    pub const SOURCE_HASH: &str = "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262";
+                    pub const SOURCE_PATH: &str = "";
+                    pub const DEPENDENCY_PATHS: &[&str] = &[""];
                     pub fn create_shader_module_embed_source(
                         device: &wgpu::Device,
                     ) -> wgpu::ShaderModule {
@@ -1,2 +1,10 @@
 mod module_to_source;
+mod remap_locations;
+mod rename_structs;
+mod validation_warnings;
+mod vertex_pulling;
 pub use module_to_source::*;
+pub(crate) use remap_locations::*;
+pub(crate) use rename_structs::*;
+pub(crate) use validation_warnings::*;
+pub(crate) use vertex_pulling::*;
@@ -0,0 +1,154 @@
+//! Remapping of the `@location` of the vertex inputs of a composed naga module, so the
+//! embedded shader reads its attributes from the locations the generated layouts use.
+
+use naga::{Binding, ShaderStage, TypeInner};
+
+use crate::FxIndexSet;
+
+/// Moves the vertex inputs at the locations `remap` returns a new location for, both in the
+/// arguments of the vertex entry points and in the members of their input structs.
+/// Returns the locations read by several inputs of an entry point after remapping, if any.
+pub(crate) fn remap_vertex_locations(
+  module: &mut naga::Module,
+  remap: impl Fn(u32) -> Option<u32>,
+) -> Result<(), Vec<String>> {
+  let remap_binding = |binding: &mut Option<Binding>| {
+    if let Some(Binding::Location { location, .. }) = binding {
+      if let Some(to) = remap(*location) {
+        *location = to;
+      }
+    }
+  };
+
+  let mut input_structs = FxIndexSet::default();
+  for entry_point in &mut module.entry_points {
+    if entry_point.stage != ShaderStage::Vertex {
+      continue;
+    }
+
+    for argument in &mut entry_point.function.arguments {
+      remap_binding(&mut argument.binding);
+      if let TypeInner::Struct { .. } = module.types[argument.ty].inner {
+        input_structs.insert(argument.ty);
+      }
+    }
+  }
+
+  // Types are interned, so the input structs are replaced by their remapped copies.
+  for handle in input_structs {
+    let mut ty = module.types[handle].clone();
+    if let TypeInner::Struct { members, .. } = &mut ty.inner {
+      members
+        .iter_mut()
+        .for_each(|member| remap_binding(&mut member.binding));
+    }
+    if ty != module.types[handle] {
+      module.types.replace(handle, ty);
+    }
+  }
+
+  let errors = module
+    .entry_points
+    .iter()
+    .filter(|entry_point| entry_point.stage == ShaderStage::Vertex)
+    .flat_map(|entry_point| {
+      let mut locations = FxIndexSet::default();
+      let mut collisions = FxIndexSet::default();
+      for location in input_locations(module, &entry_point.function) {
+        if !locations.insert(location) {
+          collisions.insert(location);
+        }
+      }
+      collisions.into_iter().map(|location| {
+        format!(
+          "@location({location}) is read by several inputs of `{}` after `remap_vertex_location`",
+          entry_point.name
+        )
+      })
+    })
+    .collect::<Vec<_>>();
+
+  match errors.is_empty() {
+    true => Ok(()),
+    false => Err(errors),
+  }
+}
+
+fn input_locations(module: &naga::Module, function: &naga::Function) -> Vec<u32> {
+  let location = |binding: &Option<Binding>| match binding {
+    Some(Binding::Location { location, .. }) => Some(*location),
+    _ => None,
+  };
+
+  function
+    .arguments
+    .iter()
+    .flat_map(|argument| match &module.types[argument.ty].inner {
+      TypeInner::Struct { members, .. } => members
+        .iter()
+        .filter_map(|member| location(&member.binding))
+        .collect(),
+      _ => location(&argument.binding).into_iter().collect::<Vec<_>>(),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn remapped(
+    source: &str,
+    remap: impl Fn(u32) -> Option<u32>,
+  ) -> Result<Vec<u32>, Vec<String>> {
+    let mut module = naga::front::wgsl::parse_str(source).unwrap();
+    remap_vertex_locations(&mut module, remap)?;
+    Ok(input_locations(&module, &module.entry_points[0].function))
+  }
+
+  #[test]
+  fn remaps_struct_members_and_arguments() {
+    let source = indoc! {r#"
+      struct VertexInput {
+          @location(0) position: vec3<f32>,
+          @location(1) uv: vec2<f32>,
+      };
+
+      @vertex
+      fn vs_main(in: VertexInput, @location(2) color: vec4<f32>) -> @builtin(position) vec4<f32> {
+          return vec4(in.position, in.uv.x) * color;
+      }
+    "#};
+
+    let locations = remapped(source, |location| match location {
+      1 => Some(4),
+      2 => Some(7),
+      _ => None,
+    });
+
+    assert_eq!(Ok(vec![0, 4, 7]), locations);
+  }
+
+  #[test]
+  fn reports_colliding_locations() {
+    let source = indoc! {r#"
+      @vertex
+      fn vs_main(@location(0) a: vec4<f32>, @location(1) b: vec4<f32>) -> @builtin(position) vec4<f32> {
+          return a * b;
+      }
+    "#};
+
+    let locations = remapped(source, |location| (location == 1).then_some(0));
+
+    assert_eq!(
+      Err(vec![
+        "@location(0) is read by several inputs of `vs_main` after `remap_vertex_location`"
+          .to_string()
+      ]),
+      locations
+    );
+  }
+}
@@ -0,0 +1,99 @@
+//! Renaming of the structs of a composed naga module, so the generated Rust structs follow the
+//! configured naming convention, eg: `LightData` for the WGSL struct `light_data`.
+
+use naga::TypeInner;
+
+use crate::bevy_util::DECORATION_PRE;
+use crate::{FxIndexSet, WgslNameCase};
+
+/// Renames the structs of `module` to the casing of `case`. The module path naga_oil decorates
+/// the names of imported structs with is kept, and a struct is left as is when its new name is
+/// taken by another type, so the renamed types stay distinct.
+pub(crate) fn rename_structs(module: &mut naga::Module, case: WgslNameCase) {
+  if case == WgslNameCase::Verbatim {
+    return;
+  }
+
+  let mut taken = module
+    .types
+    .iter()
+    .filter_map(|(_, ty)| ty.name.clone())
+    .collect::<FxIndexSet<_>>();
+
+  let renames = module
+    .types
+    .iter()
+    .filter(|(_, ty)| matches!(ty.inner, TypeInner::Struct { .. }))
+    .filter_map(|(handle, ty)| {
+      let name = ty.name.as_deref()?;
+      let (item, decoration) =
+        name.split_at(name.find(DECORATION_PRE).unwrap_or(name.len()));
+      let renamed = format!("{}{decoration}", case.apply(item));
+      (renamed != name && taken.insert(renamed.clone())).then_some((handle, renamed))
+    })
+    .collect::<Vec<_>>();
+
+  for (handle, name) in renames {
+    let mut ty = module.types[handle].clone();
+    ty.name = Some(name);
+    module.types.replace(handle, ty);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn struct_names(module: &naga::Module) -> Vec<String> {
+    module
+      .types
+      .iter()
+      .filter(|(_, ty)| matches!(ty.inner, TypeInner::Struct { .. }))
+      .filter_map(|(_, ty)| ty.name.clone())
+      .collect()
+  }
+
+  #[test]
+  fn renames_structs_unless_taken() {
+    let source = indoc! {r#"
+      struct light_data {
+          color: vec4<f32>,
+      };
+
+      struct shadow_data {
+          bias: f32,
+      };
+
+      struct ShadowData {
+          bias: f32,
+          scale: f32,
+      };
+
+      @group(0) @binding(0) var<uniform> light: light_data;
+      @group(0) @binding(1) var<uniform> shadow: shadow_data;
+      @group(0) @binding(2) var<uniform> other_shadow: ShadowData;
+    "#};
+    let mut module = naga::front::wgsl::parse_str(source).unwrap();
+
+    rename_structs(&mut module, WgslNameCase::PascalCase);
+    assert_eq!(vec!["LightData", "shadow_data", "ShadowData"], struct_names(&module));
+  }
+
+  #[test]
+  fn keeps_decoration_of_imported_structs() {
+    let source = indoc! {r#"
+      struct light_dataX_naga_oil_mod_XORSXG5C7MFXGSX {
+          color: vec4<f32>,
+      };
+
+      @group(0) @binding(0) var<uniform> light: light_dataX_naga_oil_mod_XORSXG5C7MFXGSX;
+    "#};
+    let mut module = naga::front::wgsl::parse_str(source).unwrap();
+
+    rename_structs(&mut module, WgslNameCase::PascalCase);
+    assert_eq!(vec!["LightDataX_naga_oil_mod_XORSXG5C7MFXGSX"], struct_names(&module));
+  }
+}
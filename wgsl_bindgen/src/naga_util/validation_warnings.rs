@@ -0,0 +1,214 @@
+//! Shader hygiene checks on a composed naga module, reported as warnings
+//! rather than errors since the module is still valid.
+
+use crate::WgslDiagnosticKind;
+
+/// Validates the module once for the checks needing the globals each entry point uses, which
+/// are passed the resulting info. Returns `None` when the module doesn't validate.
+pub(crate) fn module_info(module: &naga::Module) -> Option<naga::valid::ModuleInfo> {
+  naga::valid::Validator::new(
+    naga::valid::ValidationFlags::all(),
+    naga::valid::Capabilities::all(),
+  )
+  .validate(module)
+  .ok()
+}
+
+/// Lists the hygiene issues found in the module, such as unused bindings. Unreachable code isn't
+/// among them, since naga rejects the module outright. Returns an empty list when nothing was
+/// found. The unused bindings note whether they are left out of the generated bindings when
+/// `strip_unused_bindings` is set.
+pub(crate) fn validation_warnings(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+  strip_unused_bindings: bool,
+) -> Vec<(WgslDiagnosticKind, String)> {
+  let mut warnings = Vec::new();
+  let stripped = match strip_unused_bindings {
+    true => strippable_bindings(module, info),
+    false => Vec::new(),
+  };
+
+  for handle in unused_bindings(module, info) {
+    let var = &module.global_variables[handle];
+    let Some(binding) = &var.binding else {
+      continue;
+    };
+
+    let name = var.name.as_deref().unwrap_or("<unnamed>");
+    let mut message = format!(
+      "binding `{name}` at @group({}) @binding({}) is never used",
+      binding.group, binding.binding
+    );
+    if stripped.contains(&handle) {
+      message.push_str(", and is stripped from the generated bindings");
+    }
+    warnings.push((WgslDiagnosticKind::UnusedBinding, message));
+  }
+
+  warnings
+}
+
+/// Lists the bindings none of the entry points use, either directly or through the functions
+/// they call. None are listed when the module doesn't validate, since the uses are then unknown.
+pub(crate) fn unused_bindings(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+) -> Vec<naga::Handle<naga::GlobalVariable>> {
+  let Some(info) = info else {
+    return Vec::new();
+  };
+
+  // The uses of an entry point include those of the functions it calls.
+  module
+    .global_variables
+    .iter()
+    .filter(|(_, var)| var.binding.is_some())
+    .filter(|(handle, _)| {
+      (0..module.entry_points.len())
+        .all(|index| info.get_entry_point(index)[*handle].is_empty())
+    })
+    .map(|(handle, _)| handle)
+    .collect()
+}
+
+/// Lists the unused bindings which can be left out of the generated bindings. Those of a group
+/// with no used binding are kept when a later group is still in use, since wgpu expects bind
+/// groups to be consecutive.
+pub(crate) fn strippable_bindings(
+  module: &naga::Module,
+  info: Option<&naga::valid::ModuleInfo>,
+) -> Vec<naga::Handle<naga::GlobalVariable>> {
+  let unused = unused_bindings(module, info);
+  let used_groups = module
+    .global_variables
+    .iter()
+    .filter(|(handle, _)| !unused.contains(handle))
+    .filter_map(|(_, var)| var.binding.as_ref().map(|binding| binding.group))
+    .collect::<Vec<_>>();
+  let last_used_group = used_groups.iter().max();
+
+  unused
+    .into_iter()
+    .filter(|handle| {
+      let Some(binding) = &module.global_variables[*handle].binding else {
+        return false;
+      };
+      let is_after_used_groups = match last_used_group {
+        Some(last) => binding.group > *last,
+        None => true,
+      };
+      used_groups.contains(&binding.group) || is_after_used_groups
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn no_warnings_for_clean_module() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> color: vec4<f32>;
+
+            @fragment
+            fn main() -> @location(0) vec4<f32> {
+                return color;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert!(validation_warnings(&module, module_info(&module).as_ref(), false).is_empty());
+  }
+
+  #[test]
+  fn warnings_for_unused_binding() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> color: vec4<f32>;
+            @group(1) @binding(2) var<uniform> unused: vec4<f32>;
+
+            fn get_color() -> vec4<f32> {
+                return color;
+            }
+
+            @fragment
+            fn main() -> @location(0) vec4<f32> {
+                return get_color();
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert_eq!(
+      vec![(
+        WgslDiagnosticKind::UnusedBinding,
+        "binding `unused` at @group(1) @binding(2) is never used".to_string()
+      )],
+      validation_warnings(&module, module_info(&module).as_ref(), false)
+    );
+  }
+
+  #[test]
+  fn binding_used_by_uncalled_function_is_unused() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> color: vec4<f32>;
+            @group(0) @binding(1) var<uniform> tint: vec4<f32>;
+
+            fn get_tint() -> vec4<f32> {
+                return tint;
+            }
+
+            @fragment
+            fn main() -> @location(0) vec4<f32> {
+                return color;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert_eq!(
+      vec![(
+        WgslDiagnosticKind::UnusedBinding,
+        "binding `tint` at @group(0) @binding(1) is never used".to_string()
+      )],
+      validation_warnings(&module, module_info(&module).as_ref(), false)
+    );
+  }
+
+  #[test]
+  fn unused_bindings_note_when_stripped() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> color: vec4<f32>;
+            @group(0) @binding(1) var<uniform> unused_tint: vec4<f32>;
+            @group(1) @binding(0) var<uniform> unused_scale: f32;
+            @group(2) @binding(0) var<uniform> brightness: f32;
+            @group(3) @binding(0) var<uniform> unused_bias: f32;
+
+            @fragment
+            fn main() -> @location(0) vec4<f32> {
+                return color * brightness;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert_eq!(
+      vec![
+        (
+          WgslDiagnosticKind::UnusedBinding,
+          "binding `unused_tint` at @group(0) @binding(1) is never used, and is stripped from the generated bindings".to_string()
+        ),
+        (
+          WgslDiagnosticKind::UnusedBinding,
+          "binding `unused_scale` at @group(1) @binding(0) is never used".to_string()
+        ),
+        (
+          WgslDiagnosticKind::UnusedBinding,
+          "binding `unused_bias` at @group(3) @binding(0) is never used, and is stripped from the generated bindings".to_string()
+        ),
+      ],
+      validation_warnings(&module, module_info(&module).as_ref(), true)
+    );
+  }
+}
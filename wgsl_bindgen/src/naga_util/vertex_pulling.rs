@@ -0,0 +1,293 @@
+//! Vertex pulling of a composed naga module: the vertex entry points read their input structs
+//! from read-only storage buffers indexed by `@builtin(vertex_index)`, instead of from vertex
+//! buffers, eg: for GPU-driven renderers fetching the vertices of their meshes themselves.
+
+use case::CaseExt;
+use naga::proc::Layouter;
+use naga::{
+  AddressSpace, ArraySize, Binding, BuiltIn, Expression, GlobalVariable, Handle,
+  ResourceBinding, Scalar, ShaderStage, Span, Statement, StorageAccess, Type, TypeInner,
+};
+
+use crate::quote_gen::RustItemPath;
+use crate::{FxIndexMap, FxIndexSet};
+
+/// Moves the input structs of the vertex entry points to storage buffers in `group`, named
+/// after the structs, eg: `vertex_input_buffer` for `VertexInput`, at the first free bindings.
+///
+/// Each entry point keeps its body in a function named `{entry}_pulled`, called by the entry
+/// point with the structs loaded from the buffers. Returns the inputs which can't be pulled,
+/// since they aren't structs of `@location`s, if any.
+pub(crate) fn pull_vertices(
+  module: &mut naga::Module,
+  mod_name: &str,
+  group: u32,
+) -> Result<(), Vec<String>> {
+  let mut layouter = Layouter::default();
+  layouter.update(module.to_ctx()).unwrap();
+
+  let mut bindings = module
+    .global_variables
+    .iter()
+    .filter_map(|(_, var)| var.binding.as_ref())
+    .filter(|binding| binding.group == group)
+    .map(|binding| binding.binding)
+    .collect::<FxIndexSet<_>>();
+  let mut buffers = FxIndexMap::<Handle<Type>, Handle<GlobalVariable>>::default();
+  let mut errors = Vec::new();
+
+  for index in 0..module.entry_points.len() {
+    let entry_point = &module.entry_points[index];
+    if entry_point.stage != ShaderStage::Vertex {
+      continue;
+    }
+
+    let mut pulled = Vec::new();
+    for (i, argument) in entry_point.function.arguments.iter().enumerate() {
+      let name = argument.name.as_deref().unwrap_or_default();
+      match (&argument.binding, &module.types[argument.ty].inner) {
+        (Some(Binding::Location { .. }), _) => errors.push(format!(
+          "`{name}` of `{}` isn't in a struct, so it can't be pulled from a storage buffer",
+          entry_point.name
+        )),
+        (None, TypeInner::Struct { members, .. }) => {
+          let is_location =
+            |member: &&naga::StructMember| matches!(member.binding, Some(Binding::Location { .. }));
+          match members.iter().filter(is_location).count() {
+            0 => {}
+            count if count == members.len() => pulled.push(i),
+            _ => errors.push(format!(
+              "`{name}` of `{}` mixes builtins and `@location`s, so it can't be pulled from a storage buffer",
+              entry_point.name
+            )),
+          }
+        }
+        _ => {}
+      }
+    }
+
+    if pulled.is_empty() || !errors.is_empty() {
+      continue;
+    }
+
+    for &i in &pulled {
+      let ty = module.entry_points[index].function.arguments[i].ty;
+      if buffers.contains_key(&ty) {
+        continue;
+      }
+
+      let array = module.types.insert(
+        Type {
+          name: None,
+          inner: TypeInner::Array {
+            base: ty,
+            size: ArraySize::Dynamic,
+            stride: layouter[ty].to_stride(),
+          },
+        },
+        Span::UNDEFINED,
+      );
+      let struct_name = module.types[ty].name.as_deref().unwrap_or_default();
+      let struct_name = RustItemPath::from_mangled(struct_name, mod_name).name;
+      let binding = (0..).find(|binding| !bindings.contains(binding)).unwrap();
+      bindings.insert(binding);
+
+      let buffer = module.global_variables.append(
+        GlobalVariable {
+          name: Some(format!("{}_buffer", struct_name.to_snake())),
+          space: AddressSpace::Storage {
+            access: StorageAccess::LOAD,
+          },
+          binding: Some(ResourceBinding { group, binding }),
+          ty: array,
+          init: None,
+        },
+        Span::UNDEFINED,
+      );
+      buffers.insert(ty, buffer);
+    }
+
+    let u32_ty = module.types.insert(
+      Type {
+        name: None,
+        inner: TypeInner::Scalar(Scalar::U32),
+      },
+      Span::UNDEFINED,
+    );
+    let entry_point = &mut module.entry_points[index];
+    let mut body = std::mem::take(&mut entry_point.function);
+    let mut function = naga::Function {
+      name: body.name.clone(),
+      result: body.result.clone(),
+      ..Default::default()
+    };
+
+    // The arguments which aren't pulled are passed through, including the vertex index the
+    // buffers are indexed by, when the entry point already reads it.
+    let mut arguments = vec![None; body.arguments.len()];
+    let mut vertex_index = None;
+    for (i, argument) in body.arguments.iter().enumerate() {
+      if pulled.contains(&i) {
+        continue;
+      }
+
+      let expression = Expression::FunctionArgument(function.arguments.len() as u32);
+      let expression = function.expressions.append(expression, Span::UNDEFINED);
+      function.arguments.push(argument.clone());
+      arguments[i] = Some(expression);
+
+      match (&argument.binding, &module.types[argument.ty].inner) {
+        (Some(Binding::BuiltIn(BuiltIn::VertexIndex)), _) => {
+          vertex_index = Some((expression, None));
+        }
+        (None, TypeInner::Struct { members, .. }) => {
+          let member = members.iter().position(|member| {
+            member.binding == Some(Binding::BuiltIn(BuiltIn::VertexIndex))
+          });
+          if let Some(member) = member {
+            vertex_index = Some((expression, Some(member as u32)));
+          }
+        }
+        _ => {}
+      }
+    }
+
+    let vertex_index = vertex_index.unwrap_or_else(|| {
+      let expression = Expression::FunctionArgument(function.arguments.len() as u32);
+      let expression = function.expressions.append(expression, Span::UNDEFINED);
+      function.arguments.push(naga::FunctionArgument {
+        name: Some("vertex_index".to_string()),
+        ty: u32_ty,
+        binding: Some(Binding::BuiltIn(BuiltIn::VertexIndex)),
+      });
+      (expression, None)
+    });
+    let pointers = pulled
+      .iter()
+      .map(|&i| {
+        let buffer = buffers[&body.arguments[i].ty];
+        let pointer = Expression::GlobalVariable(buffer);
+        (i, function.expressions.append(pointer, Span::UNDEFINED))
+      })
+      .collect::<Vec<_>>();
+
+    let emitted = function.expressions.len();
+    let vertex_index = match vertex_index {
+      (expression, None) => expression,
+      (base, Some(index)) => function
+        .expressions
+        .append(Expression::AccessIndex { base, index }, Span::UNDEFINED),
+    };
+    for (i, pointer) in pointers {
+      let pointer = Expression::Access {
+        base: pointer,
+        index: vertex_index,
+      };
+      let pointer = function.expressions.append(pointer, Span::UNDEFINED);
+      let load = Expression::Load { pointer };
+      arguments[i] = Some(function.expressions.append(load, Span::UNDEFINED));
+    }
+    let emitted = function.expressions.range_from(emitted);
+
+    // The body is moved to a function taking the inputs without their bindings.
+    body.name = Some(format!("{}_pulled", entry_point.name));
+    body
+      .arguments
+      .iter_mut()
+      .for_each(|argument| argument.binding = None);
+    if let Some(result) = &mut body.result {
+      result.binding = None;
+    }
+    let has_result = body.result.is_some();
+    let body = module.functions.append(body, Span::UNDEFINED);
+
+    let result = has_result.then(|| {
+      function
+        .expressions
+        .append(Expression::CallResult(body), Span::UNDEFINED)
+    });
+    function
+      .body
+      .push(Statement::Emit(emitted), Span::UNDEFINED);
+    function.body.push(
+      Statement::Call {
+        function: body,
+        arguments: arguments.into_iter().map(Option::unwrap).collect(),
+        result,
+      },
+      Span::UNDEFINED,
+    );
+    function
+      .body
+      .push(Statement::Return { value: result }, Span::UNDEFINED);
+    entry_point.function = function;
+  }
+
+  match errors.is_empty() {
+    true => Ok(()),
+    false => Err(errors),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+
+  fn pulled(source: &str) -> Result<String, Vec<String>> {
+    let mut module = naga::front::wgsl::parse_str(source).unwrap();
+    pull_vertices(&mut module, "mesh", 1)?;
+    Ok(crate::naga_util::module_to_source(&module).unwrap())
+  }
+
+  #[test]
+  fn pulls_input_structs_from_storage_buffers() {
+    let source = indoc! {r#"
+      @group(1) @binding(0) var<uniform> scale: f32;
+
+      struct VertexInput {
+          @location(0) position: vec3<f32>,
+          @location(1) uv: vec2<f32>,
+      };
+
+      @vertex
+      fn vs_main(in: VertexInput, @builtin(instance_index) instance: u32) -> @builtin(position) vec4<f32> {
+          return vec4(in.position * scale, in.uv.x + f32(instance));
+      }
+    "#};
+
+    let actual = pulled(source).unwrap();
+
+    assert!(actual.contains(
+      "@group(1) @binding(1) \nvar<storage> vertex_input_buffer: array<VertexInput>;"
+    ));
+    assert!(actual
+      .contains("fn vs_main_pulled(in: VertexInput, instance_1: u32) -> vec4<f32> {"));
+    assert!(actual.contains(indoc! {"
+      fn vs_main(@builtin(instance_index) instance: u32, @builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+          let _e4 = vertex_input_buffer[vertex_index];
+          let _e5 = vs_main_pulled(_e4, instance);
+          return _e5;
+      }"
+    }));
+  }
+
+  #[test]
+  fn reports_loose_locations() {
+    let source = indoc! {r#"
+      @vertex
+      fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+          return position;
+      }
+    "#};
+
+    assert_eq!(
+      Err(vec![
+        "`position` of `vs_main` isn't in a struct, so it can't be pulled from a storage buffer"
+          .to_string()
+      ]),
+      pulled(source)
+    );
+  }
+}
@@ -4,6 +4,9 @@ use proc_macro2::Ident;
 pub(crate) const MOD_REFERENCE_ROOT: &str = "_root";
 pub(crate) const MOD_STRUCT_ASSERTIONS: &str = "layout_asserts";
 pub(crate) const MOD_BYTEMUCK_IMPLS: &str = "bytemuck_impls";
+pub(crate) const MOD_BIND_GROUP_LAYOUTS: &str = "bind_group_layouts";
+pub(crate) const MOD_SUPPORT: &str = "_support";
+pub(crate) const MOD_BEVY_HELPERS: &str = "bevy_helpers";
 
 pub(crate) fn mod_reference_root() -> Ident {
   unsafe { syn::parse_str(MOD_REFERENCE_ROOT).unwrap_unchecked() }
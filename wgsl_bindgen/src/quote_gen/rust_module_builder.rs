@@ -120,10 +120,19 @@ impl RustModule {
   }
 
   fn generate(&self) -> TokenStream {
-    let name = Ident::new(&self.name, proc_macro2::Span::call_site());
+    let declaration = self.generate_declaration();
+    let contents = self.generate_contents();
 
-    let initial_contents = &self.initial_contents;
-    let content = &self.content;
+    quote! {
+      #declaration {
+          #contents
+      }
+    }
+  }
+
+  /// The attributes, visibility and name of the module, without its body.
+  fn generate_declaration(&self) -> TokenStream {
+    let name = Ident::new(&self.name, proc_macro2::Span::call_site());
 
     let visibility = if self.is_public {
       quote!(pub)
@@ -131,21 +140,28 @@ impl RustModule {
       quote!()
     };
 
+    let mod_attr = &self.module_attributes;
+
+    quote! {
+      #mod_attr
+      #visibility mod #name
+    }
+  }
+
+  fn generate_contents(&self) -> TokenStream {
+    let initial_contents = &self.initial_contents;
+    let content = &self.content;
+
     let submodules = self
       .submodules
       .values()
       .map(|m| m.generate())
       .collect::<Vec<_>>();
 
-    let mod_attr = &self.module_attributes;
-
     quote! {
-      #mod_attr
-      #visibility mod #name {
-          #initial_contents
-          #( #content )*
-          #( #submodules )*
-      }
+      #initial_contents
+      #( #content )*
+      #( #submodules )*
     }
   }
 }
@@ -252,6 +268,14 @@ impl RustModBuilder {
     self.get_or_create_module(path).add_content(content);
   }
 
+  /// Adds attributes to the declaration of the module at `path`, eg: `#[cfg(feature = "...")]`.
+  pub fn add_module_attributes(&mut self, path: &str, attributes: TokenStream) {
+    self
+      .get_or_create_module(path)
+      .module_attributes
+      .extend(attributes);
+  }
+
   fn add_unique(
     &mut self,
     path: &str,
@@ -279,6 +303,26 @@ impl RustModBuilder {
       #( #modules )*
     }
   }
+
+  /// Generates the top level modules as separate files, returning the module declarations
+  /// along with the name and contents of each file.
+  /// The relative root module only re-exports its parent, so it is kept inline.
+  pub fn generate_files(&self) -> (TokenStream, Vec<(String, TokenStream)>) {
+    let mut declarations = quote!();
+    let mut files = Vec::new();
+
+    for module in self.modules.values() {
+      if module.name == MOD_REFERENCE_ROOT {
+        declarations.extend(module.generate());
+      } else {
+        let declaration = module.generate_declaration();
+        declarations.extend(quote!(#declaration;));
+        files.push((module.name.clone(), module.generate_contents()));
+      }
+    }
+
+    (declarations, files)
+  }
 }
 
 #[cfg(test)]
@@ -379,6 +423,42 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_generate_files() {
+    let mut mod_builder = RustModBuilder::new(true, true);
+    mod_builder.add("a::b", quote! {struct A;});
+    mod_builder.add("c", quote! {struct C;});
+
+    let (declarations, files) = mod_builder.generate_files();
+
+    assert_tokens_eq!(
+      declarations,
+      quote! {
+        mod _root {
+          pub use super::*;
+        }
+        pub mod a;
+        pub mod c;
+      }
+    );
+
+    let names = files
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(vec!["a", "c"], names);
+    assert_tokens_eq!(
+      files[0].1,
+      quote! {
+        use super::{_root, _root::*};
+        pub mod b {
+          use super::{_root, _root::*};
+          struct A;
+        }
+      }
+    );
+  }
+
   #[test]
   fn test_module_add_duplicates() -> Result<(), RustModuleBuilderError> {
     let mut mod_builder = RustModBuilder::new(false, false);
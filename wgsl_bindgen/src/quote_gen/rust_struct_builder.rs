@@ -11,7 +11,7 @@ use super::{rust_type, RustItem, RustItemPath, RustTypeInfo};
 use crate::bevy_util::demangle_str;
 use crate::quote_gen::{RustItemType, MOD_BYTEMUCK_IMPLS, MOD_STRUCT_ASSERTIONS};
 use crate::{
-  sanitized_upper_snake_case, WgslBindgenOption, WgslTypeSerializeStrategy,
+  sanitized_upper_snake_case, wgsl, WgslBindgenOption, WgslTypeSerializeStrategy,
   WgslTypeVisibility,
 };
 
@@ -29,6 +29,21 @@ impl WgslTypeVisibility {
 pub struct Padding {
   pub pad_name: Ident,
   pub pad_size_tokens: TokenStream,
+  /// Where the field was inserted to match the WGSL memory layout, or `None` when it's declared
+  /// in the shader.
+  pub inserted: Option<InsertedPadding>,
+}
+
+/// Padding inserted after a field of a struct, to fill the space up to the next member.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsertedPadding {
+  pub name: String,
+  /// The field the padding follows.
+  pub field: String,
+  /// The offset of the padding in the struct.
+  pub offset: usize,
+  /// The size of the padding, unless the size of the Rust type of the field isn't known.
+  pub size: Option<usize>,
 }
 
 impl Padding {
@@ -68,6 +83,12 @@ impl<'a> NagaToRustStructState<'a> {
         let field_matches = o.field_regex.is_match(member_name);
         (struct_matches && field_matches).then_some(o.override_type.clone())
       })
+      .or_else(|| {
+        // Vertex attributes read with another format take the type of their data.
+        options
+          .vertex_format_override(fully_qualified_name, member_name)
+          .map(wgsl::vertex_format_rust_type)
+      })
       .unwrap_or(rust_type.tokens)
   }
 
@@ -84,6 +105,15 @@ impl<'a> NagaToRustStructState<'a> {
     let fold = move |mut state: NagaToRustStructState<'a>,
                      naga_member: &'a StructMember|
           -> NagaToRustStructState<'a> {
+      // Builtins of vertex inputs aren't read from the vertex buffers, so they don't take
+      // any space in the vertex data.
+      if !is_directly_sharable
+        && matches!(naga_member.binding, Some(naga::Binding::BuiltIn(_)))
+      {
+        state.index += 1;
+        return state;
+      }
+
       let member_name = naga_member.name.as_ref().unwrap();
       let name_ident = Ident::new(member_name, Span::call_site());
       let naga_type = &naga_module.types[naga_member.ty];
@@ -113,6 +143,14 @@ impl<'a> NagaToRustStructState<'a> {
         match rust_type.aligned_size() {
           Some(rust_type_size) if required_member_size == rust_type_size => None,
           _ => {
+            let rust_size = rust_type.size.filter(|size| *size <= required_member_size);
+            let inserted = InsertedPadding {
+              name: pad_name.clone(),
+              field: member_name.clone(),
+              offset: current_offset + rust_size.unwrap_or(0),
+              size: rust_size.map(|size| required_member_size - size),
+            };
+
             let required_member_size = format!("0x{:X}", required_member_size);
             let member_size =
               syn::parse_str::<TokenStream>(&required_member_size).unwrap();
@@ -124,6 +162,7 @@ impl<'a> NagaToRustStructState<'a> {
             let padding = Padding {
               pad_name,
               pad_size_tokens,
+              inserted: Some(inserted),
             };
 
             Some(padding)
@@ -148,6 +187,7 @@ impl<'a> NagaToRustStructState<'a> {
         RustStructMemberEntry::Padding(Padding {
           pad_name: name_ident,
           pad_size_tokens,
+          inserted: None,
         })
       } else {
         let rust_type =
@@ -200,6 +240,12 @@ impl<'a> Field<'a> {
     let ty = &self.rust_type;
     quote!(#name: #ty)
   }
+
+  fn generate_fn_from_parts_param(&self) -> TokenStream {
+    let name = &self.name_ident;
+    let ty = &self.rust_type;
+    quote!(#name: impl Into<#ty>)
+  }
 }
 
 #[derive(IsVariant)]
@@ -244,9 +290,24 @@ pub struct RustStructBuilder<'a> {
   naga_module: &'a naga::Module,
   layout: naga::proc::TypeLayout,
   options: &'a WgslBindgenOption,
+  is_vertex_input: bool,
+  indirect_args: Option<wgsl::IndirectArgs>,
 }
 
 impl<'a> RustStructBuilder<'a> {
+  /// Marks the struct as the input of a vertex entry, which gets a `from_parts` constructor.
+  pub fn vertex_input(mut self, is_vertex_input: bool) -> Self {
+    self.is_vertex_input = is_vertex_input;
+    self
+  }
+
+  /// Marks the struct as laid out as the indirect command written by the shader, which gets
+  /// conversions from and to the `wgpu::util` struct of its arguments.
+  pub fn indirect_args(mut self, indirect_args: Option<wgsl::IndirectArgs>) -> Self {
+    self.indirect_args = indirect_args;
+    self
+  }
+
   fn name_ident(&self) -> Ident {
     Ident::new(&self.item_path.name.as_ref(), Span::call_site())
   }
@@ -265,6 +326,22 @@ impl<'a> RustStructBuilder<'a> {
     self.members.iter().any(|m| m.is_padding())
   }
 
+  /// The padding fields inserted to match the WGSL memory layout.
+  pub(crate) fn inserted_padding(&self) -> Vec<InsertedPadding> {
+    self
+      .members
+      .iter()
+      .filter_map(|member| match member {
+        RustStructMemberEntry::Padding(padding) => padding.inserted.clone(),
+        _ => None,
+      })
+      .collect()
+  }
+
+  pub(crate) fn item_path(&self) -> &RustItemPath {
+    self.item_path
+  }
+
   fn ty_param_use(&self) -> TokenStream {
     if self.uses_generics_for_rts() {
       quote!(<N>)
@@ -420,6 +497,124 @@ impl<'a> RustStructBuilder<'a> {
     }
   }
 
+  /// A constructor converting its arguments into the fields, so vertex data can be built from
+  /// the types of a mesh loader, eg: `[f32; 3]` positions, without naming the field types.
+  fn build_fn_from_parts(&self) -> TokenStream {
+    if !self.is_vertex_input {
+      return quote!();
+    }
+
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+    let impl_fragment = self.impl_trait_for_fragment();
+
+    let mut params = Vec::new();
+    let mut member_assignments = Vec::new();
+    for entry in &self.members {
+      match entry {
+        RustStructMemberEntry::Field(field) => {
+          let name = &field.name_ident;
+          params.push(field.generate_fn_from_parts_param());
+          member_assignments.push(quote!(#name: #name.into()));
+        }
+        RustStructMemberEntry::Padding(padding) => {
+          member_assignments.push(padding.generate_member_instantiate())
+        }
+      }
+    }
+
+    quote! {
+      #impl_fragment #struct_name_in_usage {
+        pub fn from_parts(#(#params),*) -> Self {
+          Self {
+            #(#member_assignments),*
+          }
+        }
+      }
+    }
+  }
+
+  /// The bytes of the struct as written to a buffer, with the serialization strategy in use.
+  fn build_fn_as_bytes(&self) -> TokenStream {
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+    let impl_fragment = self.impl_trait_for_fragment();
+
+    let as_bytes_fn = match self.options.serialization_strategy {
+      WgslTypeSerializeStrategy::Bytemuck => quote! {
+        pub fn as_bytes(&self) -> &[u8] {
+          bytemuck::bytes_of(self)
+        }
+      },
+      WgslTypeSerializeStrategy::Encase => {
+        let alloc = self.options.alloc_crate();
+        quote! {
+          pub fn as_wgsl_bytes(&self) -> #alloc::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(#alloc::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
+      }
+    };
+
+    quote! {
+      #impl_fragment #struct_name_in_usage {
+        #as_bytes_fn
+      }
+    }
+  }
+
+  fn build_indirect_args_conversions(&self) -> TokenStream {
+    let Some(indirect_args) = self.indirect_args else {
+      return quote!();
+    };
+    let fields = self
+      .members
+      .iter()
+      .filter_map(|m| match m {
+        RustStructMemberEntry::Field(field) => Some(field),
+        RustStructMemberEntry::Padding(_) => None,
+      })
+      .collect::<Vec<_>>();
+    // Fields of overridden types can't be converted from the arguments.
+    let is_scalar = |field: &&Field| {
+      let ty = &field.rust_type;
+      matches!(quote!(#ty).to_string().as_str(), "u32" | "i32")
+    };
+    if self.uses_padding() || !fields.iter().all(is_scalar) {
+      return quote!();
+    }
+
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+    let args = indirect_args.quote_type();
+    let args_fields = indirect_args
+      .fields()
+      .iter()
+      .map(|name| format_ident!("{name}"))
+      .collect::<Vec<_>>();
+    let struct_fields = fields
+      .iter()
+      .map(|field| &field.name_ident)
+      .collect::<Vec<_>>();
+
+    quote! {
+      impl From<#struct_name_in_usage> for #args {
+        fn from(value: #struct_name_in_usage) -> Self {
+          Self {
+            #(#args_fields: value.#struct_fields),*
+          }
+        }
+      }
+
+      impl From<#args> for #struct_name_in_usage {
+        fn from(args: #args) -> Self {
+          Self {
+            #(#struct_fields: args.#args_fields),*
+          }
+        }
+      }
+    }
+  }
+
   fn build_fields(&self) -> Vec<TokenStream> {
     let gctx = self.naga_module.to_ctx();
     let members = self
@@ -494,6 +689,89 @@ impl<'a> RustStructBuilder<'a> {
     derives
   }
 
+  /// Checks the offset and size of each field against the WGSL layout at runtime, since the
+  /// size of a field with an overridden type isn't covered by the layout assertion. A field may
+  /// be larger than its WGSL type, eg: `glam::Vec3A` for `vec3<f32>`, as long as it ends before
+  /// the next one.
+  fn build_fn_verify_layout(&self) -> TokenStream {
+    if !self.is_directly_shareable() {
+      return quote!();
+    }
+
+    let core = self.options.core_crate();
+    let alloc = self.options.alloc_crate();
+    let fields = self
+      .members
+      .iter()
+      .filter_map(|m| match m {
+        RustStructMemberEntry::Field(field) => Some(field),
+        RustStructMemberEntry::Padding(_) => None,
+      })
+      .collect::<Vec<_>>();
+    let gctx = self.naga_module.to_ctx();
+
+    let checks = fields.iter().enumerate().map(|(i, field)| {
+      let name = &field.name_ident;
+      let name_str = name.to_string();
+      let wgsl_offset = field.naga_member.offset;
+      let next_offset = fields
+        .get(i + 1)
+        .map_or(self.layout.size, |next| next.naga_member.offset);
+      let wgsl_span = Index::from((next_offset - wgsl_offset) as usize);
+      let wgsl_offset = Index::from(wgsl_offset as usize);
+
+      // Runtime sized arrays are compared by their elements, since their length is generic.
+      let (rust_type, wgsl_size) = match field.naga_type.inner {
+        naga::TypeInner::Array { base, stride, .. } if field.is_rsa => {
+          let base = &self.naga_module.types[base];
+          let base = rust_type(None, self.naga_module, base, self.options);
+          (quote!(#base), stride)
+        }
+        ref inner => {
+          let rust_type = &field.rust_type;
+          (quote!(#rust_type), inner.size(gctx))
+        }
+      };
+      let wgsl_size = Index::from(wgsl_size as usize);
+
+      quote! {
+        (
+          #name_str,
+          #core::mem::offset_of!(Self, #name),
+          #core::mem::size_of::<#rust_type>(),
+          #wgsl_offset,
+          #wgsl_size,
+          #wgsl_span,
+        )
+      }
+    });
+
+    let impl_fragment = self.impl_trait_for_fragment();
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+    let fully_qualified_name = self.item_path.get_fully_qualified_name();
+    let message =
+      format!("`{fully_qualified_name}` doesn't match the WGSL layout:{{mismatches}}");
+
+    quote! {
+      #impl_fragment #struct_name_in_usage {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+          let fields = [#(#checks),*];
+          let mut mismatches = #alloc::string::String::new();
+          for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+            if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+              mismatches += &#alloc::format!(
+                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+              );
+            }
+          }
+          assert!(mismatches.is_empty(), #message);
+        }
+      }
+    }
+  }
+
   fn build_layout_assertion(
     &self,
     custom_alignment: Option<naga::proc::Alignment>,
@@ -508,6 +786,7 @@ impl<'a> RustStructBuilder<'a> {
       quote!(#fully_qualified_name)
     };
 
+    let core = self.options.core_crate();
     let assert_member_offsets: Vec<_> = self
       .members
       .iter()
@@ -518,7 +797,7 @@ impl<'a> RustStructBuilder<'a> {
       .map(|m| {
         let m = m.naga_member;
         let name = Ident::new(m.name.as_ref().unwrap(), Span::call_site());
-        let rust_offset = quote!(std::mem::offset_of!(#struct_name, #name));
+        let rust_offset = quote!(#core::mem::offset_of!(#struct_name, #name));
         let wgsl_offset = Index::from(m.offset as usize);
         quote!(assert!(#rust_offset == #wgsl_offset);)
       })
@@ -541,7 +820,7 @@ impl<'a> RustStructBuilder<'a> {
       quote! {
         const #assertion_name: () = {
           #(#assert_member_offsets)*
-          assert!(std::mem::size_of::<#struct_name>() == #struct_size);
+          assert!(#core::mem::size_of::<#struct_name>() == #struct_size);
         };
       }
     } else {
@@ -574,7 +853,6 @@ impl<'a> RustStructBuilder<'a> {
     // This allows vertex input field types without padding like vec3 for positions.
     let is_host_shareable = self.is_host_sharable;
 
-    let has_rts_array = self.has_rts_array;
     let should_generate_padding = is_host_shareable
       && self.options.serialization_strategy == WgslTypeSerializeStrategy::Bytemuck;
 
@@ -597,18 +875,18 @@ impl<'a> RustStructBuilder<'a> {
 
     let alignment = custom_alignment.unwrap_or(self.layout.alignment) * 1u32;
     let alignment = Index::from(alignment as usize);
-    let repr_c = if !has_rts_array {
-      if should_generate_padding {
-        quote!(#[repr(C, align(#alignment))])
-      } else {
-        quote!(#[repr(C)])
-      }
+    let repr_c = if should_generate_padding {
+      quote!(#[repr(C, align(#alignment))])
     } else {
-      quote!()
+      quote!(#[repr(C)])
     };
 
     let fields = self.build_fields();
     let struct_new_fn = self.build_fn_new();
+    let struct_from_parts_fn = self.build_fn_from_parts();
+    let struct_as_bytes_fn = self.build_fn_as_bytes();
+    let indirect_args_conversions = self.build_indirect_args_conversions();
+    let struct_verify_layout_fn = self.build_fn_verify_layout();
     let init_struct = self.build_init_struct();
     let assert_layout = self.build_layout_assertion(custom_alignment);
     let unsafe_bytemuck_pod_impl = self.build_bytemuck_impls();
@@ -627,6 +905,10 @@ impl<'a> RustStructBuilder<'a> {
           }
 
           #struct_new_fn
+          #struct_from_parts_fn
+          #struct_as_bytes_fn
+          #indirect_args_conversions
+          #struct_verify_layout_fn
           #init_struct
         },
       ),
@@ -670,6 +952,8 @@ impl<'a> RustStructBuilder<'a> {
       options: &options,
       has_rts_array,
       layout,
+      is_vertex_input: false,
+      indirect_args: None,
     }
   }
 }
@@ -34,12 +34,13 @@ impl RustTypeInfo {
     self.size.is_none()
   }
 
-  pub fn quote_min_binding_size(&self) -> TokenStream {
+  pub fn quote_min_binding_size(&self, options: &WgslBindgenOption) -> TokenStream {
     if self.is_dynamic_array() {
       quote!(None)
     } else {
       let ty = quote!(#self);
-      quote!(std::num::NonZeroU64::new(std::mem::size_of::<#ty>() as _))
+      let core = options.core_crate();
+      quote!(#core::num::NonZeroU64::new(#core::mem::size_of::<#ty>() as _))
     }
   }
 }
@@ -65,10 +66,11 @@ pub(crate) fn custom_vector_matrix_assertions(
 
     let alignment = Index::from(ty.alignment_value());
     let aligned_size = Index::from(ty.aligned_size()?);
+    let core = options.core_crate();
 
     Some(quote! {
-      assert!(std::mem::size_of::<#ty>() == #aligned_size);
-      assert!(std::mem::align_of::<#ty>() == #alignment);
+      assert!(#core::mem::size_of::<#ty>() == #aligned_size);
+      assert!(#core::mem::align_of::<#ty>() == #alignment);
     })
   }
 
@@ -230,6 +232,35 @@ fn map_naga_mat_type(
   ty.get_mapped_type(&options.type_map)
 }
 
+/// Whether a vector or matrix type is missing from the type map, and is generated as an array instead.
+/// This is never the case with an empty type map, since arrays are then the intended representation.
+pub(crate) fn is_fallback_type_mapping(
+  module: &naga::Module,
+  ty: &naga::Type,
+  options: &WgslBindgenOption,
+) -> bool {
+  if options.type_map.is_empty() {
+    return false;
+  }
+
+  let t_handle = module.types.get(ty).unwrap();
+  let mut layouter = naga::proc::Layouter::default();
+  layouter.update(module.to_ctx()).unwrap();
+  let alignment = layouter[t_handle].alignment;
+
+  match ty.inner {
+    naga::TypeInner::Vector { size, scalar } => {
+      map_naga_vec_type(size, scalar, alignment, options).is_none()
+    }
+    naga::TypeInner::Matrix {
+      columns,
+      rows,
+      scalar,
+    } => map_naga_mat_type(columns, rows, scalar, alignment, options).is_none(),
+    _ => false,
+  }
+}
+
 /// Generates a Rust type information for a Naga type.
 ///
 /// Specify the invoke entry module to generate fully qualified type name.///
@@ -2,14 +2,99 @@ use std::collections::HashSet;
 
 use naga::{Handle, Type};
 
-use crate::quote_gen::{RustItem, RustItemPath, RustStructBuilder};
-use crate::{WgslBindgenOption, WgslTypeSerializeStrategy};
+use crate::quote_gen::{InsertedPadding, RustItem, RustItemPath, RustStructBuilder};
+use crate::{wgsl, WgslBindgenOption, WgslItemKind, WgslTypeSerializeStrategy};
 
 pub fn structs_items(
   invoking_entry_module: &str,
   module: &naga::Module,
   options: &WgslBindgenOption,
 ) -> Vec<RustItem> {
+  let mut items =
+    map_structs(invoking_entry_module, module, options, |builder| builder.build());
+  items.extend(synthesized_vertex_input_items(invoking_entry_module, module, options));
+  items
+}
+
+/// The structs of the loose `@location` arguments of vertex entries, which aren't declared
+/// in the shader. Like other vertex inputs, they aren't host shareable, so have no padding.
+fn synthesized_vertex_input_items(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Vec<RustItem> {
+  wgsl::get_loose_vertex_input(invoking_entry_module, module)
+    .into_iter()
+    .filter(|input| {
+      let fully_qualified_name = input.item_path.get_fully_qualified_name();
+      options
+        .vertex_struct_override(&fully_qualified_name)
+        .is_none()
+        && !options.is_excluded(WgslItemKind::Struct, &fully_qualified_name)
+        && !options.is_excluded(WgslItemKind::EntryPointIo, &fully_qualified_name)
+    })
+    .flat_map(|input| {
+      let members: Vec<_> = input.fields.into_iter().map(|(_, member)| member).collect();
+      let layout = naga::proc::TypeLayout {
+        size: 0,
+        alignment: naga::proc::Alignment::ONE,
+      };
+      RustStructBuilder::from_naga(
+        &input.item_path,
+        &members,
+        module,
+        options,
+        layout,
+        false,
+        false,
+        false,
+      )
+      .vertex_input(true)
+      .build()
+    })
+    .collect()
+}
+
+/// Lists the padding fields inserted in the generated structs to match the WGSL memory layout,
+/// by the fully qualified name of the struct. Structs without inserted padding are left out.
+pub(crate) fn inserted_padding(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Vec<(String, Vec<InsertedPadding>)> {
+  map_structs(invoking_entry_module, module, options, |builder| {
+    let padding = builder.inserted_padding();
+    if padding.is_empty() {
+      return Vec::new();
+    }
+
+    let name = builder.item_path().get_fully_qualified_name().to_string();
+    vec![(name, padding)]
+  })
+}
+
+/// Lists the types reachable from the global variables and entry point arguments,
+/// which are the ones generated as Rust types.
+pub(crate) fn generated_types(module: &naga::Module) -> HashSet<Handle<Type>> {
+  let mut types = HashSet::new();
+  for (_, global) in module.global_variables.iter() {
+    add_types_recursive(&mut types, module, global.ty);
+  }
+  for entry in &module.entry_points {
+    for argument in &entry.function.arguments {
+      add_types_recursive(&mut types, module, argument.ty);
+    }
+  }
+  types
+}
+
+/// Creates a builder for each struct to generate, collecting the results of `f`.
+fn map_structs<T>(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+  f: impl Fn(&RustStructBuilder) -> Vec<T>,
+) -> Vec<T> {
   // Initialize the layout calculator provided by naga.
   let mut layouter = naga::proc::Layouter::default();
   layouter.update(module.to_ctx()).unwrap();
@@ -19,6 +104,8 @@ pub fn structs_items(
     add_types_recursive(&mut global_variable_types, module, g.1.ty);
   }
 
+  let indirect_args = wgsl::written_indirect_args(invoking_entry_module, module, options);
+
   // Create matching Rust structs for WGSL structs.
   // This is a UniqueArena, so each struct will only be generated once.
   module
@@ -44,13 +131,22 @@ pub fn structs_items(
         let rust_item_path =
           RustItemPath::from_mangled(ty.name.as_ref().unwrap(), invoking_entry_module);
 
-        // skip if using custom struct mapping
+        let fully_qualified_name = rust_item_path.get_fully_qualified_name();
+        let is_entry_point_io = !global_variable_types.contains(&t_handle);
+
+        // skip if using custom struct mapping or excluded
         if options.type_map.contains_key(&crate::WgslType::Struct {
-          fully_qualified_name: rust_item_path.get_fully_qualified_name().into(),
-        }) {
+          fully_qualified_name: fully_qualified_name.to_string(),
+        }) || options
+          .vertex_struct_override(&fully_qualified_name)
+          .is_some()
+          || options.is_excluded(WgslItemKind::Struct, &fully_qualified_name)
+          || is_entry_point_io
+            && options.is_excluded(WgslItemKind::EntryPointIo, &fully_qualified_name)
+        {
           Vec::new()
         } else {
-          rust_struct(
+          f(&rust_struct(
             &rust_item_path,
             members,
             &layouter,
@@ -59,6 +155,7 @@ pub fn structs_items(
             options,
             &global_variable_types,
           )
+          .indirect_args(indirect_args.get(&t_handle).copied()))
         }
       } else {
         Vec::new()
@@ -67,15 +164,15 @@ pub fn structs_items(
     .collect()
 }
 
-fn rust_struct(
-  rust_item_path: &RustItemPath,
-  naga_members: &[naga::StructMember],
+fn rust_struct<'a>(
+  rust_item_path: &'a RustItemPath,
+  naga_members: &'a [naga::StructMember],
   layouter: &naga::proc::Layouter,
   t_handle: naga::Handle<naga::Type>,
-  naga_module: &naga::Module,
-  options: &WgslBindgenOption,
+  naga_module: &'a naga::Module,
+  options: &'a WgslBindgenOption,
   global_variable_types: &HashSet<Handle<Type>>,
-) -> Vec<RustItem> {
+) -> RustStructBuilder<'a> {
   let layout = layouter[t_handle];
 
   // Assume types used in global variables are host shareable and require validation.
@@ -91,7 +188,12 @@ fn rust_struct(
     == WgslTypeSerializeStrategy::Bytemuck
     && is_host_sharable;
 
-  let builder = RustStructBuilder::from_naga(
+  let is_vertex_input = naga_module.entry_points.iter().any(|entry| {
+    entry.stage == naga::ShaderStage::Vertex
+      && entry.function.arguments.iter().any(|a| a.ty == t_handle)
+  });
+
+  RustStructBuilder::from_naga(
     rust_item_path,
     naga_members,
     naga_module,
@@ -100,8 +202,8 @@ fn rust_struct(
     is_directly_sharable,
     is_host_sharable,
     has_rts_array,
-  );
-  builder.build()
+  )
+  .vertex_input(is_vertex_input)
 }
 
 fn add_types_recursive(
@@ -256,6 +358,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl Scalars {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsU32 {
@@ -268,6 +377,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsU32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsI32 {
@@ -280,6 +396,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsI32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsF32 {
@@ -292,6 +415,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsF32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsF64 {
@@ -304,6 +434,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsF64 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct MatricesF32 {
@@ -332,6 +469,13 @@ mod tests {
                 Self { a, b, c, d, e, f, g, h, i }
             }
           }
+          impl MatricesF32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct MatricesF64 {
@@ -360,6 +504,13 @@ mod tests {
                 Self { a, b, c, d, e, f, g, h, i }
             }
           }
+          impl MatricesF64 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct StaticArrays {
@@ -372,6 +523,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl StaticArrays {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct Nested {
@@ -383,6 +541,13 @@ mod tests {
                 Self { a, b }
             }
           }
+          impl Nested {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
@@ -474,6 +639,13 @@ mod tests {
                 Self { a, b, c }
             }
         }
+        impl Scalars {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
         pub struct VectorsU32 {
@@ -486,6 +658,13 @@ mod tests {
                 Self { a, b, c }
             }
         }
+        impl VectorsU32 {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
         pub struct VectorsI32 {
@@ -498,6 +677,13 @@ mod tests {
                 Self { a, b, c }
             }
         }
+        impl VectorsI32 {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
         pub struct VectorsF32 {
@@ -510,6 +696,13 @@ mod tests {
                 Self { a, b, c }
             }
         }
+        impl VectorsF32 {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
         pub struct MatricesF32 {
@@ -538,6 +731,13 @@ mod tests {
                 Self { a, b, c, d, e, f, g, h, i }
             }
         }
+        impl MatricesF32 {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
         pub struct StaticArrays {
@@ -550,6 +750,13 @@ mod tests {
                 Self { a, b, c }
             }
         }
+        impl StaticArrays {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
         pub struct Nested {
@@ -561,6 +768,13 @@ mod tests {
                 Self { a, b }
             }
         }
+        impl Nested {
+          pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+            let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+            bytes.write(self).unwrap();
+            bytes.into_inner()
+          }
+        }
       },
       actual
     );
@@ -652,6 +866,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl Scalars {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsU32 {
@@ -668,6 +889,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsU32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsI32 {
@@ -684,6 +912,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsI32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct VectorsF32 {
@@ -700,6 +935,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl VectorsF32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct MatricesF32 {
@@ -728,6 +970,13 @@ mod tests {
                 Self { a, b, c, d, e, f, g, h, i }
             }
           }
+          impl MatricesF32 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct StaticArrays {
@@ -744,6 +993,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl StaticArrays {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct Nested {
@@ -755,6 +1011,13 @@ mod tests {
                 Self { a, b }
             }
           }
+          impl Nested {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
@@ -808,6 +1071,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl Input0 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
           pub struct Nested {
@@ -819,6 +1089,13 @@ mod tests {
                 Self { a, b }
             }
           }
+          impl Nested {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
@@ -881,6 +1158,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl Input0 {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
           #[repr(C)]
           #[derive(
               Debug,
@@ -900,6 +1184,13 @@ mod tests {
                 Self { a, b }
             }
           }
+          impl Nested {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
@@ -956,6 +1247,11 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl Input0 {
+            pub fn as_bytes(&self) -> &[u8] {
+              bytemuck::bytes_of(self)
+            }
+          }
           unsafe impl bytemuck::Zeroable for Input0 {}
           unsafe impl bytemuck::Pod for Input0 {}
       },
@@ -1007,6 +1303,20 @@ mod tests {
                   Self { a, b, c }
               }
           }
+          impl Input0 {
+              pub fn from_parts(a: impl Into<u32>, b: impl Into<i32>, c: impl Into<f32>) -> Self {
+                  Self {
+                      a: a.into(),
+                      b: b.into(),
+                      c: c.into(),
+                  }
+              }
+          }
+          impl Input0 {
+            pub fn as_bytes(&self) -> &[u8] {
+              bytemuck::bytes_of(self)
+            }
+          }
           unsafe impl bytemuck::Zeroable for Input0 {}
           unsafe impl bytemuck::Pod for Input0 {}
       },
@@ -1086,6 +1396,47 @@ mod tests {
                 }
             }
         }
+        impl Input0 {
+            pub fn from_parts(a: impl Into<u32>, b: impl Into<i32>, c: impl Into<f32>) -> Self {
+                Self {
+                    a: a.into(),
+                    _pad_a: [0; 0x8 - core::mem::size_of::<u32>()],
+                    b: b.into(),
+                    _pad_b: [0; 0x18 - core::mem::size_of::<i32>()],
+                    c: c.into(),
+                    d: [0; 0x4],
+                    _pad_d: [0; 0x1C - core::mem::size_of::<u32>()],
+                }
+            }
+        }
+        impl Input0 {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl Input0 {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    ("a", std::mem::offset_of!(Self, a), std::mem::size_of::<u32>(), 0, 4, 8),
+                    ("b", std::mem::offset_of!(Self, b), std::mem::size_of::<i32>(), 8, 4, 24),
+                    ("c", std::mem::offset_of!(Self, c), std::mem::size_of::<f32>(), 32, 4, 32),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(), "`Input0` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
 
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
@@ -1132,6 +1483,32 @@ mod tests {
                 Self { a }
             }
         }
+        impl Inner {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl Inner {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    ("a", std::mem::offset_of!(Self, a), std::mem::size_of::<f32>(), 0, 4, 4),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(), "`Inner` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const INNER_ASSERTS: () = {
           assert!(std::mem::offset_of!(Inner, a) == 0);
           assert!(std::mem::size_of:: < Inner > () == 4);
@@ -1149,6 +1526,39 @@ mod tests {
                 Self { inner }
             }
         }
+        impl Outer {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl Outer {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "inner",
+                        std::mem::offset_of!(Self, inner),
+                        std::mem::size_of::<Inner>(),
+                        0,
+                        4,
+                        4,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(), "`Outer` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const OUTER_ASSERTS: () = {
           assert!(std::mem::offset_of!(Outer, inner) == 0);
           assert!(std::mem::size_of:: < Outer > () == 4);
@@ -1196,6 +1606,13 @@ mod tests {
                 Self { num, numi }
             }
           }
+          impl Atomics {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
@@ -1229,6 +1646,7 @@ mod tests {
 
     assert_tokens_eq!(
       quote! {
+          #[repr(C)]
           #[derive(Debug, PartialEq, Clone, encase::ShaderType)]
           pub struct RtsStruct {
               pub other_data: i32,
@@ -1240,6 +1658,13 @@ mod tests {
                 Self { other_data, the_array }
             }
           }
+          impl RtsStruct {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
@@ -1261,6 +1686,7 @@ mod tests {
 
     assert_tokens_eq!(
       quote! {
+        #[repr(C, align(4))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct RtsStruct<const N: usize> {
             /// size: 4, offset: 0x0, type: `i32`
@@ -1273,6 +1699,48 @@ mod tests {
                 Self { other_data, the_array }
             }
         }
+        impl<const N: usize> RtsStruct<N> {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl<const N: usize> RtsStruct<N> {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "other_data",
+                        std::mem::offset_of!(Self, other_data),
+                        std::mem::size_of::<i32>(),
+                        0,
+                        4,
+                        4,
+                    ),
+                    (
+                        "the_array",
+                        std::mem::offset_of!(Self, the_array),
+                        std::mem::size_of::<u32>(),
+                        4,
+                        4,
+                        4,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`RtsStruct` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const RTS_STRUCT_ASSERTS: () = {
             assert!(std::mem::offset_of!(RtsStruct<1>, other_data) == 0);
             assert!(std::mem::offset_of!(RtsStruct<1>, the_array) == 4);
@@ -1344,6 +1812,40 @@ mod tests {
                 Self { a }
             }
         }
+        impl UniformsData {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl UniformsData {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<[[f32; 4]; 3]>(),
+                        0,
+                        48,
+                        48,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`UniformsData` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const UNIFORMS_DATA_ASSERTS: () = {
              assert!(std::mem::offset_of!(UniformsData, a) == 0);
              assert!(std::mem::size_of::<UniformsData> () == 48);
@@ -1391,6 +1893,40 @@ mod tests {
                 Self { centered_mvp }
             }
         }
+        impl UniformsData {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl UniformsData {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "centered_mvp",
+                        std::mem::offset_of!(Self, centered_mvp),
+                        std::mem::size_of::<glam::Mat3A>(),
+                        0,
+                        48,
+                        48,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`UniformsData` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const UNIFORMS_DATA_ASSERTS: () = {
             assert!(std::mem::offset_of!(UniformsData, centered_mvp) == 0);
             assert!(std::mem::size_of:: <UniformsData>() == 48);
@@ -1451,6 +1987,64 @@ mod tests {
                 Self { a, b, c, d }
             }
         }
+        impl MatricesF32 {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl MatricesF32 {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "a",
+                        std::mem::offset_of!(Self, a),
+                        std::mem::size_of::<[[f32; 4]; 4]>(),
+                        0,
+                        64,
+                        64,
+                    ),
+                    (
+                        "b",
+                        std::mem::offset_of!(Self, b),
+                        std::mem::size_of::<[[f32; 4]; 4]>(),
+                        64,
+                        64,
+                        64,
+                    ),
+                    (
+                        "c",
+                        std::mem::offset_of!(Self, c),
+                        std::mem::size_of::<[[f32; 2]; 4]>(),
+                        128,
+                        32,
+                        32,
+                    ),
+                    (
+                        "d",
+                        std::mem::offset_of!(Self, d),
+                        std::mem::size_of::<[[f32; 4]; 3]>(),
+                        160,
+                        48,
+                        48,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`MatricesF32` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const MATRICES_F32_ASSERTS: () = {
             assert!(std::mem::offset_of!(MatricesF32, a) == 0);
             assert!(std::mem::offset_of!(MatricesF32, b) == 64);
@@ -1499,6 +2093,39 @@ mod tests {
         pub const fn Uniform(position_data: [f32; 2]) -> Uniform {
             Uniform { position_data }
         }
+        impl Uniform {
+          pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+          }
+        }
+        impl Uniform {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "position_data",
+                        std::mem::offset_of!(Self, position_data),
+                        std::mem::size_of::<[f32; 2]>(),
+                        0,
+                        8,
+                        8,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(), "`Uniform` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         const UNIFORM_ASSERTS: () = {
             assert!(std::mem::offset_of!(Uniform, position_data) == 0);
             assert!(std::mem::size_of:: < Uniform > () == 8);
@@ -1546,6 +2173,13 @@ mod tests {
                 Self { a, b, c }
             }
           }
+          impl Scalars {
+            pub fn as_wgsl_bytes(&self) -> std::vec::Vec<u8> {
+              let mut bytes = encase::StorageBuffer::new(std::vec::Vec::new());
+              bytes.write(self).unwrap();
+              bytes.into_inner()
+            }
+          }
       },
       actual
     );
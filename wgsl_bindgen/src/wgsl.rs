@@ -1,21 +1,37 @@
+use case::CaseExt;
 use naga::StructMember;
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::Index;
 
 use crate::quote_gen::RustItemPath;
+use crate::{FxIndexMap, FxIndexSet, WgslBindgenOption};
+
+pub fn shader_stage(stage: naga::ShaderStage) -> wgpu::ShaderStages {
+  match stage {
+    naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+    naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+    naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+  }
+}
 
 pub fn shader_stages(module: &naga::Module) -> wgpu::ShaderStages {
   module
     .entry_points
     .iter()
-    .map(|entry| match entry.stage {
-      naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
-      naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
-      naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
-    })
+    .map(|entry| shader_stage(entry.stage))
     .collect()
 }
 
+/// The type as written in WGSL, with the names of imported types demangled, eg: `types::Fp64`.
+pub fn type_name(module: &naga::Module, ty: &naga::Type) -> String {
+  let name = match &ty.name {
+    Some(name) => name.clone(),
+    None => ty.inner.to_wgsl(&module.to_ctx()),
+  };
+  crate::bevy_util::demangle_str(&name).into_owned()
+}
+
 pub fn buffer_binding_type(storage: naga::AddressSpace) -> TokenStream {
   match storage {
     naga::AddressSpace::Uniform => quote!(wgpu::BufferBindingType::Uniform),
@@ -79,6 +95,242 @@ pub fn vertex_format(ty: &naga::Type) -> wgpu::VertexFormat {
   }
 }
 
+/// The format of the vertex attribute of `member` in the vertex input struct
+/// `fully_qualified_name`, following `override_vertex_format`.
+pub fn vertex_attribute_format(
+  fully_qualified_name: &str,
+  member: &StructMember,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> wgpu::VertexFormat {
+  let member_name = member.name.as_deref().unwrap_or_default();
+  options
+    .vertex_format_override(fully_qualified_name, member_name)
+    .unwrap_or_else(|| vertex_format(&module.types[member.ty]))
+}
+
+/// The kind of the scalars the shader reads from vertex data with `format`, eg: `Float` for
+/// the normalized `Unorm8x4`.
+pub fn vertex_format_scalar_kind(format: wgpu::VertexFormat) -> naga::ScalarKind {
+  use wgpu::VertexFormat as F;
+
+  match format {
+    F::Uint8x2
+    | F::Uint8x4
+    | F::Uint16x2
+    | F::Uint16x4
+    | F::Uint32
+    | F::Uint32x2
+    | F::Uint32x3
+    | F::Uint32x4 => naga::ScalarKind::Uint,
+    F::Sint8x2
+    | F::Sint8x4
+    | F::Sint16x2
+    | F::Sint16x4
+    | F::Sint32
+    | F::Sint32x2
+    | F::Sint32x3
+    | F::Sint32x4 => naga::ScalarKind::Sint,
+    F::Unorm8x2
+    | F::Unorm8x4
+    | F::Snorm8x2
+    | F::Snorm8x4
+    | F::Unorm16x2
+    | F::Unorm16x4
+    | F::Snorm16x2
+    | F::Snorm16x4
+    | F::Float16x2
+    | F::Float16x4
+    | F::Float32
+    | F::Float32x2
+    | F::Float32x3
+    | F::Float32x4
+    | F::Float64
+    | F::Float64x2
+    | F::Float64x3
+    | F::Float64x4
+    | F::Unorm10_10_10_2 => naga::ScalarKind::Float,
+  }
+}
+
+/// The Rust type of the vertex data read with `format`, eg: `[u8; 4]` for `Unorm8x4`.
+pub fn vertex_format_rust_type(format: wgpu::VertexFormat) -> TokenStream {
+  use wgpu::VertexFormat as F;
+
+  let (scalar, count) = match format {
+    F::Uint8x2 | F::Unorm8x2 => (quote!(u8), 2),
+    F::Uint8x4 | F::Unorm8x4 => (quote!(u8), 4),
+    F::Sint8x2 | F::Snorm8x2 => (quote!(i8), 2),
+    F::Sint8x4 | F::Snorm8x4 => (quote!(i8), 4),
+    F::Uint16x2 | F::Unorm16x2 => (quote!(u16), 2),
+    F::Uint16x4 | F::Unorm16x4 => (quote!(u16), 4),
+    F::Sint16x2 | F::Snorm16x2 => (quote!(i16), 2),
+    F::Sint16x4 | F::Snorm16x4 => (quote!(i16), 4),
+    // Half precision floats are stored as their bits, since Rust has no stable `f16`.
+    F::Float16x2 => (quote!(u16), 2),
+    F::Float16x4 => (quote!(u16), 4),
+    F::Float32 => (quote!(f32), 1),
+    F::Float32x2 => (quote!(f32), 2),
+    F::Float32x3 => (quote!(f32), 3),
+    F::Float32x4 => (quote!(f32), 4),
+    F::Uint32 | F::Unorm10_10_10_2 => (quote!(u32), 1),
+    F::Uint32x2 => (quote!(u32), 2),
+    F::Uint32x3 => (quote!(u32), 3),
+    F::Uint32x4 => (quote!(u32), 4),
+    F::Sint32 => (quote!(i32), 1),
+    F::Sint32x2 => (quote!(i32), 2),
+    F::Sint32x3 => (quote!(i32), 3),
+    F::Sint32x4 => (quote!(i32), 4),
+    F::Float64 => (quote!(f64), 1),
+    F::Float64x2 => (quote!(f64), 2),
+    F::Float64x3 => (quote!(f64), 3),
+    F::Float64x4 => (quote!(f64), 4),
+  };
+
+  match count {
+    1 => scalar,
+    _ => {
+      let count = Index::from(count);
+      quote!([#scalar; #count])
+    }
+  }
+}
+
+/// The structs used by the buffers of `module`, with nested structs before the ones using them.
+pub fn buffer_structs(module: &naga::Module) -> FxIndexSet<naga::Handle<naga::Type>> {
+  fn visit(
+    module: &naga::Module,
+    ty: naga::Handle<naga::Type>,
+    structs: &mut FxIndexSet<naga::Handle<naga::Type>>,
+  ) {
+    match &module.types[ty].inner {
+      naga::TypeInner::Struct { members, .. } if !structs.contains(&ty) => {
+        for member in members {
+          visit(module, member.ty, structs);
+        }
+        structs.insert(ty);
+      }
+      naga::TypeInner::Array { base, .. }
+      | naga::TypeInner::BindingArray { base, .. } => visit(module, *base, structs),
+      _ => {}
+    }
+  }
+
+  let mut structs = FxIndexSet::default();
+  for (_, var) in module.global_variables.iter() {
+    if matches!(
+      var.space,
+      naga::AddressSpace::Uniform
+        | naga::AddressSpace::Storage { .. }
+        | naga::AddressSpace::PushConstant
+    ) {
+      visit(module, var.ty, &mut structs);
+    }
+  }
+  structs
+}
+
+/// The indirect commands shaders can write to storage buffers, eg: for GPU driven culling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndirectArgs {
+  Draw,
+  DrawIndexed,
+}
+
+impl IndirectArgs {
+  /// The `wgpu::util` struct of the command's arguments.
+  pub fn quote_type(self) -> TokenStream {
+    match self {
+      IndirectArgs::Draw => quote!(wgpu::util::DrawIndirectArgs),
+      IndirectArgs::DrawIndexed => quote!(wgpu::util::DrawIndexedIndirectArgs),
+    }
+  }
+
+  /// The fields of the `wgpu::util` struct, in the order they are laid out.
+  pub fn fields(self) -> &'static [&'static str] {
+    match self {
+      IndirectArgs::Draw => &[
+        "vertex_count",
+        "instance_count",
+        "first_vertex",
+        "first_instance",
+      ],
+      IndirectArgs::DrawIndexed => &[
+        "index_count",
+        "instance_count",
+        "first_index",
+        "base_vertex",
+        "first_instance",
+      ],
+    }
+  }
+}
+
+/// The indirect command `ty` is laid out as, if it's a struct in `indirect_args_structs`
+/// made of the scalars of one.
+pub fn indirect_args(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  ty: &naga::Type,
+  options: &WgslBindgenOption,
+) -> Option<IndirectArgs> {
+  let naga::TypeInner::Struct { members, .. } = &ty.inner else {
+    return None;
+  };
+  let item_path = RustItemPath::from_mangled(ty.name.as_ref()?, invoking_entry_module);
+  if !options.is_indirect_args_struct(&item_path.get_fully_qualified_name()) {
+    return None;
+  }
+
+  let kinds = members
+    .iter()
+    .enumerate()
+    .map(|(i, member)| match module.types[member.ty].inner {
+      naga::TypeInner::Scalar(scalar)
+        if member.offset == 4 * i as u32 && scalar.width == 4 =>
+      {
+        Some(scalar.kind)
+      }
+      _ => None,
+    })
+    .collect::<Option<Vec<_>>>()?;
+
+  use naga::ScalarKind::{Sint, Uint};
+  match kinds.as_slice() {
+    [Uint, Uint, Uint, Uint] => Some(IndirectArgs::Draw),
+    [Uint, Uint, Uint, Sint, Uint] => Some(IndirectArgs::DrawIndexed),
+    _ => None,
+  }
+}
+
+/// The structs laid out as indirect commands in the storage buffers `module` can write to,
+/// directly or as the elements of arrays.
+pub fn written_indirect_args(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> FxIndexMap<naga::Handle<naga::Type>, IndirectArgs> {
+  module
+    .global_variables
+    .iter()
+    .filter(|(_, var)| match var.space {
+      naga::AddressSpace::Storage { access } => {
+        access.contains(naga::StorageAccess::STORE)
+      }
+      _ => false,
+    })
+    .filter_map(|(_, var)| {
+      let ty = match module.types[var.ty].inner {
+        naga::TypeInner::Array { base, .. } => base,
+        _ => var.ty,
+      };
+      let indirect_args =
+        indirect_args(invoking_entry_module, module, &module.types[ty], options)?;
+      Some((ty, indirect_args))
+    })
+    .collect()
+}
+
 pub struct VertexInput {
   pub item_path: RustItemPath,
   pub fields: Vec<(u32, StructMember)>,
@@ -91,7 +343,7 @@ pub fn get_vertex_input_structs(
   module: &naga::Module,
 ) -> Vec<VertexInput> {
   // TODO: Handle multiple entries?
-  module
+  let mut inputs: Vec<_> = module
     .entry_points
     .iter()
     .find(|e| e.stage == naga::ShaderStage::Vertex)
@@ -134,7 +386,49 @@ pub fn get_vertex_input_structs(
         })
         .collect()
     })
-    .unwrap_or_default()
+    .unwrap_or_default();
+
+  inputs.extend(get_loose_vertex_input(invoking_entry_module, module));
+  inputs
+}
+
+/// Groups the arguments like `@location(0) position: vec3<f32>` of a vertex entry into a
+/// struct named after the entry, eg: `VsMainInput`, read from a single vertex buffer.
+pub fn get_loose_vertex_input(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+) -> Option<VertexInput> {
+  let vertex_entry = module
+    .entry_points
+    .iter()
+    .find(|e| e.stage == naga::ShaderStage::Vertex)?;
+
+  let fields: Vec<_> = vertex_entry
+    .function
+    .arguments
+    .iter()
+    .filter_map(|argument| match argument.binding.as_ref()? {
+      naga::Binding::Location { location, .. } => {
+        let member = StructMember {
+          name: argument.name.clone(),
+          ty: argument.ty,
+          binding: argument.binding.clone(),
+          // The offsets of vertex attributes come from the Rust struct instead.
+          offset: 0,
+        };
+        Some((*location, member))
+      }
+      naga::Binding::BuiltIn(_) => None,
+    })
+    .collect();
+
+  (!fields.is_empty()).then(|| {
+    let name = format!("{}Input", vertex_entry.name.to_camel());
+    VertexInput {
+      item_path: RustItemPath::new(invoking_entry_module.into(), name.into()),
+      fields,
+    }
+  })
 }
 
 #[cfg(test)]
@@ -251,8 +545,8 @@ mod tests {
     let module = naga::front::wgsl::parse_str(source).unwrap();
 
     let vertex_inputs = get_vertex_input_structs("", &module);
-    // Only structures should be included.
-    assert_eq!(2, vertex_inputs.len());
+    // The loose arguments are grouped in a synthesized struct after the structures.
+    assert_eq!(3, vertex_inputs.len());
 
     assert_eq!("VertexInput0", vertex_inputs[0].item_path.name);
     assert_eq!(3, vertex_inputs[0].fields.len());
@@ -263,5 +557,69 @@ mod tests {
     assert_eq!(4, vertex_inputs[1].fields.len());
     assert_eq!("in5", vertex_inputs[1].fields[2].1.name.as_ref().unwrap());
     assert_eq!(5, vertex_inputs[1].fields[2].0);
+
+    assert_eq!("MainInput", vertex_inputs[2].item_path.name);
+    assert_eq!(1, vertex_inputs[2].fields.len());
+    assert_eq!("in3", vertex_inputs[2].fields[0].1.name.as_ref().unwrap());
+    assert_eq!(7, vertex_inputs[2].fields[0].0);
+  }
+
+  #[test]
+  fn written_indirect_args_of_storage_buffers() {
+    let source = indoc! {r#"
+            struct DrawArgs {
+                vertex_count: u32,
+                instance_count: u32,
+                first_vertex: u32,
+                first_instance: u32,
+            };
+
+            struct DrawIndexedArgs {
+                index_count: u32,
+                instance_count: u32,
+                first_index: u32,
+                base_vertex: i32,
+                first_instance: u32,
+            };
+
+            struct ReadArgs {
+                vertex_count: u32,
+                instance_count: u32,
+                first_vertex: u32,
+                first_instance: u32,
+            };
+
+            struct Counts {
+                a: u32,
+                b: u32,
+                c: u32,
+                d: u32,
+            };
+
+            @group(0) @binding(0) var<storage, read_write> draws: array<DrawArgs>;
+            @group(0) @binding(1) var<storage, read_write> indexed: DrawIndexedArgs;
+            @group(0) @binding(2) var<storage, read> read_args: ReadArgs;
+            @group(0) @binding(3) var<storage, read_write> counts: Counts;
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let options = WgslBindgenOption {
+      indirect_args_structs: vec![regex::Regex::new("Args$").unwrap()],
+      ..Default::default()
+    };
+
+    // Read only buffers can't be written to by the shader, and `Counts` is laid out as a draw
+    // but isn't in `indirect_args_structs`.
+    let indirect_args = written_indirect_args("", &module, &options)
+      .into_iter()
+      .map(|(ty, args)| (module.types[ty].name.clone().unwrap(), args))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![
+        ("DrawArgs".to_string(), IndirectArgs::Draw),
+        ("DrawIndexedArgs".to_string(), IndirectArgs::DrawIndexed),
+      ],
+      indirect_args
+    );
   }
 }
@@ -4,6 +4,26 @@ use miette::{IntoDiagnostic, Result};
 use pretty_assertions::assert_eq;
 use wgsl_bindgen::*;
 
+/// Generates the bindings of `builder` to `tests/output/{name}.actual.rs` and compares them
+/// with `tests/output/{name}.expected.rs`.
+fn assert_bindgen_output(
+  name: &str,
+  builder: &mut WgslBindgenOptionBuilder,
+) -> Result<()> {
+  let output = format!("tests/output/{name}.actual.rs");
+  builder
+    .output(output.clone())
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let actual = read_to_string(output).unwrap();
+  let expected = read_to_string(format!("tests/output/{name}.expected.rs")).unwrap();
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
 #[test]
 fn test_bevy_bindgen() -> Result<()> {
   WgslBindgenOptionBuilder::default()
@@ -53,6 +73,648 @@ fn test_main_bindgen() -> Result<()> {
   Ok(())
 }
 
+#[test]
+fn test_no_std_bindgen() -> Result<()> {
+  WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/no_std/triangle.wgsl")
+    .workspace_root("tests/shaders/no_std")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .no_std(true)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .output("tests/output/bindgen_no_std.actual.rs".to_string())
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let actual = read_to_string("tests/output/bindgen_no_std.actual.rs").unwrap();
+  let expected = read_to_string("tests/output/bindgen_no_std.expected.rs").unwrap();
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_no_std_rejects_composer_sources() {
+  let err = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/no_std/triangle.wgsl")
+    .workspace_root("tests/shaders/no_std")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .shader_source_type(
+      WgslShaderSourceType::UseEmbed | WgslShaderSourceType::UseComposerEmbed,
+    )
+    .no_std(true)
+    .emit_rerun_if_change(false)
+    .build()
+    .err()
+    .expect("composer sources require std");
+
+  assert!(matches!(
+    err,
+    WgslBindgenError::NoStdIncompatibleSourceType {
+      source_type: WgslShaderSourceType::UseComposerEmbed
+    }
+  ));
+}
+
+#[test]
+fn test_main_bindgen_from_config_file() -> Result<()> {
+  WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let actual = read_to_string("tests/output/bindgen_main_config.actual.rs").unwrap();
+  let expected = read_to_string("tests/output/bindgen_main.expected.rs").unwrap();
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_config_file_errors() -> Result<()> {
+  let err = WgslBindgenOptionBuilder::from_config_file("tests/missing.toml")
+    .err()
+    .expect("missing config file should fail");
+
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::config_file".to_string()), code);
+  Ok(())
+}
+
+#[test]
+fn test_stale_outputs() -> Result<()> {
+  let bindgen = |output: &str| {
+    WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+      .output(output)
+      .build()
+  };
+
+  let up_to_date = bindgen("tests/output/bindgen_main.expected.rs")?
+    .stale_outputs()
+    .into_diagnostic()?;
+  assert!(up_to_date.is_empty());
+
+  let missing = bindgen("tests/output/bindgen_main_missing.actual.rs")?
+    .stale_outputs()
+    .into_diagnostic()?;
+  assert_eq!(
+    vec![std::path::PathBuf::from(
+      "tests/output/bindgen_main_missing.actual.rs"
+    )],
+    missing
+  );
+  Ok(())
+}
+
+#[test]
+fn test_generate_replaces_output_atomically() -> Result<()> {
+  let output = "tests/output/bindgen_atomic.actual.rs";
+  std::fs::write(output, "stale").into_diagnostic()?;
+
+  WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+    .output(output)
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let actual = read_to_string(output).unwrap();
+  let expected = read_to_string("tests/output/bindgen_main.expected.rs").unwrap();
+  assert_eq!(actual, expected);
+
+  // Other tests may be writing their own outputs concurrently.
+  let temp_files = std::fs::read_dir("tests/output")
+    .into_diagnostic()?
+    .filter_map(|entry| entry.ok())
+    .filter(|entry| {
+      let name = entry.file_name().to_string_lossy().into_owned();
+      name.starts_with(".bindgen_atomic.actual.rs.") && name.ends_with(".tmp")
+    })
+    .count();
+  assert_eq!(0, temp_files);
+  Ok(())
+}
+
+#[test]
+fn test_hash_sidecar_file() -> Result<()> {
+  let output = "tests/output/bindgen_sidecar.actual.rs";
+  let sidecar = "tests/output/bindgen_sidecar.actual.rs.hash";
+  let _ = std::fs::remove_file(sidecar);
+
+  let bindgen = WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+    .output(output)
+    .hash_storage(WgslHashStorage::SidecarFile)
+    .build()?;
+  bindgen.generate().into_diagnostic()?;
+
+  let hash = read_to_string(sidecar).unwrap();
+  assert_eq!(65, hash.len());
+  assert!(!read_to_string(output).unwrap().contains("SourceHash"));
+
+  // Reformatting the output doesn't defeat the hash check, even without header comments.
+  std::fs::write(output, "// formatted").into_diagnostic()?;
+  bindgen.generate().into_diagnostic()?;
+  assert_eq!("// formatted", read_to_string(output).unwrap());
+
+  std::fs::write(sidecar, "stale").into_diagnostic()?;
+  bindgen.generate().into_diagnostic()?;
+  let expected = read_to_string("tests/output/bindgen_main.expected.rs").unwrap();
+  assert_eq!(expected, read_to_string(output).unwrap());
+  assert_eq!(hash, read_to_string(sidecar).unwrap());
+  Ok(())
+}
+
+#[test]
+fn test_hash_ignores_build_options() -> Result<()> {
+  let output = "tests/output/bindgen_hash_options.actual.rs";
+  let _ = std::fs::remove_file(format!("{output}.hash"));
+
+  let generate = |configure: fn(&mut WgslBindgenOptionBuilder)| -> Result<String> {
+    let mut builder =
+      WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?;
+    builder
+      .output(output)
+      .hash_storage(WgslHashStorage::SidecarFile);
+    configure(&mut builder);
+    builder.build()?.generate().into_diagnostic()?;
+    Ok(read_to_string(output).unwrap())
+  };
+
+  generate(|_| {})?;
+  std::fs::write(output, "// formatted").into_diagnostic()?;
+
+  // Options only steering the build don't regenerate the bindings.
+  let actual = generate(|builder| {
+    builder
+      .emit_rerun_if_change(false)
+      .emit_validation_warnings(true)
+      .module_cache_dir("target/wgsl_bindgen_hash_options");
+  })?;
+  assert_eq!("// formatted", actual);
+
+  let actual = generate(|builder| {
+    builder.derive_serde(true);
+  })?;
+  assert_ne!("// formatted", actual);
+  Ok(())
+}
+
+#[test]
+fn test_dependency_graph_output() -> Result<()> {
+  let generate_graph = |graph: &str| -> Result<String> {
+    WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+      .output("tests/output/bindgen_graph.actual.rs")
+      .dependency_graph_output(graph)
+      .build()?
+      .generate()
+      .into_diagnostic()?;
+    Ok(read_to_string(graph).unwrap())
+  };
+
+  // Entries are labelled by their path, and imports by their module name.
+  let dot = generate_graph("tests/output/bindgen_graph.actual.dot")?;
+  assert_eq!(
+    indoc::indoc! {r#"
+      digraph shaders {
+        rankdir=LR;
+        n0 [label="tests/shaders/basic/main.wgsl", shape=box];
+        n1 [label="bindings"];
+        n2 [label="types"];
+        n0 -> n1;
+        n0 -> n2;
+      }
+    "#},
+    dot
+  );
+
+  let mermaid = generate_graph("tests/output/bindgen_graph.actual.mmd")?;
+  assert_eq!(
+    indoc::indoc! {r#"
+      flowchart LR
+        n0["tests/shaders/basic/main.wgsl"]
+        n1("bindings")
+        n2("types")
+        n0 --> n1
+        n0 --> n2
+    "#},
+    mermaid
+  );
+  Ok(())
+}
+
+#[test]
+fn test_binding_reference_output() -> Result<()> {
+  WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+    .add_entry_point("tests/shaders/issue_35/clear.wgsl")
+    .output("tests/output/bindgen_reference.actual.rs")
+    .binding_reference_output("tests/output/binding_reference.actual.md")
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let actual = read_to_string("tests/output/binding_reference.actual.md").unwrap();
+  let expected = read_to_string("tests/output/binding_reference.expected.md").unwrap();
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_reflection_output() -> Result<()> {
+  WgslBindgenOptionBuilder::default()
+    .add_entry_point_source(
+      "reflection",
+      indoc::indoc! {"
+        struct VertexInput {
+            @location(0) position: vec3<f32>,
+            @location(1) uv: vec2<f32>,
+        };
+
+        struct Light {
+            position: vec3<f32>,
+            intensity: f32,
+            color: vec3<f32>,
+        };
+
+        @group(0) @binding(0) var<uniform> light: Light;
+
+        @vertex
+        fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+            return vec4(in.position * light.color * light.intensity, in.uv.x);
+        }
+      "},
+    )
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .output("tests/output/bindgen_reflection.actual.rs")
+    .reflection_output("tests/output/reflection.actual.json")
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let json = read_to_string("tests/output/reflection.actual.json").unwrap();
+  let reflection: serde_json::Value = serde_json::from_str(&json).into_diagnostic()?;
+  let entry = &reflection["entries"][0];
+
+  let light = &entry["struct_layouts"][1];
+  assert_eq!("reflection::Light", light["name"]);
+  assert_eq!(32, light["size"]);
+  assert_eq!(16, light["alignment"]);
+  let fields = light["fields"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|f| {
+      let name = f["name"].as_str().unwrap();
+      let wgsl_type = f["wgsl_type"].as_str().unwrap();
+      (name, wgsl_type, f["offset"].as_u64().unwrap(), f["size"].as_u64().unwrap())
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      ("position", "vec3<f32>", 0, 12),
+      ("intensity", "f32", 12, 4),
+      ("color", "vec3<f32>", 16, 12),
+    ],
+    fields
+  );
+
+  let vertex_layout = &entry["vertex_layouts"][0];
+  assert_eq!("reflection::VertexInput", vertex_layout["name"]);
+  let attributes = vertex_layout["attributes"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|a| {
+      let name = a["name"].as_str().unwrap();
+      (a["location"].as_u64().unwrap(), name, a["format"].as_str().unwrap())
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(vec![(0, "position", "Float32x3"), (1, "uv", "Float32x2")], attributes);
+
+  assert_eq!("vs_main", entry["entry_points"][0]["name"]);
+  assert_eq!("reflection::WgpuBindGroup0", entry["bind_groups"][0]["name"]);
+  Ok(())
+}
+
+#[test]
+fn test_c_header_output() -> Result<()> {
+  WgslBindgenOptionBuilder::default()
+    .add_entry_point_source(
+      "lights",
+      indoc::indoc! {"
+        struct Light {
+            position: vec3<f32>,
+            intensity: f32,
+            color: vec3<f32>,
+        };
+
+        struct Scene {
+            view: mat3x3<f32>,
+            light_count: u32,
+            lights: array<Light, 4>,
+            offsets: array<vec3<f32>, 2>,
+        };
+
+        struct Particles {
+            count: atomic<u32>,
+            positions: array<vec2<f32>>,
+        };
+
+        @group(0) @binding(0) var<uniform> scene: Scene;
+        @group(0) @binding(1) var<storage, read_write> particles: Particles;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            let light = scene.lights[0].color * scene.view * scene.offsets[0];
+            particles.positions[atomicAdd(&particles.count, scene.light_count)] = light.xy;
+        }
+      "},
+    )
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .output("tests/output/bindgen_c_header.actual.rs")
+    .c_header_output("tests/output/c_header.actual.h")
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let header = read_to_string("tests/output/c_header.actual.h").unwrap();
+  assert_eq!(
+    indoc::indoc! {r#"
+      // File automatically generated by wgsl_bindgen.
+      // Changes made to this file will not be saved.
+
+      #pragma once
+
+      #include <assert.h>
+      #include <stddef.h>
+      #include <stdint.h>
+
+      typedef struct lights_Light {
+        float position[3];
+        float intensity;
+        float color[3];
+        uint8_t _pad0[4];
+      } lights_Light;
+      static_assert(sizeof(lights_Light) == 32, "size of lights_Light");
+      static_assert(offsetof(lights_Light, position) == 0, "offset of lights_Light.position");
+      static_assert(offsetof(lights_Light, intensity) == 12, "offset of lights_Light.intensity");
+      static_assert(offsetof(lights_Light, color) == 16, "offset of lights_Light.color");
+
+      typedef struct lights_Scene {
+        float view[3][4];
+        uint32_t light_count;
+        uint8_t _pad0[12];
+        lights_Light lights[4];
+        float offsets[2][4];
+      } lights_Scene;
+      static_assert(sizeof(lights_Scene) == 224, "size of lights_Scene");
+      static_assert(offsetof(lights_Scene, view) == 0, "offset of lights_Scene.view");
+      static_assert(offsetof(lights_Scene, light_count) == 48, "offset of lights_Scene.light_count");
+      static_assert(offsetof(lights_Scene, lights) == 64, "offset of lights_Scene.lights");
+      static_assert(offsetof(lights_Scene, offsets) == 192, "offset of lights_Scene.offsets");
+
+      typedef struct lights_Particles {
+        uint32_t count;
+        uint8_t _pad0[4];
+        float positions[][2];
+      } lights_Particles;
+      static_assert(offsetof(lights_Particles, count) == 0, "offset of lights_Particles.count");
+      static_assert(offsetof(lights_Particles, positions) == 8, "offset of lights_Particles.positions");
+    "#},
+    header
+  );
+  Ok(())
+}
+
+#[test]
+fn test_typescript_output() -> Result<()> {
+  WgslBindgenOptionBuilder::default()
+    .add_entry_point_source(
+      "particles",
+      indoc::indoc! {"
+        #import procedural::noise::Noise
+
+        struct Particle {
+            position: vec3<f32>,
+            noise: Noise,
+        };
+
+        struct Params {
+            gravity: vec3<f32>,
+            transform: mat2x2<f32>,
+        };
+
+        @group(0) @binding(0) var<uniform> params: Params;
+        @group(1) @binding(0) var<storage, read_write> particles: array<Particle>;
+
+        @compute @workgroup_size(64)
+        fn update(@builtin(global_invocation_id) id: vec3<u32>) {
+            particles[id.x].position += params.gravity * f32(particles[id.x].noise.seed);
+            particles[id.x].position.x += (params.transform * particles[id.x].position.xy).y;
+        }
+      "},
+    )
+    .add_composable_source("procedural::noise", "struct Noise { seed: u32, scale: f32 }")
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .output("tests/output/bindgen_typescript.actual.rs")
+    .typescript_output("tests/output/typescript.actual.ts")
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  let typescript = read_to_string("tests/output/typescript.actual.ts").unwrap();
+  assert_eq!(
+    indoc::indoc! {r#"
+      // File automatically generated by wgsl_bindgen.
+      // Changes made to this file will not be saved.
+
+      export namespace particles {
+        export const ENTRY_UPDATE = "update";
+
+        /** The bindings of `@group(0)`. */
+        export const WgpuBindGroup0 = {
+          group: 0,
+          bindings: {
+            params: 0,
+          },
+        } as const;
+
+        /** The bindings of `@group(1)`. */
+        export const WgpuBindGroup1 = {
+          group: 1,
+          bindings: {
+            particles: 0,
+          },
+        } as const;
+
+        /** The layout of `Params`, 32 bytes aligned to 16. */
+        export const Params = {
+          size: 32,
+          alignment: 16,
+          /** Typed arrays over the fields of a `Params` at `byteOffset` in `buffer`. */
+          view(buffer: ArrayBuffer, byteOffset = 0) {
+            return {
+              gravity: new Float32Array(buffer, byteOffset + 0, 3),
+              transform: new Float32Array(buffer, byteOffset + 16, 4),
+            };
+          },
+        } as const;
+
+        /** The layout of `Particle`, 32 bytes aligned to 16. */
+        export const Particle = {
+          size: 32,
+          alignment: 16,
+          /** Typed arrays over the fields of a `Particle` at `byteOffset` in `buffer`. */
+          view(buffer: ArrayBuffer, byteOffset = 0) {
+            return {
+              position: new Float32Array(buffer, byteOffset + 0, 3),
+              noise: procedural.noise.Noise.view(buffer, byteOffset + 12),
+            };
+          },
+        } as const;
+      }
+
+      export namespace procedural.noise {
+        /** The layout of `Noise`, 8 bytes aligned to 4. */
+        export const Noise = {
+          size: 8,
+          alignment: 4,
+          /** Typed arrays over the fields of a `Noise` at `byteOffset` in `buffer`. */
+          view(buffer: ArrayBuffer, byteOffset = 0) {
+            return {
+              seed: new Uint32Array(buffer, byteOffset + 0, 1),
+              scale: new Float32Array(buffer, byteOffset + 4, 1),
+            };
+          },
+        } as const;
+      }
+    "#},
+    typescript
+  );
+  Ok(())
+}
+
+#[test]
+fn test_module_cache_dir() -> Result<()> {
+  let cache_dir = std::path::Path::new("tests/output/module_cache");
+  let _ = std::fs::remove_dir_all(cache_dir);
+
+  let bindgen = |entry: &str| {
+    WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+      .entry_points(vec![entry.to_string()])
+      .module_cache_dir(cache_dir)
+      .build()
+  };
+  let expected = read_to_string("tests/output/bindgen_main.expected.rs").unwrap();
+  let cached_files = || {
+    std::fs::read_dir(cache_dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .collect::<Vec<_>>()
+  };
+
+  let main = bindgen("tests/shaders/basic/main.wgsl")?;
+  assert_eq!(expected, main.generate_string().into_diagnostic()?);
+  let cached = cached_files();
+  assert_eq!(1, cached.len());
+
+  // The bindings generated from the cached module are unchanged.
+  assert_eq!(expected, main.generate_string().into_diagnostic()?);
+  assert_eq!(cached, cached_files());
+
+  // The cached module is used instead of composing the entry again.
+  let cached_module = std::fs::read(&cached[0]).into_diagnostic()?;
+  let minimal = bindgen("tests/shaders/minimal.wgsl")?;
+  minimal.generate_string().into_diagnostic()?;
+  let minimal_module = cached_files()
+    .into_iter()
+    .find(|path| *path != cached[0])
+    .unwrap();
+  std::fs::copy(minimal_module, &cached[0]).into_diagnostic()?;
+  assert!(main
+    .generate_string()
+    .into_diagnostic()?
+    .contains("pub struct Uniforms {"));
+
+  // An unreadable cached module is composed again.
+  std::fs::write(&cached[0], "corrupt").into_diagnostic()?;
+  assert_eq!(expected, main.generate_string().into_diagnostic()?);
+  assert_eq!(cached_module, std::fs::read(&cached[0]).into_diagnostic()?);
+  Ok(())
+}
+
+#[test]
+fn test_generate_preserves_user_regions() -> Result<()> {
+  let output = "tests/output/bindgen_user_regions.actual.rs";
+  let region = indoc::indoc! {"
+    // wgsl_bindgen: begin user
+    pub fn helper() -> u32 {
+        42
+    }
+    // wgsl_bindgen: end user
+  "};
+  std::fs::write(output, format!("pub mod stale {{}}\n{region}")).into_diagnostic()?;
+
+  let bindgen = WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+    .output(output)
+    .build()?;
+  bindgen.generate().into_diagnostic()?;
+
+  let actual = read_to_string(output).unwrap();
+  let expected = read_to_string("tests/output/bindgen_main.expected.rs").unwrap();
+  assert_eq!(format!("{expected}\n{region}"), actual);
+
+  // The regions are part of the expected output, so the bindings aren't stale.
+  bindgen.check().into_diagnostic()?;
+  Ok(())
+}
+
+#[test]
+fn test_check() -> Result<()> {
+  let bindgen = |output: &str| {
+    WgslBindgenOptionBuilder::from_config_file("tests/bindgen_main.toml")?
+      .output(output)
+      .build()
+  };
+
+  bindgen("tests/output/bindgen_main.expected.rs")?
+    .check()
+    .into_diagnostic()?;
+
+  let err = bindgen("tests/output/bindgen_main_missing.actual.rs")?
+    .check()
+    .expect_err("missing output should be stale");
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::stale_output".to_string()), code);
+  assert_eq!(
+    "Generated bindings are out of date:\ntests/output/bindgen_main_missing.actual.rs: missing",
+    err.to_string()
+  );
+
+  let err = bindgen("tests/output/bindgen_minimal.expected.rs")?
+    .check()
+    .expect_err("different output should be stale");
+  assert!(err
+    .to_string()
+    .contains("tests/output/bindgen_minimal.expected.rs: "));
+  assert!(err.to_string().contains("first difference at line"));
+  Ok(())
+}
+
 #[test]
 fn test_struct_alignment_minimal() -> Result<()> {
   WgslBindgenOptionBuilder::default()
@@ -97,6 +759,1892 @@ fn test_struct_alignment_padding() -> Result<()> {
   Ok(())
 }
 
+#[test]
+fn test_shader_label_override() -> Result<()> {
+  assert_bindgen_output(
+    "shader_label_override",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .add_entry_point("tests/shaders/padding.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .add_override_shader_label(("minimal", "Minimal Shader"))
+      .shader_label_formatter(|mod_name, _| format!("{mod_name}::ShaderModule")),
+  )
+}
+
+#[test]
+fn test_try_create_shader_module() -> Result<()> {
+  assert_bindgen_output(
+    "try_create_shader_module",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .generate_try_create_shader_module(true),
+  )
+}
+
+#[test]
+fn test_relative_path_source_type() -> Result<()> {
+  assert_bindgen_output(
+    "relative_path_source_type",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .shader_source_type(WgslShaderSourceType::UseRelativePath),
+  )
+}
+
+#[test]
+fn test_wasm_fetch_sources() -> Result<()> {
+  // Only the composed entry is read, not its dependencies.
+  assert_bindgen_output(
+    "wasm_fetch_sources",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .shader_source_type(WgslShaderSourceType::UseRelativePath)
+      .wasm_fetch_feature("web"),
+  )
+}
+
+#[test]
+fn test_multiple_import_roots() -> Result<()> {
+  assert_bindgen_output(
+    "multiple_import_roots",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/import_roots/main.wgsl")
+      .workspace_root("tests/shaders/import_roots")
+      .add_import_root("engine", "tests/shaders/import_roots/engine_lib")
+      .add_import_root("game", "tests/shaders/import_roots/game_lib")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_inline_sources() -> Result<()> {
+  let mut builder = WgslBindgenOptionBuilder::default();
+  builder
+    .add_entry_point("tests/shaders/minimal.wgsl")
+    .add_entry_point_source(
+      "generated",
+      indoc::indoc! {"
+        #import procedural::noise::{Noise};
+
+        @group(0) @binding(0)
+        var<uniform> noise: Noise;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            let seed = noise.seed;
+        }
+      "},
+    )
+    .add_composable_source("procedural::noise", "struct Noise { seed: u32 }")
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true);
+
+  let bindgen = builder.build()?;
+  assert_eq!(vec!["tests/shaders/minimal.wgsl"], bindgen.source_files());
+
+  assert_bindgen_output("inline_sources", &mut builder)
+}
+
+#[test]
+fn test_entries_sharing_imports() -> Result<()> {
+  let entry_source = |binding: u32| {
+    format!(
+      "#import procedural::noise::{{Noise, noise_seed}}
+
+      @group(0) @binding({binding})
+      var<uniform> noise: Noise;
+
+      @compute @workgroup_size(1)
+      fn main() {{
+          let seed = noise_seed(noise);
+      }}"
+    )
+  };
+  let bindgen = |entries: std::ops::Range<u32>| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    for index in entries {
+      builder.add_entry_point_source(format!("noise_{index}"), entry_source(index));
+    }
+    builder
+      .add_composable_source(
+        "procedural::noise",
+        "struct Noise { seed: u32 }\nfn noise_seed(noise: Noise) -> u32 { return noise.seed; }",
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .build()
+  };
+
+  // The composer reused across the entries composes each of them as it would on its own.
+  let actual = bindgen(0..16)?.generate_string().into_diagnostic()?;
+  for index in 0..16 {
+    let alone = bindgen(index..index + 1)?
+      .generate_string()
+      .into_diagnostic()?;
+    let entry_mod = |output: &str| {
+      let start = output.find(&format!("pub mod noise_{index} {{")).unwrap();
+      let end = start + output[start..].find("\n}\n").unwrap();
+      output[start..end].to_string()
+    };
+    assert_eq!(entry_mod(&alone), entry_mod(&actual));
+  }
+  Ok(())
+}
+
+#[test]
+fn test_support_module() -> Result<()> {
+  let entry = |color: &str| {
+    format!(
+      "@vertex fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {{\n\
+         return vec4<f32>(f32(index));\n\
+       }}\n\
+       @fragment fn fs_main() -> @location(0) vec4<f32> {{ return vec4<f32>({color}); }}"
+    )
+  };
+  // The shared items are generated once and re-exported by each entry module.
+  assert_bindgen_output(
+    "support_module",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("red", entry("1.0, 0.0, 0.0, 1.0"))
+      .add_entry_point_source("blue", entry("0.0, 0.0, 1.0, 1.0"))
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_generate_report() -> Result<()> {
+  let (_, report) = WgslBindgenOptionBuilder::default()
+    .add_entry_point_source(
+      "report",
+      indoc::indoc! {"
+        struct VertexInput {
+            @location(0) position: vec3<f32>,
+        };
+
+        struct Uniforms {
+            color: vec4<f32>,
+        };
+
+        @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+        @group(1) @binding(0) var color_texture: texture_2d<f32>;
+        @group(1) @binding(1) var color_sampler: sampler;
+
+        @vertex
+        fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+            return vec4(in.position, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return uniforms.color;
+        }
+      "},
+    )
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string_with_report()
+    .into_diagnostic()?;
+
+  assert_eq!(1, report.entries.len());
+
+  let entry = &report.entries[0];
+  assert_eq!("report", entry.mod_name);
+  assert_eq!(vec!["report::VertexInput", "report::Uniforms"], entry.structs);
+  assert_eq!(vec!["report::VertexInput"], entry.vertex_inputs);
+
+  let entry_points = entry
+    .entry_points
+    .iter()
+    .map(|e| (e.name.as_str(), e.stage.as_str()))
+    .collect::<Vec<_>>();
+  assert_eq!(vec![("vs_main", "vertex"), ("fs_main", "fragment")], entry_points);
+
+  let bind_groups = entry
+    .bind_groups
+    .iter()
+    .map(|group| {
+      let bindings = group
+        .bindings
+        .iter()
+        .map(|b| (b.binding, b.name.as_str(), b.resource))
+        .collect::<Vec<_>>();
+      (group.name.as_str(), bindings)
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      ("report::WgpuBindGroup0", vec![(0, "uniforms", BindResourceType::Buffer)]),
+      (
+        "report::WgpuBindGroup1",
+        vec![
+          (0, "color_texture", BindResourceType::Texture),
+          (1, "color_sampler", BindResourceType::Sampler),
+        ]
+      ),
+    ],
+    bind_groups
+  );
+  let warnings = entry
+    .warnings
+    .iter()
+    .map(|w| (w.kind, w.message.as_str()))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      (
+        WgslDiagnosticKind::UnusedBinding,
+        "binding `color_texture` at @group(1) @binding(0) is never used"
+      ),
+      (
+        WgslDiagnosticKind::UnusedBinding,
+        "binding `color_sampler` at @group(1) @binding(1) is never used"
+      ),
+      (
+        WgslDiagnosticKind::VisibilityBroadened,
+        "binding `uniforms` at @group(0) @binding(0) is only used by fragment, but is visible to vertex and fragment"
+      ),
+    ],
+    warnings
+  );
+  Ok(())
+}
+
+#[test]
+fn test_diagnostics() -> Result<()> {
+  let builder = || {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source(
+        "diagnostics",
+        indoc::indoc! {"
+          struct Params {
+              scale: f32,
+              offset: vec3<f32>,
+              skew: mat2x3<f32>,
+          };
+
+          @group(0) @binding(0) var<uniform> params: Params;
+
+          @compute @workgroup_size(1)
+          fn main() {
+              let scale = params.scale;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    builder
+  };
+
+  let (_, report) = builder()
+    .build()?
+    .generate_string_with_report()
+    .into_diagnostic()?;
+  let diagnostics = report
+    .diagnostics()
+    .map(ToString::to_string)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      "tests/shaders/diagnostics.wgsl: `mat2x3<f32>` is missing from the type map and is generated as an array",
+      "tests/shaders/diagnostics.wgsl: padding `_pad_scale` of 12 bytes inserted in `diagnostics::Params` at offset 0x4, after `scale`",
+    ],
+    diagnostics
+  );
+
+  let err = builder()
+    .deny_diagnostic(WgslDiagnosticKind::PaddingInserted)
+    .build()?
+    .generate_string()
+    .expect_err("inserted padding should be denied");
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::denied_diagnostic".to_string()), code);
+  assert_eq!(
+    "Generation reported denied diagnostics:\ntests/shaders/diagnostics.wgsl: padding `_pad_scale` of 12 bytes inserted in `diagnostics::Params` at offset 0x4, after `scale`",
+    err.to_string()
+  );
+  Ok(())
+}
+
+#[test]
+fn test_exclude_items() -> Result<()> {
+  assert_bindgen_output(
+    "exclude_items",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "exclude",
+        indoc::indoc! {"
+          const DEBUG_LEVEL: u32 = 2u;
+          const SCALE: f32 = 1.5;
+  
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+          };
+  
+          struct InternalState {
+              frame: u32,
+          };
+  
+          struct Uniforms {
+              color: vec4<f32>,
+          };
+  
+          @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+          @group(0) @binding(1) var<storage, read_write> state: InternalState;
+  
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              state.frame = state.frame + DEBUG_LEVEL;
+              return vec4(in.position * SCALE, 1.0) * uniforms.color;
+          }
+        "},
+      )
+      .add_exclude_item((WgslItemKind::Struct, "::Internal"))
+      .add_exclude_item((WgslItemKind::EntryPointIo, ".*"))
+      .add_exclude_item((WgslItemKind::Const, "DEBUG_"))
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_entry_point_named() -> Result<()> {
+  assert_bindgen_output(
+    "entry_point_named",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_named("tests/shaders/minimal.wgsl", "minimal_renamed")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_entry_points_glob() -> Result<()> {
+  assert_bindgen_output(
+    "entry_points_glob",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .add_entry_points_glob("tests/shaders/*.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_output_partitions() -> Result<()> {
+  let entry = |name: &str| {
+    format!(
+      "#import common::params::{{Params}}\n\
+       @group(0) @binding(0) var<uniform> params: Params;\n\
+       @compute @workgroup_size(1) fn {name}() {{ let scale = params.scale; }}"
+    )
+  };
+  let bindgen = |output_module_path: Option<&str>| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .add_entry_point_source("blur", entry("blur"))
+      .add_entry_point_source("simulate", entry("simulate"))
+      .add_composable_source("common::params", "struct Params { scale: f32 }")
+      .add_output_partition(("tests/output/bindgen_partition_postfx.actual.rs", "/blur"))
+      .add_output_partition((
+        "tests/output/bindgen_partition_compute.actual.rs",
+        "/simulate",
+      ))
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .output("tests/output/bindgen_partition_main.actual.rs");
+    if let Some(path) = output_module_path {
+      builder.output_module_path(path);
+    }
+    builder.build()
+  };
+
+  let err = bindgen(None)?
+    .generate()
+    .expect_err("partitions need a module path");
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::invalid_output_module_path".to_string()), code);
+
+  bindgen(Some("crate::shader_bindings"))?
+    .generate()
+    .into_diagnostic()?;
+
+  // The shared types are only generated in the main output.
+  for partition in ["main", "postfx", "compute"] {
+    let actual =
+      read_to_string(format!("tests/output/bindgen_partition_{partition}.actual.rs"))
+        .unwrap();
+    let expected =
+      read_to_string(format!("tests/output/bindgen_partition_{partition}.expected.rs"))
+        .unwrap();
+    assert_eq!(actual, expected);
+  }
+  Ok(())
+}
+
+#[test]
+fn test_directory_output_mode() -> Result<()> {
+  let files = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/basic/main.wgsl")
+    .workspace_root("tests/shaders/additional")
+    .additional_scan_dir((None, "tests/shaders/additional"))
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .ir_capabilities(naga::valid::Capabilities::PUSH_CONSTANT)
+    .output_mode(WgslOutputMode::Directory)
+    .build()?
+    .generate_files()
+    .into_diagnostic()?;
+
+  let names = files
+    .iter()
+    .map(|(name, _)| name.as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      "mod.rs",
+      "layout_asserts.rs",
+      "_support.rs",
+      "main.rs",
+      "bytemuck_impls.rs",
+      "bind_group_layouts.rs"
+    ],
+    names
+  );
+
+  for (name, actual) in [&files[0], &files[3]] {
+    let stem = name.trim_end_matches(".rs");
+    std::fs::write(format!("tests/output/directory_{stem}.actual.rs"), actual)
+      .into_diagnostic()?;
+    let expected =
+      read_to_string(format!("tests/output/directory_{stem}.expected.rs")).unwrap();
+    assert_eq!(*actual, expected);
+  }
+  Ok(())
+}
+
+#[test]
+fn test_output_in_out_dir() -> Result<()> {
+  std::env::set_var("OUT_DIR", "tests/output");
+  WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/minimal.wgsl")
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .output_in_out_dir("bindgen_out_dir.actual.rs")
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  // The lints are allowed per item, since inner attributes can't be `include!`d.
+  let actual = read_to_string("tests/output/bindgen_out_dir.actual.rs").unwrap();
+  let expected = read_to_string("tests/output/bindgen_out_dir.expected.rs").unwrap();
+
+  assert_eq!(actual, expected);
+  Ok(())
+}
+
+#[test]
+fn test_entry_point_features() -> Result<()> {
+  assert_bindgen_output(
+    "entry_point_features",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .add_entry_point_source(
+        "bloom",
+        indoc::indoc! {"
+          struct Bloom { threshold: f32 }
+  
+          @group(0) @binding(0)
+          var<uniform> bloom: Bloom;
+  
+          @compute @workgroup_size(1)
+          fn main() {
+              let threshold = bloom.threshold;
+          }
+        "},
+      )
+      .add_entry_point_feature(("bloom", "postfx"))
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_bevy_helpers() -> Result<()> {
+  assert_bindgen_output(
+    "bevy_helpers",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "sprite",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+          };
+  
+          struct Material {
+              color: vec4<f32>,
+          };
+  
+          @group(0) @binding(0) var<uniform> material: Material;
+  
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, 1.0);
+          }
+  
+          @fragment
+          fn fs_main() -> @location(0) vec4<f32> {
+              return material.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .bevy_feature("bevy"),
+  )
+}
+
+#[test]
+fn test_generic_device() -> Result<()> {
+  // Bind groups are created from resources of a real device, so they aren't generic.
+  assert_bindgen_output(
+    "generic_device",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .generic_device(true),
+  )
+}
+
+#[test]
+fn test_loose_vertex_inputs() -> Result<()> {
+  // The `@location` arguments are grouped in a struct, leaving out the builtins.
+  assert_bindgen_output(
+    "loose_vertex_inputs",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "loose",
+        indoc::indoc! {"
+          @vertex
+          fn vs_main(
+              @builtin(vertex_index) index: u32,
+              @location(0) position: vec3<f32>,
+              @location(2) color: vec4<f32>,
+          ) -> @builtin(position) vec4<f32> {
+              return vec4(position, f32(index)) * color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_override_vertex_format() -> Result<()> {
+  // The fields take the type of the packed data, and the attributes its format.
+  assert_bindgen_output(
+    "override_vertex_format",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "packed",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) color: vec4<f32>,
+              @location(2) normal: vec4<f32>,
+          };
+  
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, 1.0) * in.color * in.normal;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .override_vertex_format(vec![
+        ("packed::VertexInput", "color", WgslVertexFormat::Unorm8x4).into(),
+        ("packed::VertexInput", "normal", WgslVertexFormat::Snorm16x4).into(),
+      ]),
+  )
+}
+
+#[test]
+fn test_override_vertex_struct() -> Result<()> {
+  // Only the layout is generated, from the fields of the user provided struct.
+  assert_bindgen_output(
+    "override_vertex_struct",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "mesh",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) uv: vec2<f32>,
+          };
+  
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, in.uv.x);
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .add_override_vertex_struct((
+        "mesh::VertexInput",
+        qs::quote!(crate::assets::Vertex),
+      )),
+  )
+}
+
+#[test]
+fn test_vertex_layout_validation() -> Result<()> {
+  let builder = |format| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source(
+        "layout",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) joints: vec4<u32>,
+          };
+
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, f32(in.joints.x));
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .override_vertex_format(vec![("layout::VertexInput", "joints", format).into()]);
+    builder
+  };
+
+  // The rules depending on the Rust layout are checked when compiling the bindings.
+  assert_bindgen_output(
+    "vertex_layout_validation",
+    &mut builder(WgslVertexFormat::Uint8x4),
+  )?;
+
+  // Normalized data is read as floats, which don't match the `vec4<u32>` of the shader.
+  let err = builder(WgslVertexFormat::Unorm8x4)
+    .build()?
+    .generate_string()
+    .unwrap_err();
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::invalid_vertex_layout".to_string()), code);
+  assert!(err.to_string().ends_with(
+    "`layout::VertexInput::joints` at @location(1) can't be read as `vec4<u32>` from `Unorm8x4` data"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_vertex_input_builtins() -> Result<()> {
+  // Builtins aren't read from the vertex buffer, so they take no space in the vertex data.
+  assert_bindgen_output(
+    "vertex_input_builtins",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "instanced",
+        indoc::indoc! {"
+          struct VertexInput {
+              @builtin(vertex_index) vertex_index: u32,
+              @location(0) position: vec3<f32>,
+              @builtin(instance_index) instance_index: u32,
+              @location(1) color: vec4<f32>,
+          };
+  
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, f32(in.vertex_index + in.instance_index)) * in.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_vertex_format_directives() -> Result<()> {
+  let builder = |source: &str| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source("packed", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    builder
+  };
+
+  // The shader still sees floats, while the fields hold the normalized integers.
+  let mut packed = builder(indoc::indoc! {"
+    struct VertexInput {
+        @location(0) position: vec3<f32>,
+        // @vertex_format(Unorm16x2)
+        @location(1) uv: vec2<f32>,
+        @location(2) color: vec4<f32>, // @vertex_format(Unorm8x4)
+    };
+
+    @vertex
+    fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+        return vec4(in.position, in.uv.x) * in.color;
+    }
+  "});
+  assert_bindgen_output("vertex_format_directives", &mut packed)?;
+
+  let error = builder(indoc::indoc! {"
+    struct VertexInput {
+        @location(0) color: vec4<f32>, // @vertex_format(Unorm7x4)
+    };
+
+    @vertex
+    fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+        return in.color;
+    }
+  "})
+  .build()
+  .err()
+  .unwrap();
+  assert!(error.to_string().contains(
+    "Unknown vertex format `Unorm7x4` in the `@vertex_format` comment at line 2"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_instance_vertex_inputs() -> Result<()> {
+  // Only the per-vertex input still takes a step mode.
+  assert_bindgen_output(
+    "instance_vertex_inputs",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "sprites",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec2<f32>,
+          };
+  
+          // @instance
+          struct Sprite {
+              @location(1) offset: vec2<f32>,
+          };
+  
+          struct TintInstance {
+              @location(2) color: vec4<f32>,
+          };
+  
+          @vertex
+          fn vs_main(vertex: VertexInput, sprite: Sprite, tint: TintInstance) -> @builtin(position) vec4<f32> {
+              return vec4(vertex.position + sprite.offset, 0.0, 1.0) * tint.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .add_instance_vertex_input(Regex::new("Instance$").unwrap()),
+  )
+}
+
+#[test]
+fn test_remap_vertex_location() -> Result<()> {
+  let builder = |source_type: WgslShaderSourceType| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source(
+        "mesh",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) uv: vec2<f32>,
+          };
+
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, in.uv.x);
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .shader_source_type(source_type)
+      .add_remap_vertex_location(("^mesh$", 1, 5));
+    builder
+  };
+
+  // Both the attributes and the embedded shader read `uv` from the remapped location.
+  assert_bindgen_output(
+    "remap_vertex_location",
+    &mut builder(WgslShaderSourceType::UseEmbed),
+  )?;
+
+  let error = builder(WgslShaderSourceType::UseComposerEmbed)
+    .build()
+    .err()
+    .unwrap();
+  assert!(error
+    .to_string()
+    .contains("`UseComposerEmbed` compiles the shader sources as written"));
+  Ok(())
+}
+
+#[test]
+fn test_vertex_pulling() -> Result<()> {
+  let builder = |source_type: WgslShaderSourceType| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source(
+        "mesh",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) uv: vec2<f32>,
+          };
+
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, in.uv.x);
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .shader_source_type(source_type)
+      .add_vertex_pulling(("^mesh$", 0));
+    builder
+  };
+
+  // The input struct gets the storage layout, read from a binding instead of a vertex buffer.
+  assert_bindgen_output("vertex_pulling", &mut builder(WgslShaderSourceType::UseEmbed))?;
+
+  let error = builder(WgslShaderSourceType::UseComposerEmbed)
+    .build()
+    .err()
+    .unwrap();
+  assert!(error
+    .to_string()
+    .contains("`UseComposerEmbed` compiles the shader sources as written"));
+  Ok(())
+}
+
+#[test]
+fn test_vertex_input_from_parts() -> Result<()> {
+  // Vertex data converts from the plain arrays of mesh loaders.
+  assert_bindgen_output(
+    "vertex_input_from_parts",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "mesh",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) color: vec4<f32>,
+          };
+  
+          @vertex
+          fn vs_main(in: VertexInput, @location(2) uv: vec2<f32>) -> @builtin(position) vec4<f32> {
+              return vec4(in.position, uv.x) * in.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .override_vertex_format(vec![(
+        "mesh::VertexInput",
+        "color",
+        WgslVertexFormat::Unorm8x4,
+      )
+        .into()]),
+  )
+}
+
+#[test]
+fn test_float64_vertex_attributes() -> Result<()> {
+  let mut builder = WgslBindgenOptionBuilder::default();
+  builder
+    .add_entry_point_source(
+      "precise",
+      indoc::indoc! {"
+        struct VertexInput {
+            @location(0) position: vec3<f64>,
+            @location(1) uv: vec2<f32>,
+        };
+
+        @vertex
+        fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+            return vec4<f32>(vec3<f32>(in.position), in.uv.x);
+        }
+      "},
+    )
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .ir_capabilities(naga::valid::Capabilities::FLOAT64);
+  assert_bindgen_output("float64_vertex_attributes", &mut builder)?;
+
+  let (_, report) = builder
+    .build()?
+    .generate_string_with_report()
+    .into_diagnostic()?;
+  let diagnostics = report
+    .diagnostics()
+    .filter(|d| d.kind == WgslDiagnosticKind::Float64VertexAttribute)
+    .map(ToString::to_string)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      "tests/shaders/precise.wgsl: `precise::VertexInput::position` at @location(0) is read from `Float64x3` data, which requires `wgpu::Features::VERTEX_ATTRIBUTE_64BIT`"
+    ],
+    diagnostics
+  );
+  Ok(())
+}
+
+#[test]
+fn test_override_vertex_stride() -> Result<()> {
+  // The attributes are still checked against the overridden stride.
+  assert_bindgen_output(
+    "override_vertex_stride",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "mesh",
+        indoc::indoc! {"
+          struct VertexInput {
+              @location(0) position: vec3<f32>,
+              @location(1) normal: vec3<f32>,
+          };
+  
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+              return vec4(in.position + in.normal, 1.0);
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(RustWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .add_override_vertex_stride(("^mesh::VertexInput$", 40)),
+  )
+}
+
+#[test]
+fn test_typed_buffers() -> Result<()> {
+  // Only the buffers of the address spaces the shaders bind are generated.
+  assert_bindgen_output(
+    "typed_buffers",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "particles",
+        indoc::indoc! {"
+          struct Particle {
+              position: vec4<f32>,
+          };
+  
+          @group(0) @binding(0) var<storage, read_write> particles: array<Particle, 64>;
+  
+          @compute @workgroup_size(64)
+          fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+              particles[id.x].position.y -= 1.0;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Encase)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_buffer_init_fns() -> Result<()> {
+  // Runtime sized structs have no fixed size to initialize a buffer with.
+  assert_bindgen_output(
+    "buffer_init_fns",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "particles",
+        indoc::indoc! {"
+          struct Params {
+              gravity: f32,
+          };
+  
+          struct Particles {
+              positions: array<vec4<f32>>,
+          };
+  
+          @group(0) @binding(0) var<uniform> params: Params;
+          @group(0) @binding(1) var<storage, read_write> particles: Particles;
+  
+          @compute @workgroup_size(64)
+          fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+              particles.positions[id.x].y -= params.gravity;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Encase)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_buffer_write_fns() -> Result<()> {
+  assert_bindgen_output(
+    "buffer_write_fns",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "particles",
+        indoc::indoc! {"
+          struct Params {
+              gravity: f32,
+          };
+  
+          @group(0) @binding(0) var<uniform> params: Params;
+  
+          @compute @workgroup_size(64)
+          fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+              let gravity = params.gravity;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_dynamic_buffer_init_fns() -> Result<()> {
+  // Only uniform bindings are packed for dynamic offsets.
+  assert_bindgen_output(
+    "dynamic_buffer_init_fns",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "objects",
+        indoc::indoc! {"
+          struct Object {
+              transform: mat4x4<f32>,
+          };
+  
+          struct Light {
+              color: vec4<f32>,
+          };
+  
+          @group(0) @binding(0) var<uniform> object: Object;
+          @group(0) @binding(1) var<storage, read> light: Light;
+  
+          @vertex
+          fn vs_main() -> @builtin(position) vec4<f32> {
+              return object.transform * light.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Encase)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_texture_descriptors() -> Result<()> {
+  // Cube textures are created as 2D textures with 6 layers.
+  assert_bindgen_output(
+    "texture_descriptors",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "post",
+        indoc::indoc! {"
+          @group(0) @binding(0) var color: texture_multisampled_2d<f32>;
+          @group(0) @binding(1) var depth: texture_depth_2d;
+          @group(0) @binding(2) var sky: texture_cube<f32>;
+          @group(0) @binding(3) var output: texture_storage_2d<rgba8unorm, write>;
+          @group(0) @binding(4) var shadows: texture_depth_2d_array;
+  
+          @compute @workgroup_size(8, 8)
+          fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+              let color = textureLoad(color, id.xy, 0);
+              let depth = textureLoad(depth, id.xy, 0);
+              let size = textureDimensions(sky);
+              let layers = textureNumLayers(shadows);
+              textureStore(output, id.xy, color * depth);
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_readback_fns() -> Result<()> {
+  assert_bindgen_output(
+    "readback_fns",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "particles",
+        indoc::indoc! {"
+          struct Particle {
+              position: vec4<f32>,
+          };
+  
+          struct Stats {
+              count: u32,
+          };
+  
+          @group(0) @binding(0) var<storage, read_write> particles: array<Particle>;
+          @group(0) @binding(1) var<storage, read_write> stats: Stats;
+  
+          @compute @workgroup_size(64)
+          fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+              particles[id.x].position.y -= 1.0;
+              stats.count += 1u;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_staging_belt_feature() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Params {
+        gravity: f32,
+    };
+
+    @group(0) @binding(0) var<uniform> params: Params;
+
+    @compute @workgroup_size(64)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        let gravity = params.gravity;
+    }
+  "};
+  assert_bindgen_output(
+    "staging_belt_feature",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("particles", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Encase)
+      .type_map(GlamWgslTypeMap)
+      .staging_belt_feature("staging-belt")
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_indirect_args() -> Result<()> {
+  assert_bindgen_output(
+    "indirect_args",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "culling",
+        indoc::indoc! {"
+          struct DrawArgs {
+              vertex_count: u32,
+              instance_count: u32,
+              first_vertex: u32,
+              first_instance: u32,
+          };
+  
+          @group(0) @binding(0) var<storage, read_write> draws: array<DrawArgs>;
+          @group(0) @binding(1) var<storage, read_write> last_draw: DrawArgs;
+  
+          @compute @workgroup_size(64)
+          fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+              draws[id.x].instance_count = 0u;
+              last_draw = draws[id.x];
+          }
+        "},
+      )
+      .add_indirect_args_struct(Regex::new("DrawArgs$").unwrap())
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_frames_in_flight_buffers() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Camera {
+        view_proj: mat4x4<f32>,
+    };
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+
+    @vertex
+    fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+        return camera.view_proj * vec4(position, 1.0);
+    }
+  "};
+  assert_bindgen_output(
+    "frames_in_flight_buffers",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("scene", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .frames_in_flight_buffers(true)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_uniform_arenas() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Object {
+        model: mat4x4<f32>,
+    };
+
+    @group(0) @binding(0) var<uniform> object: Object;
+
+    @vertex
+    fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+        return object.model * vec4(position, 1.0);
+    }
+  "};
+  assert_bindgen_output(
+    "uniform_arenas",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("scene", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .uniform_arenas(true)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_sampler_directives() -> Result<()> {
+  let builder = |source: &str| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source("post", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    builder
+  };
+
+  // Samplers without a preset are left to the caller.
+  let mut post = builder(indoc::indoc! {"
+    @group(0) @binding(0) var color: texture_2d<f32>;
+    // @sampler(linear, repeat)
+    @group(0) @binding(1) var color_sampler: sampler;
+    @group(0) @binding(2) var depth: texture_depth_2d;
+    @group(0) @binding(3) var shadow: sampler_comparison; // @sampler(linear, greater-equal)
+    @group(0) @binding(4) var point: sampler;
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+        let visibility = textureSampleCompare(depth, shadow, uv, 0.5);
+        let texel = textureSample(color, point, uv);
+        return textureSample(color, color_sampler, uv) * texel * visibility;
+    }
+  "});
+  assert_bindgen_output("sampler_directives", &mut post)?;
+
+  let error = builder(indoc::indoc! {"
+    // @sampler(bilinear)
+    @group(0) @binding(0) var color_sampler: sampler;
+  "})
+  .build()
+  .err()
+  .unwrap();
+  let error = error.to_string();
+  assert!(error.contains("Invalid `@sampler` comment at line 1"));
+  assert!(error.ends_with(": unknown sampler argument `bilinear`"));
+  Ok(())
+}
+
+#[test]
+fn test_runtime_array_buffer_sizes() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Lights {
+        ambient: vec4<f32>,
+        intensities: array<f32>,
+    };
+
+    @group(0) @binding(0) var<storage, read> lights: Lights;
+    @group(0) @binding(1) var<storage, read_write> indices: array<u32>;
+    @group(0) @binding(2) var<uniform> scale: f32;
+
+    @compute
+    @workgroup_size(64)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        indices[id.x] = u32(lights.intensities[id.x] * lights.ambient.x * scale);
+    }
+  "};
+  assert_bindgen_output(
+    "runtime_array_buffer_sizes",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("lighting", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_array_codec_fns() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Particle {
+        position: vec3<f32>,
+        mass: f32,
+    };
+
+    @group(0) @binding(0) var<storage, read_write> particles: array<Particle>;
+    @group(0) @binding(1) var<storage, read_write> normals: array<vec3<f32>>;
+
+    @compute
+    @workgroup_size(64)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        particles[id.x].position += normals[id.x] * particles[id.x].mass;
+    }
+  "};
+  assert_bindgen_output(
+    "array_codec_fns",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("simulation", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_bind_group_bundles() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Camera {
+        view_proj: mat4x4<f32>,
+    };
+
+    struct Material {
+        color: vec4<f32>,
+        roughness: f32,
+    };
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+
+    @group(1) @binding(0) var<uniform> material: Material;
+    @group(1) @binding(1) var albedo: texture_2d<f32>;
+    @group(1) @binding(2) var albedo_sampler: sampler;
+
+    @vertex
+    fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+        return camera.view_proj * vec4(position, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+        let color = textureSample(albedo, albedo_sampler, vec2(0.5));
+        return color * material.color * material.roughness;
+    }
+  "};
+  assert_bindgen_output(
+    "bind_group_bundles",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("scene", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .bind_group_bundles(true)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_ping_pong_buffers() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Particle {
+        position: vec2<f32>,
+        velocity: vec2<f32>,
+    };
+
+    @group(0) @binding(0) var<storage, read> particles_in: array<Particle>;
+    @group(0) @binding(1) var<storage, read_write> particles_out: array<Particle>;
+    @group(0) @binding(2) var<uniform> delta_time: f32;
+
+    @compute
+    @workgroup_size(64)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        let particle = particles_in[id.x];
+        particles_out[id.x].position = particle.position + particle.velocity * delta_time;
+    }
+  "};
+  assert_bindgen_output(
+    "ping_pong_buffers",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("simulation", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .ping_pong_buffers(true)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_shadowed_uniforms() -> Result<()> {
+  let source = indoc::indoc! {"
+    struct Light {
+        color: vec4<f32>,
+    };
+
+    @group(0) @binding(0) var<uniform> light: Light;
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+        return light.color;
+    }
+  "};
+  assert_bindgen_output(
+    "shadowed_uniforms",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("lighting", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .shadowed_uniforms(true)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_strip_unused_bindings() -> Result<()> {
+  let source = indoc::indoc! {"
+    @group(0) @binding(0) var<uniform> scale: f32;
+    @group(0) @binding(1) var<uniform> unused_offset: f32;
+    @group(1) @binding(0) var<uniform> unused_tint: f32;
+    @group(2) @binding(0) var<storage, read_write> values: array<f32>;
+    @group(3) @binding(0) var<uniform> unused_bias: f32;
+
+    @compute @workgroup_size(64)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        values[id.x] = values[id.x] * scale;
+    }
+  "};
+  // The unused bindings of group 1 are kept, since group 2 is still in use.
+  assert_bindgen_output(
+    "strip_unused_bindings",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source("scale", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .strip_unused_bindings(true)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_push_constant_limits() -> Result<()> {
+  let camera = indoc::indoc! {"
+    struct Camera {
+        view_proj: mat4x4<f32>,
+    };
+
+    var<push_constant> camera: Camera;
+
+    @vertex
+    fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+        return camera.view_proj * vec4(position, 1.0);
+    }
+  "};
+  let material = indoc::indoc! {"
+    struct Material {
+        transform: mat4x4<f32>,
+        color: vec4<f32>,
+    };
+
+    var<push_constant> material: Material;
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+        return material.transform * material.color;
+    }
+  "};
+  let bindgen = |source: String, max_push_constant_size: Option<u32>| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source("push", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .ir_capabilities(naga::valid::Capabilities::PUSH_CONSTANT)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    if let Some(size) = max_push_constant_size {
+      builder.max_push_constant_size(size);
+    }
+    builder.build()?.generate_string()
+  };
+  let code =
+    |err: &WgslBindgenError| miette::Diagnostic::code(err).map(|code| code.to_string());
+
+  assert!(bindgen(camera.to_string(), Some(128)).is_ok());
+
+  let err = bindgen(camera.to_string(), Some(32)).unwrap_err();
+  assert_eq!(Some("wgsl_bindgen::invalid_push_constants".to_string()), code(&err));
+  assert!(err.to_string().ends_with(
+    "`camera` of `Camera` (64 bytes) exceeds the `max_push_constant_size` of 32 bytes"
+  ));
+
+  // The range of the layout is sized after the first push constants, shared by all the stages.
+  let err = bindgen(format!("{camera}\n{material}"), None).unwrap_err();
+  assert_eq!(Some("wgsl_bindgen::invalid_push_constants".to_string()), code(&err));
+  assert!(err.to_string().ends_with(
+    "`material` of `Material` (80 bytes) used by the fragment stage exceeds the range 0..64 of the pipeline layout"
+  ));
+
+  Ok(())
+}
+
+#[test]
+fn test_workgroup_storage_limit() -> Result<()> {
+  let source = indoc::indoc! {"
+    var<workgroup> tile: array<vec4<f32>, 1024>;
+
+    @compute @workgroup_size(64)
+    fn blur(@builtin(local_invocation_index) index: u32) {
+        tile[index] = vec4(0.0);
+    }
+  "};
+  let builder = |max_size: u32| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source("blur", source)
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .max_compute_workgroup_storage_size(max_size)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    builder
+  };
+
+  assert_bindgen_output("workgroup_storage_limit", &mut builder(16384))?;
+
+  let err = builder(8192).build()?.generate_string().unwrap_err();
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::workgroup_storage_exceeded".to_string()), code);
+  assert!(err.to_string().ends_with(
+    "`blur` uses 16384 bytes of workgroup storage, over the `max_compute_workgroup_storage_size` of 8192 bytes: `tile` of `array<vec4<f32>, 1024>` (16384 bytes)"
+  ));
+
+  Ok(())
+}
+
+#[test]
+fn test_uniform_binding_size_limit() -> Result<()> {
+  let builder = || {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source(
+        "settings",
+        indoc::indoc! {"
+          struct Settings {
+              exposure: f32,
+              curve: array<vec4<f32>, 32>,
+          };
+
+          @group(0) @binding(0) var<uniform> settings: Settings;
+
+          @fragment
+          fn fs_main() -> @location(0) vec4<f32> {
+              return settings.curve[0] * settings.exposure;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .max_uniform_buffer_binding_size(256)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    builder
+  };
+  let message = "tests/shaders/settings.wgsl: uniform binding `settings` at @group(0) @binding(0) of `Settings` takes 528 bytes, over the `max_uniform_buffer_binding_size` of 256 bytes; bind it as `var<storage, read>` instead";
+
+  let (_, report) = builder()
+    .build()?
+    .generate_string_with_report()
+    .into_diagnostic()?;
+  let diagnostics = report
+    .diagnostics()
+    .filter(|d| d.kind == WgslDiagnosticKind::UniformBindingTooLarge)
+    .map(ToString::to_string)
+    .collect::<Vec<_>>();
+  assert_eq!(vec![message], diagnostics);
+
+  let err = builder()
+    .deny_diagnostic(WgslDiagnosticKind::UniformBindingTooLarge)
+    .build()?
+    .generate_string()
+    .expect_err("the oversized uniform binding should be denied");
+  assert_eq!(
+    format!("Generation reported denied diagnostics:\n{message}"),
+    err.to_string()
+  );
+  Ok(())
+}
+
+#[test]
+fn test_inferred_ir_capabilities() -> Result<()> {
+  let builder = |deny_ir_capabilities| {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point_source(
+        "constants",
+        indoc::indoc! {"
+          struct Constants {
+              color: vec4<f32>,
+          };
+
+          var<push_constant> constants: Constants;
+
+          @fragment
+          fn fs_main() -> @location(0) vec4<f32> {
+              return constants.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .deny_ir_capabilities(deny_ir_capabilities)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+    builder
+  };
+
+  // The push constants enable their capability without `ir_capabilities`.
+  assert_bindgen_output(
+    "inferred_ir_capabilities",
+    &mut builder(naga::valid::Capabilities::empty()),
+  )?;
+
+  let err = builder(naga::valid::Capabilities::PUSH_CONSTANT)
+    .build()?
+    .generate_string()
+    .unwrap_err();
+  assert!(format!("{err:?}").contains("PUSH_CONSTANT"));
+  Ok(())
+}
+
+#[test]
+fn test_naming_conventions() -> Result<()> {
+  assert_bindgen_output(
+    "naming_conventions",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point_source(
+        "LightPass",
+        indoc::indoc! {"
+          struct light_data {
+              color: vec4<f32>,
+          };
+  
+          @group(0) @binding(0) var<uniform> light: light_data;
+  
+          @fragment
+          fn fs_main() -> @location(0) vec4<f32> {
+              return light.color;
+          }
+        "},
+      )
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .module_name_case(WgslNameCase::SnakeCase)
+      .struct_name_case(WgslNameCase::PascalCase)
+      .entry_point_const_prefix("SHADER_ENTRY_")
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_shader_entry_derives() -> Result<()> {
+  assert_bindgen_output(
+    "shader_entry_derives",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/minimal.wgsl")
+      .workspace_root("tests/shaders")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .shader_entry_derive_serde(true)
+      .shader_entry_derive_strum(true),
+  )
+}
+
+#[test]
+fn test_enable_directives_map_to_features() -> Result<()> {
+  assert_bindgen_output(
+    "enable_directives_map_to_features",
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("tests/shaders/directives/enable.wgsl")
+      .workspace_root("tests/shaders/directives")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true),
+  )
+}
+
+#[test]
+fn test_error_codes() -> Result<()> {
+  let err = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/minimal.wgsl")
+    .workspace_root("tests/shaders")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .build()?
+    .generate()
+    .unwrap_err();
+
+  let code = miette::Diagnostic::code(&err).map(|code| code.to_string());
+  assert_eq!(Some("wgsl_bindgen::output_not_specified".to_string()), code);
+  Ok(())
+}
+
 #[test]
 #[ignore = "It doesn't like path symbols inside a nested type like array."]
 fn test_path_import() -> Result<()> {
@@ -58,6 +58,7 @@ fn build_bevy_deptree() -> DependencyTree {
       SourceFilePath::new("tests/shaders/bevy_pbr_wgsl/wireframe.wgsl"),
     ],
     vec![],
+    vec![],
   )
   .into_diagnostic()
   .expect("build_bevy_deptree error")
@@ -140,3 +141,55 @@ fn test_bevy_mesh_wgsl_dependency_order() {
     ]
   );
 }
+
+#[test]
+fn test_bevy_dependency_queries() {
+  let deptree = build_bevy_deptree();
+  let path =
+    |name: &str| SourceFilePath::new(format!("tests/shaders/bevy_pbr_wgsl/{name}"));
+
+  let entry_points = deptree.entry_points().iter().cloned().collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      path("mesh.wgsl"),
+      path("output_VERTEX_UVS.wgsl"),
+      path("pbr.wgsl"),
+      path("wireframe.wgsl"),
+    ],
+    entry_points
+  );
+
+  let skinning = deptree.source_file(&path("skinning.wgsl")).unwrap();
+  assert_eq!("bevy_pbr::skinning", skinning.module_name.as_ref().unwrap().as_str());
+  assert!(deptree.source_file(&path("missing.wgsl")).is_none());
+
+  let wireframe = path("wireframe.wgsl");
+  let wireframe_imports = deptree
+    .import_edges()
+    .into_iter()
+    .filter(|(importer, _)| **importer == wireframe)
+    .map(|(_, imported)| imported.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    vec![
+      path("mesh_types.wgsl"),
+      path("mesh_view_bindings.wgsl"),
+      path("skinning.wgsl"),
+      path("mesh_functions.wgsl"),
+    ],
+    wireframe_imports
+  );
+
+  assert_eq!(
+    indexset![wireframe.clone()],
+    deptree.entry_points_depending_on(&path("skinning.wgsl"))
+  );
+  assert_eq!(
+    indexset![path("mesh.wgsl"), path("pbr.wgsl"), wireframe],
+    deptree.entry_points_depending_on(&path("mesh_view_types.wgsl"))
+  );
+  assert_eq!(
+    indexset![path("pbr.wgsl")],
+    deptree.entry_points_depending_on(&path("pbr.wgsl"))
+  );
+}
@@ -0,0 +1,756 @@
+#![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderEntry {
+    Simulation,
+}
+impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Simulation];
+    pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+        match *self {
+            Self::Simulation => simulation::create_pipeline_layout(device),
+        }
+    }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Simulation => simulation::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Simulation => &[(wgpu::ShaderStages::COMPUTE, simulation::ENTRY_MAIN)],
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Simulation => 1,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Simulation => vec![],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Simulation => 0,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Simulation => simulation::RESOURCES,
+        }
+    }
+    pub fn create_shader_module_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match *self {
+            Self::Simulation => simulation::create_shader_module_embed_source(device),
+        }
+    }
+    pub fn create_compute_pipelines_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> Vec<wgpu::ComputePipeline> {
+        let entry_points: &[&str] = match *self {
+            Self::Simulation => &[simulation::ENTRY_MAIN],
+        };
+        if entry_points.is_empty() {
+            return Vec::new();
+        }
+        let module = self.create_shader_module_embed_source(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipelines = entry_points
+            .iter()
+            .map(|entry_point| {
+                device
+                    .create_compute_pipeline(
+                        &wgpu::ComputePipelineDescriptor {
+                            label: Some(&format!("Compute Pipeline {entry_point}")),
+                            layout: Some(&layout),
+                            module: &module,
+                            entry_point: Some(entry_point),
+                            compilation_options: Default::default(),
+                            cache: None,
+                        },
+                    )
+            })
+            .collect();
+        pipelines
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "simulation.wgsl" => Some(Self::Simulation),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Simulation => "simulation.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Simulation => simulation::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Simulation => "simulation",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simulation" => Ok(Self::Simulation),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
+}
+mod _root {
+    pub use super::*;
+}
+pub mod layout_asserts {
+    use super::{_root, _root::*};
+    const WGSL_BASE_TYPE_ASSERTS: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+    const SIMULATION_PARTICLE_ASSERTS: () = {
+        assert!(std::mem::offset_of!(simulation::Particle, position) == 0);
+        assert!(std::mem::offset_of!(simulation::Particle, mass) == 12);
+        assert!(std::mem::size_of:: < simulation::Particle > () == 16);
+    };
+}
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    /// A storage buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct StorageBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> StorageBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                            | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// Reads back the contents of `buffer`, which needs the `COPY_SRC` usage, by copying it to a
+    /// staging buffer mapped for reading. Blocks while polling the device until the mapping
+    /// finishes, so it isn't available on the web, where buffers are mapped asynchronously.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_buffer(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<std::vec::Vec<u8>, wgpu::BufferAsyncError> {
+        let staging = device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("read_buffer"),
+                    size: buffer.size(),
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            );
+        let mut encoder = device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+        queue.submit([encoder.finish()]);
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice
+            .map_async(
+                wgpu::MapMode::Read,
+                move |result| {
+                    let _ = sender.send(result);
+                },
+            );
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+        let bytes = slice.get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(bytes)
+    }
+}
+pub mod simulation {
+    use super::{_root, _root::*};
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Particle {
+        /// size: 12, offset: 0x0, type: `vec3<f32>`
+        pub position: glam::Vec3A,
+        pub _pad_position: [u8; 0xC - core::mem::size_of::<glam::Vec3A>()],
+        /// size: 4, offset: 0xC, type: `f32`
+        pub mass: f32,
+    }
+    impl Particle {
+        pub const fn new(position: glam::Vec3A, mass: f32) -> Self {
+            Self {
+                position,
+                _pad_position: [0; 0xC - core::mem::size_of::<glam::Vec3A>()],
+                mass,
+            }
+        }
+    }
+    impl Particle {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Particle {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "position",
+                    std::mem::offset_of!(Self, position),
+                    std::mem::size_of::<glam::Vec3A>(),
+                    0,
+                    12,
+                    12,
+                ),
+                (
+                    "mass",
+                    std::mem::offset_of!(Self, mass),
+                    std::mem::size_of::<f32>(),
+                    12,
+                    4,
+                    4,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`simulation::Particle` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct ParticleInit {
+        pub position: glam::Vec3A,
+        pub mass: f32,
+    }
+    impl ParticleInit {
+        pub const fn build(&self) -> Particle {
+            Particle {
+                position: self.position,
+                _pad_position: [0; 0xC - core::mem::size_of::<glam::Vec3A>()],
+                mass: self.mass,
+            }
+        }
+    }
+    impl From<ParticleInit> for Particle {
+        fn from(data: ParticleInit) -> Self {
+            data.build()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesParams<'a> {
+        pub particles: wgpu::BufferBinding<'a>,
+        pub normals: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup0Entries<'a> {
+        pub particles: wgpu::BindGroupEntry<'a>,
+        pub normals: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup0Entries<'a> {
+        pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+            Self {
+                particles: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.particles),
+                },
+                normals: wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Buffer(params.normals),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 2] {
+            [self.particles, self.normals]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0(wgpu::BindGroup);
+    impl WgpuBindGroup0 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("Simulation::BindGroup0::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "particles"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage {
+                            read_only: false,
+                        },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                /// @binding(1): "normals"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage {
+                            read_only: false,
+                        },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Simulation::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
+        }
+        pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
+            render_pass.set_bind_group(0, &self.0, &[]);
+        }
+    }
+    #[derive(Debug, Copy, Clone)]
+    pub struct WgpuBindGroups<'a> {
+        pub bind_group0: &'a WgpuBindGroup0,
+    }
+    impl<'a> WgpuBindGroups<'a> {
+        pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
+            self.bind_group0.set(pass);
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut wgpu::ComputePass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_particles(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<std::vec::Vec<_root::simulation::Particle>, wgpu::BufferAsyncError> {
+        let bytes = _root::_support::read_buffer(device, queue, buffer)?;
+        Ok(decode_particles(&bytes))
+    }
+    /// Encodes `values` as the bytes of the binding, padding each element to the WGSL stride.
+    pub fn encode_particles(
+        values: &[_root::simulation::Particle],
+    ) -> std::vec::Vec<u8> {
+        let size = std::mem::size_of::<_root::simulation::Particle>();
+        assert!(
+            size <= 16,
+            "the elements of `particles` are larger than their WGSL stride of 16 bytes"
+        );
+        let mut bytes = std::vec![0; values.len() * 16];
+        for (chunk, value) in bytes.chunks_exact_mut(16).zip(values) {
+            chunk[..size].copy_from_slice(bytemuck::bytes_of(value));
+        }
+        bytes
+    }
+    /// Decodes the elements of the binding from `bytes`, skipping the padding after each
+    /// element.
+    pub fn decode_particles(bytes: &[u8]) -> std::vec::Vec<_root::simulation::Particle> {
+        let size = std::mem::size_of::<_root::simulation::Particle>();
+        assert!(
+            size <= 16,
+            "the elements of `particles` are larger than their WGSL stride of 16 bytes"
+        );
+        bytes
+            .chunks_exact(16)
+            .map(|chunk| bytemuck::pod_read_unaligned(&chunk[..size]))
+            .collect()
+    }
+    /// Encodes `values` as the bytes of the binding, padding each element to the WGSL stride.
+    pub fn encode_normals(values: &[glam::Vec3A]) -> std::vec::Vec<u8> {
+        let size = std::mem::size_of::<glam::Vec3A>();
+        assert!(
+            size <= 16,
+            "the elements of `normals` are larger than their WGSL stride of 16 bytes"
+        );
+        let mut bytes = std::vec![0; values.len() * 16];
+        for (chunk, value) in bytes.chunks_exact_mut(16).zip(values) {
+            chunk[..size].copy_from_slice(bytemuck::bytes_of(value));
+        }
+        bytes
+    }
+    /// Decodes the elements of the binding from `bytes`, skipping the padding after each
+    /// element.
+    pub fn decode_normals(bytes: &[u8]) -> std::vec::Vec<glam::Vec3A> {
+        let size = std::mem::size_of::<glam::Vec3A>();
+        assert!(
+            size <= 16,
+            "the elements of `normals` are larger than their WGSL stride of 16 bytes"
+        );
+        bytes
+            .chunks_exact(16)
+            .map(|chunk| bytemuck::pod_read_unaligned(&chunk[..size]))
+            .collect()
+    }
+    /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+    pub const fn particles_min_buffer_size(n: usize) -> u64 {
+        n as u64 * 16
+    }
+    pub fn create_particles_buffer_for(device: &wgpu::Device, n: usize) -> wgpu::Buffer {
+        device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("Simulation::particles"),
+                    size: particles_min_buffer_size(n),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            )
+    }
+    /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+    pub const fn normals_min_buffer_size(n: usize) -> u64 {
+        n as u64 * 16
+    }
+    pub fn create_normals_buffer_for(device: &wgpu::Device, n: usize) -> wgpu::Buffer {
+        device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("Simulation::normals"),
+                    size: normals_min_buffer_size(n),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            )
+    }
+    pub mod compute {
+        pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+        pub const MAIN_WORKGROUP_STORAGE_SIZE: u32 = 0;
+        pub fn create_main_pipeline_embed_source(
+            device: &wgpu::Device,
+        ) -> wgpu::ComputePipeline {
+            let module = super::create_shader_module_embed_source(device);
+            let layout = super::create_pipeline_layout(device);
+            device
+                .create_compute_pipeline(
+                    &wgpu::ComputePipelineDescriptor {
+                        label: Some("Compute Pipeline main"),
+                        layout: Some(&layout),
+                        module: &module,
+                        entry_point: Some("main"),
+                        compilation_options: Default::default(),
+                        cache: None,
+                    },
+                )
+        }
+    }
+    pub const ENTRY_MAIN: &str = "main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        max_storage_buffers_per_shader_stage: 2,
+        max_storage_buffer_binding_size: 16,
+        max_compute_invocations_per_workgroup: 64,
+        max_compute_workgroup_size_x: 64,
+        max_compute_workgroup_size_y: 1,
+        max_compute_workgroup_size_z: 1,
+        max_compute_workgroups_per_dimension: 1,
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 0,
+        uniform_bytes: 0,
+        storage_buffers: 2,
+        textures_1d: 0,
+        textures_2d: 0,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 0,
+        push_constant_size: 0,
+    };
+    #[derive(Debug)]
+    pub struct WgpuPipelineLayout;
+    impl WgpuPipelineLayout {
+        pub fn bind_group_layout_entries(
+            entries: [wgpu::BindGroupLayout; 1],
+        ) -> [wgpu::BindGroupLayout; 1] {
+            entries
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("Simulation::PipelineLayout"),
+                    bind_group_layouts: &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                    ],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    pub const SOURCE_HASH: &str = "c50fdd9cc0e011e8d2c08886a4d8790c567eae4c25d88aadf75269f1d66baf23";
+    pub const SOURCE_PATH: &str = "tests/shaders/simulation.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &["simulation.wgsl"];
+    pub fn create_shader_module_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("simulation.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    pub const SHADER_STRING: &'static str = r#"
+struct Particle {
+    position: vec3<f32>,
+    mass: f32,
+}
+
+@group(0) @binding(0) 
+var<storage, read_write> particles: array<Particle>;
+@group(0) @binding(1) 
+var<storage, read_write> normals: array<vec3<f32>>;
+
+@compute @workgroup_size(64, 1, 1) 
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let _e8 = normals[id.x];
+    let _e13 = particles[id.x].mass;
+    let _e15 = particles[id.x].position;
+    particles[id.x].position = (_e15 + (_e8 * _e13));
+    return;
+}
+"#;
+}
+pub mod bytemuck_impls {
+    use super::{_root, _root::*};
+    unsafe impl bytemuck::Zeroable for simulation::Particle {}
+    unsafe impl bytemuck::Pod for simulation::Particle {}
+}
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 1;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        simulation::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Simulation, 0) => Some(0),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,837 @@
+#![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderEntry {
+    Sprite,
+}
+impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Sprite];
+    pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+        match *self {
+            Self::Sprite => sprite::create_pipeline_layout(device),
+        }
+    }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Sprite => sprite::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Sprite => {
+                &[
+                    (wgpu::ShaderStages::VERTEX, sprite::ENTRY_VS_MAIN),
+                    (wgpu::ShaderStages::FRAGMENT, sprite::ENTRY_FS_MAIN),
+                ]
+            }
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Sprite => 1,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Sprite => vec![sprite::VertexInput::vertex_buffer_layout(step_mode)],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Sprite => 0,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Sprite => sprite::RESOURCES,
+        }
+    }
+    pub fn create_shader_module_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match *self {
+            Self::Sprite => sprite::create_shader_module_embed_source(device),
+        }
+    }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_embed_source<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Option<wgpu::RenderPipeline> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return None;
+        }
+        let module = self.create_shader_module_embed_source(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Some(pipeline)
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "sprite.wgsl" => Some(Self::Sprite),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Sprite => "sprite.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Sprite => sprite::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Sprite => "sprite",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sprite" => Ok(Self::Sprite),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
+}
+#[cfg(feature = "bevy")]
+impl ShaderEntry {
+    /// The handle of the shader of the entry in Bevy's `Assets<Shader>`.
+    pub const fn shader_handle(
+        &self,
+    ) -> ::bevy::asset::Handle<::bevy::render::render_resource::Shader> {
+        match *self {
+            Self::Sprite => sprite::bevy_helpers::SHADER_HANDLE,
+        }
+    }
+    /// Adds the embedded shader of each entry to Bevy's `Assets<Shader>`, under its
+    /// `shader_handle`.
+    pub fn add_shaders(
+        shaders: &mut ::bevy::asset::Assets<::bevy::render::render_resource::Shader>,
+    ) {
+        shaders
+            .insert(
+                &sprite::bevy_helpers::SHADER_HANDLE,
+                ::bevy::render::render_resource::Shader::from_wgsl(
+                    sprite::SHADER_STRING,
+                    sprite::SOURCE_PATH,
+                ),
+            );
+    }
+}
+mod _root {
+    pub use super::*;
+}
+pub mod layout_asserts {
+    use super::{_root, _root::*};
+    const WGSL_BASE_TYPE_ASSERTS: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+    const SPRITE_MATERIAL_ASSERTS: () = {
+        assert!(std::mem::offset_of!(sprite::Material, color) == 0);
+        assert!(std::mem::size_of:: < sprite::Material > () == 16);
+    };
+}
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    /// The entry point and buffers of a [`VertexEntry`] without its pipeline constants, so it
+    /// can be created in a const fn.
+    #[derive(Debug)]
+    pub struct ConstVertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    impl<const N: usize> ConstVertexEntry<N> {
+        /// A [`VertexEntry`] with these buffers and the pipeline-overridable `constants`.
+        pub fn to_entry(
+            &self,
+            constants: std::collections::HashMap<String, f64>,
+        ) -> VertexEntry<N> {
+            VertexEntry {
+                entry_point: self.entry_point,
+                buffers: self.buffers.clone(),
+                constants,
+            }
+        }
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: Some(entry.entry_point),
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub targets: [Option<wgpu::ColorTargetState>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    pub fn fragment_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntry<N>,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: Some(entry.entry_point),
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    /// A uniform buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct UniformBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> UniformBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+}
+pub mod sprite {
+    use super::{_root, _root::*};
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct VertexInput {
+        pub position: glam::Vec3A,
+    }
+    impl VertexInput {
+        pub const fn new(position: glam::Vec3A) -> Self {
+            Self { position }
+        }
+    }
+    impl VertexInput {
+        pub fn from_parts(position: impl Into<glam::Vec3A>) -> Self {
+            Self { position: position.into() }
+        }
+    }
+    impl VertexInput {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl VertexInput {
+        pub const ATTRIBUTE_COUNT: usize = 1;
+        pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x3,
+                offset: std::mem::offset_of!(Self, position) as u64,
+                shader_location: 0,
+            },
+        ];
+        pub const fn vertex_buffer_layout(
+            step_mode: wgpu::VertexStepMode,
+        ) -> wgpu::VertexBufferLayout<'static> {
+            wgpu::VertexBufferLayout {
+                array_stride: Self::STRIDE,
+                step_mode,
+                attributes: &Self::VERTEX_ATTRIBUTES,
+            }
+        }
+    }
+    const VERTEX_INPUT_LAYOUT_ASSERTS: () = {
+        assert!(
+            std::mem::size_of:: < VertexInput > () % 4 == 0,
+            "the array stride of `sprite::VertexInput` must be a multiple of 4 bytes"
+        );
+        assert!(
+            std::mem::size_of:: < VertexInput > () <= 2048,
+            "the array stride of `sprite::VertexInput` must be at most 2048 bytes"
+        );
+        assert!(
+            std::mem::offset_of!(VertexInput, position) % 4 == 0,
+            "`sprite::VertexInput::position` must be aligned to 4 bytes in the vertex buffer"
+        );
+        assert!(
+            std::mem::offset_of!(VertexInput, position) + 12 <= std::mem::size_of:: <
+            VertexInput > (),
+            "`sprite::VertexInput::position` must be read within the array stride"
+        );
+    };
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Material {
+        /// size: 16, offset: 0x0, type: `vec4<f32>`
+        pub color: glam::Vec4,
+    }
+    impl Material {
+        pub const fn new(color: glam::Vec4) -> Self {
+            Self { color }
+        }
+    }
+    impl Material {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Material {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color",
+                    std::mem::offset_of!(Self, color),
+                    std::mem::size_of::<glam::Vec4>(),
+                    0,
+                    16,
+                    16,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`sprite::Material` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesParams<'a> {
+        pub material: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup0Entries<'a> {
+        pub material: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup0Entries<'a> {
+        pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+            Self {
+                material: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.material),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 1] {
+            [self.material]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0(wgpu::BindGroup);
+    impl WgpuBindGroup0 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("Sprite::BindGroup0::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "material"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::sprite::Material>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Sprite::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
+        }
+        pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
+            render_pass.set_bind_group(0, &self.0, &[]);
+        }
+    }
+    #[derive(Debug, Copy, Clone)]
+    pub struct WgpuBindGroups<'a> {
+        pub bind_group0: &'a WgpuBindGroup0,
+    }
+    impl<'a> WgpuBindGroups<'a> {
+        pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+            self.bind_group0.set(pass);
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    pub fn create_material_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::sprite::Material,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Sprite::material"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_material(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::sprite::Material,
+    ) {
+        write_material_at(queue, buffer, 0, value)
+    }
+    pub fn write_material_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::sprite::Material,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_material_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::sprite::Material],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Sprite::material"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 1,
+        uniform_bytes: 16,
+        storage_buffers: 0,
+        textures_1d: 0,
+        textures_2d: 0,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 0,
+        push_constant_size: 0,
+    };
+    pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
+    pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
+            constants: Default::default(),
+        }
+    }
+    /// `vs_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes.
+    pub fn vs_main_entry_default() -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+            constants: Default::default(),
+        }
+    }
+    /// The buffers of `vs_main_entry_default` in a const fn, eg: to keep them in a static.
+    pub const fn vs_main_const_entry() -> ConstVertexEntry<1> {
+        ConstVertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+        }
+    }
+    pub use _root::_support::{FragmentEntry, fragment_state};
+    pub fn fs_main_entry(
+        targets: [Option<wgpu::ColorTargetState>; 1],
+    ) -> FragmentEntry<1> {
+        FragmentEntry {
+            entry_point: ENTRY_FS_MAIN,
+            targets,
+            constants: Default::default(),
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuPipelineLayout;
+    impl WgpuPipelineLayout {
+        pub fn bind_group_layout_entries(
+            entries: [wgpu::BindGroupLayout; 1],
+        ) -> [wgpu::BindGroupLayout; 1] {
+            entries
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("Sprite::PipelineLayout"),
+                    bind_group_layouts: &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                    ],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    pub const SOURCE_HASH: &str = "44af8e7e328154a6b2524bec67fb470165d66f6d776b3d030d23fb8219f98f6e";
+    pub const SOURCE_PATH: &str = "tests/shaders/sprite.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &["sprite.wgsl"];
+    pub fn create_shader_module_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("sprite.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    pub const SHADER_STRING: &'static str = r#"
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+}
+
+struct Material {
+    color: vec4<f32>,
+}
+
+@group(0) @binding(0) 
+var<uniform> material: Material;
+
+@vertex 
+fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(in.position, 1f);
+}
+
+@fragment 
+fn fs_main() -> @location(0) vec4<f32> {
+    let _e2 = material.color;
+    return _e2;
+}
+"#;
+    #[cfg(feature = "bevy")]
+    pub mod bevy_helpers {
+        use super::{_root, _root::*};
+        /// The handle of the shader in Bevy's `Assets<Shader>`.
+        pub const SHADER_HANDLE: ::bevy::asset::Handle<
+            ::bevy::render::render_resource::Shader,
+        > = ::bevy::asset::Handle::weak_from_u128(0x618414c4a09c133589518f83c2ad87ad);
+        /// The layout entries of `@group(0)`, eg: to implement `AsBindGroup::bind_group_layout_entries`.
+        pub fn bind_group_layout_entries_0() -> Vec<
+            ::bevy::render::render_resource::BindGroupLayoutEntry,
+        > {
+            super::WgpuBindGroup0::LAYOUT_DESCRIPTOR.entries.to_vec()
+        }
+        pub fn vs_main_vertex_state(
+            vertex_input: wgpu::VertexStepMode,
+            shader_defs: Vec<::bevy::render::render_resource::ShaderDefVal>,
+        ) -> ::bevy::render::render_resource::VertexState {
+            let buffers: &[wgpu::VertexBufferLayout<'static>] = &[
+                _root::sprite::VertexInput::vertex_buffer_layout(vertex_input),
+            ];
+            ::bevy::render::render_resource::VertexState {
+                shader: SHADER_HANDLE,
+                shader_defs,
+                entry_point: super::ENTRY_VS_MAIN.into(),
+                buffers: buffers
+                    .iter()
+                    .map(|layout| ::bevy::render::render_resource::VertexBufferLayout {
+                        array_stride: layout.array_stride,
+                        step_mode: layout.step_mode,
+                        attributes: layout.attributes.to_vec(),
+                    })
+                    .collect(),
+            }
+        }
+        pub fn fs_main_fragment_state(
+            targets: Vec<Option<::bevy::render::render_resource::ColorTargetState>>,
+            shader_defs: Vec<::bevy::render::render_resource::ShaderDefVal>,
+        ) -> ::bevy::render::render_resource::FragmentState {
+            ::bevy::render::render_resource::FragmentState {
+                shader: SHADER_HANDLE,
+                shader_defs,
+                entry_point: super::ENTRY_FS_MAIN.into(),
+                targets,
+            }
+        }
+    }
+}
+pub mod bytemuck_impls {
+    use super::{_root, _root::*};
+    unsafe impl bytemuck::Zeroable for sprite::VertexInput {}
+    unsafe impl bytemuck::Pod for sprite::VertexInput {}
+    unsafe impl bytemuck::Zeroable for sprite::Material {}
+    unsafe impl bytemuck::Pod for sprite::Material {}
+}
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 1;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        sprite::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Sprite, 0) => Some(0),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,1120 @@
+#![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderEntry {
+    Scene,
+}
+impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Scene];
+    pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+        match *self {
+            Self::Scene => scene::create_pipeline_layout(device),
+        }
+    }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Scene => scene::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Scene => {
+                &[
+                    (wgpu::ShaderStages::VERTEX, scene::ENTRY_VS_MAIN),
+                    (wgpu::ShaderStages::FRAGMENT, scene::ENTRY_FS_MAIN),
+                ]
+            }
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Scene => 2,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Scene => vec![scene::VsMainInput::vertex_buffer_layout(step_mode)],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Scene => 0,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Scene => scene::RESOURCES,
+        }
+    }
+    pub fn create_shader_module_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match *self {
+            Self::Scene => scene::create_shader_module_embed_source(device),
+        }
+    }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_embed_source<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Option<wgpu::RenderPipeline> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return None;
+        }
+        let module = self.create_shader_module_embed_source(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Some(pipeline)
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "scene.wgsl" => Some(Self::Scene),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Scene => "scene.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Scene => scene::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Scene => "scene",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scene" => Ok(Self::Scene),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
+}
+mod _root {
+    pub use super::*;
+}
+pub mod layout_asserts {
+    use super::{_root, _root::*};
+    const WGSL_BASE_TYPE_ASSERTS: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+    const SCENE_CAMERA_ASSERTS: () = {
+        assert!(std::mem::offset_of!(scene::Camera, view_proj) == 0);
+        assert!(std::mem::size_of:: < scene::Camera > () == 64);
+    };
+    const SCENE_MATERIAL_ASSERTS: () = {
+        assert!(std::mem::offset_of!(scene::Material, color) == 0);
+        assert!(std::mem::offset_of!(scene::Material, roughness) == 16);
+        assert!(std::mem::size_of:: < scene::Material > () == 32);
+    };
+}
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    /// The entry point and buffers of a [`VertexEntry`] without its pipeline constants, so it
+    /// can be created in a const fn.
+    #[derive(Debug)]
+    pub struct ConstVertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    impl<const N: usize> ConstVertexEntry<N> {
+        /// A [`VertexEntry`] with these buffers and the pipeline-overridable `constants`.
+        pub fn to_entry(
+            &self,
+            constants: std::collections::HashMap<String, f64>,
+        ) -> VertexEntry<N> {
+            VertexEntry {
+                entry_point: self.entry_point,
+                buffers: self.buffers.clone(),
+                constants,
+            }
+        }
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: Some(entry.entry_point),
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub targets: [Option<wgpu::ColorTargetState>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    pub fn fragment_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntry<N>,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: Some(entry.entry_point),
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    /// A uniform buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct UniformBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> UniformBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+}
+pub mod scene {
+    use super::{_root, _root::*};
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Camera {
+        /// size: 64, offset: 0x0, type: `mat4x4<f32>`
+        pub view_proj: glam::Mat4,
+    }
+    impl Camera {
+        pub const fn new(view_proj: glam::Mat4) -> Self {
+            Self { view_proj }
+        }
+    }
+    impl Camera {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Camera {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "view_proj",
+                    std::mem::offset_of!(Self, view_proj),
+                    std::mem::size_of::<glam::Mat4>(),
+                    0,
+                    64,
+                    64,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`scene::Camera` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Material {
+        /// size: 16, offset: 0x0, type: `vec4<f32>`
+        pub color: glam::Vec4,
+        /// size: 4, offset: 0x10, type: `f32`
+        pub roughness: f32,
+        pub _pad_roughness: [u8; 0x10 - core::mem::size_of::<f32>()],
+    }
+    impl Material {
+        pub const fn new(color: glam::Vec4, roughness: f32) -> Self {
+            Self {
+                color,
+                roughness,
+                _pad_roughness: [0; 0x10 - core::mem::size_of::<f32>()],
+            }
+        }
+    }
+    impl Material {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Material {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color",
+                    std::mem::offset_of!(Self, color),
+                    std::mem::size_of::<glam::Vec4>(),
+                    0,
+                    16,
+                    16,
+                ),
+                (
+                    "roughness",
+                    std::mem::offset_of!(Self, roughness),
+                    std::mem::size_of::<f32>(),
+                    16,
+                    4,
+                    16,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`scene::Material` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct MaterialInit {
+        pub color: glam::Vec4,
+        pub roughness: f32,
+    }
+    impl MaterialInit {
+        pub const fn build(&self) -> Material {
+            Material {
+                color: self.color,
+                roughness: self.roughness,
+                _pad_roughness: [0; 0x10 - core::mem::size_of::<f32>()],
+            }
+        }
+    }
+    impl From<MaterialInit> for Material {
+        fn from(data: MaterialInit) -> Self {
+            data.build()
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct VsMainInput {
+        pub position: glam::Vec3A,
+    }
+    impl VsMainInput {
+        pub const fn new(position: glam::Vec3A) -> Self {
+            Self { position }
+        }
+    }
+    impl VsMainInput {
+        pub fn from_parts(position: impl Into<glam::Vec3A>) -> Self {
+            Self { position: position.into() }
+        }
+    }
+    impl VsMainInput {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl VsMainInput {
+        pub const ATTRIBUTE_COUNT: usize = 1;
+        pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x3,
+                offset: std::mem::offset_of!(Self, position) as u64,
+                shader_location: 0,
+            },
+        ];
+        pub const fn vertex_buffer_layout(
+            step_mode: wgpu::VertexStepMode,
+        ) -> wgpu::VertexBufferLayout<'static> {
+            wgpu::VertexBufferLayout {
+                array_stride: Self::STRIDE,
+                step_mode,
+                attributes: &Self::VERTEX_ATTRIBUTES,
+            }
+        }
+    }
+    const VS_MAIN_INPUT_LAYOUT_ASSERTS: () = {
+        assert!(
+            std::mem::size_of:: < VsMainInput > () % 4 == 0,
+            "the array stride of `scene::VsMainInput` must be a multiple of 4 bytes"
+        );
+        assert!(
+            std::mem::size_of:: < VsMainInput > () <= 2048,
+            "the array stride of `scene::VsMainInput` must be at most 2048 bytes"
+        );
+        assert!(
+            std::mem::offset_of!(VsMainInput, position) % 4 == 0,
+            "`scene::VsMainInput::position` must be aligned to 4 bytes in the vertex buffer"
+        );
+        assert!(
+            std::mem::offset_of!(VsMainInput, position) + 12 <= std::mem::size_of:: <
+            VsMainInput > (),
+            "`scene::VsMainInput::position` must be read within the array stride"
+        );
+    };
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesParams<'a> {
+        pub camera: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup0Entries<'a> {
+        pub camera: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup0Entries<'a> {
+        pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+            Self {
+                camera: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.camera),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 1] {
+            [self.camera]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0(wgpu::BindGroup);
+    impl WgpuBindGroup0 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scene::BindGroup0::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "camera"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::scene::Camera>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Scene::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
+        }
+        pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
+            render_pass.set_bind_group(0, &self.0, &[]);
+        }
+    }
+    /// The values of the uniform buffers of [`WgpuBindGroup0Bundle`].
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct WgpuBindGroup0Values {
+        pub camera: _root::scene::Camera,
+    }
+    /// The bind group together with the uniform buffers it binds, eg: for a material.
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0Bundle {
+        pub bind_group: WgpuBindGroup0,
+        pub camera: wgpu::Buffer,
+    }
+    impl WgpuBindGroup0Bundle {
+        /// Creates the uniform buffers initialized with `values`, and the bind group binding
+        /// them with the other resources.
+        pub fn new(device: &wgpu::Device, values: &WgpuBindGroup0Values) -> Self {
+            let camera = create_camera_buffer_init(device, &values.camera);
+            let bind_group = WgpuBindGroup0::from_bindings(
+                device,
+                WgpuBindGroup0Entries::new(WgpuBindGroup0EntriesParams {
+                    camera: camera.as_entire_buffer_binding(),
+                }),
+            );
+            Self { bind_group, camera }
+        }
+        /// Writes `values` to the uniform buffers.
+        pub fn update(&self, queue: &wgpu::Queue, values: &WgpuBindGroup0Values) {
+            write_camera(queue, &self.camera, &values.camera);
+        }
+        pub fn set<'a>(&self, pass: &mut wgpu::RenderPass<'a>) {
+            self.bind_group.set(pass);
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup1EntriesParams<'a> {
+        pub material: wgpu::BufferBinding<'a>,
+        pub albedo: &'a wgpu::TextureView,
+        pub albedo_sampler: &'a wgpu::Sampler,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup1Entries<'a> {
+        pub material: wgpu::BindGroupEntry<'a>,
+        pub albedo: wgpu::BindGroupEntry<'a>,
+        pub albedo_sampler: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup1Entries<'a> {
+        pub fn new(params: WgpuBindGroup1EntriesParams<'a>) -> Self {
+            Self {
+                material: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.material),
+                },
+                albedo: wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(params.albedo),
+                },
+                albedo_sampler: wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(params.albedo_sampler),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 3] {
+            [self.material, self.albedo, self.albedo_sampler]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup1(wgpu::BindGroup);
+    impl WgpuBindGroup1 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("Scene::BindGroup1::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "material"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::scene::Material>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+                /// @binding(1): "albedo"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float {
+                            filterable: true,
+                        },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                /// @binding(2): "albedo_sampler"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup1Entries,
+        ) -> Self {
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Scene::BindGroup1",
+                    &bindings.as_array(),
+                ),
+            )
+        }
+        pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
+            render_pass.set_bind_group(1, &self.0, &[]);
+        }
+    }
+    /// The values of the uniform buffers of [`WgpuBindGroup1Bundle`].
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct WgpuBindGroup1Values {
+        pub material: _root::scene::Material,
+    }
+    /// The bind group together with the uniform buffers it binds, eg: for a material.
+    #[derive(Debug)]
+    pub struct WgpuBindGroup1Bundle {
+        pub bind_group: WgpuBindGroup1,
+        pub material: wgpu::Buffer,
+    }
+    impl WgpuBindGroup1Bundle {
+        /// Creates the uniform buffers initialized with `values`, and the bind group binding
+        /// them with the other resources.
+        pub fn new<'a>(
+            device: &wgpu::Device,
+            values: &WgpuBindGroup1Values,
+            albedo: &'a wgpu::TextureView,
+            albedo_sampler: &'a wgpu::Sampler,
+        ) -> Self {
+            let material = create_material_buffer_init(device, &values.material);
+            let bind_group = WgpuBindGroup1::from_bindings(
+                device,
+                WgpuBindGroup1Entries::new(WgpuBindGroup1EntriesParams {
+                    material: material.as_entire_buffer_binding(),
+                    albedo,
+                    albedo_sampler,
+                }),
+            );
+            Self { bind_group, material }
+        }
+        /// Writes `values` to the uniform buffers.
+        pub fn update(&self, queue: &wgpu::Queue, values: &WgpuBindGroup1Values) {
+            write_material(queue, &self.material, &values.material);
+        }
+        pub fn set<'a>(&self, pass: &mut wgpu::RenderPass<'a>) {
+            self.bind_group.set(pass);
+        }
+    }
+    #[derive(Debug, Copy, Clone)]
+    pub struct WgpuBindGroups<'a> {
+        pub bind_group0: &'a WgpuBindGroup0,
+        pub bind_group1: &'a WgpuBindGroup1,
+    }
+    impl<'a> WgpuBindGroups<'a> {
+        pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+            self.bind_group0.set(pass);
+            self.bind_group1.set(pass);
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group1: &'a WgpuBindGroup1,
+    ) {
+        bind_group0.set(pass);
+        bind_group1.set(pass);
+    }
+    pub fn create_camera_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::scene::Camera,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Scene::camera"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_camera(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::scene::Camera,
+    ) {
+        write_camera_at(queue, buffer, 0, value)
+    }
+    pub fn write_camera_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::scene::Camera,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_camera_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::scene::Camera],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Scene::camera"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn create_material_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::scene::Material,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Scene::material"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_material(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::scene::Material,
+    ) {
+        write_material_at(queue, buffer, 0, value)
+    }
+    pub fn write_material_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::scene::Material,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_material_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::scene::Material],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Scene::material"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn albedo_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Scene::albedo"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_albedo(texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Scene::albedo"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    aspect: wgpu::TextureAspect::All,
+                    ..Default::default()
+                },
+            )
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 2,
+        uniform_bytes: 96,
+        storage_buffers: 0,
+        textures_1d: 0,
+        textures_2d: 1,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 1,
+        push_constant_size: 0,
+    };
+    pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
+    pub fn vs_main_entry(vs_main_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VsMainInput::vertex_buffer_layout(vs_main_input)],
+            constants: Default::default(),
+        }
+    }
+    /// `vs_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes.
+    pub fn vs_main_entry_default() -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VsMainInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+            constants: Default::default(),
+        }
+    }
+    /// The buffers of `vs_main_entry_default` in a const fn, eg: to keep them in a static.
+    pub const fn vs_main_const_entry() -> ConstVertexEntry<1> {
+        ConstVertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VsMainInput::vertex_buffer_layout(wgpu::VertexStepMode::Vertex)],
+        }
+    }
+    pub use _root::_support::{FragmentEntry, fragment_state};
+    pub fn fs_main_entry(
+        targets: [Option<wgpu::ColorTargetState>; 1],
+    ) -> FragmentEntry<1> {
+        FragmentEntry {
+            entry_point: ENTRY_FS_MAIN,
+            targets,
+            constants: Default::default(),
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuPipelineLayout;
+    impl WgpuPipelineLayout {
+        pub fn bind_group_layout_entries(
+            entries: [wgpu::BindGroupLayout; 2],
+        ) -> [wgpu::BindGroupLayout; 2] {
+            entries
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("Scene::PipelineLayout"),
+                    bind_group_layouts: &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                        &WgpuBindGroup1::get_bind_group_layout(device),
+                    ],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    pub const SOURCE_HASH: &str = "9a886a5775866e333b53a19a845380eefbb6f806f4c8f2ccf2ab308a1df92040";
+    pub const SOURCE_PATH: &str = "tests/shaders/scene.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &["scene.wgsl"];
+    pub fn create_shader_module_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("scene.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    pub const SHADER_STRING: &'static str = r#"
+struct Camera {
+    view_proj: mat4x4<f32>,
+}
+
+struct Material {
+    color: vec4<f32>,
+    roughness: f32,
+}
+
+@group(0) @binding(0) 
+var<uniform> camera: Camera;
+@group(1) @binding(0) 
+var<uniform> material: Material;
+@group(1) @binding(1) 
+var albedo: texture_2d<f32>;
+@group(1) @binding(2) 
+var albedo_sampler: sampler;
+
+@vertex 
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    let _e2 = camera.view_proj;
+    return (_e2 * vec4<f32>(position, 1f));
+}
+
+@fragment 
+fn fs_main() -> @location(0) vec4<f32> {
+    let color = textureSample(albedo, albedo_sampler, vec2(0.5f));
+    let _e7 = material.color;
+    let _e11 = material.roughness;
+    return ((color * _e7) * _e11);
+}
+"#;
+}
+pub mod bytemuck_impls {
+    use super::{_root, _root::*};
+    unsafe impl bytemuck::Zeroable for scene::Camera {}
+    unsafe impl bytemuck::Pod for scene::Camera {}
+    unsafe impl bytemuck::Zeroable for scene::Material {}
+    unsafe impl bytemuck::Pod for scene::Material {}
+    unsafe impl bytemuck::Zeroable for scene::VsMainInput {}
+    unsafe impl bytemuck::Pod for scene::VsMainInput {}
+}
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 2;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        scene::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+        scene::WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Scene, 0) => Some(0),
+            (ShaderEntry::Scene, 1) => Some(1),
+            _ => None,
+        }
+    }
+}
@@ -4,19 +4,173 @@ pub enum ShaderEntry {
     Pbr,
 }
 impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Pbr];
     pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-        match self {
+        match *self {
             Self::Pbr => pbr::create_pipeline_layout(device),
         }
     }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Pbr => pbr::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Pbr => &[(wgpu::ShaderStages::FRAGMENT, pbr::ENTRY_FRAGMENT)],
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Pbr => 3,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Pbr => vec![],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Pbr => 0,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Pbr => pbr::RESOURCES,
+        }
+    }
     pub fn create_shader_module_embed_source(
         &self,
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
-        match self {
+        match *self {
             Self::Pbr => pbr::create_shader_module_embed_source(device),
         }
     }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "pbr.wgsl" => Some(Self::Pbr),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Pbr => "pbr.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Pbr => pbr::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Pbr => "pbr",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbr" => Ok(Self::Pbr),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
 }
 mod _root {
     pub use super::*;
@@ -207,6 +361,181 @@ pub mod layout_asserts {
         assert!(std::mem::size_of:: < bevy_pbr::mesh_types::Mesh > () == 144);
     };
 }
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub targets: [Option<wgpu::ColorTargetState>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    pub fn fragment_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntry<N>,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: Some(entry.entry_point),
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    /// A uniform buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct UniformBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> UniformBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// A storage buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct StorageBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> StorageBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                            | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// Reads back the contents of `buffer`, which needs the `COPY_SRC` usage, by copying it to a
+    /// staging buffer mapped for reading. Blocks while polling the device until the mapping
+    /// finishes, so it isn't available on the web, where buffers are mapped asynchronously.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_buffer(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<std::vec::Vec<u8>, wgpu::BufferAsyncError> {
+        let staging = device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("read_buffer"),
+                    size: buffer.size(),
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            );
+        let mut encoder = device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+        queue.submit([encoder.finish()]);
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice
+            .map_async(
+                wgpu::MapMode::Read,
+                move |result| {
+                    let _ = sender.send(result);
+                },
+            );
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+        let bytes = slice.get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(bytes)
+    }
+}
 pub mod bevy_pbr {
     use super::{_root, _root::*};
     pub mod mesh_vertex_output {
@@ -228,6 +557,11 @@ pub mod bevy_pbr {
                 }
             }
         }
+        impl MeshVertexOutput {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
     }
     pub mod pbr {
         use super::{_root, _root::*};
@@ -274,6 +608,89 @@ pub mod bevy_pbr {
                     }
                 }
             }
+            impl StandardMaterial {
+                pub fn as_bytes(&self) -> &[u8] {
+                    bytemuck::bytes_of(self)
+                }
+            }
+            impl StandardMaterial {
+                /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+                #[cfg(debug_assertions)]
+                pub fn verify_layout() {
+                    let fields = [
+                        (
+                            "base_color",
+                            std::mem::offset_of!(Self, base_color),
+                            std::mem::size_of::<glam::Vec4>(),
+                            0,
+                            16,
+                            16,
+                        ),
+                        (
+                            "emissive",
+                            std::mem::offset_of!(Self, emissive),
+                            std::mem::size_of::<glam::Vec4>(),
+                            16,
+                            16,
+                            16,
+                        ),
+                        (
+                            "perceptual_roughness",
+                            std::mem::offset_of!(Self, perceptual_roughness),
+                            std::mem::size_of::<f32>(),
+                            32,
+                            4,
+                            4,
+                        ),
+                        (
+                            "metallic",
+                            std::mem::offset_of!(Self, metallic),
+                            std::mem::size_of::<f32>(),
+                            36,
+                            4,
+                            4,
+                        ),
+                        (
+                            "reflectance",
+                            std::mem::offset_of!(Self, reflectance),
+                            std::mem::size_of::<f32>(),
+                            40,
+                            4,
+                            4,
+                        ),
+                        (
+                            "flags",
+                            std::mem::offset_of!(Self, flags),
+                            std::mem::size_of::<u32>(),
+                            44,
+                            4,
+                            4,
+                        ),
+                        (
+                            "alpha_cutoff",
+                            std::mem::offset_of!(Self, alpha_cutoff),
+                            std::mem::size_of::<f32>(),
+                            48,
+                            4,
+                            16,
+                        ),
+                    ];
+                    let mut mismatches = std::string::String::new();
+                    for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                        if offset != wgsl_offset || size < wgsl_size || size > wgsl_span
+                        {
+                            mismatches
+                                += &std::format!(
+                                    "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                                );
+                        }
+                    }
+                    assert!(
+                        mismatches.is_empty(),
+                        "`bevy_pbr::pbr::types::StandardMaterial` doesn't match the WGSL layout:{mismatches}"
+                    );
+                }
+            }
             #[repr(C)]
             #[derive(Debug, PartialEq, Clone, Copy)]
             pub struct StandardMaterialInit {
@@ -363,6 +780,104 @@ pub mod bevy_pbr {
                 }
             }
         }
+        impl View {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl View {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "view_proj",
+                        std::mem::offset_of!(Self, view_proj),
+                        std::mem::size_of::<glam::Mat4>(),
+                        0,
+                        64,
+                        64,
+                    ),
+                    (
+                        "inverse_view_proj",
+                        std::mem::offset_of!(Self, inverse_view_proj),
+                        std::mem::size_of::<glam::Mat4>(),
+                        64,
+                        64,
+                        64,
+                    ),
+                    (
+                        "view",
+                        std::mem::offset_of!(Self, view),
+                        std::mem::size_of::<glam::Mat4>(),
+                        128,
+                        64,
+                        64,
+                    ),
+                    (
+                        "inverse_view",
+                        std::mem::offset_of!(Self, inverse_view),
+                        std::mem::size_of::<glam::Mat4>(),
+                        192,
+                        64,
+                        64,
+                    ),
+                    (
+                        "projection",
+                        std::mem::offset_of!(Self, projection),
+                        std::mem::size_of::<glam::Mat4>(),
+                        256,
+                        64,
+                        64,
+                    ),
+                    (
+                        "inverse_projection",
+                        std::mem::offset_of!(Self, inverse_projection),
+                        std::mem::size_of::<glam::Mat4>(),
+                        320,
+                        64,
+                        64,
+                    ),
+                    (
+                        "world_position",
+                        std::mem::offset_of!(Self, world_position),
+                        std::mem::size_of::<glam::Vec3A>(),
+                        384,
+                        12,
+                        12,
+                    ),
+                    (
+                        "width",
+                        std::mem::offset_of!(Self, width),
+                        std::mem::size_of::<f32>(),
+                        396,
+                        4,
+                        4,
+                    ),
+                    (
+                        "height",
+                        std::mem::offset_of!(Self, height),
+                        std::mem::size_of::<f32>(),
+                        400,
+                        4,
+                        16,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::View` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct ViewInit {
@@ -438,6 +953,80 @@ pub mod bevy_pbr {
                 }
             }
         }
+        impl DirectionalLight {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl DirectionalLight {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "view_projection",
+                        std::mem::offset_of!(Self, view_projection),
+                        std::mem::size_of::<glam::Mat4>(),
+                        0,
+                        64,
+                        64,
+                    ),
+                    (
+                        "color",
+                        std::mem::offset_of!(Self, color),
+                        std::mem::size_of::<glam::Vec4>(),
+                        64,
+                        16,
+                        16,
+                    ),
+                    (
+                        "direction_to_light",
+                        std::mem::offset_of!(Self, direction_to_light),
+                        std::mem::size_of::<glam::Vec3A>(),
+                        80,
+                        12,
+                        12,
+                    ),
+                    (
+                        "flags",
+                        std::mem::offset_of!(Self, flags),
+                        std::mem::size_of::<u32>(),
+                        92,
+                        4,
+                        4,
+                    ),
+                    (
+                        "shadow_depth_bias",
+                        std::mem::offset_of!(Self, shadow_depth_bias),
+                        std::mem::size_of::<f32>(),
+                        96,
+                        4,
+                        4,
+                    ),
+                    (
+                        "shadow_normal_bias",
+                        std::mem::offset_of!(Self, shadow_normal_bias),
+                        std::mem::size_of::<f32>(),
+                        100,
+                        4,
+                        12,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::DirectionalLight` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct DirectionalLightInit {
@@ -507,6 +1096,82 @@ pub mod bevy_pbr {
                 }
             }
         }
+        impl Lights {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl Lights {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "directional_lights",
+                        std::mem::offset_of!(Self, directional_lights),
+                        std::mem::size_of::<
+                            [_root::bevy_pbr::mesh_view_types::DirectionalLight; 1],
+                        >(),
+                        0,
+                        112,
+                        112,
+                    ),
+                    (
+                        "ambient_color",
+                        std::mem::offset_of!(Self, ambient_color),
+                        std::mem::size_of::<glam::Vec4>(),
+                        112,
+                        16,
+                        16,
+                    ),
+                    (
+                        "cluster_dimensions",
+                        std::mem::offset_of!(Self, cluster_dimensions),
+                        std::mem::size_of::<[u32; 4]>(),
+                        128,
+                        16,
+                        16,
+                    ),
+                    (
+                        "cluster_factors",
+                        std::mem::offset_of!(Self, cluster_factors),
+                        std::mem::size_of::<glam::Vec4>(),
+                        144,
+                        16,
+                        16,
+                    ),
+                    (
+                        "n_directional_lights",
+                        std::mem::offset_of!(Self, n_directional_lights),
+                        std::mem::size_of::<u32>(),
+                        160,
+                        4,
+                        4,
+                    ),
+                    (
+                        "spot_light_shadowmap_offset",
+                        std::mem::offset_of!(Self, spot_light_shadowmap_offset),
+                        std::mem::size_of::<i32>(),
+                        164,
+                        4,
+                        12,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::Lights` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct LightsInit {
@@ -575,6 +1240,89 @@ pub mod bevy_pbr {
                 }
             }
         }
+        impl PointLight {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl PointLight {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "light_custom_data",
+                        std::mem::offset_of!(Self, light_custom_data),
+                        std::mem::size_of::<glam::Vec4>(),
+                        0,
+                        16,
+                        16,
+                    ),
+                    (
+                        "color_inverse_square_range",
+                        std::mem::offset_of!(Self, color_inverse_square_range),
+                        std::mem::size_of::<glam::Vec4>(),
+                        16,
+                        16,
+                        16,
+                    ),
+                    (
+                        "position_radius",
+                        std::mem::offset_of!(Self, position_radius),
+                        std::mem::size_of::<glam::Vec4>(),
+                        32,
+                        16,
+                        16,
+                    ),
+                    (
+                        "flags",
+                        std::mem::offset_of!(Self, flags),
+                        std::mem::size_of::<u32>(),
+                        48,
+                        4,
+                        4,
+                    ),
+                    (
+                        "shadow_depth_bias",
+                        std::mem::offset_of!(Self, shadow_depth_bias),
+                        std::mem::size_of::<f32>(),
+                        52,
+                        4,
+                        4,
+                    ),
+                    (
+                        "shadow_normal_bias",
+                        std::mem::offset_of!(Self, shadow_normal_bias),
+                        std::mem::size_of::<f32>(),
+                        56,
+                        4,
+                        4,
+                    ),
+                    (
+                        "spot_light_tan_angle",
+                        std::mem::offset_of!(Self, spot_light_tan_angle),
+                        std::mem::size_of::<f32>(),
+                        60,
+                        4,
+                        4,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::PointLight` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
+        #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct PointLights<const N: usize> {
             /// size: 64, offset: 0x0, type: `array<bevy_pbr::mesh_view_types::PointLight>`
@@ -587,6 +1335,43 @@ pub mod bevy_pbr {
                 Self { data }
             }
         }
+        impl<const N: usize> PointLights<N> {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl<const N: usize> PointLights<N> {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "data",
+                        std::mem::offset_of!(Self, data),
+                        std::mem::size_of::<
+                            _root::bevy_pbr::mesh_view_types::PointLight,
+                        >(),
+                        0,
+                        64,
+                        64,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::PointLights` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
+        #[repr(C, align(4))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct ClusterLightIndexLists<const N: usize> {
             /// size: 4, offset: 0x0, type: `array<u32>`
@@ -597,6 +1382,41 @@ pub mod bevy_pbr {
                 Self { data }
             }
         }
+        impl<const N: usize> ClusterLightIndexLists<N> {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl<const N: usize> ClusterLightIndexLists<N> {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "data",
+                        std::mem::offset_of!(Self, data),
+                        std::mem::size_of::<u32>(),
+                        0,
+                        4,
+                        4,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::ClusterLightIndexLists` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
+        #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct ClusterOffsetsAndCounts<const N: usize> {
             /// size: 16, offset: 0x0, type: `array<vec4<u32>>`
@@ -607,6 +1427,40 @@ pub mod bevy_pbr {
                 Self { data }
             }
         }
+        impl<const N: usize> ClusterOffsetsAndCounts<N> {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl<const N: usize> ClusterOffsetsAndCounts<N> {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "data",
+                        std::mem::offset_of!(Self, data),
+                        std::mem::size_of::<[u32; 4]>(),
+                        0,
+                        16,
+                        16,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_view_types::ClusterOffsetsAndCounts` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         pub const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u32;
         pub const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u32;
         pub const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u32;
@@ -638,6 +1492,56 @@ pub mod bevy_pbr {
                 }
             }
         }
+        impl Mesh {
+            pub fn as_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+        impl Mesh {
+            /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+            #[cfg(debug_assertions)]
+            pub fn verify_layout() {
+                let fields = [
+                    (
+                        "model",
+                        std::mem::offset_of!(Self, model),
+                        std::mem::size_of::<glam::Mat4>(),
+                        0,
+                        64,
+                        64,
+                    ),
+                    (
+                        "inverse_transpose_model",
+                        std::mem::offset_of!(Self, inverse_transpose_model),
+                        std::mem::size_of::<glam::Mat4>(),
+                        64,
+                        64,
+                        64,
+                    ),
+                    (
+                        "flags",
+                        std::mem::offset_of!(Self, flags),
+                        std::mem::size_of::<u32>(),
+                        128,
+                        4,
+                        16,
+                    ),
+                ];
+                let mut mismatches = std::string::String::new();
+                for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                    if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                        mismatches
+                            += &std::format!(
+                                "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                            );
+                    }
+                }
+                assert!(
+                    mismatches.is_empty(),
+                    "`bevy_pbr::mesh_types::Mesh` doesn't match the WGSL layout:{mismatches}"
+                );
+            }
+        }
         #[repr(C)]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct MeshInit {
@@ -872,7 +1776,7 @@ pub mod pbr {
                     visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::Cube,
+                        view_dimension: wgpu::TextureViewDimension::CubeArray,
                         multisampled: false,
                     },
                     count: None,
@@ -890,7 +1794,7 @@ pub mod pbr {
                     visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::D2,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
                         multisampled: false,
                     },
                     count: None,
@@ -911,19 +1815,16 @@ pub mod pbr {
             device: &wgpu::Device,
             bindings: WgpuBindGroup0Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Pbr::BindGroup0"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Pbr::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
         }
-        pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
         }
     }
@@ -981,19 +1882,16 @@ pub mod pbr {
             device: &wgpu::Device,
             bindings: WgpuBindGroup1Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Pbr::BindGroup1"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Pbr::BindGroup1",
+                    &bindings.as_array(),
+                ),
+            )
         }
-        pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
         }
     }
@@ -1049,19 +1947,16 @@ pub mod pbr {
             device: &wgpu::Device,
             bindings: WgpuBindGroup2Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Pbr::BindGroup2"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Pbr::BindGroup2",
+                    &bindings.as_array(),
+                ),
+            )
         }
-        pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        pub fn set<'a>(&self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(2, &self.0, &[]);
         }
     }
@@ -1088,27 +1983,384 @@ pub mod pbr {
         bind_group1.set(pass);
         bind_group2.set(pass);
     }
-    pub const ENTRY_FRAGMENT: &str = "fragment";
-    #[derive(Debug)]
-    pub struct FragmentEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub targets: [Option<wgpu::ColorTargetState>; N],
-        pub constants: std::collections::HashMap<String, f64>,
+    pub fn create_view_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::bevy_pbr::mesh_view_types::View,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::view"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
     }
-    pub fn fragment_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a FragmentEntry<N>,
-    ) -> wgpu::FragmentState<'a> {
-        wgpu::FragmentState {
-            module,
-            entry_point: entry.entry_point,
-            targets: &entry.targets,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
+    pub fn write_view(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::bevy_pbr::mesh_view_types::View,
+    ) {
+        write_view_at(queue, buffer, 0, value)
+    }
+    pub fn write_view_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::bevy_pbr::mesh_view_types::View,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_view_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::bevy_pbr::mesh_view_types::View],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::view"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn create_lights_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::bevy_pbr::mesh_view_types::Lights,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::lights"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_lights(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::bevy_pbr::mesh_view_types::Lights,
+    ) {
+        write_lights_at(queue, buffer, 0, value)
+    }
+    pub fn write_lights_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::bevy_pbr::mesh_view_types::Lights,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_lights_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::bevy_pbr::mesh_view_types::Lights],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::lights"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn create_material_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::bevy_pbr::pbr::types::StandardMaterial,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::material"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_material(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::bevy_pbr::pbr::types::StandardMaterial,
+    ) {
+        write_material_at(queue, buffer, 0, value)
+    }
+    pub fn write_material_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::bevy_pbr::pbr::types::StandardMaterial,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_material_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::bevy_pbr::pbr::types::StandardMaterial],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::material"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub fn create_mesh_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::bevy_pbr::mesh_types::Mesh,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::mesh"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_mesh(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::bevy_pbr::mesh_types::Mesh,
+    ) {
+        write_mesh_at(queue, buffer, 0, value)
+    }
+    pub fn write_mesh_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::bevy_pbr::mesh_types::Mesh,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_mesh_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::bevy_pbr::mesh_types::Mesh],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Pbr::mesh"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+    pub const fn point_lights_min_buffer_size(n: usize) -> u64 {
+        n as u64 * 64
+    }
+    pub fn create_point_lights_buffer_for(
+        device: &wgpu::Device,
+        n: usize,
+    ) -> wgpu::Buffer {
+        device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("Pbr::point_lights"),
+                    size: point_lights_min_buffer_size(n),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            )
+    }
+    /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+    pub const fn cluster_light_index_lists_min_buffer_size(n: usize) -> u64 {
+        n as u64 * 4
+    }
+    pub fn create_cluster_light_index_lists_buffer_for(
+        device: &wgpu::Device,
+        n: usize,
+    ) -> wgpu::Buffer {
+        device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("Pbr::cluster_light_index_lists"),
+                    size: cluster_light_index_lists_min_buffer_size(n),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            )
+    }
+    /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+    pub const fn cluster_offsets_and_counts_min_buffer_size(n: usize) -> u64 {
+        n as u64 * 16
+    }
+    pub fn create_cluster_offsets_and_counts_buffer_for(
+        device: &wgpu::Device,
+        n: usize,
+    ) -> wgpu::Buffer {
+        device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("Pbr::cluster_offsets_and_counts"),
+                    size: cluster_offsets_and_counts_min_buffer_size(n),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            )
+    }
+    pub fn point_shadow_textures_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Pbr::point_shadow_textures"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_point_shadow_textures(
+        texture: &wgpu::Texture,
+        base_array_layer: u32,
+        array_layer_count: u32,
+    ) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Pbr::point_shadow_textures"),
+                    dimension: Some(wgpu::TextureViewDimension::CubeArray),
+                    aspect: wgpu::TextureAspect::DepthOnly,
+                    base_array_layer,
+                    array_layer_count: Some(array_layer_count),
+                    ..Default::default()
+                },
+            )
+    }
+    pub fn directional_shadow_textures_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Pbr::directional_shadow_textures"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
         }
     }
+    pub fn create_view_for_directional_shadow_textures(
+        texture: &wgpu::Texture,
+        base_array_layer: u32,
+        array_layer_count: u32,
+    ) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Pbr::directional_shadow_textures"),
+                    dimension: Some(wgpu::TextureViewDimension::D2Array),
+                    aspect: wgpu::TextureAspect::DepthOnly,
+                    base_array_layer,
+                    array_layer_count: Some(array_layer_count),
+                    ..Default::default()
+                },
+            )
+    }
+    pub const ENTRY_FRAGMENT: &str = "fragment";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        max_storage_buffers_per_shader_stage: 3,
+        max_storage_buffer_binding_size: 64,
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 4,
+        uniform_bytes: 800,
+        storage_buffers: 3,
+        textures_1d: 0,
+        textures_2d: 0,
+        textures_2d_array: 1,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 1,
+        storage_textures: 0,
+        samplers: 2,
+        push_constant_size: 0,
+    };
+    pub use _root::_support::{FragmentEntry, fragment_state};
     pub fn fragment_entry(
         targets: [Option<wgpu::ColorTargetState>; 1],
     ) -> FragmentEntry<1> {
@@ -1141,6 +2393,23 @@ pub mod pbr {
                 },
             )
     }
+    pub const SOURCE_HASH: &str = "d06fda49cc1a437132aa1f9ff43efb9b1795cd16130074e49a8ad400deb59c07";
+    pub const SOURCE_PATH: &str = "tests/shaders/bevy_pbr_wgsl/pbr.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &[
+        "pbr.wgsl",
+        "mesh_vertex_output.wgsl",
+        "pbr/types.wgsl",
+        "mesh_types.wgsl",
+        "mesh_bindings.wgsl",
+        "mesh_view_types.wgsl",
+        "mesh_view_bindings.wgsl",
+        "utils.wgsl",
+        "pbr/lighting.wgsl",
+        "clustered_forward.wgsl",
+        "shadows.wgsl",
+        "pbr/functions.wgsl",
+        "pbr/bindings.wgsl",
+    ];
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -1288,8 +2557,8 @@ fn saturateX_naga_oil_mod_XMJSXM6K7OBRHEOR2OV2GS3DTX(value: f32) -> f32 {
 }
 
 fn EnvBRDFApproxX_naga_oil_mod_XMJSXM6K7OBRHEOR2OBRHEOR2NRUWO2DUNFXGOX(f0_: vec3<f32>, perceptual_roughness_1: f32, NoV: f32) -> vec3<f32> {
-    let c0_ = vec4<f32>(-1f, -0.0275f, -0.572f, 0.022f);
-    let c1_ = vec4<f32>(1f, 0.0425f, 1.04f, -0.04f);
+    const c0_ = vec4<f32>(-1f, -0.0275f, -0.572f, 0.022f);
+    const c1_ = vec4<f32>(1f, 0.0425f, 1.04f, -0.04f);
     let r = ((perceptual_roughness_1 * c0_) + c1_);
     let a004_ = ((min((r.x * r.x), exp2((-9.28f * NoV))) * r.x) + r.y);
     let AB = ((vec2<f32>(-1.04f, 1.04f) * a004_) + r.zw);
@@ -1572,7 +2841,7 @@ fn fetch_directional_shadowX_naga_oil_mod_XMJSXM6K7OBRHEOR2ONUGCZDPO5ZQX(light_i
     if ((any((offset_position_ndc.xy < vec2(-1f))) || (offset_position_ndc.z < 0f)) || any((offset_position_ndc > vec3(1f)))) {
         return 1f;
     }
-    let flip_correction = vec2<f32>(0.5f, -0.5f);
+    const flip_correction = vec2<f32>(0.5f, -0.5f);
     let light_local = ((offset_position_ndc.xy * flip_correction) + vec2<f32>(0.5f, 0.5f));
     let depth_2 = offset_position_ndc.z;
     let _e57 = textureSampleCompareLevel(directional_shadow_texturesX_naga_oil_mod_XMJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXGX, directional_shadow_textures_samplerX_naga_oil_mod_XMJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXGX, light_local, i32(light_id_2), depth_2);
@@ -1807,3 +3076,26 @@ fn fragment(mesh: MeshVertexOutputX_naga_oil_mod_XMJSXM6K7OBRHEOR2NVSXG2C7OZSXE5
 }
 "#;
 }
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 3;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        pbr::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+        pbr::WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+        pbr::WgpuBindGroup2::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Pbr, 0) => Some(0),
+            (ShaderEntry::Pbr, 1) => Some(1),
+            (ShaderEntry::Pbr, 2) => Some(2),
+            _ => None,
+        }
+    }
+}
@@ -4,16 +4,55 @@ pub enum ShaderEntry {
     Main,
 }
 impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Main];
     pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-        match self {
+        match *self {
             Self::Main => main::create_pipeline_layout(device),
         }
     }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Main => main::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Main => &[(wgpu::ShaderStages::COMPUTE, main::ENTRY_MAIN)],
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Main => 2,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Main => vec![],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Main => 32,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Main => main::RESOURCES,
+        }
+    }
     pub fn create_shader_module_embed_source(
         &self,
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
-        match self {
+        match *self {
             Self::Main => main::create_shader_module_embed_source(device),
         }
     }
@@ -22,20 +61,205 @@ impl ShaderEntry {
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
     ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
-        match self {
+        match *self {
             Self::Main => main::create_shader_module_from_path(device, shader_defs),
         }
     }
+    pub fn create_compute_pipelines_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> Vec<wgpu::ComputePipeline> {
+        let entry_points: &[&str] = match *self {
+            Self::Main => &[main::ENTRY_MAIN],
+        };
+        if entry_points.is_empty() {
+            return Vec::new();
+        }
+        let module = self.create_shader_module_embed_source(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipelines = entry_points
+            .iter()
+            .map(|entry_point| {
+                device
+                    .create_compute_pipeline(
+                        &wgpu::ComputePipelineDescriptor {
+                            label: Some(&format!("Compute Pipeline {entry_point}")),
+                            layout: Some(&layout),
+                            module: &module,
+                            entry_point: Some(entry_point),
+                            compilation_options: Default::default(),
+                            cache: None,
+                        },
+                    )
+            })
+            .collect();
+        pipelines
+    }
+    pub fn create_compute_pipelines_from_path(
+        &self,
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<Vec<wgpu::ComputePipeline>, naga_oil::compose::ComposerError> {
+        let entry_points: &[&str] = match *self {
+            Self::Main => &[main::ENTRY_MAIN],
+        };
+        if entry_points.is_empty() {
+            return Ok(Vec::new());
+        }
+        let module = self.create_shader_module_from_path(device, shader_defs)?;
+        let layout = self.create_pipeline_layout(device);
+        let pipelines = entry_points
+            .iter()
+            .map(|entry_point| {
+                device
+                    .create_compute_pipeline(
+                        &wgpu::ComputePipelineDescriptor {
+                            label: Some(&format!("Compute Pipeline {entry_point}")),
+                            layout: Some(&layout),
+                            module: &module,
+                            entry_point: Some(entry_point),
+                            compilation_options: Default::default(),
+                            cache: None,
+                        },
+                    )
+            })
+            .collect();
+        Ok(pipelines)
+    }
     pub fn shader_entry_filename(&self) -> &'static str {
-        match self {
+        match *self {
             Self::Main => "main.wgsl",
         }
     }
     pub fn shader_paths(&self) -> &[&str] {
-        match self {
+        match *self {
             Self::Main => main::SHADER_PATHS,
         }
     }
+    pub fn load_naga_module(
+        &self,
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        match *self {
+            Self::Main => main::load_naga_module_from_dir(base_dir, shader_defs),
+        }
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "tests/shaders/basic/main.wgsl" => Some(Self::Main),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Main => "tests/shaders/basic/main.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Main => main::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Main => "main",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "main" => Ok(Self::Main),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
 }
 mod _root {
     pub use super::*;
@@ -58,6 +282,161 @@ pub mod layout_asserts {
         assert!(std::mem::size_of:: < main::Style > () == 256);
     };
 }
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    /// A uniform buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct UniformBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> UniformBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// A storage buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct StorageBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> StorageBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                            | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
+    /// Reads back the contents of `buffer`, which needs the `COPY_SRC` usage, by copying it to a
+    /// staging buffer mapped for reading. Blocks while polling the device until the mapping
+    /// finishes, so it isn't available on the web, where buffers are mapped asynchronously.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_buffer(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+    ) -> Result<std::vec::Vec<u8>, wgpu::BufferAsyncError> {
+        let staging = device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("read_buffer"),
+                    size: buffer.size(),
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            );
+        let mut encoder = device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+        queue.submit([encoder.finish()]);
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice
+            .map_async(
+                wgpu::MapMode::Read,
+                move |result| {
+                    let _ = sender.send(result);
+                },
+            );
+        device.poll(wgpu::Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+        let bytes = slice.get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(bytes)
+    }
+}
 pub mod main {
     use super::{_root, _root::*};
     #[repr(C, align(256))]
@@ -78,6 +457,48 @@ pub mod main {
             }
         }
     }
+    impl Style {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Style {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color",
+                    std::mem::offset_of!(Self, color),
+                    std::mem::size_of::<glam::Vec4>(),
+                    0,
+                    16,
+                    16,
+                ),
+                (
+                    "width",
+                    std::mem::offset_of!(Self, width),
+                    std::mem::size_of::<f32>(),
+                    16,
+                    4,
+                    16,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`main::Style` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
     #[repr(C)]
     #[derive(Debug, PartialEq, Clone, Copy)]
     pub struct StyleInit {
@@ -203,19 +624,16 @@ pub mod main {
             device: &wgpu::Device,
             bindings: WgpuBindGroup0Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Main::BindGroup0"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Main::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
         }
-        pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
+        pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
         }
     }
@@ -271,19 +689,16 @@ pub mod main {
             device: &wgpu::Device,
             bindings: WgpuBindGroup1Entries,
         ) -> Self {
-            let bind_group_layout = Self::get_bind_group_layout(&device);
-            let entries = bindings.as_array();
-            let bind_group = device
-                .create_bind_group(
-                    &wgpu::BindGroupDescriptor {
-                        label: Some("Main::BindGroup1"),
-                        layout: &bind_group_layout,
-                        entries: &entries,
-                    },
-                );
-            Self(bind_group)
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Main::BindGroup1",
+                    &bindings.as_array(),
+                ),
+            )
         }
-        pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
+        pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
         }
     }
@@ -306,8 +721,129 @@ pub mod main {
         bind_group0.set(pass);
         bind_group1.set(pass);
     }
+    /// Encodes `values` as the bytes of the binding, padding each element to the WGSL stride.
+    pub fn encode_buffer(values: &[f32]) -> std::vec::Vec<u8> {
+        let size = std::mem::size_of::<f32>();
+        assert!(
+            size <= 4,
+            "the elements of `buffer` are larger than their WGSL stride of 4 bytes"
+        );
+        let mut bytes = std::vec![0; values.len() * 4];
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+            chunk[..size].copy_from_slice(bytemuck::bytes_of(value));
+        }
+        bytes
+    }
+    /// Decodes the elements of the binding from `bytes`, skipping the padding after each
+    /// element.
+    pub fn decode_buffer(bytes: &[u8]) -> std::vec::Vec<f32> {
+        let size = std::mem::size_of::<f32>();
+        assert!(
+            size <= 4,
+            "the elements of `buffer` are larger than their WGSL stride of 4 bytes"
+        );
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| bytemuck::pod_read_unaligned(&chunk[..size]))
+            .collect()
+    }
+    /// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+    pub const fn buffer_min_buffer_size(n: usize) -> u64 {
+        n as u64 * 4
+    }
+    pub fn create_buffer_buffer_for(device: &wgpu::Device, n: usize) -> wgpu::Buffer {
+        device
+            .create_buffer(
+                &wgpu::BufferDescriptor {
+                    label: Some("Main::buffer"),
+                    size: buffer_min_buffer_size(n),
+                    usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                        | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            )
+    }
+    pub fn texture_float_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Main::texture_float"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_texture_float(texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Main::texture_float"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    aspect: wgpu::TextureAspect::All,
+                    ..Default::default()
+                },
+            )
+    }
+    pub fn texture_sint_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Main::texture_sint"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_texture_sint(texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Main::texture_sint"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    aspect: wgpu::TextureAspect::All,
+                    ..Default::default()
+                },
+            )
+    }
+    pub fn texture_uint_texture_descriptor(
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Main::texture_uint"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }
+    }
+    pub fn create_view_for_texture_uint(texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture
+            .create_view(
+                &wgpu::TextureViewDescriptor {
+                    label: Some("Main::texture_uint"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    aspect: wgpu::TextureAspect::All,
+                    ..Default::default()
+                },
+            )
+    }
     pub mod compute {
         pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [1, 1, 1];
+        pub const MAIN_WORKGROUP_STORAGE_SIZE: u32 = 0;
         pub fn create_main_pipeline_embed_source(
             device: &wgpu::Device,
         ) -> wgpu::ComputePipeline {
@@ -349,6 +885,41 @@ pub mod main {
         }
     }
     pub const ENTRY_MAIN: &str = "main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS;
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        max_storage_buffers_per_shader_stage: 1,
+        max_storage_buffer_binding_size: 4,
+        max_compute_invocations_per_workgroup: 1,
+        max_compute_workgroup_size_x: 1,
+        max_compute_workgroup_size_y: 1,
+        max_compute_workgroup_size_z: 1,
+        max_compute_workgroups_per_dimension: 1,
+        max_push_constant_size: 32,
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 1,
+        uniform_bytes: 4,
+        storage_buffers: 1,
+        textures_1d: 0,
+        textures_2d: 3,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 0,
+        push_constant_size: 32,
+    };
     #[derive(Debug)]
     pub struct WgpuPipelineLayout;
     impl WgpuPipelineLayout {
@@ -376,6 +947,13 @@ pub mod main {
                 },
             )
     }
+    pub const SOURCE_HASH: &str = "8351f683bde62c7a997b5d6dcb69502e2921b9f4cd073e67d7e73fe0c8751ea9";
+    pub const SOURCE_PATH: &str = "tests/shaders/basic/main.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &[
+        "../basic/main.wgsl",
+        "../basic/bindings.wgsl",
+        "types.wgsl",
+    ];
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -463,6 +1041,74 @@ fn main(@builtin(global_invocation_id) id: vec3<u32>) {
                 ..Default::default()
             })
     }
+    pub fn load_naga_module_from_reader(
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        let mut composer = naga_oil::compose::Composer::default()
+            .with_capabilities(wgpu::naga::valid::Capabilities::from_bits_retain(1));
+        composer
+            .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
+                source: &read_source("../basic/bindings.wgsl"),
+                file_path: "../basic/bindings.wgsl",
+                language: naga_oil::compose::ShaderLanguage::Wgsl,
+                shader_defs: shader_defs.clone(),
+                as_name: Some("bindings".into()),
+                ..Default::default()
+            })?;
+        composer
+            .add_composable_module(naga_oil::compose::ComposableModuleDescriptor {
+                source: &read_source("types.wgsl"),
+                file_path: "types.wgsl",
+                language: naga_oil::compose::ShaderLanguage::Wgsl,
+                shader_defs: shader_defs.clone(),
+                as_name: Some("types".into()),
+                ..Default::default()
+            })?;
+        composer
+            .make_naga_module(naga_oil::compose::NagaModuleDescriptor {
+                source: &read_source("../basic/main.wgsl"),
+                file_path: "../basic/main.wgsl",
+                shader_defs,
+                ..Default::default()
+            })
+    }
+    pub fn load_naga_module_from_dir(
+        base_dir: &std::path::Path,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::naga::Module, naga_oil::compose::ComposerError> {
+        load_naga_module_from_reader(
+            |path| std::fs::read_to_string(base_dir.join(path)).unwrap(),
+            shader_defs,
+        )
+    }
+    pub fn create_shader_module_from_reader(
+        device: &wgpu::Device,
+        read_source: impl Fn(&str) -> String,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+        let module = load_naga_module_from_reader(read_source, shader_defs)?;
+        let info = wgpu::naga::valid::Validator::new(
+                wgpu::naga::valid::ValidationFlags::empty(),
+                wgpu::naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .unwrap();
+        let shader_string = wgpu::naga::back::wgsl::write_string(
+                &module,
+                &info,
+                wgpu::naga::back::wgsl::WriterFlags::empty(),
+            )
+            .expect("failed to convert naga module to source");
+        let source = std::borrow::Cow::Owned(shader_string);
+        Ok(
+            device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("main.wgsl"),
+                    source: wgpu::ShaderSource::Wgsl(source),
+                }),
+        )
+    }
     pub fn create_shader_module_from_path(
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
@@ -498,3 +1144,24 @@ pub mod bytemuck_impls {
     unsafe impl bytemuck::Zeroable for main::Style {}
     unsafe impl bytemuck::Pod for main::Style {}
 }
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 2;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        main::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+        main::WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Main, 0) => Some(0),
+            (ShaderEntry::Main, 1) => Some(1),
+            _ => None,
+        }
+    }
+}
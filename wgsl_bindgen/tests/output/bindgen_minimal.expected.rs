@@ -1,204 +1,618 @@
 #![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ShaderEntry {
-  Minimal,
+    Minimal,
 }
 impl ShaderEntry {
-  pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-    match self {
-      Self::Minimal => minimal::create_pipeline_layout(device),
+    pub const ALL: &'static [Self] = &[Self::Minimal];
+    pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+        match *self {
+            Self::Minimal => minimal::create_pipeline_layout(device),
+        }
     }
-  }
-  pub fn create_shader_module_embed_source(
-    &self,
-    device: &wgpu::Device,
-  ) -> wgpu::ShaderModule {
-    match self {
-      Self::Minimal => minimal::create_shader_module_embed_source(device),
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Minimal => minimal::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Minimal => &[(wgpu::ShaderStages::COMPUTE, minimal::ENTRY_MAIN)],
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Minimal => 1,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Minimal => vec![],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Minimal => 0,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Minimal => minimal::RESOURCES,
+        }
+    }
+    pub fn create_shader_module_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match *self {
+            Self::Minimal => minimal::create_shader_module_embed_source(device),
+        }
+    }
+    pub fn create_compute_pipelines_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> Vec<wgpu::ComputePipeline> {
+        let entry_points: &[&str] = match *self {
+            Self::Minimal => &[minimal::ENTRY_MAIN],
+        };
+        if entry_points.is_empty() {
+            return Vec::new();
+        }
+        let module = self.create_shader_module_embed_source(device);
+        let layout = self.create_pipeline_layout(device);
+        let pipelines = entry_points
+            .iter()
+            .map(|entry_point| {
+                device
+                    .create_compute_pipeline(
+                        &wgpu::ComputePipelineDescriptor {
+                            label: Some(&format!("Compute Pipeline {entry_point}")),
+                            layout: Some(&layout),
+                            module: &module,
+                            entry_point: Some(entry_point),
+                            compilation_options: Default::default(),
+                            cache: None,
+                        },
+                    )
+            })
+            .collect();
+        pipelines
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "minimal.wgsl" => Some(Self::Minimal),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Minimal => "minimal.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Minimal => minimal::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Minimal => "minimal",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(Self::Minimal),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
     }
-  }
 }
 mod _root {
-  pub use super::*;
+    pub use super::*;
 }
 pub mod layout_asserts {
-  use super::{_root, _root::*};
-  const WGSL_BASE_TYPE_ASSERTS: () = {
-    assert!(std::mem::size_of::<glam::Vec3A>() == 16);
-    assert!(std::mem::align_of::<glam::Vec3A>() == 16);
-    assert!(std::mem::size_of::<glam::Vec4>() == 16);
-    assert!(std::mem::align_of::<glam::Vec4>() == 16);
-    assert!(std::mem::size_of::<glam::Mat3A>() == 48);
-    assert!(std::mem::align_of::<glam::Mat3A>() == 16);
-    assert!(std::mem::size_of::<glam::Mat4>() == 64);
-    assert!(std::mem::align_of::<glam::Mat4>() == 16);
-  };
-  const MINIMAL_UNIFORMS_ASSERTS: () = {
-    assert!(std::mem::offset_of!(minimal::Uniforms, color) == 0);
-    assert!(std::mem::offset_of!(minimal::Uniforms, width) == 16);
-    assert!(std::mem::size_of::<minimal::Uniforms>() == 256);
-  };
+    use super::{_root, _root::*};
+    const WGSL_BASE_TYPE_ASSERTS: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+    const MINIMAL_UNIFORMS_ASSERTS: () = {
+        assert!(std::mem::offset_of!(minimal::Uniforms, color) == 0);
+        assert!(std::mem::offset_of!(minimal::Uniforms, width) == 16);
+        assert!(std::mem::size_of:: < minimal::Uniforms > () == 256);
+    };
+}
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    /// Creates a bind group with a layout created from `layout_descriptor`.
+    pub fn create_bind_group(
+        device: &wgpu::Device,
+        layout_descriptor: &wgpu::BindGroupLayoutDescriptor,
+        label: &str,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let bind_group_layout = device.create_bind_group_layout(layout_descriptor);
+        device
+            .create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &bind_group_layout,
+                    entries,
+                },
+            )
+    }
+    /// A uniform buffer holding a `T`, written and bound as a whole.
+    #[derive(Debug)]
+    pub struct UniformBuffer<T> {
+        buffer: wgpu::Buffer,
+        _marker: std::marker::PhantomData<T>,
+    }
+    impl<T: bytemuck::Pod> UniformBuffer<T> {
+        pub fn new_init(device: &wgpu::Device, value: &T) -> Self {
+            use wgpu::util::DeviceExt;
+            let buffer = device
+                .create_buffer_init(
+                    &wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::bytes_of(value),
+                        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    },
+                );
+            Self {
+                buffer,
+                _marker: std::marker::PhantomData,
+            }
+        }
+        pub fn write(&self, queue: &wgpu::Queue, value: &T) {
+            queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+        }
+        pub fn buffer(&self) -> &wgpu::Buffer {
+            &self.buffer
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BindingResource<'_> {
+            self.buffer.as_entire_binding()
+        }
+        pub fn as_entire_buffer_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.buffer.as_entire_buffer_binding()
+        }
+    }
 }
 pub mod minimal {
-  use super::{_root, _root::*};
-  #[repr(C, align(256))]
-  #[derive(Debug, PartialEq, Clone, Copy)]
-  pub struct Uniforms {
-    /// size: 16, offset: 0x0, type: `vec4<f32>`
-    pub color: glam::Vec4,
-    /// size: 4, offset: 0x10, type: `f32`
-    pub width: f32,
-    pub _pad_width: [u8; 0x10 - core::mem::size_of::<f32>()],
-  }
-  impl Uniforms {
-    pub const fn new(color: glam::Vec4, width: f32) -> Self {
-      Self {
-        color,
-        width,
-        _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
-      }
-    }
-  }
-  #[repr(C)]
-  #[derive(Debug, PartialEq, Clone, Copy)]
-  pub struct UniformsInit {
-    pub color: glam::Vec4,
-    pub width: f32,
-  }
-  impl UniformsInit {
-    pub const fn build(&self) -> Uniforms {
-      Uniforms {
-        color: self.color,
-        width: self.width,
-        _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
-      }
-    }
-  }
-  impl From<UniformsInit> for Uniforms {
-    fn from(data: UniformsInit) -> Self {
-      data.build()
-    }
-  }
-  #[derive(Debug)]
-  pub struct WgpuBindGroup0EntriesParams<'a> {
-    pub uniform_buf: wgpu::BufferBinding<'a>,
-  }
-  #[derive(Clone, Debug)]
-  pub struct WgpuBindGroup0Entries<'a> {
-    pub uniform_buf: wgpu::BindGroupEntry<'a>,
-  }
-  impl<'a> WgpuBindGroup0Entries<'a> {
-    pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
-      Self {
-        uniform_buf: wgpu::BindGroupEntry {
-          binding: 0,
-          resource: wgpu::BindingResource::Buffer(params.uniform_buf),
-        },
-      }
-    }
-    pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 1] {
-      [self.uniform_buf]
-    }
-    pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
-      self.as_array().into_iter().collect()
-    }
-  }
-  #[derive(Debug)]
-  pub struct WgpuBindGroup0(wgpu::BindGroup);
-  impl WgpuBindGroup0 {
-    pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> =
-      wgpu::BindGroupLayoutDescriptor {
-        label: Some("Minimal::BindGroup0::LayoutDescriptor"),
-        entries: &[
-          /// @binding(0): "uniform_buf"
-          wgpu::BindGroupLayoutEntry {
-            binding: 0,
-            visibility: wgpu::ShaderStages::COMPUTE,
-            ty: wgpu::BindingType::Buffer {
-              ty: wgpu::BufferBindingType::Uniform,
-              has_dynamic_offset: false,
-              min_binding_size: std::num::NonZeroU64::new(std::mem::size_of::<
-                _root::minimal::Uniforms,
-              >() as _),
-            },
-            count: None,
-          },
-        ],
-      };
-    pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
-      device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
-    }
-    pub fn from_bindings(device: &wgpu::Device, bindings: WgpuBindGroup0Entries) -> Self {
-      let bind_group_layout = Self::get_bind_group_layout(&device);
-      let entries = bindings.as_array();
-      let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: Some("Minimal::BindGroup0"),
-        layout: &bind_group_layout,
-        entries: &entries,
-      });
-      Self(bind_group)
-    }
-    pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
-      render_pass.set_bind_group(0, &self.0, &[]);
-    }
-  }
-  #[derive(Debug, Copy, Clone)]
-  pub struct WgpuBindGroups<'a> {
-    pub bind_group0: &'a WgpuBindGroup0,
-  }
-  impl<'a> WgpuBindGroups<'a> {
-    pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
-      self.bind_group0.set(pass);
-    }
-  }
-  pub fn set_bind_groups<'a>(
-    pass: &mut wgpu::ComputePass<'a>,
-    bind_group0: &'a WgpuBindGroup0,
-  ) {
-    bind_group0.set(pass);
-  }
-  pub mod compute {
-    pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [1, 1, 1];
-    pub fn create_main_pipeline_embed_source(
-      device: &wgpu::Device,
-    ) -> wgpu::ComputePipeline {
-      let module = super::create_shader_module_embed_source(device);
-      let layout = super::create_pipeline_layout(device);
-      device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-        label: Some("Compute Pipeline main"),
-        layout: Some(&layout),
-        module: &module,
-        entry_point: Some("main"),
-        compilation_options: Default::default(),
-        cache: None,
-      })
-    }
-  }
-  pub const ENTRY_MAIN: &str = "main";
-  #[derive(Debug)]
-  pub struct WgpuPipelineLayout;
-  impl WgpuPipelineLayout {
-    pub fn bind_group_layout_entries(
-      entries: [wgpu::BindGroupLayout; 1],
-    ) -> [wgpu::BindGroupLayout; 1] {
-      entries
-    }
-  }
-  pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
-    device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-      label: Some("Minimal::PipelineLayout"),
-      bind_group_layouts: &[&WgpuBindGroup0::get_bind_group_layout(device)],
-      push_constant_ranges: &[],
-    })
-  }
-  pub fn create_shader_module_embed_source(device: &wgpu::Device) -> wgpu::ShaderModule {
-    let source = std::borrow::Cow::Borrowed(SHADER_STRING);
-    device.create_shader_module(wgpu::ShaderModuleDescriptor {
-      label: Some("minimal.wgsl"),
-      source: wgpu::ShaderSource::Wgsl(source),
-    })
-  }
-  pub const SHADER_STRING: &'static str = r#"
+    use super::{_root, _root::*};
+    #[repr(C, align(256))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Uniforms {
+        /// size: 16, offset: 0x0, type: `vec4<f32>`
+        pub color: glam::Vec4,
+        /// size: 4, offset: 0x10, type: `f32`
+        pub width: f32,
+        pub _pad_width: [u8; 0x10 - core::mem::size_of::<f32>()],
+    }
+    impl Uniforms {
+        pub const fn new(color: glam::Vec4, width: f32) -> Self {
+            Self {
+                color,
+                width,
+                _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
+            }
+        }
+    }
+    impl Uniforms {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl Uniforms {
+        /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+        #[cfg(debug_assertions)]
+        pub fn verify_layout() {
+            let fields = [
+                (
+                    "color",
+                    std::mem::offset_of!(Self, color),
+                    std::mem::size_of::<glam::Vec4>(),
+                    0,
+                    16,
+                    16,
+                ),
+                (
+                    "width",
+                    std::mem::offset_of!(Self, width),
+                    std::mem::size_of::<f32>(),
+                    16,
+                    4,
+                    16,
+                ),
+            ];
+            let mut mismatches = std::string::String::new();
+            for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+                if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                    mismatches
+                        += &std::format!(
+                            "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                        );
+                }
+            }
+            assert!(
+                mismatches.is_empty(),
+                "`minimal::Uniforms` doesn't match the WGSL layout:{mismatches}"
+            );
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct UniformsInit {
+        pub color: glam::Vec4,
+        pub width: f32,
+    }
+    impl UniformsInit {
+        pub const fn build(&self) -> Uniforms {
+            Uniforms {
+                color: self.color,
+                width: self.width,
+                _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
+            }
+        }
+    }
+    impl From<UniformsInit> for Uniforms {
+        fn from(data: UniformsInit) -> Self {
+            data.build()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesParams<'a> {
+        pub uniform_buf: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup0Entries<'a> {
+        pub uniform_buf: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup0Entries<'a> {
+        pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+            Self {
+                uniform_buf: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.uniform_buf),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 1] {
+            [self.uniform_buf]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0(wgpu::BindGroup);
+    impl WgpuBindGroup0 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("Minimal::BindGroup0::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "uniform_buf"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::minimal::Uniforms>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            Self(
+                _root::_support::create_bind_group(
+                    device,
+                    &Self::LAYOUT_DESCRIPTOR,
+                    "Minimal::BindGroup0",
+                    &bindings.as_array(),
+                ),
+            )
+        }
+        pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
+            render_pass.set_bind_group(0, &self.0, &[]);
+        }
+    }
+    #[derive(Debug, Copy, Clone)]
+    pub struct WgpuBindGroups<'a> {
+        pub bind_group0: &'a WgpuBindGroup0,
+    }
+    impl<'a> WgpuBindGroups<'a> {
+        pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
+            self.bind_group0.set(pass);
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut wgpu::ComputePass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    pub fn create_uniform_buf_buffer_init(
+        device: &wgpu::Device,
+        value: &_root::minimal::Uniforms,
+    ) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+        device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Minimal::uniform_buf"),
+                    contents: bytemuck::bytes_of(value),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            )
+    }
+    pub fn write_uniform_buf(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        value: &_root::minimal::Uniforms,
+    ) {
+        write_uniform_buf_at(queue, buffer, 0, value)
+    }
+    pub fn write_uniform_buf_at(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        value: &_root::minimal::Uniforms,
+    ) {
+        queue.write_buffer(buffer, offset, bytemuck::bytes_of(value));
+    }
+    /// Packs `values` into one buffer at offsets aligned for dynamic offset bindings,
+    /// returning the buffer and the offset of each value, eg: for `set_bind_group`.
+    pub fn create_uniform_buf_dynamic_buffer_init(
+        device: &wgpu::Device,
+        limits: &wgpu::Limits,
+        values: &[_root::minimal::Uniforms],
+    ) -> (wgpu::Buffer, std::vec::Vec<wgpu::DynamicOffset>) {
+        use wgpu::util::DeviceExt;
+        let alignment = limits.min_uniform_buffer_offset_alignment as usize;
+        let mut packed = std::vec::Vec::new();
+        let mut offsets = std::vec::Vec::with_capacity(values.len());
+        for value in values {
+            let offset = packed.len().next_multiple_of(alignment);
+            packed.resize(offset, 0);
+            packed.extend_from_slice(bytemuck::bytes_of(value));
+            offsets.push(offset as wgpu::DynamicOffset);
+        }
+        let buffer = device
+            .create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("Minimal::uniform_buf"),
+                    contents: &packed,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
+        (buffer, offsets)
+    }
+    pub mod compute {
+        pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [1, 1, 1];
+        pub const MAIN_WORKGROUP_STORAGE_SIZE: u32 = 0;
+        pub fn create_main_pipeline_embed_source(
+            device: &wgpu::Device,
+        ) -> wgpu::ComputePipeline {
+            let module = super::create_shader_module_embed_source(device);
+            let layout = super::create_pipeline_layout(device);
+            device
+                .create_compute_pipeline(
+                    &wgpu::ComputePipelineDescriptor {
+                        label: Some("Compute Pipeline main"),
+                        layout: Some(&layout),
+                        module: &module,
+                        entry_point: Some("main"),
+                        compilation_options: Default::default(),
+                        cache: None,
+                    },
+                )
+        }
+    }
+    pub const ENTRY_MAIN: &str = "main";
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        max_compute_invocations_per_workgroup: 1,
+        max_compute_workgroup_size_x: 1,
+        max_compute_workgroup_size_y: 1,
+        max_compute_workgroup_size_z: 1,
+        max_compute_workgroups_per_dimension: 1,
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+    }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 1,
+        uniform_bytes: 32,
+        storage_buffers: 0,
+        textures_1d: 0,
+        textures_2d: 0,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 0,
+        push_constant_size: 0,
+    };
+    #[derive(Debug)]
+    pub struct WgpuPipelineLayout;
+    impl WgpuPipelineLayout {
+        pub fn bind_group_layout_entries(
+            entries: [wgpu::BindGroupLayout; 1],
+        ) -> [wgpu::BindGroupLayout; 1] {
+            entries
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("Minimal::PipelineLayout"),
+                    bind_group_layouts: &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                    ],
+                    push_constant_ranges: &[],
+                },
+            )
+    }
+    pub const SOURCE_HASH: &str = "2fe6fb5100dd24365a553c588eedb71444ed2689ab48e3ec41b0f130a5496914";
+    pub const SOURCE_PATH: &str = "tests/shaders/minimal.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &["minimal.wgsl"];
+    pub fn create_shader_module_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("minimal.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    pub const SHADER_STRING: &'static str = r#"
 struct Uniforms {
     color: vec4<f32>,
     width: f32,
@@ -214,7 +628,26 @@ fn main(@builtin(global_invocation_id) id: vec3<u32>) {
 "#;
 }
 pub mod bytemuck_impls {
-  use super::{_root, _root::*};
-  unsafe impl bytemuck::Zeroable for minimal::Uniforms {}
-  unsafe impl bytemuck::Pod for minimal::Uniforms {}
+    use super::{_root, _root::*};
+    unsafe impl bytemuck::Zeroable for minimal::Uniforms {}
+    unsafe impl bytemuck::Pod for minimal::Uniforms {}
+}
+pub mod bind_group_layouts {
+    use super::{_root, _root::*};
+    pub const COUNT: usize = 1;
+    pub const DESCRIPTORS: [wgpu::BindGroupLayoutDescriptor<'static>; COUNT] = [
+        minimal::WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+    ];
+    pub fn create_all(device: &wgpu::Device) -> Vec<wgpu::BindGroupLayout> {
+        DESCRIPTORS
+            .iter()
+            .map(|descriptor| device.create_bind_group_layout(descriptor))
+            .collect()
+    }
+    pub fn index(entry: ShaderEntry, group_no: u32) -> Option<usize> {
+        match (entry, group_no) {
+            (ShaderEntry::Minimal, 0) => Some(0),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,528 @@
+use super::{_root, _root::*};
+#[repr(C, align(16))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Style {
+    /// size: 16, offset: 0x0, type: `vec4<f32>`
+    pub color: glam::Vec4,
+    /// size: 4, offset: 0x10, type: `f32`
+    pub width: f32,
+    pub _pad_width: [u8; 0x10 - core::mem::size_of::<f32>()],
+}
+impl Style {
+    pub const fn new(color: glam::Vec4, width: f32) -> Self {
+        Self {
+            color,
+            width,
+            _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
+        }
+    }
+}
+impl Style {
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+impl Style {
+    /// Panics with the fields whose offset or size disagrees with the WGSL layout.
+    #[cfg(debug_assertions)]
+    pub fn verify_layout() {
+        let fields = [
+            (
+                "color",
+                std::mem::offset_of!(Self, color),
+                std::mem::size_of::<glam::Vec4>(),
+                0,
+                16,
+                16,
+            ),
+            (
+                "width",
+                std::mem::offset_of!(Self, width),
+                std::mem::size_of::<f32>(),
+                16,
+                4,
+                16,
+            ),
+        ];
+        let mut mismatches = std::string::String::new();
+        for (name, offset, size, wgsl_offset, wgsl_size, wgsl_span) in fields {
+            if offset != wgsl_offset || size < wgsl_size || size > wgsl_span {
+                mismatches
+                    += &std::format!(
+                        "\n  {name}: offset {offset} and size {size}, expected offset {wgsl_offset} and size {wgsl_size}"
+                    );
+            }
+        }
+        assert!(
+            mismatches.is_empty(),
+            "`main::Style` doesn't match the WGSL layout:{mismatches}"
+        );
+    }
+}
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct StyleInit {
+    pub color: glam::Vec4,
+    pub width: f32,
+}
+impl StyleInit {
+    pub const fn build(&self) -> Style {
+        Style {
+            color: self.color,
+            width: self.width,
+            _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
+        }
+    }
+}
+impl From<StyleInit> for Style {
+    fn from(data: StyleInit) -> Self {
+        data.build()
+    }
+}
+#[derive(Debug)]
+pub struct WgpuBindGroup0EntriesParams<'a> {
+    pub buffer: wgpu::BufferBinding<'a>,
+    pub texture_float: &'a wgpu::TextureView,
+    pub texture_sint: &'a wgpu::TextureView,
+    pub texture_uint: &'a wgpu::TextureView,
+}
+#[derive(Clone, Debug)]
+pub struct WgpuBindGroup0Entries<'a> {
+    pub buffer: wgpu::BindGroupEntry<'a>,
+    pub texture_float: wgpu::BindGroupEntry<'a>,
+    pub texture_sint: wgpu::BindGroupEntry<'a>,
+    pub texture_uint: wgpu::BindGroupEntry<'a>,
+}
+impl<'a> WgpuBindGroup0Entries<'a> {
+    pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+        Self {
+            buffer: wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(params.buffer),
+            },
+            texture_float: wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(params.texture_float),
+            },
+            texture_sint: wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(params.texture_sint),
+            },
+            texture_uint: wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(params.texture_uint),
+            },
+        }
+    }
+    pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 4] {
+        [self.buffer, self.texture_float, self.texture_sint, self.texture_uint]
+    }
+    pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+        self.as_array().into_iter().collect()
+    }
+}
+#[derive(Debug)]
+pub struct WgpuBindGroup0(wgpu::BindGroup);
+impl WgpuBindGroup0 {
+    pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+        label: Some("Main::BindGroup0::LayoutDescriptor"),
+        entries: &[
+            /// @binding(0): "buffer"
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage {
+                        read_only: false,
+                    },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            /// @binding(1): "texture_float"
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float {
+                        filterable: true,
+                    },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            /// @binding(2): "texture_sint"
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Sint,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            /// @binding(3): "texture_uint"
+            wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Uint,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+        ],
+    };
+    pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+    }
+    pub fn from_bindings(
+        device: &wgpu::Device,
+        bindings: WgpuBindGroup0Entries,
+    ) -> Self {
+        Self(
+            _root::_support::create_bind_group(
+                device,
+                &Self::LAYOUT_DESCRIPTOR,
+                "Main::BindGroup0",
+                &bindings.as_array(),
+            ),
+        )
+    }
+    pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
+        render_pass.set_bind_group(0, &self.0, &[]);
+    }
+}
+#[derive(Debug)]
+pub struct WgpuBindGroup1EntriesParams<'a> {
+    pub ONE: wgpu::BufferBinding<'a>,
+}
+#[derive(Clone, Debug)]
+pub struct WgpuBindGroup1Entries<'a> {
+    pub ONE: wgpu::BindGroupEntry<'a>,
+}
+impl<'a> WgpuBindGroup1Entries<'a> {
+    pub fn new(params: WgpuBindGroup1EntriesParams<'a>) -> Self {
+        Self {
+            ONE: wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(params.ONE),
+            },
+        }
+    }
+    pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 1] {
+        [self.ONE]
+    }
+    pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+        self.as_array().into_iter().collect()
+    }
+}
+#[derive(Debug)]
+pub struct WgpuBindGroup1(wgpu::BindGroup);
+impl WgpuBindGroup1 {
+    pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+        label: Some("Main::BindGroup1::LayoutDescriptor"),
+        entries: &[
+            /// @binding(0): "_root::bindings::ONE"
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: std::num::NonZeroU64::new(
+                        std::mem::size_of::<f32>() as _,
+                    ),
+                },
+                count: None,
+            },
+        ],
+    };
+    pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+        device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+    }
+    pub fn from_bindings(
+        device: &wgpu::Device,
+        bindings: WgpuBindGroup1Entries,
+    ) -> Self {
+        Self(
+            _root::_support::create_bind_group(
+                device,
+                &Self::LAYOUT_DESCRIPTOR,
+                "Main::BindGroup1",
+                &bindings.as_array(),
+            ),
+        )
+    }
+    pub fn set<'a>(&self, render_pass: &mut wgpu::ComputePass<'a>) {
+        render_pass.set_bind_group(1, &self.0, &[]);
+    }
+}
+#[derive(Debug, Copy, Clone)]
+pub struct WgpuBindGroups<'a> {
+    pub bind_group0: &'a WgpuBindGroup0,
+    pub bind_group1: &'a WgpuBindGroup1,
+}
+impl<'a> WgpuBindGroups<'a> {
+    pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
+        self.bind_group0.set(pass);
+        self.bind_group1.set(pass);
+    }
+}
+pub fn set_bind_groups<'a>(
+    pass: &mut wgpu::ComputePass<'a>,
+    bind_group0: &'a WgpuBindGroup0,
+    bind_group1: &'a WgpuBindGroup1,
+) {
+    bind_group0.set(pass);
+    bind_group1.set(pass);
+}
+/// Encodes `values` as the bytes of the binding, padding each element to the WGSL stride.
+pub fn encode_buffer(values: &[f32]) -> std::vec::Vec<u8> {
+    let size = std::mem::size_of::<f32>();
+    assert!(
+        size <= 4,
+        "the elements of `buffer` are larger than their WGSL stride of 4 bytes"
+    );
+    let mut bytes = std::vec![0; values.len() * 4];
+    for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+        chunk[..size].copy_from_slice(bytemuck::bytes_of(value));
+    }
+    bytes
+}
+/// Decodes the elements of the binding from `bytes`, skipping the padding after each
+/// element.
+pub fn decode_buffer(bytes: &[u8]) -> std::vec::Vec<f32> {
+    let size = std::mem::size_of::<f32>();
+    assert!(
+        size <= 4,
+        "the elements of `buffer` are larger than their WGSL stride of 4 bytes"
+    );
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| bytemuck::pod_read_unaligned(&chunk[..size]))
+        .collect()
+}
+/// The size of a buffer for the binding holding `n` elements of its runtime sized array.
+pub const fn buffer_min_buffer_size(n: usize) -> u64 {
+    n as u64 * 4
+}
+pub fn create_buffer_buffer_for(device: &wgpu::Device, n: usize) -> wgpu::Buffer {
+    device
+        .create_buffer(
+            &wgpu::BufferDescriptor {
+                label: Some("Main::buffer"),
+                size: buffer_min_buffer_size(n),
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        )
+}
+pub fn texture_float_texture_descriptor(
+    size: wgpu::Extent3d,
+    format: wgpu::TextureFormat,
+) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("Main::texture_float"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    }
+}
+pub fn create_view_for_texture_float(texture: &wgpu::Texture) -> wgpu::TextureView {
+    texture
+        .create_view(
+            &wgpu::TextureViewDescriptor {
+                label: Some("Main::texture_float"),
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                aspect: wgpu::TextureAspect::All,
+                ..Default::default()
+            },
+        )
+}
+pub fn texture_sint_texture_descriptor(
+    size: wgpu::Extent3d,
+    format: wgpu::TextureFormat,
+) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("Main::texture_sint"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    }
+}
+pub fn create_view_for_texture_sint(texture: &wgpu::Texture) -> wgpu::TextureView {
+    texture
+        .create_view(
+            &wgpu::TextureViewDescriptor {
+                label: Some("Main::texture_sint"),
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                aspect: wgpu::TextureAspect::All,
+                ..Default::default()
+            },
+        )
+}
+pub fn texture_uint_texture_descriptor(
+    size: wgpu::Extent3d,
+    format: wgpu::TextureFormat,
+) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("Main::texture_uint"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    }
+}
+pub fn create_view_for_texture_uint(texture: &wgpu::Texture) -> wgpu::TextureView {
+    texture
+        .create_view(
+            &wgpu::TextureViewDescriptor {
+                label: Some("Main::texture_uint"),
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                aspect: wgpu::TextureAspect::All,
+                ..Default::default()
+            },
+        )
+}
+pub mod compute {
+    pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [1, 1, 1];
+    pub const MAIN_WORKGROUP_STORAGE_SIZE: u32 = 0;
+    pub fn create_main_pipeline_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ComputePipeline {
+        let module = super::create_shader_module_embed_source(device);
+        let layout = super::create_pipeline_layout(device);
+        device
+            .create_compute_pipeline(
+                &wgpu::ComputePipelineDescriptor {
+                    label: Some("Compute Pipeline main"),
+                    layout: Some(&layout),
+                    module: &module,
+                    entry_point: Some("main"),
+                    compilation_options: Default::default(),
+                    cache: None,
+                },
+            )
+    }
+}
+pub const ENTRY_MAIN: &str = "main";
+/// The device features required by this shader module.
+pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::PUSH_CONSTANTS;
+/// The minimal device limits required by this shader module.
+pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+    max_storage_buffers_per_shader_stage: 1,
+    max_storage_buffer_binding_size: 4,
+    max_compute_invocations_per_workgroup: 1,
+    max_compute_workgroup_size_x: 1,
+    max_compute_workgroup_size_y: 1,
+    max_compute_workgroup_size_z: 1,
+    max_compute_workgroups_per_dimension: 1,
+    max_push_constant_size: 32,
+    ..wgpu::Limits::downlevel_webgl2_defaults()
+};
+/// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+pub fn is_supported(adapter: &wgpu::Adapter) -> Result<(), _root::UnsupportedReason> {
+    _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
+}
+/// The resources bound by this shader module, for budgeting across shaders.
+pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+    uniform_buffers: 1,
+    uniform_bytes: 4,
+    storage_buffers: 1,
+    textures_1d: 0,
+    textures_2d: 3,
+    textures_2d_array: 0,
+    textures_3d: 0,
+    textures_cube: 0,
+    textures_cube_array: 0,
+    storage_textures: 0,
+    samplers: 0,
+    push_constant_size: 32,
+};
+#[derive(Debug)]
+pub struct WgpuPipelineLayout;
+impl WgpuPipelineLayout {
+    pub fn bind_group_layout_entries(
+        entries: [wgpu::BindGroupLayout; 2],
+    ) -> [wgpu::BindGroupLayout; 2] {
+        entries
+    }
+}
+pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+    device
+        .create_pipeline_layout(
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Main::PipelineLayout"),
+                bind_group_layouts: &[
+                    &WgpuBindGroup0::get_bind_group_layout(device),
+                    &WgpuBindGroup1::get_bind_group_layout(device),
+                ],
+                push_constant_ranges: &[
+                    wgpu::PushConstantRange {
+                        stages: wgpu::ShaderStages::COMPUTE,
+                        range: 0..32,
+                    },
+                ],
+            },
+        )
+}
+pub const SOURCE_HASH: &str = "8351f683bde62c7a997b5d6dcb69502e2921b9f4cd073e67d7e73fe0c8751ea9";
+pub const SOURCE_PATH: &str = "tests/shaders/basic/main.wgsl";
+pub const DEPENDENCY_PATHS: &[&str] = &[
+    "../basic/main.wgsl",
+    "../basic/bindings.wgsl",
+    "types.wgsl",
+];
+pub fn create_shader_module_embed_source(device: &wgpu::Device) -> wgpu::ShaderModule {
+    let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+    device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("main.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(source),
+        })
+}
+pub const SHADER_STRING: &'static str = r#"
+struct Style {
+    color: vec4<f32>,
+    width: f32,
+}
+
+@group(1) @binding(0) 
+var<uniform> ONEX_naga_oil_mod_XMJUW4ZDJNZTXGX: f32;
+@group(0) @binding(0) 
+var<storage, read_write> buffer: array<f32>;
+@group(0) @binding(1) 
+var texture_float: texture_2d<f32>;
+@group(0) @binding(2) 
+var texture_sint: texture_2d<i32>;
+@group(0) @binding(3) 
+var texture_uint: texture_2d<u32>;
+var<push_constant> const_style: Style;
+
+@compute @workgroup_size(1, 1, 1) 
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let _e5 = ONEX_naga_oil_mod_XMJUW4ZDJNZTXGX;
+    let _e11 = const_style.color.w;
+    let _e15 = const_style.width;
+    let _e17 = buffer[id.x];
+    buffer[id.x] = (_e17 * (((2f * _e5) * _e11) * _e15));
+    return;
+}
+"#;
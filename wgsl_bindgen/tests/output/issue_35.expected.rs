@@ -4,20 +4,219 @@ pub enum ShaderEntry {
     Clear,
 }
 impl ShaderEntry {
+    pub const ALL: &'static [Self] = &[Self::Clear];
     pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
-        match self {
+        match *self {
             Self::Clear => clear::create_pipeline_layout(device),
         }
     }
+    pub fn is_supported(
+        &self,
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), UnsupportedReason> {
+        match *self {
+            Self::Clear => clear::is_supported(adapter),
+        }
+    }
+    pub fn entry_points(&self) -> &'static [(wgpu::ShaderStages, &'static str)] {
+        match *self {
+            Self::Clear => {
+                &[
+                    (wgpu::ShaderStages::VERTEX, clear::ENTRY_VERTEX_MAIN),
+                    (wgpu::ShaderStages::FRAGMENT, clear::ENTRY_FRAGMENT_MAIN),
+                ]
+            }
+        }
+    }
+    pub fn bind_group_count(&self) -> u32 {
+        match *self {
+            Self::Clear => 0,
+        }
+    }
+    /// The layouts of the vertex inputs, stepping with `step_mode` except for the inputs in
+    /// `instance_vertex_inputs`, which step per instance like in the `*_entry` functions.
+    pub fn vertex_buffer_layouts(
+        &self,
+        step_mode: wgpu::VertexStepMode,
+    ) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match *self {
+            Self::Clear => vec![vertices::VertexIn::vertex_buffer_layout(step_mode)],
+        }
+    }
+    pub fn push_constant_size(&self) -> u32 {
+        match *self {
+            Self::Clear => 0,
+        }
+    }
+    pub const fn resources(&self) -> ShaderResources {
+        match *self {
+            Self::Clear => clear::RESOURCES,
+        }
+    }
     pub fn create_shader_module_embedded(
         &self,
         device: &wgpu::Device,
         shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
     ) -> wgpu::ShaderModule {
-        match self {
+        match *self {
             Self::Clear => clear::create_shader_module_embedded(device, shader_defs),
         }
     }
+    /// Creates a render pipeline of the `vertex` and `fragment` entries of this shader, eg:
+    /// from its `*_instanced_entry` function, or returns `None` without a vertex stage.
+    pub fn create_render_pipeline_embedded<const N: usize, const M: usize>(
+        &self,
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        vertex: &_support::VertexEntry<N>,
+        fragment: Option<&_support::FragmentEntry<M>>,
+        primitive: wgpu::PrimitiveState,
+        depth_stencil: Option<wgpu::DepthStencilState>,
+    ) -> Option<wgpu::RenderPipeline> {
+        let has_vertex = self
+            .entry_points()
+            .iter()
+            .any(|(stage, _)| *stage == wgpu::ShaderStages::VERTEX);
+        if !has_vertex {
+            return None;
+        }
+        let module = self.create_shader_module_embedded(device, shader_defs);
+        let layout = self.create_pipeline_layout(device);
+        let pipeline = device
+            .create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some(&format!("Render Pipeline {self}")),
+                    layout: Some(&layout),
+                    vertex: _support::vertex_state(&module, vertex),
+                    fragment: fragment
+                        .map(|entry| _support::fragment_state(&module, entry)),
+                    primitive,
+                    depth_stencil,
+                    multisample: Default::default(),
+                    multiview: None,
+                    cache: None,
+                },
+            );
+        Some(pipeline)
+    }
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "tests/shaders/issue_35/clear.wgsl" => Some(Self::Clear),
+            _ => None,
+        }
+    }
+    pub fn relative_path(&self) -> &'static str {
+        match *self {
+            Self::Clear => "tests/shaders/issue_35/clear.wgsl",
+        }
+    }
+    pub fn dependency_paths(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Clear => clear::DEPENDENCY_PATHS,
+        }
+    }
+}
+impl std::fmt::Display for ShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            Self::Clear => "clear",
+        };
+        f.write_str(name)
+    }
+}
+impl std::str::FromStr for ShaderEntry {
+    type Err = UnknownShaderEntry;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clear" => Ok(Self::Clear),
+            _ => Err(UnknownShaderEntry(s.to_string())),
+        }
+    }
+}
+/// The error returned when parsing the name of an unknown shader entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownShaderEntry(pub String);
+impl std::fmt::Display for UnknownShaderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown shader entry `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownShaderEntry {}
+/// The reason an adapter can't run a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The adapter is missing features required by the shader.
+    MissingFeatures(wgpu::Features),
+    /// The shader requires a higher limit than the adapter supports.
+    LimitExceeded { name: &'static str, required: u64, supported: u64 },
+}
+impl std::fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "adapter is missing required features {features:?}")
+            }
+            Self::LimitExceeded { name, required, supported } => {
+                write!(
+                    f,
+                    "adapter limit `{name}` of {supported} is below the required {required}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for UnsupportedReason {}
+/// The resources bound by a shader module. Binding arrays count once per element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShaderResources {
+    pub uniform_buffers: u32,
+    /// The total size of the uniform bindings.
+    pub uniform_bytes: u64,
+    pub storage_buffers: u32,
+    pub textures_1d: u32,
+    pub textures_2d: u32,
+    pub textures_2d_array: u32,
+    pub textures_3d: u32,
+    pub textures_cube: u32,
+    pub textures_cube_array: u32,
+    pub storage_textures: u32,
+    pub samplers: u32,
+    pub push_constant_size: u32,
+}
+impl ShaderResources {
+    /// Sums the resources of two shaders, keeping the largest push constant size.
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            uniform_buffers: self.uniform_buffers + other.uniform_buffers,
+            uniform_bytes: self.uniform_bytes + other.uniform_bytes,
+            storage_buffers: self.storage_buffers + other.storage_buffers,
+            textures_1d: self.textures_1d + other.textures_1d,
+            textures_2d: self.textures_2d + other.textures_2d,
+            textures_2d_array: self.textures_2d_array + other.textures_2d_array,
+            textures_3d: self.textures_3d + other.textures_3d,
+            textures_cube: self.textures_cube + other.textures_cube,
+            textures_cube_array: self.textures_cube_array + other.textures_cube_array,
+            storage_textures: self.storage_textures + other.storage_textures,
+            samplers: self.samplers + other.samplers,
+            push_constant_size: if self.push_constant_size > other.push_constant_size {
+                self.push_constant_size
+            } else {
+                other.push_constant_size
+            },
+        }
+    }
+    /// The number of sampled textures of all dimensions.
+    pub const fn sampled_textures(&self) -> u32 {
+        self.textures_1d + self.textures_2d + self.textures_2d_array + self.textures_3d
+            + self.textures_cube + self.textures_cube_array
+    }
+    /// Sums the resources of the given shaders.
+    pub fn total(entries: &[ShaderEntry]) -> Self {
+        entries.iter().fold(Self::default(), |total, entry| total.add(entry.resources()))
+    }
 }
 mod _root {
     pub use super::*;
@@ -35,6 +234,97 @@ pub mod layout_asserts {
         assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
     };
 }
+pub mod _support {
+    use super::{_root, _root::*};
+    /// Checks the adapter supports the `features` and `limits` required by a shader module.
+    pub fn check_support(
+        adapter: &wgpu::Adapter,
+        features: wgpu::Features,
+        limits: &wgpu::Limits,
+    ) -> Result<(), _root::UnsupportedReason> {
+        let missing_features = features.difference(adapter.features());
+        if !missing_features.is_empty() {
+            return Err(_root::UnsupportedReason::MissingFeatures(missing_features));
+        }
+        let mut exceeded = None;
+        limits
+            .check_limits_with_fail_fn(
+                &adapter.limits(),
+                true,
+                |name, required, supported| {
+                    exceeded = Some(_root::UnsupportedReason::LimitExceeded {
+                        name,
+                        required,
+                        supported,
+                    });
+                },
+            );
+        match exceeded {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    /// The entry point and buffers of a [`VertexEntry`] without its pipeline constants, so it
+    /// can be created in a const fn.
+    #[derive(Debug)]
+    pub struct ConstVertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+    }
+    impl<const N: usize> ConstVertexEntry<N> {
+        /// A [`VertexEntry`] with these buffers and the pipeline-overridable `constants`.
+        pub fn to_entry(
+            &self,
+            constants: std::collections::HashMap<String, f64>,
+        ) -> VertexEntry<N> {
+            VertexEntry {
+                entry_point: self.entry_point,
+                buffers: self.buffers.clone(),
+                constants,
+            }
+        }
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: Some(entry.entry_point),
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub targets: [Option<wgpu::ColorTargetState>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+    }
+    pub fn fragment_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntry<N>,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: Some(entry.entry_point),
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                ..Default::default()
+            },
+        }
+    }
+}
 pub mod vertices {
     use super::{_root, _root::*};
     #[repr(C)]
@@ -46,7 +336,19 @@ pub mod vertices {
         VertexIn { position }
     }
     impl VertexIn {
-        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 1] = [
+        pub fn from_parts(position: impl Into<glam::Vec4>) -> Self {
+            Self { position: position.into() }
+        }
+    }
+    impl VertexIn {
+        pub fn as_bytes(&self) -> &[u8] {
+            bytemuck::bytes_of(self)
+        }
+    }
+    impl VertexIn {
+        pub const ATTRIBUTE_COUNT: usize = 1;
+        pub const STRIDE: u64 = std::mem::size_of::<Self>() as u64;
+        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; Self::ATTRIBUTE_COUNT] = [
             wgpu::VertexAttribute {
                 format: wgpu::VertexFormat::Float32x4,
                 offset: std::mem::offset_of!(Self, position) as u64,
@@ -57,12 +359,31 @@ pub mod vertices {
             step_mode: wgpu::VertexStepMode,
         ) -> wgpu::VertexBufferLayout<'static> {
             wgpu::VertexBufferLayout {
-                array_stride: std::mem::size_of::<Self>() as u64,
+                array_stride: Self::STRIDE,
                 step_mode,
                 attributes: &Self::VERTEX_ATTRIBUTES,
             }
         }
     }
+    const VERTEX_IN_LAYOUT_ASSERTS: () = {
+        assert!(
+            std::mem::size_of:: < VertexIn > () % 4 == 0,
+            "the array stride of `vertices::VertexIn` must be a multiple of 4 bytes"
+        );
+        assert!(
+            std::mem::size_of:: < VertexIn > () <= 2048,
+            "the array stride of `vertices::VertexIn` must be at most 2048 bytes"
+        );
+        assert!(
+            std::mem::offset_of!(VertexIn, position) % 4 == 0,
+            "`vertices::VertexIn::position` must be aligned to 4 bytes in the vertex buffer"
+        );
+        assert!(
+            std::mem::offset_of!(VertexIn, position) + 16 <= std::mem::size_of:: <
+            VertexIn > (),
+            "`vertices::VertexIn::position` must be read within the array stride"
+        );
+    };
 }
 pub mod bytemuck_impls {
     use super::{_root, _root::*};
@@ -73,26 +394,34 @@ pub mod clear {
     use super::{_root, _root::*};
     pub const ENTRY_VERTEX_MAIN: &str = "vertex_main";
     pub const ENTRY_FRAGMENT_MAIN: &str = "fragment_main";
-    #[derive(Debug)]
-    pub struct VertexEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-        pub constants: std::collections::HashMap<String, f64>,
-    }
-    pub fn vertex_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a VertexEntry<N>,
-    ) -> wgpu::VertexState<'a> {
-        wgpu::VertexState {
-            module,
-            entry_point: entry.entry_point,
-            buffers: &entry.buffers,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
-        }
+    /// The device features required by this shader module.
+    pub const REQUIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
+    /// The minimal device limits required by this shader module.
+    pub const REQUIRED_LIMITS: wgpu::Limits = wgpu::Limits {
+        ..wgpu::Limits::downlevel_webgl2_defaults()
+    };
+    /// Checks the adapter supports [`REQUIRED_FEATURES`] and [`REQUIRED_LIMITS`].
+    pub fn is_supported(
+        adapter: &wgpu::Adapter,
+    ) -> Result<(), _root::UnsupportedReason> {
+        _root::_support::check_support(adapter, REQUIRED_FEATURES, &REQUIRED_LIMITS)
     }
+    /// The resources bound by this shader module, for budgeting across shaders.
+    pub const RESOURCES: _root::ShaderResources = _root::ShaderResources {
+        uniform_buffers: 0,
+        uniform_bytes: 0,
+        storage_buffers: 0,
+        textures_1d: 0,
+        textures_2d: 0,
+        textures_2d_array: 0,
+        textures_3d: 0,
+        textures_cube: 0,
+        textures_cube_array: 0,
+        storage_textures: 0,
+        samplers: 0,
+        push_constant_size: 0,
+    };
+    pub use _root::_support::{ConstVertexEntry, VertexEntry, vertex_state};
     pub fn vertex_main_entry(vertex_in: wgpu::VertexStepMode) -> VertexEntry<1> {
         VertexEntry {
             entry_point: ENTRY_VERTEX_MAIN,
@@ -100,26 +429,26 @@ pub mod clear {
             constants: Default::default(),
         }
     }
-    #[derive(Debug)]
-    pub struct FragmentEntry<const N: usize> {
-        pub entry_point: &'static str,
-        pub targets: [Option<wgpu::ColorTargetState>; N],
-        pub constants: std::collections::HashMap<String, f64>,
+    /// `vertex_main_entry` with `wgpu::VertexStepMode::Vertex` for all of its step modes.
+    pub fn vertex_main_entry_default() -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VERTEX_MAIN,
+            buffers: [
+                vertices::VertexIn::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+            ],
+            constants: Default::default(),
+        }
     }
-    pub fn fragment_state<'a, const N: usize>(
-        module: &'a wgpu::ShaderModule,
-        entry: &'a FragmentEntry<N>,
-    ) -> wgpu::FragmentState<'a> {
-        wgpu::FragmentState {
-            module,
-            entry_point: entry.entry_point,
-            targets: &entry.targets,
-            compilation_options: wgpu::PipelineCompilationOptions {
-                constants: &entry.constants,
-                ..Default::default()
-            },
+    /// The buffers of `vertex_main_entry_default` in a const fn, eg: to keep them in a static.
+    pub const fn vertex_main_const_entry() -> ConstVertexEntry<1> {
+        ConstVertexEntry {
+            entry_point: ENTRY_VERTEX_MAIN,
+            buffers: [
+                vertices::VertexIn::vertex_buffer_layout(wgpu::VertexStepMode::Vertex),
+            ],
         }
     }
+    pub use _root::_support::{FragmentEntry, fragment_state};
     pub fn fragment_main_entry(
         targets: [Option<wgpu::ColorTargetState>; 1],
     ) -> FragmentEntry<1> {
@@ -148,6 +477,12 @@ pub mod clear {
                 },
             )
     }
+    pub const SOURCE_HASH: &str = "60c7f30be0450c26dfd21c8700c21a4ccd4e669aaf1e2ad277317c766516ee8c";
+    pub const SOURCE_PATH: &str = "tests/shaders/issue_35/clear.wgsl";
+    pub const DEPENDENCY_PATHS: &[&str] = &[
+        "../../../tests/shaders/issue_35/clear.wgsl",
+        "../../../tests/shaders/issue_35/vertices.wgsl",
+    ];
     pub fn load_shader_modules_embedded(
         composer: &mut naga_oil::compose::Composer,
         shader_defs: &std::collections::HashMap<
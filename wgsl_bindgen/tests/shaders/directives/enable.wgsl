@@ -0,0 +1,12 @@
+enable dual_source_blending;
+requires pointer_composite_access;
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    @location(0) @second_blend_source mask: vec4<f32>,
+}
+
+@fragment
+fn fs_main() -> FragmentOutput {
+    return FragmentOutput(vec4<f32>(1.0), vec4<f32>(0.5));
+}
@@ -0,0 +1,4 @@
+struct Light {
+  direction: vec3<f32>,
+  intensity: f32,
+}
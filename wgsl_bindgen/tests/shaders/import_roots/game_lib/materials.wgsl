@@ -0,0 +1,3 @@
+struct Material {
+  albedo: vec4<f32>,
+}
@@ -0,0 +1,13 @@
+#import engine::lighting::{Light};
+#import game::materials::{Material};
+
+@group(0) @binding(0)
+var<uniform> light: Light;
+
+@group(0) @binding(1)
+var<uniform> material: Material;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return material.albedo * light.intensity;
+}
@@ -0,0 +1,58 @@
+//! Command line interface for wgsl_bindgen, driven by a `wgsl_bindgen.toml` configuration file.
+//!
+//! This allows generating the bindings from build systems other than cargo, asset pipelines
+//! or pre-commit hooks, without writing a build script.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use miette::Result;
+use wgsl_bindgen::WgslBindgenOptionBuilder;
+
+/// Generate type safe Rust bindings for wgsl shaders.
+#[derive(Parser)]
+#[command(name = "wgsl_bindgen", version, about)]
+struct Cli {
+  /// The configuration file. Relative paths inside it are resolved against its directory.
+  #[arg(short, long, global = true, default_value = "wgsl_bindgen.toml")]
+  config: PathBuf,
+
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Generate the bindings and write them to the configured output.
+  Generate {
+    /// Regenerate even if the shaders didn't change since the bindings were written.
+    #[arg(long)]
+    force: bool,
+  },
+
+  /// Fail if the bindings on disk are out of date, without writing them.
+  Check,
+
+  /// Generate the bindings, then regenerate them whenever an entry point or one of its imports changes.
+  Watch,
+}
+
+fn main() -> Result<()> {
+  let cli = Cli::parse();
+
+  let mut builder = WgslBindgenOptionBuilder::from_config_file(&cli.config)?;
+  // Cargo directives are meaningless outside of a build script.
+  builder.emit_rerun_if_change(false);
+  if let Command::Generate { force: true } = cli.command {
+    builder.skip_hash_check(true);
+  }
+  let bindgen = builder.build()?;
+
+  match cli.command {
+    Command::Generate { .. } => bindgen.generate()?,
+    Command::Check => bindgen.check()?,
+    Command::Watch => bindgen.run_watch()?,
+  }
+
+  Ok(())
+}
@@ -0,0 +1,179 @@
+//! # wgsl_bindgen_macro
+//! Generates the [wgsl_bindgen](https://docs.rs/wgsl_bindgen) bindings at macro expansion time,
+//! for projects that want the bindings without a build script and an output file to manage.
+//!
+//! ```ignore
+//! mod shader_bindings {
+//!     wgsl_bindgen_macro::include_wgsl_bindgen!(
+//!         "shaders/triangle.wgsl",
+//!         "shaders/compute.wgsl",
+//!         workspace_root = "shaders",
+//!         serialization_strategy = "bytemuck",
+//!         type_map = "glam",
+//!     );
+//! }
+//! ```
+//!
+//! The options can also be loaded from a configuration file with `config = "wgsl_bindgen.toml"`,
+//! in which case its `output` is ignored.
+
+use std::path::PathBuf;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Ident, LitStr, Token};
+use wgsl_bindgen::{
+  GlamWgslTypeMap, NalgebraWgslTypeMap, RustWgslTypeMap, WgslBindgenOptionBuilder,
+  WgslTypeSerializeStrategy,
+};
+
+/// Either the path of an entry point, or a `key = "value"` option.
+enum Arg {
+  Entry(LitStr),
+  Option(Ident, LitStr),
+}
+
+impl Parse for Arg {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    if input.peek(LitStr) {
+      return Ok(Self::Entry(input.parse()?));
+    }
+
+    let key = input.parse()?;
+    input.parse::<Token![=]>()?;
+    Ok(Self::Option(key, input.parse()?))
+  }
+}
+
+/// Generates the bindings for the given entry points in place.
+///
+/// Paths are relative to the crate invoking the macro. The options are:
+/// - `workspace_root`: the directory imports are resolved from. Defaults to the directory of the first entry point.
+/// - `module_import_root`: the prefix applied to the entry points.
+/// - `serialization_strategy`: either `encase` or `bytemuck`. Defaults to `encase`.
+/// - `type_map`: one of `rust`, `glam` or `nalgebra`. Defaults to `rust`.
+/// - `config`: a `wgsl_bindgen.toml` to load the options from instead.
+#[proc_macro]
+pub fn include_wgsl_bindgen(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let args =
+    parse_macro_input!(input with Punctuated::<Arg, Token![,]>::parse_terminated);
+  expand(args)
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+fn expand(args: Punctuated<Arg, Token![,]>) -> syn::Result<TokenStream> {
+  let manifest_dir =
+    PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+  let resolve = |path: &LitStr| manifest_dir.join(path.value());
+
+  let mut entries = vec![];
+  let mut config = None;
+  let mut workspace_root = None;
+  let mut module_import_root = None;
+  let mut serialization_strategy = None;
+  let mut type_map = None;
+
+  for arg in args {
+    let (key, value) = match arg {
+      Arg::Entry(path) => {
+        entries.push(path);
+        continue;
+      }
+      Arg::Option(key, value) => (key, value),
+    };
+
+    let slot = match key.to_string().as_str() {
+      "config" => &mut config,
+      "workspace_root" => &mut workspace_root,
+      "module_import_root" => &mut module_import_root,
+      "serialization_strategy" => &mut serialization_strategy,
+      "type_map" => &mut type_map,
+      _ => return Err(syn::Error::new(key.span(), format!("unknown option `{key}`"))),
+    };
+    *slot = Some(value);
+  }
+
+  let mut builder = match &config {
+    Some(path) => WgslBindgenOptionBuilder::from_config_file(resolve(path))
+      .map_err(|e| syn::Error::new(path.span(), e))?,
+    None => {
+      let Some(first) = entries.first() else {
+        return Err(syn::Error::new(
+          Span::call_site(),
+          "expected the path of an entry point, or `config = \"...\"`",
+        ));
+      };
+
+      let mut builder = WgslBindgenOptionBuilder::default();
+      let first = resolve(first);
+      builder.workspace_root(first.parent().unwrap_or(&manifest_dir));
+      builder
+    }
+  };
+
+  for entry in &entries {
+    builder.add_entry_point(resolve(entry).to_string_lossy());
+  }
+  if let Some(root) = &workspace_root {
+    builder.workspace_root(resolve(root));
+  }
+  if let Some(root) = &module_import_root {
+    builder.module_import_root(root.value());
+  }
+
+  // The type map is built for the serialization strategy, so it has to be set first.
+  if let Some(strategy) = &serialization_strategy {
+    builder.serialization_strategy(match strategy.value().as_str() {
+      "encase" => WgslTypeSerializeStrategy::Encase,
+      "bytemuck" => WgslTypeSerializeStrategy::Bytemuck,
+      _ => {
+        return Err(syn::Error::new(
+          strategy.span(),
+          "expected either `encase` or `bytemuck`",
+        ))
+      }
+    });
+  } else if config.is_none() {
+    builder.serialization_strategy(WgslTypeSerializeStrategy::default());
+  }
+
+  match type_map.as_ref().map(|map| (map, map.value())) {
+    Some((_, map)) if map == "rust" => builder.type_map(RustWgslTypeMap),
+    Some((_, map)) if map == "glam" => builder.type_map(GlamWgslTypeMap),
+    Some((_, map)) if map == "nalgebra" => builder.type_map(NalgebraWgslTypeMap),
+    Some((map, _)) => {
+      return Err(syn::Error::new(
+        map.span(),
+        "expected one of `rust`, `glam` or `nalgebra`",
+      ))
+    }
+    None if config.is_none() => builder.type_map(RustWgslTypeMap),
+    None => &mut builder,
+  };
+
+  let error = |e| syn::Error::new(Span::call_site(), e);
+  let bindgen = builder
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .includable_output(true)
+    .build()
+    .map_err(error)?;
+  let bindings = bindgen
+    .generate_string()
+    .map_err(error)?
+    .parse::<TokenStream>()?;
+
+  // Referencing the shaders with `include_str!` makes rustc expand the macro again when they change.
+  let source_files = bindgen
+    .source_files()
+    .into_iter()
+    .map(|path| manifest_dir.join(path).to_string_lossy().into_owned());
+
+  Ok(quote! {
+    const _: &[&str] = &[#(include_str!(#source_files)),*];
+    #bindings
+  })
+}
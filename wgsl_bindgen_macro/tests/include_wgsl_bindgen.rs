@@ -0,0 +1,15 @@
+mod shader_bindings {
+  wgsl_bindgen_macro::include_wgsl_bindgen!(
+    "../wgsl_bindgen/tests/shaders/minimal.wgsl",
+    serialization_strategy = "bytemuck",
+  );
+}
+
+use shader_bindings::*;
+
+#[test]
+fn test_include_wgsl_bindgen() {
+  assert_eq!(&[ShaderEntry::Minimal], ShaderEntry::ALL);
+  assert_eq!(32, std::mem::size_of::<minimal::Uniforms>());
+  assert_eq!("main", minimal::ENTRY_MAIN);
+}
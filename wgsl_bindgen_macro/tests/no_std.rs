@@ -0,0 +1,22 @@
+//! Compiles the bindings generated for `no_std` crates, which only refer to `core` and `alloc`.
+
+#![no_std]
+
+extern crate alloc;
+
+mod no_std_bindings {
+  wgsl_bindgen_macro::include_wgsl_bindgen!(config = "tests/no_std.toml");
+}
+
+use no_std_bindings::{culling, simulation};
+
+#[test]
+fn no_std_bindings_compile() {
+  let particles = [simulation::Particle {
+    position: [0.0; 2],
+    velocity: [1.0; 2],
+  }];
+  let bytes: alloc::vec::Vec<u8> = simulation::encode_particles_out(&particles);
+  assert_eq!(particles.as_slice(), simulation::decode_particles_out(&bytes));
+  assert_eq!(16, culling::DrawArgs::new(3, 1, 0, 0).as_bytes().len());
+}
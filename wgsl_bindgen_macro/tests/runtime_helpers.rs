@@ -0,0 +1,181 @@
+//! Compiles the runtime helpers of the generated bindings, which the string
+//! snapshots of `wgsl_bindgen` can't check on their own.
+
+mod sprites_bindings {
+  wgsl_bindgen_macro::include_wgsl_bindgen!(config = "tests/sprites.toml");
+}
+
+mod culling_bindings {
+  wgsl_bindgen_macro::include_wgsl_bindgen!(config = "tests/culling.toml");
+}
+
+use culling_bindings::culling;
+use sprites_bindings::sprites;
+
+static SPRITES_VERTEX_ENTRY: sprites::ConstVertexEntry<2> =
+  sprites::vs_main_const_entry();
+
+#[test]
+fn const_vertex_entry_matches_the_default() {
+  let entry = SPRITES_VERTEX_ENTRY.to_entry(Default::default());
+  let default_entry = sprites::vs_main_entry_default();
+  assert_eq!(default_entry.entry_point, entry.entry_point);
+  assert_eq!(default_entry.buffers, entry.buffers);
+  assert_eq!(wgpu::VertexStepMode::Vertex, entry.buffers[1].step_mode);
+  assert_eq!(
+    wgpu::VertexStepMode::Instance,
+    sprites::vs_main_instanced_entry().buffers[1].step_mode
+  );
+}
+
+#[test]
+fn required_limits_fit_the_downlevel_defaults() {
+  let webgl2 = wgpu::Limits::downlevel_webgl2_defaults();
+  assert!(sprites::REQUIRED_LIMITS.check_limits(&webgl2));
+  assert!(sprites::REQUIRED_FEATURES.is_empty());
+
+  assert_eq!(2, culling::REQUIRED_LIMITS.max_storage_buffers_per_shader_stage);
+  assert!(culling::REQUIRED_LIMITS.check_limits(&wgpu::Limits::default()));
+}
+
+#[test]
+fn support_checks_take_an_adapter() {
+  type SupportCheck =
+    fn(&wgpu::Adapter) -> Result<(), sprites_bindings::UnsupportedReason>;
+  let _: SupportCheck = sprites::is_supported;
+  let _ = sprites_bindings::ShaderEntry::is_supported;
+  let _ = culling_bindings::ShaderEntry::is_supported;
+}
+
+#[test]
+fn indirect_args_convert_to_wgpu() {
+  let draw = culling::DrawArgs::new(3, 2, 1, 0);
+  let args = wgpu::util::DrawIndirectArgs::from(draw);
+  assert_eq!(draw.instance_count, args.instance_count);
+  assert_eq!(draw, culling::DrawArgs::from(args));
+  assert_eq!(draw.as_bytes(), args.as_bytes());
+  let _: fn(&wgpu::Device, u64) -> wgpu::Buffer = culling::create_draws_indirect_buffer;
+}
+
+#[test]
+fn runtime_array_sizes_are_usize_arithmetic() {
+  const PARTICLES_SIZE: u64 = culling::particles_min_buffer_size(8);
+  assert_eq!(16 + 8 * 16, PARTICLES_SIZE);
+  assert_eq!(3 * 16, culling::draws_min_buffer_size(3));
+  let _: fn(&wgpu::Device, usize) -> wgpu::Buffer = culling::create_particles_buffer_for;
+}
+
+#[test]
+fn render_pipelines_take_the_generated_entries() {
+  let _ = |device: &wgpu::Device| -> Option<wgpu::RenderPipeline> {
+    let target = wgpu::ColorTargetState::from(wgpu::TextureFormat::Rgba8Unorm);
+    sprites_bindings::ShaderEntry::Sprites.create_render_pipeline_embed_source(
+      device,
+      &sprites::vs_main_instanced_entry(),
+      Some(&sprites::fs_main_entry([Some(target)])),
+      wgpu::PrimitiveState::default(),
+      None,
+    )
+  };
+}
+
+mod helpers_bindings {
+  wgsl_bindgen_macro::include_wgsl_bindgen!(config = "tests/helpers.toml");
+}
+
+use helpers_bindings::{_support, scene, simulation};
+
+#[test]
+fn bind_group_bundles_own_their_uniform_buffers() {
+  let _ =
+    |device: &wgpu::Device, queue: &wgpu::Queue, pass: &mut wgpu::RenderPass<'_>| {
+      let values = scene::WgpuBindGroup0Values {
+        camera: scene::Camera {
+          view_proj: [[0.0; 4]; 4],
+        },
+      };
+      let bundle = scene::WgpuBindGroup0Bundle::new(device, &values);
+      bundle.update(queue, &values);
+      bundle.set(pass);
+    };
+}
+
+#[test]
+fn ping_pong_buffers_swap_their_bind_groups() {
+  let _ = |device: &wgpu::Device, delta_time: wgpu::BufferBinding<'_>| {
+    let mut particles: _support::PingPong<simulation::WgpuBindGroup0> =
+      simulation::create_particles_in_ping_pong(device, 1024, delta_time);
+    particles.swap();
+    let _: (&wgpu::Buffer, &wgpu::Buffer) = (particles.src(), particles.dst());
+  };
+}
+
+#[test]
+fn uniform_arenas_hand_out_dynamic_offsets() {
+  let _ = |device: &wgpu::Device, queue: &wgpu::Queue| {
+    let mut arena = _support::UniformArena::<scene::Material>::new(
+      device,
+      &wgpu::Limits::default(),
+      16,
+    );
+    let material = scene::Material { color: [1.0; 4] };
+    let handle = arena.alloc(queue, &material).unwrap();
+    arena.write(queue, handle, &material);
+    let _: wgpu::DynamicOffset = handle.offset();
+    let _: wgpu::BindingResource<'_> = arena.binding();
+  };
+}
+
+#[test]
+fn n_buffered_uniforms_write_the_frame_in_flight() {
+  let _ = |device: &wgpu::Device, queue: &wgpu::Queue| {
+    let camera = scene::Camera {
+      view_proj: [[0.0; 4]; 4],
+    };
+    let frames = _support::NBuffered::<_, 2, _>::new(device, &camera, |buffer| {
+      scene::WgpuBindGroup0::from_bindings(
+        device,
+        scene::WgpuBindGroup0Entries::new(scene::WgpuBindGroup0EntriesParams {
+          camera: buffer.as_entire_buffer_binding(),
+        }),
+      )
+    });
+    frames.write_for_frame(queue, 3, &camera);
+    let _: &scene::WgpuBindGroup0 = frames.bind_group(3);
+  };
+}
+
+#[test]
+fn shadowed_uniforms_flush_when_dirty() {
+  let _ = |device: &wgpu::Device, queue: &wgpu::Queue| {
+    let mut material =
+      _support::Shadowed::new(device, scene::Material { color: [1.0; 4] });
+    material.get_mut().color[3] = 0.5;
+    assert!(material.flush(queue));
+    let _: wgpu::BufferBinding<'_> = material.as_entire_buffer_binding();
+  };
+}
+
+#[test]
+fn buffers_are_read_back_blocking() {
+  type Readback = fn(
+    &wgpu::Device,
+    &wgpu::Queue,
+    &wgpu::Buffer,
+  ) -> Result<Vec<simulation::Particle>, wgpu::BufferAsyncError>;
+  let _: Readback = simulation::read_particles_out;
+}
+
+#[cfg(feature = "staging-belt")]
+#[test]
+fn staging_belt_uploads_are_enabled_by_the_feature() {
+  type StagedWrite = fn(
+    &mut wgpu::util::StagingBelt,
+    &mut wgpu::CommandEncoder,
+    &wgpu::Device,
+    &wgpu::Buffer,
+    wgpu::BufferAddress,
+    &scene::Camera,
+  );
+  let _: StagedWrite = scene::write_camera_staged;
+}
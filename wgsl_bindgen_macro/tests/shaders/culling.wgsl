@@ -0,0 +1,21 @@
+struct DrawArgs {
+    vertex_count: u32,
+    instance_count: u32,
+    first_vertex: u32,
+    first_instance: u32,
+};
+
+struct Particles {
+    count: u32,
+    positions: array<vec4<f32>>,
+};
+
+@group(0) @binding(0) var<storage, read_write> draws: array<DrawArgs>;
+@group(0) @binding(1) var<storage, read_write> particles: Particles;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let visible = particles.positions[id.x].w > 0.0;
+    draws[id.x].instance_count = select(0u, 1u, visible);
+    particles.count = arrayLength(&particles.positions);
+}
@@ -0,0 +1,23 @@
+struct Camera {
+    view_proj: mat4x4<f32>,
+};
+
+struct Material {
+    color: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> camera: Camera;
+
+@group(1) @binding(0) var<uniform> material: Material;
+@group(1) @binding(1) var albedo: texture_2d<f32>;
+@group(1) @binding(2) var albedo_sampler: sampler;
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    return camera.view_proj * vec4<f32>(position, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return textureSample(albedo, albedo_sampler, vec2<f32>(0.5)) * material.color;
+}
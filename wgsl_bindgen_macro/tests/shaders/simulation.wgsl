@@ -0,0 +1,14 @@
+struct Particle {
+    position: vec2<f32>,
+    velocity: vec2<f32>,
+};
+
+@group(0) @binding(0) var<storage, read> particles_in: array<Particle>;
+@group(0) @binding(1) var<storage, read_write> particles_out: array<Particle>;
+@group(0) @binding(2) var<uniform> delta_time: f32;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let particle = particles_in[id.x];
+    particles_out[id.x].position = particle.position + particle.velocity * delta_time;
+}
@@ -0,0 +1,23 @@
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+};
+
+struct InstanceInput {
+    @location(1) offset: vec2<f32>,
+};
+
+struct Style {
+    tint: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> style: Style;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(vertex.position + instance.offset, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return style.tint;
+}